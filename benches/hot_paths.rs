@@ -0,0 +1,221 @@
+//! cargo bench --bench=hot_paths
+//!
+//! 主要な変換処理と探索のベンチマーク。入力は固定のフィクスチャなので、実行間で比較できる。
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use momoden_password::*;
+
+/// ベンチマーク用のパスワード。(ラベル, パスワード) の組で、いずれも有効。
+fn fixtures() -> Vec<(&'static str, Password)> {
+    // 最短: 1 文字。
+    let short = Password::parse("ふ").unwrap();
+
+    // 典型: デフォルトのセーブデータをエンコードしたもの。
+    let typical = SerializedBytes::from_savedata(&Savedata::default()).to_password();
+
+    // 最長: 固定シードの疑似乱数で埋めたバイト列のチェックサムを合わせたもの。
+    let mut state: u32 = 0x12345678;
+    let buf: Vec<_> = (0..Password::MAX_LEN)
+        .map(|_| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            SerializedByte::new(((state >> 16) & 0x3F) as u8).unwrap()
+        })
+        .collect();
+    let max = SerializedBytes::new(&buf)
+        .unwrap()
+        .with_fixed_checksum()
+        .to_password();
+
+    let res = vec![("short", short), ("typical", typical), ("max", max)];
+    assert!(res.iter().all(|(_, password)| password.is_valid()));
+
+    res
+}
+
+fn bench_codec(c: &mut Criterion) {
+    let fixtures = fixtures();
+
+    let mut group = c.benchmark_group("codec");
+
+    for (label, password) in &fixtures {
+        let s = password.display().to_string();
+        let bytes = SerializedBytes::from_password(password);
+        let savedata = bytes.to_savedata().unwrap();
+
+        group.throughput(Throughput::Elements(password.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("Password::parse", label), &s, |b, s| {
+            b.iter(|| Password::parse(black_box(s)).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("SerializedBytes::from_password", label),
+            password,
+            |b, password| b.iter(|| SerializedBytes::from_password(black_box(password))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("SerializedBytes::to_password", label),
+            &bytes,
+            |b, bytes| b.iter(|| black_box(bytes).to_password()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("SerializedBytes::from_savedata", label),
+            &savedata,
+            |b, savedata| b.iter(|| SerializedBytes::from_savedata(black_box(savedata))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("SerializedBytes::to_savedata", label),
+            &bytes,
+            |b, bytes| b.iter(|| black_box(bytes).to_savedata().unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Password::is_valid", label),
+            password,
+            |b, password| b.iter(|| black_box(password).is_valid()),
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_search(c: &mut Criterion) {
+    // (ラベル, パターン)。ワイルドカードの個数が同じで長さが異なるもの。
+    const PATTERNS: &[(&str, &str)] = &[
+        ("short", "???"),
+        ("typical", "おにのばかやろう???"),
+        (
+            "max",
+            "おにのばかやろうおにのばかやろうおにのばかやろうおにのばかやろうおにの???",
+        ),
+    ];
+
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+
+    for &(label, pattern) in PATTERNS {
+        let pattern = Pattern::parse(pattern).unwrap();
+        let options = SearchOptions::default();
+
+        // 1 回の探索で調べうる候補数。
+        group.throughput(Throughput::Elements(1 << (6 * 3)));
+
+        group.bench_with_input(BenchmarkId::new("search", label), &pattern, |b, pattern| {
+            b.iter(|| search(black_box(pattern), &options))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_reuse(c: &mut Criterion) {
+    // フィールドを 1 つ書き換えてはエンコード/デコードし直すループ。
+    const N: u16 = 1000;
+
+    let (_, password) = fixtures().pop().unwrap();
+    let savedata = SerializedBytes::from_password(&password)
+        .to_savedata()
+        .unwrap();
+
+    let mut group = c.benchmark_group("reuse");
+    group.throughput(Throughput::Elements(N.into()));
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            let mut savedata = savedata.clone();
+            for xp in 0..N {
+                savedata.xp = xp;
+                let bytes = SerializedBytes::from_savedata(black_box(&savedata));
+                let password = bytes.to_password();
+                let bytes = SerializedBytes::from_password(black_box(&password));
+                savedata = bytes.to_savedata().unwrap();
+            }
+            savedata
+        })
+    });
+
+    group.bench_function("into", |b| {
+        let mut bytes = SerializedBytes::from_password(&password);
+        b.iter(|| {
+            let mut savedata = savedata.clone();
+            for xp in 0..N {
+                savedata.xp = xp;
+                black_box(&savedata).encode_into(&mut bytes);
+                let password = bytes.to_password();
+                black_box(&password).decode_into(&mut bytes);
+                bytes.decode_into(&mut savedata).unwrap();
+            }
+            savedata
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_validate_stream(c: &mut Criterion) {
+    // 有効、チェックサム不一致、パース失敗の行とコメント、空行を繰り返す入力。
+    const N: usize = 10000;
+
+    let input = "# comment\nおにのばか\n\nおにのばき\nあいう?\n".repeat(N);
+
+    let mut group = c.benchmark_group("validate_stream");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_function("validate_stream", |b| {
+        b.iter(|| {
+            let mut n_valid = 0_usize;
+            validate_stream(black_box(input.as_bytes()), |_, _, res| {
+                n_valid += usize::from(res.is_ok())
+            })
+            .unwrap();
+            n_valid
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_dictionary_scan(c: &mut Criterion) {
+    // 辞書の各単語をパースする。無効な文字を含む単語も混ぜる。
+    const N: usize = 10000;
+
+    let mut state: u32 = 0x87654321;
+    let words: Vec<String> = (0..N)
+        .map(|i| {
+            let len = 1 + i % Password::MAX_LEN;
+            (0..len)
+                .map(|_| {
+                    state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                    let x = ((state >> 16) % 0x41) as u8;
+                    PasswordChar::from_inner(x).map_or('ん', PasswordChar::to_char)
+                })
+                .collect()
+        })
+        .collect();
+    let n_chars: usize = words.iter().map(|w| w.chars().count()).sum();
+
+    let mut group = c.benchmark_group("dictionary_scan");
+    group.throughput(Throughput::Elements(n_chars as u64));
+
+    group.bench_function("Password::parse", |b| {
+        b.iter(|| {
+            black_box(&words)
+                .iter()
+                .filter(|w| Password::parse(w).is_ok())
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_codec,
+    bench_search,
+    bench_reuse,
+    bench_validate_stream,
+    bench_dictionary_scan
+);
+criterion_main!(benches);
@@ -0,0 +1,312 @@
+//! cargo run --example=editor -- 'ふ'
+//!
+//! パスワードをロードし、セーブデータの各フィールドを編集する TUI。
+//! 編集結果のパスワードとチェックサムは画面下部に随時表示される。
+//!
+//! * ↑↓/PageUp/PageDown: 移動
+//! * Enter: 値の編集開始/確定 (10 進数、または 0x で始まる 16 進数)
+//! * Space: 真偽値の切り替え
+//! * Esc: 編集の取り消し
+//! * q: 終了
+//!
+//! 無効なパスワードが与えられた場合は編集画面を出さず、1 文字の置換で有効になる候補を示す。
+
+use std::io::Write as _;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, execute, queue, terminal};
+
+use momoden_password::*;
+
+fn main() -> anyhow::Result<()> {
+    let password = std::env::args().nth(1).unwrap_or_else(|| "ふ".to_owned());
+    let password = Password::parse(&password)?;
+
+    let _guard = TerminalGuard::new()?;
+
+    let savedata = match SerializedBytes::from_password(&password).to_savedata() {
+        Some(savedata) => savedata,
+        None => match select_suggestion(&password)? {
+            Some(savedata) => savedata,
+            None => return Ok(()),
+        },
+    };
+
+    Editor::new(savedata).run()?;
+
+    Ok(())
+}
+
+/// 終了時(パニック時も含む)に端末の状態を元に戻す。
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> anyhow::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            std::io::stdout(),
+            terminal::EnterAlternateScreen,
+            cursor::Hide
+        )?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(
+            std::io::stdout(),
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// 押されたキーを 1 つ読む。
+fn read_key() -> anyhow::Result<KeyEvent> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Release {
+                return Ok(key);
+            }
+        }
+    }
+}
+
+/// 修正候補を選ばせ、選ばれた候補のセーブデータを返す。
+fn select_suggestion(password: &Password) -> anyhow::Result<Option<Savedata>> {
+    let suggestions = password.repair(1);
+    let mut list = ListView::default();
+
+    loop {
+        let mut header = vec![
+            format!("invalid password: {}", password.display_pretty()),
+            String::new(),
+        ];
+        if suggestions.is_empty() {
+            header.push("no valid password within 1 substitution (q: quit)".to_owned());
+        } else {
+            header.push("suggestions (Enter: edit, q: quit):".to_owned());
+        }
+        let rows: Vec<_> = suggestions
+            .iter()
+            .map(|cand| cand.display_pretty().to_string())
+            .collect();
+        list.draw(&header, &rows, &[])?;
+
+        let key = read_key()?;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            KeyCode::Enter if !suggestions.is_empty() => {
+                let cand = &suggestions[list.cursor];
+                return Ok(SerializedBytes::from_password(cand).to_savedata());
+            }
+            code => list.navigate(code, rows.len()),
+        }
+    }
+}
+
+/// 縦方向にスクロールするリスト表示。
+#[derive(Debug, Default)]
+struct ListView {
+    cursor: usize,
+    scroll: usize,
+}
+
+impl ListView {
+    fn navigate(&mut self, code: KeyCode, len: usize) {
+        let page = list_height(0, 0).max(1);
+        self.cursor = match code {
+            KeyCode::Up => self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor + 1,
+            KeyCode::PageUp => self.cursor.saturating_sub(page),
+            KeyCode::PageDown => self.cursor + page,
+            KeyCode::Home => 0,
+            KeyCode::End => len,
+            _ => self.cursor,
+        }
+        .min(len.saturating_sub(1));
+    }
+
+    /// ヘッダ、リスト、フッタを描画する。
+    fn draw(
+        &mut self,
+        header: &[String],
+        rows: &[String],
+        footer: &[String],
+    ) -> anyhow::Result<()> {
+        let height = list_height(header.len(), footer.len()).max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+
+        let mut out = std::io::stdout().lock();
+        queue!(
+            out,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+
+        let mut y = 0;
+        for line in header {
+            queue!(out, cursor::MoveTo(0, y), Print(line))?;
+            y += 1;
+        }
+        for (i, row) in rows.iter().enumerate().skip(self.scroll).take(height) {
+            queue!(out, cursor::MoveTo(0, y))?;
+            if i == self.cursor {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(format!("> {row}")),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(format!("  {row}")))?;
+            }
+            y += 1;
+        }
+
+        let (_, term_height) = terminal::size()?;
+        let footer_top = term_height.saturating_sub(footer.len() as u16);
+        for (i, line) in footer.iter().enumerate() {
+            queue!(out, cursor::MoveTo(0, footer_top + i as u16), Print(line))?;
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// ヘッダとフッタを除いた、リスト表示に使える行数を返す。
+fn list_height(header_len: usize, footer_len: usize) -> usize {
+    let (_, height) = terminal::size().unwrap_or((80, 24));
+    usize::from(height).saturating_sub(header_len + footer_len + 1)
+}
+
+/// セーブデータエディタ。
+#[derive(Debug)]
+struct Editor {
+    savedata: Savedata,
+    list: ListView,
+    /// 編集中の入力文字列。
+    input: Option<String>,
+    message: String,
+}
+
+impl Editor {
+    fn new(savedata: Savedata) -> Self {
+        Self {
+            savedata,
+            list: ListView::default(),
+            input: None,
+            message: String::new(),
+        }
+    }
+
+    fn run(&mut self) -> anyhow::Result<()> {
+        loop {
+            self.draw()?;
+
+            let key = read_key()?;
+            let field = &Savedata::FIELDS[self.list.cursor];
+
+            if let Some(input) = &mut self.input {
+                match key.code {
+                    KeyCode::Enter => {
+                        let input = self.input.take().unwrap();
+                        self.message = match parse_value(field.kind(), &input) {
+                            Some(value) => match field.set(&mut self.savedata, value) {
+                                Ok(()) => String::new(),
+                                Err(e) => e.to_string(),
+                            },
+                            None => format!("{}: invalid input '{input}'", field.name()),
+                        };
+                    }
+                    KeyCode::Esc => self.input = None,
+                    KeyCode::Backspace => {
+                        input.pop();
+                    }
+                    KeyCode::Char(c) if c.is_ascii_alphanumeric() => input.push(c),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Enter => {
+                    self.input = Some(String::new());
+                    self.message.clear();
+                }
+                KeyCode::Char(' ') if field.kind() == SavedataFieldKind::Bool => {
+                    let value = field.get(&self.savedata) ^ 1;
+                    field.set(&mut self.savedata, value)?;
+                }
+                code => self.list.navigate(code, Savedata::FIELDS.len()),
+            }
+        }
+    }
+
+    fn draw(&mut self) -> anyhow::Result<()> {
+        let header = vec!["↑↓: move  Enter: edit  Space: toggle  Esc: cancel  q: quit".to_owned()];
+
+        let rows: Vec<_> = Savedata::FIELDS
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let value = match &self.input {
+                    Some(input) if i == self.list.cursor => format!("{input}_"),
+                    _ => format_value(field.kind(), field.get(&self.savedata)),
+                };
+                let (min, max) = field.kind().range();
+                format!("{:<22} {:<12} ({min}..={max})", field.name(), value)
+            })
+            .collect();
+
+        let bytes = SerializedBytes::from_savedata(&self.savedata);
+        let password = bytes.to_password();
+        let checksum = bytes.checksum_calculated();
+        let normalized = if self.savedata.normalize() == self.savedata {
+            ""
+        } else {
+            " (equipment changes on load)"
+        };
+        let footer = vec![
+            self.message.clone(),
+            format!("password: {}", password.display_pretty()),
+            format!(
+                "checksum: add=0x{:02X} xor=0x{:02X}{normalized}",
+                checksum.sum_add().get(),
+                checksum.sum_xor().get()
+            ),
+        ];
+
+        self.list.draw(&header, &rows, &footer)
+    }
+}
+
+fn format_value(kind: SavedataFieldKind, value: u16) -> String {
+    match kind {
+        SavedataFieldKind::Bool => if value != 0 { "[x]" } else { "[ ]" }.to_owned(),
+        SavedataFieldKind::Item if value == 0 => "-".to_owned(),
+        _ => format!("{value} (0x{value:X})"),
+    }
+}
+
+/// 入力文字列を値として解釈する。10 進数、0x で始まる 16 進数、および真偽値の true/false を受け付ける。
+fn parse_value(kind: SavedataFieldKind, s: &str) -> Option<u16> {
+    match (kind, s) {
+        (SavedataFieldKind::Bool, "true") => Some(1),
+        (SavedataFieldKind::Bool, "false") => Some(0),
+        _ => match s.strip_prefix("0x") {
+            Some(hex) => u16::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        },
+    }
+}
@@ -8,15 +8,10 @@ fn main() -> anyhow::Result<()> {
     let password = Password::parse(&password)?;
     let bytes = SerializedBytes::from_password(&password);
 
-    if let Some(savedata) = bytes.to_savedata() {
-        println!("{savedata:?}");
-    } else {
-        println!(
-            "checksum mismatch: embed={:?}, calculated={:?}",
-            bytes.checksum_embed(),
-            bytes.checksum_calculated()
-        );
-    };
+    match bytes.to_savedata_checked() {
+        Ok(savedata) => println!("{savedata:?}"),
+        Err(e) => println!("{e} (len={})", e.len),
+    }
 
     Ok(())
 }
@@ -0,0 +1,125 @@
+/* NES Momotarou Densetsu (J) password library: C API */
+
+#ifndef MOMODEN_PASSWORD_H
+#define MOMODEN_PASSWORD_H
+
+/* This file is generated by cbindgen. Do not edit manually. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// 成功。
+#define MOMODEN_PASSWORD_OK 0
+
+// 引数にヌルポインタが渡された。
+#define MOMODEN_PASSWORD_ERR_NULL -1
+
+// 文字列が UTF-8 として正しくない。
+#define MOMODEN_PASSWORD_ERR_UTF8 -2
+
+// パスワードの文字数が正しくない。
+#define MOMODEN_PASSWORD_ERR_INVALID_LENGTH -3
+
+// パスワードに無効な文字が含まれている。
+#define MOMODEN_PASSWORD_ERR_INVALID_CHAR -4
+
+// パスワードのチェックサムが一致しない。
+#define MOMODEN_PASSWORD_ERR_CHECKSUM -5
+
+// セーブデータのフィールドが範囲外。
+#define MOMODEN_PASSWORD_ERR_OUT_OF_RANGE -6
+
+// 出力バッファが小さすぎる。
+#define MOMODEN_PASSWORD_ERR_BUFFER_TOO_SMALL -7
+
+// 内部エラー(パニック)。
+#define MOMODEN_PASSWORD_ERR_PANIC -100
+
+// `momoden_password_encode()` の出力に十分なバッファサイズ(終端の NUL を含む)。
+#define MOMODEN_PASSWORD_ENCODE_BUF_LEN 115
+
+// `Savedata` の C 向け表現。
+//
+// フラグ群はビットマスクで、`Savedata` 内の各構造体のフィールド順に bit0 から割り当てる。
+typedef struct SavedataC {
+  // 経験値。
+  uint16_t xp;
+  // 所持金。
+  uint16_t purse;
+  // 預金 (0..=0x3F)。
+  uint8_t deposit;
+  // 年齢。
+  uint8_t age;
+  // 加齢タイマー上位バイト。
+  uint8_t age_timer_hi;
+  // 術習得状態 (bit0: きんたん, ..., bit7: ほうひ)。
+  uint8_t spells;
+  // イベント進行状態 (bit0: hanasaka, ..., bit7: hohoemi)。
+  uint8_t events;
+  // 宝物所持状態 (bit0: dragon, ..., bit4: swallow)。
+  uint8_t treasures;
+  // お供存在状態 (bit0: 犬, bit1: キジ, bit2: 猿)。
+  uint8_t minions;
+  // 復活地点ID (0..=0xF)。
+  uint8_t respawn;
+  // ひえんブックマーク (bit0: 旅立ちの村, ..., bit9: 飛燕の城)。
+  uint16_t bookmarks;
+  // 兜インデックス (0..=3)。
+  uint8_t helm;
+  // 武器インデックス (0..=0xF)。
+  uint8_t weapon;
+  // 鎧インデックス (0..=0xF)。
+  uint8_t armor;
+  // 靴インデックス (0..=7)。
+  uint8_t shoes;
+  // いでたち0インデックス (0..=3)。
+  uint8_t accessory0;
+  // いでたち1インデックス (0..=3)。
+  uint8_t accessory1;
+  // いでたち2インデックス (0..=1)。
+  uint8_t accessory2;
+  // いでたち3インデックス (0..=1)。
+  uint8_t accessory3;
+  // インベントリ内のアイテム数 (0..=8)。
+  uint8_t inventory_len;
+  // インベントリ内のアイテムID (1..=0x3F)。先頭 `inventory_len` 個のみが有効。
+  uint8_t inventory[8];
+} SavedataC;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// パスワード文字列(NUL 終端 UTF-8)をデコードし、それに記録されたセーブデータを `out` に書き込む。
+//
+// 書き込まれるのはパスワードに記録された生のセーブデータで、ロード時の装備の正規化は行わない。
+//
+// # Safety
+//
+// `password` は NUL 終端文字列を、`out` は書き込み可能な `SavedataC` を指していなければならない。
+int momoden_password_decode(const char *password,
+                            struct SavedataC *out);
+
+// セーブデータをパスワード化し、NUL 終端 UTF-8 文字列として `buf` に書き込む。
+//
+// `len` は `buf` のサイズ(バイト単位)。`MOMODEN_PASSWORD_ENCODE_BUF_LEN` 以上あれば常に十分。
+//
+// # Safety
+//
+// `savedata` は有効な `SavedataC` を、`buf` は `len` バイト書き込み可能な領域を指していなければならない。
+int momoden_password_encode(const struct SavedataC *savedata,
+                            char *buf,
+                            size_t len);
+
+// パスワード文字列(NUL 終端 UTF-8)が有効ならば `MOMODEN_PASSWORD_OK` を返す。
+//
+// # Safety
+//
+// `password` は NUL 終端文字列を指していなければならない。
+int momoden_password_validate(const char *password);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MOMODEN_PASSWORD_H */
@@ -0,0 +1,74 @@
+# maturin develop --extras=test && pytest
+
+import pytest
+
+import momoden_password
+from momoden_password import Password, PasswordError, Savedata
+
+
+def test_parse():
+    password = Password.parse("おにのばか")
+    assert str(password) == "おにのばか"
+    assert repr(password) == "Password('おにのばか')"
+    assert len(password) == 5
+    assert password.is_valid()
+    assert not password.is_special()
+    assert password == Password.parse("おにのばか")
+
+
+def test_parse_error():
+    with pytest.raises(PasswordError, match="invalid character 'x' at position 2"):
+        Password.parse("おにx")
+    with pytest.raises(ValueError, match="must contain"):
+        Password.parse("")
+
+
+def test_savedata():
+    savedata = Password.parse("おにのばか").savedata()
+    assert set(savedata.keys()) >= {"xp", "purse", "inventory"}
+    assert "xp" in savedata
+    assert savedata["xp"] == savedata.to_dict()["xp"]
+    assert dict(savedata.to_dict()) == {key: savedata[key] for key in savedata}
+    with pytest.raises(KeyError):
+        savedata["nonexistent"]
+
+    with pytest.raises(PasswordError, match="checksum mismatch"):
+        Password.parse("おにのばき").savedata()
+
+
+def test_round_trip():
+    savedata = Password.parse("おにのばか").savedata()
+    password = savedata.to_password()
+    assert password.is_valid()
+    assert password.savedata() == savedata
+
+    d = savedata.to_dict()
+    d["xp"] = 1234
+    d["spells"]["hien"] = True
+    edited = Savedata(d)
+    assert edited["xp"] == 1234
+    assert edited.to_password().savedata() == edited
+
+    assert Savedata().to_password().savedata() == Savedata()
+
+
+def test_savedata_invalid():
+    d = Savedata().to_dict()
+    d["deposit"] = 0x40
+    with pytest.raises(ValueError, match="invalid savedata"):
+        Savedata(d)
+
+
+def test_search():
+    passwords = momoden_password.search("おにのば?")
+    assert Password.parse("おにのばか") in passwords
+    assert all(password.is_valid() for password in passwords)
+    assert passwords == sorted(passwords)
+
+    assert momoden_password.search("あ???", limit=3) == momoden_password.search("あ???")[:3]
+    assert momoden_password.search("あ???", limit=3, threads=4) == momoden_password.search(
+        "あ???", limit=3
+    )
+
+    with pytest.raises(PasswordError):
+        momoden_password.search("x")
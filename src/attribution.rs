@@ -0,0 +1,190 @@
+use std::ops::Range;
+
+use crate::layout::SERIALIZED_LAYOUT;
+use crate::password::Password;
+
+/// パスワードの 1 文字がデコード後にどのフィールドのビットを担うか。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CharAttribution {
+    /// パスワード内の位置(0 始まり)。
+    pub pos: usize,
+    /// この文字の 6bit に含まれるフィールド(先頭から順)。
+    pub fields: Vec<FieldOverlap>,
+    /// エンコード後の形でこの文字を変えると、以降の全ての文字が変わるかどうか。
+    ///
+    /// パスワードは直前の文字との XOR で連鎖しているので、デコード後のバイトを 1 つ変えると
+    /// エンコード後のパスワードではそれ以降の全ての文字が変わる。最後の文字のみ `false`。
+    pub perturbs_following: bool,
+}
+
+/// 1 文字と 1 フィールドの重なり。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldOverlap {
+    /// フィールド名。チェックサムは `checksum.add`, `checksum.xor`。
+    pub name: String,
+    /// フィールドのビット列のうち、この文字が担う範囲(フィールドの上位ビットを 0 とする)。
+    pub field_bits: Range<usize>,
+    /// フィールド全体のビット数。
+    pub field_len: usize,
+}
+
+impl FieldOverlap {
+    /// フィールド全体のうち、この文字が担うビットの割合を返す。
+    pub fn fraction(&self) -> f64 {
+        self.field_bits.len() as f64 / self.field_len as f64
+    }
+}
+
+/// 長さ `password_len` のパスワードの各文字が担うフィールドを返す。
+///
+/// 先頭 2 文字はチェックサムで、以降の文字がゲーム状態のビット列を 6bit ずつ担う。
+/// インベントリは 8 個全てが格納されているものとして扱う(実際には終端の 0 以降は読まれない)。
+///
+/// # Panics
+///
+/// `password_len` が `Password::MIN_LEN..=Password::MAX_LEN` の範囲外ならパニックする。
+pub fn attribution(password_len: usize) -> Vec<CharAttribution> {
+    assert!(
+        matches!(password_len, Password::MIN_LEN..=Password::MAX_LEN),
+        "password length {password_len} is out of range"
+    );
+
+    // (名前, チェックサムを除いたビット列における範囲)
+    let spans: Vec<(String, Range<usize>)> = SERIALIZED_LAYOUT
+        .iter()
+        .map(|spec| (spec.field.to_string(), spec.bits()))
+        .collect();
+
+    (0..password_len)
+        .map(|pos| {
+            let fields = match pos {
+                0 => vec![checksum_overlap("checksum.add")],
+                1 => vec![checksum_overlap("checksum.xor")],
+                _ => {
+                    let window = 6 * (pos - 2)..6 * (pos - 1);
+                    spans
+                        .iter()
+                        .filter_map(|(name, span)| {
+                            let start = span.start.max(window.start);
+                            let end = span.end.min(window.end);
+                            (start < end).then(|| FieldOverlap {
+                                name: name.clone(),
+                                field_bits: start - span.start..end - span.start,
+                                field_len: span.len(),
+                            })
+                        })
+                        .collect()
+                }
+            };
+
+            CharAttribution {
+                pos,
+                fields,
+                perturbs_following: pos + 1 < password_len,
+            }
+        })
+        .collect()
+}
+
+fn checksum_overlap(name: &str) -> FieldOverlap {
+    FieldOverlap {
+        name: name.to_owned(),
+        field_bits: 0..6,
+        field_len: 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn test_attribution() {
+        let attr = attribution(Password::MAX_LEN);
+        assert_eq!(attr.len(), Password::MAX_LEN);
+
+        assert_eq!(attr[0].fields[0].name, "checksum.add");
+        assert_eq!(attr[1].fields[0].name, "checksum.xor");
+
+        // 3 文字目は age_timer_hi の上位 6bit を、4 文字目はその残りと purse (high) の上位 4bit を担う。
+        assert_eq!(
+            attr[2].fields,
+            [FieldOverlap {
+                name: "age_timer_hi".to_owned(),
+                field_bits: 0..6,
+                field_len: 8,
+            }]
+        );
+        assert_eq!(attr[2].fields[0].fraction(), 0.75);
+        assert_eq!(attr[3].fields[0].name, "age_timer_hi");
+        assert_eq!(attr[3].fields[0].field_bits, 6..8);
+        assert_eq!(attr[3].fields[1].name, "purse (high)");
+        assert_eq!(attr[3].fields[1].field_bits, 0..4);
+
+        assert!(attr[..Password::MAX_LEN - 1]
+            .iter()
+            .all(|a| a.perturbs_following));
+        assert!(!attr[Password::MAX_LEN - 1].perturbs_following);
+    }
+
+    #[test]
+    fn test_attribution_covers_all_fields() {
+        // 各フィールドのビットはちょうど 1 回ずつ、順に現れる。
+        let mut covered: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        for a in attribution(Password::MAX_LEN) {
+            let n_bits: usize = a.fields.iter().map(|f| f.field_bits.len()).sum();
+            assert!(n_bits <= 6);
+            for f in a.fields {
+                let (next, len) = covered.entry(f.name.clone()).or_insert((0, f.field_len));
+                assert_eq!(f.field_bits.start, *next, "{}", f.name);
+                *next = f.field_bits.end;
+                assert_eq!(*len, f.field_len);
+            }
+        }
+
+        assert_eq!(covered.len(), 2 + SERIALIZED_LAYOUT.len());
+        for (name, (next, len)) in covered {
+            assert_eq!(next, len, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_attribution_matches_trace() {
+        use crate::serialized::SerializedBytes;
+
+        let password =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        let attr = attribution(password.len());
+        let trace = SerializedBytes::decode_trace(&password);
+
+        for field in &trace.fields {
+            let mut offset = 0;
+            for (byte, range) in &field.sources {
+                let expected = offset..offset + range.len();
+                assert!(attr[*byte]
+                    .fields
+                    .iter()
+                    .any(|f| f.name == field.name && f.field_bits == expected));
+                offset = expected.end;
+            }
+        }
+    }
+
+    #[test]
+    fn test_attribution_short() {
+        let attr = attribution(1);
+        assert_eq!(attr.len(), 1);
+        assert!(!attr[0].perturbs_following);
+
+        // 3 文字目以降がないので、ゲーム状態のビットは担わない。
+        assert!(attribution(2).iter().all(|a| a.fields.len() == 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_attribution_empty() {
+        attribution(0);
+    }
+}
@@ -0,0 +1,352 @@
+//! 桃太郎伝説 (FC) パスワードツール。
+//!
+//! 終了コード:
+//!
+//! * 0: 成功
+//! * 1: パスワードが無効である、または結果が見つからない
+//! * 2: 入力が不正である(コマンドライン引数、パスワード文字列、JSON など)
+//!
+//! 結果は標準出力へ、エラーや補足情報は標準エラー出力へ出力する。
+
+use std::io::{Read as _, Write as _};
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use momoden_password::*;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "NES Momotarou Densetsu (J) password tool")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// パスワードをロードし、セーブデータ(生のもの、および実際にロードされるもの)を出力する。
+    Load {
+        password: Password,
+
+        /// JSON 形式で出力する。
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// セーブデータをパスワード化する。
+    ///
+    /// 元となるセーブデータを --json または --from で指定し(省略時はデフォルト値)、
+    /// 各フィールドをオプションで上書きできる。
+    Save {
+        /// 元となるセーブデータを JSON ファイルから読み込む("-" ならば標準入力)。
+        #[arg(long, value_name = "FILE", conflicts_with = "from")]
+        json: Option<PathBuf>,
+
+        /// 元となるセーブデータをパスワードから読み込む(実際にロードされる状態を用いる)。
+        #[arg(long, value_name = "PASSWORD")]
+        from: Option<Password>,
+
+        /// 経験値。
+        #[arg(long)]
+        xp: Option<u16>,
+
+        /// 所持金。
+        #[arg(long)]
+        purse: Option<u16>,
+
+        /// 預金。
+        #[arg(long)]
+        deposit: Option<Deposit>,
+
+        /// 年齢。
+        #[arg(long)]
+        age: Option<u8>,
+
+        /// 加齢タイマー上位バイト。
+        #[arg(long)]
+        age_timer_hi: Option<u8>,
+
+        /// 復活地点ID。
+        #[arg(long)]
+        respawn: Option<RespawnId>,
+
+        /// パスワードを空白区切りで出力する。
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// パターン('?' はワイルドカード)にマッチする有効なパスワードを列挙する。
+    Search {
+        pattern: Pattern,
+
+        /// 出力するパスワードの最大個数。
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// 探索に用いるスレッド数。
+        #[arg(long, default_value_t = 1)]
+        threads: usize,
+
+        /// 特殊パスワード(音楽室/美術室)を除外する。
+        #[arg(long)]
+        exclude_special: bool,
+    },
+
+    /// 無効なパスワードに対し、1 文字の置換で有効になる候補を列挙する。
+    Fix { password: Password },
+}
+
+/// コマンドの失敗。
+#[derive(Debug)]
+enum Failure {
+    /// パスワードが無効である、または結果が見つからない。
+    Rejected(String),
+
+    /// 入力が不正である。
+    Input(String),
+}
+
+impl Failure {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Rejected(_) => ExitCode::from(1),
+            Self::Input(_) => ExitCode::from(2),
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::Rejected(msg) | Self::Input(msg) => msg,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let res = match cli.command {
+        Command::Load { password, json } => cmd_load(&password, json),
+        Command::Save {
+            json,
+            from,
+            xp,
+            purse,
+            deposit,
+            age,
+            age_timer_hi,
+            respawn,
+            pretty,
+        } => {
+            let edits = SaveEdits {
+                xp,
+                purse,
+                deposit,
+                age,
+                age_timer_hi,
+                respawn,
+            };
+            cmd_save(json, from, &edits, pretty)
+        }
+        Command::Search {
+            pattern,
+            limit,
+            threads,
+            exclude_special,
+        } => {
+            let options = SearchOptions {
+                limit,
+                exclude_special,
+                threads,
+                ..Default::default()
+            };
+            cmd_search(&pattern, &options)
+        }
+        Command::Fix { password } => cmd_fix(&password),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("error: {}", failure.message());
+            failure.exit_code()
+        }
+    }
+}
+
+/// 標準出力に 1 行出力する。出力できなかった場合(パイプが閉じられた場合など)は `Break` を返す。
+fn out(line: impl std::fmt::Display) -> ControlFlow<()> {
+    match writeln!(std::io::stdout().lock(), "{line}") {
+        Ok(()) => ControlFlow::Continue(()),
+        Err(_) => ControlFlow::Break(()),
+    }
+}
+
+fn load_savedata(password: &Password) -> Result<Savedata, Failure> {
+    let bytes = SerializedBytes::from_password(password);
+
+    bytes.to_savedata().ok_or_else(|| {
+        Failure::Rejected(format!(
+            "checksum mismatch: embed={:?}, calculated={:?}",
+            bytes.checksum_embed(),
+            bytes.checksum_calculated()
+        ))
+    })
+}
+
+fn cmd_load(password: &Password, json: bool) -> Result<(), Failure> {
+    let savedata = load_savedata(password)?;
+    let savedata_norm = savedata.normalize();
+
+    if json {
+        #[derive(serde::Serialize)]
+        struct Output<'a> {
+            password: String,
+            special: bool,
+            raw: &'a Savedata,
+            normalized: &'a Savedata,
+        }
+
+        let output = Output {
+            password: password.display().to_string(),
+            special: password.is_special(),
+            raw: &savedata,
+            normalized: &savedata_norm,
+        };
+        let _ = out(serde_json::to_string_pretty(&output).unwrap());
+    } else {
+        let _ = out(format_args!("password: {}", password.display_pretty()));
+        if password.is_special() {
+            let _ = out("special: true");
+        }
+        let _ = out(format_args!("raw: {savedata:#?}"));
+        let _ = out(format_args!("normalized: {savedata_norm:#?}"));
+    }
+
+    Ok(())
+}
+
+/// `save` サブコマンドでのフィールド上書き指定。
+#[derive(Debug)]
+struct SaveEdits {
+    xp: Option<u16>,
+    purse: Option<u16>,
+    deposit: Option<Deposit>,
+    age: Option<u8>,
+    age_timer_hi: Option<u8>,
+    respawn: Option<RespawnId>,
+}
+
+impl SaveEdits {
+    fn apply(&self, savedata: &mut Savedata) {
+        if let Some(xp) = self.xp {
+            savedata.xp = xp;
+        }
+        if let Some(purse) = self.purse {
+            savedata.purse = purse;
+        }
+        if let Some(deposit) = self.deposit {
+            savedata.deposit = deposit;
+        }
+        if let Some(age) = self.age {
+            savedata.age = age;
+        }
+        if let Some(age_timer_hi) = self.age_timer_hi {
+            savedata.age_timer_hi = age_timer_hi;
+        }
+        if let Some(respawn) = self.respawn {
+            savedata.respawn = respawn;
+        }
+    }
+}
+
+fn cmd_save(
+    json: Option<PathBuf>,
+    from: Option<Password>,
+    edits: &SaveEdits,
+    pretty: bool,
+) -> Result<(), Failure> {
+    let mut savedata = if let Some(path) = json {
+        read_savedata_json(&path)?
+    } else if let Some(password) = from {
+        load_savedata(&password)?.normalize()
+    } else {
+        Savedata::default()
+    };
+
+    edits.apply(&mut savedata);
+
+    let password = SerializedBytes::from_savedata(&savedata).to_password();
+    if pretty {
+        let _ = out(password.display_pretty());
+    } else {
+        let _ = out(password.display());
+    }
+
+    Ok(())
+}
+
+fn read_savedata_json(path: &PathBuf) -> Result<Savedata, Failure> {
+    let mut buf = String::new();
+    let res = if path.as_os_str() == "-" {
+        std::io::stdin().read_to_string(&mut buf).map(|_| ())
+    } else {
+        std::fs::File::open(path).and_then(|mut file| file.read_to_string(&mut buf).map(|_| ()))
+    };
+    res.map_err(|e| Failure::Input(format!("cannot read {}: {e}", path.display())))?;
+
+    serde_json::from_str(&buf)
+        .map_err(|e| Failure::Input(format!("invalid savedata JSON in {}: {e}", path.display())))
+}
+
+fn cmd_search(pattern: &Pattern, options: &SearchOptions) -> Result<(), Failure> {
+    let mut count = 0_usize;
+
+    if options.threads <= 1 {
+        // 単一スレッドならば見つかり次第出力する。
+        search_with(pattern, options, |password| {
+            count += 1;
+            out(password.display())
+        });
+    } else {
+        for password in search(pattern, options) {
+            count += 1;
+            if out(password.display()).is_break() {
+                break;
+            }
+        }
+    }
+
+    eprintln!("count: {count}");
+
+    if count == 0 {
+        return Err(Failure::Rejected("no valid password found".to_owned()));
+    }
+
+    Ok(())
+}
+
+fn cmd_fix(password: &Password) -> Result<(), Failure> {
+    if password.is_valid() {
+        eprintln!("password is already valid");
+        let _ = out(password.display());
+        return Ok(());
+    }
+
+    let candidates = password.repair(1);
+    for cand in &candidates {
+        let _ = out(cand.display());
+    }
+    let count = candidates.len();
+
+    eprintln!("count: {count}");
+
+    if count == 0 {
+        return Err(Failure::Rejected(
+            "no valid password within 1 substitution".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
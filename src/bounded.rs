@@ -2,126 +2,773 @@ use std::num::ParseIntError;
 
 use thiserror::Error;
 
+#[cfg(not(feature = "forbid-unsafe"))]
 use crate::macros::assert_unchecked;
+use crate::macros::unsafe_or;
 
-/// 値域が `MIN..=MAX` に制限された `u8`。
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct BoundedU8<const MIN: u8, const MAX: u8>(u8);
+/// 値域が制限された整数型を定義する。
+///
+/// `BoundedU8`, `BoundedU16` の間で API が乖離しないよう、共通部分は全てここで生成する。
+macro_rules! define_bounded_int {
+    ($name:ident, $inner:ident; [$($from:ty)*]) => {
+        #[doc = concat!("値域が `MIN..=MAX` に制限された `", stringify!($inner), "`。")]
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(
+            feature = "forbid-unsafe",
+            derive(
+                zerocopy::Immutable,
+                zerocopy::IntoBytes,
+                zerocopy::KnownLayout,
+                zerocopy::TryFromBytes
+            )
+        )]
+        pub struct $name<const MIN: $inner, const MAX: $inner>($inner);
 
-impl<const MIN: u8, const MAX: u8> BoundedU8<MIN, MAX> {
-    /// 最小の内部値。
-    pub const MIN_VALUE: u8 = {
-        // MIN_VALUE はインスタンス生成時に必ず使われるので、
-        // ここに assert を書いておけばコンパイル時に MIN <= MAX のチェックができる。
-        // (ただし、インスタンスが生成されない型についてはチェックできない)
-        assert!(MIN <= MAX);
-        MIN
+        impl<const MIN: $inner, const MAX: $inner> $name<MIN, MAX> {
+            /// 最小の内部値。
+            pub const MIN_VALUE: $inner = {
+                // MIN_VALUE はインスタンス生成時に必ず使われるので、
+                // ここに assert を書いておけばコンパイル時に MIN <= MAX のチェックができる。
+                // (ただし、インスタンスが生成されない型についてはチェックできない)
+                assert!(MIN <= MAX);
+                MIN
+            };
+
+            /// 最大の内部値。
+            pub const MAX_VALUE: $inner = MAX;
+
+            /// 最小値。
+            pub const MIN: Self = Self(Self::MIN_VALUE);
+
+            /// 最大値。
+            pub const MAX: Self = Self(Self::MAX_VALUE);
+
+            /// 値の個数。
+            pub const COUNT: usize = (Self::MAX_VALUE - Self::MIN_VALUE) as usize + 1;
+
+            /// 引数が値域内にあるかどうかを返す。
+            pub const fn in_range(x: $inner) -> bool {
+                Self::MIN_VALUE <= x && x <= Self::MAX_VALUE
+            }
+
+            #[doc = concat!("`", stringify!($inner), "` から `", stringify!($name), "` を作る。引数が値域内になければ `None` を返す。")]
+            pub const fn new(inner: $inner) -> Option<Self> {
+                if Self::in_range(inner) {
+                    Some(Self(inner))
+                } else {
+                    None
+                }
+            }
+
+            #[doc = concat!("`", stringify!($inner), "` から `", stringify!($name), "` を作る。")]
+            ///
+            /// # Safety
+            ///
+            /// 引数は値域内になければならない。
+            #[cfg(not(feature = "forbid-unsafe"))]
+            pub const unsafe fn new_unchecked(inner: $inner) -> Self {
+                assert_unchecked!(Self::in_range(inner));
+                Self(inner)
+            }
+
+            #[doc = concat!("`", stringify!($inner), "` から `", stringify!($name), "` を作る。")]
+            ///
+            /// `forbid-unsafe` feature 有効時は安全な関数となり、引数が値域外ならパニックする。
+            #[cfg(feature = "forbid-unsafe")]
+            pub const fn new_unchecked(inner: $inner) -> Self {
+                match Self::new(inner) {
+                    Some(x) => x,
+                    None => panic!("value out of range"),
+                }
+            }
+
+            /// 内部値を返す。
+            pub const fn get(self) -> $inner {
+                self.0
+            }
+
+            /// `start` 以上 `end` 以下の値を昇順で返す。
+            ///
+            /// `start > end` の場合、空のイテレータを返す。
+            pub fn range(
+                start: Self,
+                end: Self,
+            ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator {
+                (start.0..=end.0).map(Self)
+            }
+
+            /// `self` 以上の値を昇順で返す。
+            pub fn iter_from(
+                self,
+            ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator {
+                Self::range(self, Self::MAX)
+            }
+
+            #[doc = concat!("値域がより広い `", stringify!($name), "` に変換する。")]
+            ///
+            /// 変換先の値域が変換元の値域を包含していなければコンパイルエラーとなる。
+            ///
+            /// ```compile_fail
+            #[doc = concat!("# use momoden_password::", stringify!($name), ";")]
+            #[doc = concat!("let x = ", stringify!($name), "::<0, 0xF>::MAX;")]
+            #[doc = concat!("let _: ", stringify!($name), "<0, 7> = x.widen();")]
+            /// ```
+            pub const fn widen<const MIN2: $inner, const MAX2: $inner>(self) -> $name<MIN2, MAX2> {
+                const { assert!(MIN2 <= MIN && MAX <= MAX2) };
+
+                $name(self.0)
+            }
+
+            #[doc = concat!("値域が異なる `", stringify!($name), "` に変換する。値が変換先の値域外にあればエラーを返す。")]
+            pub const fn try_narrow<const MIN2: $inner, const MAX2: $inner>(
+                self,
+            ) -> Result<$name<MIN2, MAX2>, BoundedIntegerRangeError> {
+                if self.0 < $name::<MIN2, MAX2>::MIN_VALUE {
+                    Err(BoundedIntegerRangeError::BelowMin)
+                } else if self.0 > $name::<MIN2, MAX2>::MAX_VALUE {
+                    Err(BoundedIntegerRangeError::AboveMax)
+                } else {
+                    Ok($name(self.0))
+                }
+            }
+
+            /// 指定された基数で文字列をパースする。
+            pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, BoundedIntegerParseError> {
+                let value = $inner::from_str_radix(s, radix)?;
+
+                if value < Self::MIN_VALUE {
+                    return Err(BoundedIntegerParseError::BelowMin);
+                } else if value > Self::MAX_VALUE {
+                    return Err(BoundedIntegerParseError::AboveMax);
+                }
+
+                Ok(Self(value))
+            }
+        }
+
+        /// 最小値が 0 の場合、デフォルト値は 0 となる。
+        ///
+        /// 最小値が非 0 の場合、`Default` は実装しない。
+        impl<const MAX: $inner> Default for $name<0, MAX> {
+            fn default() -> Self {
+                Self(0)
+            }
+        }
+
+        $(
+            impl<const MIN: $inner, const MAX: $inner> From<$name<MIN, MAX>> for $from {
+                fn from(x: $name<MIN, MAX>) -> Self {
+                    Self::from(x.get())
+                }
+            }
+        )*
+
+        impl<const MIN: $inner, const MAX: $inner> std::str::FromStr for $name<MIN, MAX> {
+            type Err = BoundedIntegerParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_str_radix(s, 10)
+            }
+        }
+
+        impl_fmt_traits!($name, $inner; Binary, Debug, Display, LowerExp, LowerHex, Octal, UpperExp, UpperHex);
+
+        /// 値域内から一様に選ぶ。
+        #[cfg(feature = "arbitrary")]
+        impl<'a, const MIN: $inner, const MAX: $inner> arbitrary::Arbitrary<'a> for $name<MIN, MAX> {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                u.int_in_range(MIN..=MAX).map(|x| Self::new(x).unwrap())
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                <$inner as arbitrary::Arbitrary>::size_hint(depth)
+            }
+        }
+
+        /// 内部値としてシリアライズする。
+        #[cfg(feature = "serde")]
+        impl<const MIN: $inner, const MAX: $inner> serde::Serialize for $name<MIN, MAX> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize(serializer)
+            }
+        }
+
+        /// 内部値からデシリアライズする。値域外ならばエラーとなる。
+        #[cfg(feature = "serde")]
+        impl<'de, const MIN: $inner, const MAX: $inner> serde::Deserialize<'de> for $name<MIN, MAX> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = $inner::deserialize(deserializer)?;
+                Self::new(value).ok_or_else(|| {
+                    serde::de::Error::custom(format_args!(
+                        "number {value} is out of range {}..={}",
+                        Self::MIN_VALUE,
+                        Self::MAX_VALUE
+                    ))
+                })
+            }
+        }
+
+        /// 値域を `minimum`/`maximum` とする整数のスキーマ。値域ごとに異なるので、常にインライン展開される。
+        #[cfg(feature = "schemars")]
+        impl<const MIN: $inner, const MAX: $inner> schemars::JsonSchema for $name<MIN, MAX> {
+            fn inline_schema() -> bool {
+                true
+            }
+
+            fn schema_name() -> std::borrow::Cow<'static, str> {
+                format!("{}_{MIN}_{MAX}", stringify!($name)).into()
+            }
+
+            fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                schemars::json_schema!({
+                    "type": "integer",
+                    "minimum": MIN,
+                    "maximum": MAX,
+                })
+            }
+        }
     };
+}
 
-    /// 最大の内部値。
-    pub const MAX_VALUE: u8 = MAX;
+macro_rules! impl_fmt_traits {
+    ($name:ident, $inner:ty; $($trait:ident),*) => {
+        $(
+            impl<const MIN: $inner, const MAX: $inner> std::fmt::$trait for $name<MIN, MAX> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::$trait::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+
+define_bounded_int!(BoundedU8, u8; [i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize]);
+define_bounded_int!(BoundedU16, u16; [i32 i64 i128 u16 u32 u64 u128 usize]);
+
+impl<const MIN: u8, const MAX: u8> BoundedU8<MIN, MAX> {
+    /// 全ての値を昇順で並べたスライス。
+    #[cfg(not(feature = "forbid-unsafe"))]
+    pub const ALL: &'static [Self] = {
+        // `Self` は `u8` と同じレイアウトを持つので、全ての `u8` 値を並べたテーブルの部分スライスを流用できる。
+        let table: &'static [u8; 0x100] = &U8_TABLE;
+        unsafe {
+            std::slice::from_raw_parts(
+                table.as_ptr().add(Self::MIN_VALUE as usize).cast::<Self>(),
+                Self::COUNT,
+            )
+        }
+    };
 
-    /// 最小値。
-    pub const MIN: Self = unsafe { Self::new_unchecked(Self::MIN_VALUE) };
+    /// 全ての値を昇順で並べたスライス。
+    #[cfg(feature = "forbid-unsafe")]
+    pub const ALL: &'static [Self] = {
+        // 全ての `u8` 値に対応するテーブルから値域内の部分を切り出す。値域外の要素は外部に露出しない。
+        let (_, all) = Self::TABLE.split_at(Self::MIN_VALUE as usize);
+        all.split_at(Self::COUNT).0
+    };
 
-    /// 最大値。
-    pub const MAX: Self = unsafe { Self::new_unchecked(Self::MAX_VALUE) };
+    #[cfg(feature = "forbid-unsafe")]
+    const TABLE: &'static [Self; 0x100] = &{
+        let mut table = [Self(0); 0x100];
+        let mut i = 0;
+        while i < table.len() {
+            table[i] = Self(i as u8);
+            i += 1;
+        }
+        table
+    };
 
-    /// 引数が値域内にあるかどうかを返す。
-    pub const fn in_range(x: u8) -> bool {
-        Self::MIN_VALUE <= x && x <= Self::MAX_VALUE
+    /// 全ての値を昇順で返す。
+    pub fn all(
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator {
+        Self::ALL.iter().copied()
     }
 
-    /// `u8` から `BoundedU8` を作る。引数が値域内になければ `None` を返す。
-    pub const fn new(inner: u8) -> Option<Self> {
-        if Self::in_range(inner) {
-            Some(unsafe { Self::new_unchecked(inner) })
-        } else {
-            None
+    /// 全ての値を昇順で並べた配列を返す。
+    ///
+    /// `N` は `Self::COUNT` と等しくなければならない(さもなくばコンパイルエラーとなる)。
+    pub const fn all_array<const N: usize>() -> [Self; N] {
+        const { assert!(N == Self::COUNT) };
+
+        let mut res = [Self::MIN; N];
+        let mut i = 0;
+        while i < N {
+            res[i] = Self(Self::MIN_VALUE + i as u8);
+            i += 1;
         }
+
+        res
     }
 
-    /// `u8` から `BoundedU8` を作る。
-    ///
-    /// # Safety
-    ///
-    /// 引数は値域内になければならない。
-    pub const unsafe fn new_unchecked(inner: u8) -> Self {
-        assert_unchecked!(Self::in_range(inner));
-        Self(inner)
+    /// `BoundedU8` のスライスを `u8` のスライスとして見る。コピーは行わない。
+    pub fn slice_as_u8(xs: &[Self]) -> &[u8] {
+        // `Self` は `#[repr(transparent)]` なので `u8` と同じレイアウトを持つ。
+        unsafe_or!(
+            std::slice::from_raw_parts(xs.as_ptr().cast::<u8>(), xs.len()),
+            zerocopy::IntoBytes::as_bytes(xs)
+        )
     }
 
-    /// 内部値を返す。
-    pub const fn get(self) -> u8 {
-        self.0
+    /// `u8` のスライスを `BoundedU8` のスライスとして見る。コピーは行わない。
+    ///
+    /// 値域外の要素があればエラーを返す(最初のもののみを報告する)。
+    pub fn slice_from_u8(xs: &[u8]) -> Result<&[Self], BoundedSliceError> {
+        if let Some(index) = xs.iter().position(|&x| !Self::in_range(x)) {
+            return Err(BoundedSliceError {
+                index,
+                value: xs[index],
+            });
+        }
+
+        Ok(unsafe_or!(
+            std::slice::from_raw_parts(xs.as_ptr().cast::<Self>(), xs.len()),
+            <[Self] as zerocopy::TryFromBytes>::try_ref_from_bytes(xs)
+                .unwrap_or_else(|_| unreachable!())
+        ))
     }
+}
 
+impl<const MIN: u16, const MAX: u16> BoundedU16<MIN, MAX> {
     /// 全ての値を昇順で返す。
+    ///
+    /// (`BoundedU16` は値の個数が多くなりうるので、`ALL` は提供しない)
     pub fn all(
-    ) -> impl Iterator<Item = Self> + DoubleEndedIterator + ExactSizeIterator + std::iter::FusedIterator
-    {
-        (Self::MIN_VALUE..=Self::MAX_VALUE).map(|i| unsafe { Self::new_unchecked(i) })
+    ) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + std::iter::FusedIterator {
+        (Self::MIN_VALUE..=Self::MAX_VALUE).map(Self)
     }
+}
+
+/// `BoundedU8` / `BoundedU16` をラップした独自の newtype を定義する。
+///
+/// 同じ値域を持つ型どうしであっても別の型として扱われるので、取り違えを防げる。
+/// `BoundedU8` / `BoundedU16` のメソッドと定数は、以下を除いて全て転送される
+/// (`ALL`, `all_array()` は `BoundedU8` をラップする場合のみ)。
+///
+/// * `slice_as_u8()`, `slice_from_u8()`: newtype のスライスの変換には `unsafe` なコードを
+///   呼び出し側のクレートに展開する必要があるので、提供しない。`into_bounded()` 等で要素ごとに変換すること。
+/// * `widen()`, `try_narrow()` の変換先は newtype ではなく `BoundedU8` / `BoundedU16` となる。
+///
+/// 値域の最小値がリテラル `0` ならば `Default` も実装される(デフォルト値は 0)。
+///
+/// ```
+/// momoden_password::define_bounded! {
+///     /// 武器インデックス。
+///     pub struct Weapon: u8 in 0..=0xF;
+///
+///     /// 鎧インデックス。
+///     pub struct Armor: u8 in 0..=0xF;
+/// }
+///
+/// let weapon = Weapon::new(3).unwrap();
+/// assert_eq!(weapon.get(), 3);
+/// assert_eq!(Weapon::default().get(), 0);
+/// ```
+///
+/// 同じ値域でも別の型なので、代入はコンパイルエラーとなる:
+///
+/// ```compile_fail
+/// use momoden_password::*;
+///
+/// let mut equipment = Equipment::default();
+/// equipment.armor = equipment.weapon;
+/// ```
+#[macro_export]
+macro_rules! define_bounded {
+    () => {};
 
-    /// 指定された基数で文字列をパースする。
-    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, BoundedIntegerParseError> {
-        let value = u8::from_str_radix(s, radix)?;
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: $inner:ident in 0..=$max:literal;
+        $($rest:tt)*
+    ) => {
+        $crate::define_bounded!(@define $(#[$meta])* $vis $name, $inner, 0, $max);
 
-        if value < Self::MIN_VALUE {
-            return Err(BoundedIntegerParseError::BelowMin);
-        } else if value > Self::MAX_VALUE {
-            return Err(BoundedIntegerParseError::AboveMax);
+        impl ::std::default::Default for $name {
+            fn default() -> Self {
+                Self::MIN
+            }
         }
 
-        Ok(unsafe { Self::new_unchecked(value) })
-    }
-}
+        $crate::define_bounded!($($rest)*);
+    };
 
-/// 最小値が 0 の場合、デフォルト値は 0 となる。
-///
-/// 最小値が非 0 の場合、`Default` は実装しない。
-impl<const MAX: u8> Default for BoundedU8<0, MAX> {
-    fn default() -> Self {
-        unsafe { Self::new_unchecked(0) }
-    }
-}
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: $inner:ident in $min:literal..=$max:literal;
+        $($rest:tt)*
+    ) => {
+        $crate::define_bounded!(@define $(#[$meta])* $vis $name, $inner, $min, $max);
+
+        $crate::define_bounded!($($rest)*);
+    };
+
+    (@bounded u8, $min:literal, $max:literal) => { $crate::BoundedU8<$min, $max> };
+    (@bounded u16, $min:literal, $max:literal) => { $crate::BoundedU16<$min, $max> };
+    (@bounded u8, $min:ident, $max:ident) => { $crate::BoundedU8<$min, $max> };
+    (@bounded u16, $min:ident, $max:ident) => { $crate::BoundedU16<$min, $max> };
+
+    // `BoundedU8` にのみ存在するメソッドを転送する。
+    (@u8_only u8, $name:ident, $min:literal, $max:literal) => {
+        #[allow(dead_code)]
+        impl $name {
+            /// 全ての値を昇順で並べたスライス。
+            pub const ALL: &'static [Self] = &Self::all_array::<{ Self::COUNT }>();
+
+            /// 全ての値を昇順で並べた配列を返す。
+            ///
+            /// `N` は `Self::COUNT` と等しくなければならない(さもなくばコンパイルエラーとなる)。
+            pub const fn all_array<const N: usize>() -> [Self; N] {
+                let all = <$crate::define_bounded!(@bounded u8, $min, $max)>::all_array::<N>();
+
+                let mut res = [Self::MIN; N];
+                let mut i = 0;
+                while i < N {
+                    res[i] = Self(all[i]);
+                    i += 1;
+                }
+
+                res
+            }
+        }
+    };
+    (@u8_only u16, $name:ident, $min:literal, $max:literal) => {};
+
+    (@define $(#[$meta:meta])* $vis:vis $name:ident, $inner:ident, $min:literal, $max:literal) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        $vis struct $name($crate::define_bounded!(@bounded $inner, $min, $max));
+
+        #[allow(dead_code)]
+        impl $name {
+            /// 最小の内部値。
+            pub const MIN_VALUE: $inner = <$crate::define_bounded!(@bounded $inner, $min, $max)>::MIN_VALUE;
+
+            /// 最大の内部値。
+            pub const MAX_VALUE: $inner = <$crate::define_bounded!(@bounded $inner, $min, $max)>::MAX_VALUE;
+
+            /// 最小値。
+            pub const MIN: Self = Self::from_bounded(<$crate::define_bounded!(@bounded $inner, $min, $max)>::MIN);
+
+            /// 最大値。
+            pub const MAX: Self = Self::from_bounded(<$crate::define_bounded!(@bounded $inner, $min, $max)>::MAX);
+
+            /// 値の個数。
+            pub const COUNT: usize = <$crate::define_bounded!(@bounded $inner, $min, $max)>::COUNT;
+
+            /// 引数が値域内にあるかどうかを返す。
+            pub const fn in_range(x: $inner) -> bool {
+                <$crate::define_bounded!(@bounded $inner, $min, $max)>::in_range(x)
+            }
+
+            /// 内部値から値を作る。引数が値域内になければ `None` を返す。
+            pub const fn new(inner: $inner) -> ::std::option::Option<Self> {
+                match <$crate::define_bounded!(@bounded $inner, $min, $max)>::new(inner) {
+                    ::std::option::Option::Some(x) => ::std::option::Option::Some(Self(x)),
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            $crate::__define_bounded_unsafe!($inner, $crate::define_bounded!(@bounded $inner, $min, $max));
+
+            /// ラップされる値域制限付き整数から値を作る。
+            pub const fn from_bounded(x: $crate::define_bounded!(@bounded $inner, $min, $max)) -> Self {
+                Self(x)
+            }
+
+            /// ラップしている値域制限付き整数を返す。
+            pub const fn into_bounded(self) -> $crate::define_bounded!(@bounded $inner, $min, $max) {
+                self.0
+            }
+
+            /// 内部値を返す。
+            pub const fn get(self) -> $inner {
+                self.0.get()
+            }
+
+            /// 全ての値を昇順で返す。
+            pub fn all() -> impl ::std::iter::DoubleEndedIterator<Item = Self>
+                + ::std::iter::ExactSizeIterator
+                + ::std::iter::FusedIterator {
+                <$crate::define_bounded!(@bounded $inner, $min, $max)>::all().map(Self)
+            }
+
+            /// `start` 以上 `end` 以下の値を昇順で返す。
+            ///
+            /// `start > end` の場合、空のイテレータを返す。
+            pub fn range(
+                start: Self,
+                end: Self,
+            ) -> impl ::std::iter::DoubleEndedIterator<Item = Self>
+                + ::std::iter::ExactSizeIterator
+                + ::std::iter::FusedIterator {
+                <$crate::define_bounded!(@bounded $inner, $min, $max)>::range(start.0, end.0).map(Self)
+            }
+
+            /// `self` 以上の値を昇順で返す。
+            pub fn iter_from(
+                self,
+            ) -> impl ::std::iter::DoubleEndedIterator<Item = Self>
+                + ::std::iter::ExactSizeIterator
+                + ::std::iter::FusedIterator {
+                self.0.iter_from().map(Self)
+            }
+
+            /// 値域がより広い値域制限付き整数に変換する。
+            ///
+            /// 変換先の値域が変換元の値域を包含していなければコンパイルエラーとなる。
+            pub const fn widen<const MIN2: $inner, const MAX2: $inner>(
+                self,
+            ) -> $crate::define_bounded!(@bounded $inner, MIN2, MAX2) {
+                self.0.widen()
+            }
+
+            /// 値域が異なる値域制限付き整数に変換する。値が変換先の値域外にあればエラーを返す。
+            pub const fn try_narrow<const MIN2: $inner, const MAX2: $inner>(
+                self,
+            ) -> ::std::result::Result<
+                $crate::define_bounded!(@bounded $inner, MIN2, MAX2),
+                $crate::BoundedIntegerRangeError,
+            > {
+                self.0.try_narrow()
+            }
+
+            /// 指定された基数で文字列をパースする。
+            pub fn from_str_radix(
+                s: &str,
+                radix: u32,
+            ) -> ::std::result::Result<Self, $crate::BoundedIntegerParseError> {
+                <$crate::define_bounded!(@bounded $inner, $min, $max)>::from_str_radix(s, radix).map(Self)
+            }
+        }
+
+        $crate::define_bounded!(@u8_only $inner, $name, $min, $max);
+
+        impl ::std::convert::From<$crate::define_bounded!(@bounded $inner, $min, $max)> for $name {
+            fn from(x: $crate::define_bounded!(@bounded $inner, $min, $max)) -> Self {
+                Self(x)
+            }
+        }
+
+        impl ::std::convert::From<$name> for $crate::define_bounded!(@bounded $inner, $min, $max) {
+            fn from(x: $name) -> Self {
+                x.0
+            }
+        }
+
+        impl ::std::convert::From<$name> for $inner {
+            fn from(x: $name) -> Self {
+                x.get()
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::BoundedIntegerParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                Self::from_str_radix(s, 10)
+            }
+        }
+
+        $crate::define_bounded!(@fmt $name; Binary, Debug, Display, LowerExp, LowerHex, Octal, UpperExp, UpperHex);
+
+        $crate::__define_bounded_serde!($name, $crate::define_bounded!(@bounded $inner, $min, $max));
+
+        $crate::__define_bounded_schemars!($name, $crate::define_bounded!(@bounded $inner, $min, $max));
 
-macro_rules! impl_primitive_from_bounded_u8 {
-    ($($ty:ty)*) => {
+        $crate::__define_bounded_arbitrary!($name, $crate::define_bounded!(@bounded $inner, $min, $max));
+    };
+
+    (@fmt $name:ident; $($trait:ident),*) => {
         $(
-            impl<const MIN: u8, const MAX: u8> From<BoundedU8<MIN, MAX>> for $ty {
-                fn from(x: BoundedU8<MIN, MAX>) -> Self {
-                    Self::from(x.get())
+            impl ::std::fmt::$trait for $name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::fmt::$trait::fmt(&self.0, f)
                 }
             }
         )*
     };
 }
 
-impl_primitive_from_bounded_u8!(i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+/// `define_bounded!` で定義された型に serde のトレイトを実装する。
+///
+/// `define_bounded!` は呼び出し側のクレートで展開されるため、
+/// このクレートの `serde` feature の有無はこのマクロの定義を切り替えることで反映する。
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_serde {
+    ($name:ident, $bounded:ty) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                $crate::__private::serde::Serialize::serialize(&self.0, serializer)
+            }
+        }
 
-impl<const MIN: u8, const MAX: u8> std::str::FromStr for BoundedU8<MIN, MAX> {
-    type Err = BoundedIntegerParseError;
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                <$bounded as $crate::__private::serde::Deserialize<'de>>::deserialize(deserializer)
+                    .map(Self)
+            }
+        }
+    };
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::from_str_radix(s, 10)
-    }
+/// `define_bounded!` で定義された型に serde のトレイトを実装する(`serde` feature 無効時は何もしない)。
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_serde {
+    ($name:ident, $bounded:ty) => {};
 }
 
-macro_rules! impl_fmt_traits {
-    ($($trait:ident),*) => {
-        $(
-            impl<const MIN: u8, const MAX: u8> std::fmt::$trait for BoundedU8<MIN, MAX> {
-                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                    std::fmt::$trait::fmt(&self.0, f)
-                }
+/// `define_bounded!` で定義された型に `JsonSchema` を実装する。
+///
+/// `define_bounded!` は呼び出し側のクレートで展開されるため、
+/// このクレートの `schemars` feature の有無はこのマクロの定義を切り替えることで反映する。
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_schemars {
+    ($name:ident, $bounded:ty) => {
+        impl $crate::__private::schemars::JsonSchema for $name {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(::std::stringify!($name))
             }
-        )*
+
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(::std::concat!(
+                    ::std::module_path!(),
+                    "::",
+                    ::std::stringify!($name)
+                ))
+            }
+
+            fn json_schema(
+                generator: &mut $crate::__private::schemars::SchemaGenerator,
+            ) -> $crate::__private::schemars::Schema {
+                <$bounded as $crate::__private::schemars::JsonSchema>::json_schema(generator)
+            }
+        }
     };
 }
 
-impl_fmt_traits!(Binary, Debug, Display, LowerExp, LowerHex, Octal, UpperExp, UpperHex);
+/// `define_bounded!` で定義された型に `JsonSchema` を実装する(`schemars` feature 無効時は何もしない)。
+#[cfg(not(feature = "schemars"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_schemars {
+    ($name:ident, $bounded:ty) => {};
+}
 
+/// `define_bounded!` で定義された型に `arbitrary::Arbitrary` を実装する。
+///
+/// `define_bounded!` は呼び出し側のクレートで展開されるため、
+/// このクレートの `arbitrary` feature の有無はこのマクロの定義を切り替えることで反映する。
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_arbitrary {
+    ($name:ident, $bounded:ty) => {
+        impl<'a> $crate::__private::arbitrary::Arbitrary<'a> for $name {
+            fn arbitrary(
+                u: &mut $crate::__private::arbitrary::Unstructured<'a>,
+            ) -> $crate::__private::arbitrary::Result<Self> {
+                <$bounded as $crate::__private::arbitrary::Arbitrary<'a>>::arbitrary(u).map(Self)
+            }
+
+            fn size_hint(depth: usize) -> (usize, ::std::option::Option<usize>) {
+                <$bounded as $crate::__private::arbitrary::Arbitrary<'a>>::size_hint(depth)
+            }
+        }
+    };
+}
+
+/// `define_bounded!` で定義された型に `arbitrary::Arbitrary` を実装する(`arbitrary` feature 無効時は何もしない)。
+#[cfg(not(feature = "arbitrary"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_arbitrary {
+    ($name:ident, $bounded:ty) => {};
+}
+
+/// `define_bounded!` で定義された型に `unsafe` なメソッドを定義する。
+///
+/// `define_bounded!` は呼び出し側のクレートで展開されるため、
+/// このクレートの `forbid-unsafe` feature の有無はこのマクロの定義を切り替えることで反映する。
+#[cfg(not(feature = "forbid-unsafe"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_unsafe {
+    ($inner:ty, $bounded:ty) => {
+        /// 内部値から値を作る。
+        ///
+        /// # Safety
+        ///
+        /// 引数は値域内になければならない。
+        pub const unsafe fn new_unchecked(inner: $inner) -> Self {
+            Self(<$bounded>::new_unchecked(inner))
+        }
+    };
+}
+
+/// `define_bounded!` で定義された型に `new_unchecked` を定義する(`forbid-unsafe` feature 有効時は安全な関数になる)。
+#[cfg(feature = "forbid-unsafe")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_bounded_unsafe {
+    ($inner:ty, $bounded:ty) => {
+        /// 内部値から値を作る。
+        ///
+        /// `forbid-unsafe` feature 有効時は安全な関数となり、引数が値域外ならパニックする。
+        pub const fn new_unchecked(inner: $inner) -> Self {
+            Self(<$bounded>::new_unchecked(inner))
+        }
+    };
+}
+
+/// 全ての `u8` 値を昇順で並べたテーブル。`BoundedU8::ALL` の実体。
+#[cfg(not(feature = "forbid-unsafe"))]
+const U8_TABLE: [u8; 0x100] = {
+    let mut table = [0; 0x100];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// 値域の異なる型への変換時に発生しうるエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum BoundedIntegerRangeError {
+    /// 最小値よりも小さい。
+    #[error("number is below MIN")]
+    BelowMin,
+
+    /// 最大値よりも大きい。
+    #[error("number is above MAX")]
+    AboveMax,
+}
+
+/// `u8` のスライスを `BoundedU8` のスライスとして見る際に発生しうるエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("number {value:#04X} at index {index} is out of range")]
+pub struct BoundedSliceError {
+    /// 値域外の要素のインデックス。
+    pub index: usize,
+    /// 値域外の要素の値。
+    pub value: u8,
+}
+
+/// 値域が制限された整数型のパース時に発生しうるエラー。
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
 pub enum BoundedIntegerParseError {
     /// 最小値よりも小さい。
@@ -141,6 +788,92 @@ pub enum BoundedIntegerParseError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_all() {
+        type Num = BoundedU8<3, 44>;
+        assert_eq!(Num::COUNT, 42);
+        assert_eq!(Num::ALL.len(), Num::COUNT);
+        assert!(Num::all().eq(Num::ALL.iter().copied()));
+        assert!(Num::all().map(Num::get).eq(3..=44));
+        assert_eq!(Num::all_array::<42>(), Num::ALL);
+
+        type Item = BoundedU8<1, 0x3F>;
+        assert_eq!(Item::ALL.len(), Item::COUNT);
+        assert!(Item::all().eq(Item::ALL.iter().copied()));
+        assert!(Item::all().rev().map(Item::get).eq((1..=0x3F).rev()));
+
+        type Full = BoundedU8<0, 0xFF>;
+        assert_eq!(Full::COUNT, 0x100);
+        assert!(Full::all().map(Full::get).eq(0..=0xFF));
+
+        type Single = BoundedU8<0xFF, 0xFF>;
+        assert_eq!(Single::ALL, [Single::MAX]);
+    }
+
+    #[test]
+    fn test_range() {
+        type Num = BoundedU8<1, 0x3F>;
+        let n = |x| Num::new(x).unwrap();
+
+        assert!(Num::range(n(0x20), n(0x3F)).map(Num::get).eq(0x20..=0x3F));
+        assert_eq!(Num::range(n(0x20), n(0x3F)).len(), 0x20);
+        assert!(Num::range(n(5), n(5)).eq([n(5)]));
+        assert_eq!(Num::range(n(6), n(5)).len(), 0);
+        assert!(Num::range(n(6), n(5)).next().is_none());
+        assert!(Num::range(Num::MIN, Num::MAX).eq(Num::all()));
+        assert!(Num::range(n(3), n(6)).rev().map(Num::get).eq([6, 5, 4, 3]));
+
+        assert!(n(0x3D).iter_from().map(Num::get).eq([0x3D, 0x3E, 0x3F]));
+        assert!(Num::MAX.iter_from().eq([Num::MAX]));
+        assert!(Num::MIN.iter_from().rev().eq(Num::all().rev()));
+
+        type Wide = BoundedU16<0, 1000>;
+        let w = |x| Wide::new(x).unwrap();
+        assert_eq!(Wide::range(w(10), w(1000)).len(), 991);
+        assert_eq!(Wide::range(w(11), w(10)).len(), 0);
+        assert!(w(998).iter_from().rev().map(Wide::get).eq([1000, 999, 998]));
+    }
+
+    #[test]
+    fn test_widen() {
+        type Narrow = BoundedU8<2, 7>;
+        type Wide = BoundedU8<0, 0xF>;
+
+        for x in Narrow::all() {
+            assert_eq!(x.widen::<0, 0xF>().get(), x.get());
+        }
+        let x: Wide = Narrow::MAX.widen();
+        assert_eq!(x.get(), 7);
+        let x: BoundedU8<2, 7> = Narrow::MIN.widen();
+        assert_eq!(x, Narrow::MIN);
+    }
+
+    #[test]
+    fn test_try_narrow() {
+        type Narrow = BoundedU8<2, 7>;
+        type Wide = BoundedU8<0, 0xF>;
+
+        for x in Wide::all() {
+            let res = x.try_narrow::<2, 7>();
+            match x.get() {
+                0..=1 => assert_eq!(res, Err(BoundedIntegerRangeError::BelowMin)),
+                2..=7 => assert_eq!(res.unwrap().get(), x.get()),
+                8.. => assert_eq!(res, Err(BoundedIntegerRangeError::AboveMax)),
+            }
+        }
+
+        // 値域が交差するだけの場合も値次第で変換できる。
+        let x = BoundedU8::<5, 10>::MAX;
+        assert_eq!(
+            x.try_narrow::<0, 9>(),
+            Err(BoundedIntegerRangeError::AboveMax)
+        );
+        assert_eq!(x.try_narrow::<8, 0xFF>().unwrap().get(), 10);
+
+        // 値域が包含されていれば必ず成功する。
+        assert!(Narrow::all().all(|x| x.try_narrow::<0, 0xF>().is_ok()));
+    }
+
     #[test]
     fn test_from_str_radix() {
         type Num = BoundedU8<3, 44>;
@@ -161,4 +894,216 @@ mod tests {
             Err(BoundedIntegerParseError::AboveMax)
         ));
     }
+
+    #[test]
+    fn test_slice_as_u8() {
+        type Num = BoundedU8<0, 0x3F>;
+
+        let xs = [Num::MIN, Num::new(0x20).unwrap(), Num::MAX];
+        let view = Num::slice_as_u8(&xs);
+        assert_eq!(view, [0, 0x20, 0x3F]);
+        assert_eq!(view.as_ptr(), xs.as_ptr().cast::<u8>());
+        assert!(Num::slice_as_u8(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_slice_from_u8() {
+        type Num = BoundedU8<1, 0x3F>;
+
+        let xs = [1, 0x20, 0x3F];
+        let view = Num::slice_from_u8(&xs).unwrap();
+        assert!(view.iter().map(|x| x.get()).eq(xs));
+        assert_eq!(view.as_ptr().cast::<u8>(), xs.as_ptr());
+        assert_eq!(Num::slice_from_u8(&[]), Ok(&[][..]));
+
+        assert_eq!(
+            Num::slice_from_u8(&[1, 2, 0x40, 3, 0]),
+            Err(BoundedSliceError {
+                index: 2,
+                value: 0x40
+            })
+        );
+        assert_eq!(
+            Num::slice_from_u8(&[1, 0]),
+            Err(BoundedSliceError { index: 1, value: 0 })
+        );
+    }
+
+    crate::define_bounded! {
+        struct Newtype: u8 in 3..=44;
+        struct NewtypeU16: u16 in 0..=1000;
+    }
+
+    #[test]
+    fn test_define_bounded() {
+        assert_eq!(Newtype::MIN.get(), 3);
+        assert_eq!(Newtype::MAX.get(), 44);
+        assert_eq!(Newtype::COUNT, 42);
+        assert_eq!(Newtype::new(2), None);
+        assert_eq!(
+            Newtype::new(44).unwrap().into_bounded(),
+            BoundedU8::<3, 44>::MAX
+        );
+        assert!(Newtype::all().map(Newtype::get).eq(3..=44));
+        assert!(Newtype::range(Newtype::new(40).unwrap(), Newtype::MAX)
+            .map(Newtype::get)
+            .eq(40..=44));
+        assert!(Newtype::new(43)
+            .unwrap()
+            .iter_from()
+            .map(Newtype::get)
+            .eq([43, 44]));
+        assert_eq!(Newtype::from_str_radix("2C", 16).unwrap().get(), 44);
+        assert!(matches!(
+            "45".parse::<Newtype>(),
+            Err(BoundedIntegerParseError::AboveMax)
+        ));
+        assert_eq!(format!("{:02X}", Newtype::MAX), "2C");
+        assert_eq!(u8::from(Newtype::MIN), 3);
+
+        assert_eq!(Newtype::ALL.len(), Newtype::COUNT);
+        assert!(Newtype::ALL.iter().copied().eq(Newtype::all()));
+        assert_eq!(Newtype::all_array::<42>(), Newtype::ALL);
+        assert_eq!(
+            Newtype::MAX.widen::<0, 0xFF>(),
+            BoundedU8::<0, 0xFF>::new(44).unwrap()
+        );
+        assert_eq!(
+            Newtype::MAX.try_narrow::<0, 44>(),
+            Ok(BoundedU8::<0, 44>::MAX)
+        );
+        assert_eq!(
+            Newtype::MAX.try_narrow::<0, 43>(),
+            Err(BoundedIntegerRangeError::AboveMax)
+        );
+        assert_eq!(
+            NewtypeU16::MAX.widen::<0, 0xFFFF>(),
+            BoundedU16::<0, 0xFFFF>::new(1000).unwrap()
+        );
+        assert_eq!(
+            NewtypeU16::MAX.try_narrow::<1001, 2000>(),
+            Err(BoundedIntegerRangeError::BelowMin)
+        );
+
+        // クレート内の newtype でも `BoundedU8` と同じく使える。
+        assert!(crate::savedata::WeaponIndex::ALL
+            .iter()
+            .map(|w| w.get())
+            .eq(BoundedU8::<0, 0xF>::ALL.iter().map(|x| x.get())));
+
+        assert_eq!(NewtypeU16::default().get(), 0);
+        assert_eq!(NewtypeU16::MAX.get(), 1000);
+        assert_eq!(
+            NewtypeU16::from(BoundedU16::<0, 1000>::MAX),
+            NewtypeU16::MAX
+        );
+    }
+
+    #[test]
+    fn test_new_unchecked() {
+        // forbid-unsafe feature の有無によらず、同じ名前で呼べる。
+        type Num = BoundedU8<3, 44>;
+        assert_eq!(
+            unsafe_or!(Num::new_unchecked(44), Num::new_unchecked(44)),
+            Num::MAX
+        );
+        assert_eq!(
+            unsafe_or!(Newtype::new_unchecked(3), Newtype::new_unchecked(3)),
+            Newtype::MIN
+        );
+        assert_eq!(
+            unsafe_or!(
+                BoundedU16::<0, 1000>::new_unchecked(1000),
+                BoundedU16::<0, 1000>::new_unchecked(1000)
+            ),
+            BoundedU16::MAX
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "forbid-unsafe")]
+    #[should_panic(expected = "value out of range")]
+    fn test_new_unchecked_out_of_range() {
+        Newtype::new_unchecked(45);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        type Num = BoundedU8<3, 44>;
+
+        assert_eq!(serde_json::to_string(&Num::MAX).unwrap(), "44");
+        assert_eq!(serde_json::from_str::<Num>("3").unwrap(), Num::MIN);
+        assert!(serde_json::from_str::<Num>("2").is_err());
+        assert!(serde_json::from_str::<Num>("45").is_err());
+
+        assert_eq!(serde_json::to_string(&Newtype::MIN).unwrap(), "3");
+        assert_eq!(serde_json::from_str::<Newtype>("44").unwrap(), Newtype::MAX);
+        assert!(serde_json::from_str::<Newtype>("45").is_err());
+        assert!(serde_json::from_str::<NewtypeU16>("1001").is_err());
+    }
+
+    #[test]
+    fn test_u16_all() {
+        type Num = BoundedU16<300, 1000>;
+        assert_eq!(Num::COUNT, 701);
+        assert_eq!(Num::all().len(), Num::COUNT);
+        assert!(Num::all().map(Num::get).eq(300..=1000));
+        assert!(Num::all().rev().map(Num::get).eq((300..=1000).rev()));
+
+        type Full = BoundedU16<0, 0xFFFF>;
+        assert_eq!(Full::COUNT, 0x10000);
+        assert_eq!(Full::default().get(), 0);
+    }
+
+    #[test]
+    fn test_u16_widen_narrow() {
+        let x = BoundedU16::<300, 1000>::MAX;
+        assert_eq!(x.widen::<0, 0xFFFF>().get(), 1000);
+        assert_eq!(
+            x.try_narrow::<0, 999>(),
+            Err(BoundedIntegerRangeError::AboveMax)
+        );
+        assert_eq!(
+            BoundedU16::<300, 1000>::MIN.try_narrow::<301, 0xFFFF>(),
+            Err(BoundedIntegerRangeError::BelowMin)
+        );
+        assert_eq!(x.try_narrow::<1000, 1000>().unwrap().get(), 1000);
+    }
+
+    #[test]
+    fn test_u16_from_str_radix() {
+        type Num = BoundedU16<300, 1000>;
+
+        assert_eq!(Num::from_str_radix("300", 10).unwrap().get(), 300);
+        assert_eq!(Num::from_str_radix("3E8", 16).unwrap().get(), 1000);
+        assert_eq!("512".parse::<Num>().unwrap().get(), 512);
+
+        assert!(matches!(
+            Num::from_str_radix("A", 10),
+            Err(BoundedIntegerParseError::Parse(_))
+        ));
+        assert!(matches!(
+            Num::from_str_radix("299", 10),
+            Err(BoundedIntegerParseError::BelowMin)
+        ));
+        assert!(matches!(
+            Num::from_str_radix("1001", 10),
+            Err(BoundedIntegerParseError::AboveMax)
+        ));
+        assert!(matches!(
+            Num::from_str_radix("65536", 10),
+            Err(BoundedIntegerParseError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_u16_fmt() {
+        let x = BoundedU16::<0, 0xFFFF>::new(0xBEEF).unwrap();
+        assert_eq!(format!("{x}"), "48879");
+        assert_eq!(format!("{x:?}"), "48879");
+        assert_eq!(format!("{x:04X}"), "BEEF");
+        assert_eq!(format!("{x:x}"), "beef");
+        assert_eq!(u32::from(x), 0xBEEF);
+    }
 }
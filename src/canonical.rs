@@ -0,0 +1,534 @@
+use std::ops::RangeInclusive;
+
+use crate::layout::FieldId;
+use crate::password::{Password, PasswordChar};
+use crate::savedata::*;
+use crate::serialized::{encode_password_bytes, SerializedBytes};
+
+impl Savedata {
+    /// このゲーム状態を表す正規のパスワードを返す。
+    ///
+    /// `normalize()` した結果が `self.normalize()` と一致するゲーム状態を表す有効なパスワードのうち、
+    /// 最短のもの(同じ長さの中では辞書順で最小のもの)を返す。
+    ///
+    /// パスワードの末尾で途切れたビットは 1 として読まれるので、ビット列の末尾の 1 は省略できる。
+    /// また、読まれない末尾のビットや、正規化すると同じになる装備インデックスは自由に選べる。
+    /// これらの組み合わせを全て調べる。
+    pub fn canonical_password(&self) -> Password {
+        let target = self.normalize();
+        let candidates = candidate_bits(&target);
+
+        let min_len = candidates.iter().map(|&(_, n)| n).min().unwrap();
+        let max_len = candidates[0].0.len().div_ceil(6);
+
+        for n_data in min_len..=max_len {
+            let best = candidates
+                .iter()
+                .filter(|&&(_, n)| n <= n_data)
+                .flat_map(|(bits, _)| encodings(bits, n_data))
+                .filter(|password| !password.is_special())
+                .min();
+            if let Some(best) = best {
+                debug_assert_eq!(best.equivalence_key(), Some(target));
+                return best;
+            }
+        }
+
+        // 末尾に読まれないビットを補ったエンコード結果は必ず見つかる。
+        unreachable!("no encoding found for {target:?}")
+    }
+
+    /// このゲーム状態を(`normalize()` した結果が一致するという意味で)表せるパスワードの長さの範囲を返す。
+    ///
+    /// 下限は `canonical_password()` の長さ。最短のものから末尾に 1 のビットや読まれないビットを
+    /// 補っていけば、`Password::MAX_LEN` までの全ての長さで表せる。
+    pub fn all_password_lengths(&self) -> RangeInclusive<usize> {
+        self.canonical_password().len()..=Password::MAX_LEN
+    }
+}
+
+impl SerializedBytes {
+    /// `normalize()` した結果が `savedata.normalize()` と一致するゲーム状態を表すバイト列のうち、最短のものを返す。
+    ///
+    /// パスワードの末尾で途切れたビットは 1 として読まれるので、ビット列の末尾の 1 は省略できる。
+    /// 正規化すると同じになる装備インデックスのうち最も短くできるものを選び、末尾の 1 を省略したうえでチェックサムを格納する。
+    /// フラグや所持品が埋まった状態ほどビット列の末尾に 1 が続くので、`from_savedata()` よりずっと短くなる
+    /// (全てのビットが 1 なら "ふ" に相当する 1 バイトになる)。
+    ///
+    /// `Savedata::canonical_password()` と異なり、エンコード結果が特殊パスワードになる場合も避けない。
+    pub fn from_savedata_minimal(savedata: &Savedata) -> Self {
+        let target = savedata.normalize();
+        let best = equivalent_equipments(target.equipment)
+            .into_iter()
+            .map(|equipment| Savedata {
+                equipment,
+                ..target.clone()
+            })
+            .min_by_key(Savedata::min_encoded_len)
+            .unwrap();
+
+        Self::from_savedata_with_len(&best, best.min_encoded_len()).unwrap()
+    }
+}
+
+impl Savedata {
+    /// `SerializedBytes::from_savedata_minimal()` をエンコードした、このゲーム状態を表す最短のパスワードを返す。
+    pub fn shortest_password(&self) -> Password {
+        SerializedBytes::from_savedata_minimal(self).to_password()
+    }
+}
+
+impl Password {
+    /// `Password::from_savedata_with_charset_preference()` が試す、読まれない末尾のビット数の上限。
+    pub const CHARSET_PREFERENCE_FREE_BITS_MAX: usize = 12;
+
+    /// `savedata` を表すパスワードのうち、文字の重みの合計が最小のものとその合計を返す。
+    ///
+    /// `Savedata::canonical_password()` と同様に、正規化すると同じになる装備インデックス、末尾の 1 のビットの省略、
+    /// 読まれない末尾のビットの自由度を使って、書き写しで取り違えやすい文字などを避けたパスワードを選ぶ。
+    /// 重みの合計が同じなら、短いもの、同じ長さの中では辞書順(内部値順)で最小のものを選ぶ。
+    ///
+    /// 読まれない末尾のビットはたかだか `CHARSET_PREFERENCE_FREE_BITS_MAX` ビットまで試す。
+    /// 自由度がなく候補が全て特殊パスワードになる場合は、`SerializedBytes::from_savedata()` による
+    /// 通常のエンコード結果とその重みの合計を返す。
+    pub fn from_savedata_with_charset_preference(
+        savedata: &Savedata,
+        weights: &CharWeights,
+    ) -> (Password, u32) {
+        let target = savedata.normalize();
+        let candidates = candidate_bits(&target);
+
+        let n_bits = candidates[0].0.len();
+        let min_len = candidates.iter().map(|&(_, n)| n).min().unwrap();
+        let max_len = Self::MAX_LEN
+            .saturating_sub(2)
+            .min((n_bits + Self::CHARSET_PREFERENCE_FREE_BITS_MAX) / 6);
+
+        // (重みの合計, 内部値)
+        let mut best: Option<(u32, Vec<u8>)> = None;
+        for n_data in min_len..=max_len {
+            for (bits, _) in candidates.iter().filter(|&&(_, n)| n <= n_data) {
+                for_each_encoding(bits, n_data, |inner| {
+                    let score = weights.score_inner(inner);
+                    if let Some((best_score, best_inner)) = &best {
+                        if (score, inner.len(), inner)
+                            >= (*best_score, best_inner.len(), best_inner.as_slice())
+                        {
+                            return;
+                        }
+                    }
+                    if !Password::from_inner_bytes(inner).unwrap().is_special() {
+                        best = Some((score, inner.to_vec()));
+                    }
+                });
+            }
+        }
+
+        match best {
+            Some((score, inner)) => {
+                let password = Password::from_inner_bytes(&inner).unwrap();
+                debug_assert_eq!(password.equivalence_key(), Some(target));
+                (password, score)
+            }
+            None => {
+                let password = SerializedBytes::from_savedata(savedata).to_password();
+                let score = weights.score(&password);
+                (password, score)
+            }
+        }
+    }
+}
+
+/// パスワードの文字ごとの重み。`Password::from_savedata_with_charset_preference()` で使う。
+///
+/// 既定では全ての文字の重みが 0。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CharWeights([u32; 0x40]);
+
+impl Default for CharWeights {
+    fn default() -> Self {
+        Self([0; 0x40])
+    }
+}
+
+impl CharWeights {
+    /// 書き写しで取り違えやすい文字の重みを 1、それ以外を 0 とした重み。
+    ///
+    /// 対象は 'ば' 行と 'ぱ' 行の各文字, 'ぬ' と 'め', 'わ' と 'れ'。
+    pub fn confusable() -> Self {
+        use PasswordChar::*;
+
+        let mut weights = Self::default();
+        for pc in [Ba, Bi, Bu, Be, Bo, Pa, Pi, Pu, Pe, Po, Nu, Me, Wa, Re] {
+            weights.set(pc, 1);
+        }
+        weights
+    }
+
+    /// 文字 `pc` の重みを返す。
+    pub fn get(&self, pc: PasswordChar) -> u32 {
+        self.0[usize::from(pc.to_inner())]
+    }
+
+    /// 文字 `pc` の重みを `weight` にする。
+    pub fn set(&mut self, pc: PasswordChar, weight: u32) {
+        self.0[usize::from(pc.to_inner())] = weight;
+    }
+
+    /// 文字 `pc` の重みを `weight` にしたものを返す。
+    pub fn with(mut self, pc: PasswordChar, weight: u32) -> Self {
+        self.set(pc, weight);
+        self
+    }
+
+    /// パスワードの各文字の重みの合計を返す(`u32::MAX` で飽和する)。
+    pub fn score(&self, password: &Password) -> u32 {
+        self.score_inner(&password.to_inner_bytes())
+    }
+
+    /// 内部値の列の各文字の重みの合計を返す。
+    fn score_inner(&self, inner: &[u8]) -> u32 {
+        inner
+            .iter()
+            .fold(0_u32, |acc, &b| acc.saturating_add(self.0[usize::from(b)]))
+    }
+}
+
+/// `normalize()` した結果が `target` と一致するゲーム状態のビット列と、それを表せる最小のデータ部のバイト数を全て返す。
+///
+/// `target` は `normalize()` 済みでなければならない。ビット列は装備の部分のみが異なり、長さは全て等しい。
+fn candidate_bits(target: &Savedata) -> Vec<(Vec<bool>, usize)> {
+    // 装備以外のビットは共通なので、装備の部分のみ差し替える。
+    let base: Vec<bool> = target.to_bits().as_bitslice().iter().map(|b| *b).collect();
+    let offset = FieldId::Helm.spec().bit_offset;
+
+    equivalent_equipments(target.equipment)
+        .into_iter()
+        .map(|equipment| {
+            let mut bits = base.clone();
+            for (i, b) in equipment.to_bits().as_bitslice().iter().enumerate() {
+                bits[offset + i] = *b;
+            }
+            // 末尾の 1 を除いたビット列を表せる最小のバイト数。
+            let min_len = bits.iter().rposition(|&b| !b).map_or(0, |i| i / 6 + 1);
+            (bits, min_len)
+        })
+        .collect()
+}
+
+/// `normalize()` した結果が `equipment.normalize()` と一致する装備を全て返す。
+fn equivalent_equipments(equipment: Equipment) -> Vec<Equipment> {
+    let target = equipment.normalize();
+
+    // 兜といでたち1 は他のスロットに影響しない。いでたち2, 3 は正規化で変化しない。
+    let helms: Vec<_> = HelmIndex::all()
+        .filter(|&helm| Equipment::normalize_helm(helm).unwrap_or_default() == target.helm)
+        .collect();
+    let accessory1s: Vec<_> = Accessory1Index::all()
+        .filter(|&accessory1| {
+            Equipment::normalize_accessory1(accessory1).unwrap_or_default() == target.accessory1
+        })
+        .collect();
+
+    // 武器, 鎧, 靴, いでたち0 は次のスロットに影響するので、前から順に決めていく。
+    let mut chains = vec![];
+    for weapon in WeaponIndex::all() {
+        let (w, armor_from_weapon) = Equipment::normalize_weapon(weapon);
+        if w.unwrap_or_default() != target.weapon {
+            continue;
+        }
+        for armor in ArmorIndex::all() {
+            let (a, shoes_from_armor) = Equipment::normalize_armor(armor);
+            if a.or(armor_from_weapon).unwrap_or_default() != target.armor {
+                continue;
+            }
+            for shoes in ShoesIndex::all() {
+                let (s, accessory0_from_shoes) = Equipment::normalize_shoes(shoes);
+                if s.or(shoes_from_armor).unwrap_or_default() != target.shoes {
+                    continue;
+                }
+                for accessory0 in Accessory0Index::all() {
+                    let c = Equipment::normalize_accessory0(accessory0);
+                    if c.or(accessory0_from_shoes).unwrap_or_default() != target.accessory0 {
+                        continue;
+                    }
+                    chains.push(Equipment {
+                        weapon,
+                        armor,
+                        shoes,
+                        accessory0,
+                        ..target
+                    });
+                }
+            }
+        }
+    }
+
+    let mut res = vec![];
+    for &chain in &chains {
+        for &helm in &helms {
+            for &accessory1 in &accessory1s {
+                let e = Equipment {
+                    helm,
+                    accessory1,
+                    ..chain
+                };
+                debug_assert_eq!(e.normalize(), target);
+                res.push(e);
+            }
+        }
+    }
+    res
+}
+
+/// ビット列 `bits` をデータ部 `n_data` バイトのパスワードとしてエンコードしたものを全て返す。
+fn encodings(bits: &[bool], n_data: usize) -> Vec<Password> {
+    let mut res = vec![];
+    for_each_encoding(bits, n_data, |inner| {
+        res.push(Password::from_inner_bytes(inner).unwrap());
+    });
+    res
+}
+
+/// ビット列 `bits` をデータ部 `n_data` バイトのパスワードとしてエンコードしたものの内部値を全て `f` に渡す。
+///
+/// `bits` が `n_data` バイトに収まらない部分は全て 1 でなければならない(省略する)。
+/// `n_data` バイトに余りがあれば、その部分は読まれないので全ての値を試す。
+/// `n_data` が 0 なら、データ部を持たない最短の有効なパスワード "ふ" のみを渡す。
+fn for_each_encoding(bits: &[bool], n_data: usize, mut f: impl FnMut(&[u8])) {
+    if n_data == 0 {
+        f(&[PasswordChar::Hu.to_inner()]);
+        return;
+    }
+
+    let len = 2 + n_data;
+    let n_free = (6 * n_data).saturating_sub(bits.len());
+    // 読まれないビットを含むバイトの位置。それより前のバイトは共通。
+    let free_start = 2 + (6 * n_data - n_free) / 6;
+
+    let mut bytes = [0_u8; Password::MAX_LEN];
+    for (j, b) in bytes[2..free_start].iter_mut().enumerate() {
+        *b = bits[6 * j..6 * j + 6]
+            .iter()
+            .fold(0, |acc, &bit| (acc << 1) | u8::from(bit));
+    }
+    let fixed_add = bytes[2..free_start]
+        .iter()
+        .fold(0_u8, |acc, &b| acc.wrapping_add(b));
+    let fixed_xor = bytes[2..free_start].iter().fold(0, |acc, &b| acc ^ b);
+
+    // 読まれないビットを含むバイトのうち、`bits` に含まれる部分。
+    let tail = bits[(6 * (free_start - 2)).min(bits.len())..]
+        .iter()
+        .take(6 * (len - free_start))
+        .fold(0_u32, |acc, &bit| (acc << 1) | u32::from(bit));
+
+    let mut chars = [0_u8; Password::MAX_LEN];
+    for free in 0..1_u32 << n_free {
+        let value = (tail << n_free) | free;
+        let (mut sum_add, mut sum_xor) = (fixed_add, fixed_xor);
+        for (j, b) in bytes.iter_mut().enumerate().take(len).skip(free_start) {
+            *b = ((value >> (6 * (len - 1 - j))) & 0x3F) as u8;
+            let b = *b;
+            sum_add = sum_add.wrapping_add(b);
+            sum_xor ^= b;
+        }
+        bytes[0] = sum_add & 0x3F;
+        bytes[1] = sum_xor;
+
+        chars[..len].copy_from_slice(&bytes[..len]);
+        encode_password_bytes(&mut chars[..len]);
+
+        f(&chars[..len]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_canonical_password() {
+        let fu = Password::parse("ふ").unwrap();
+        let savedata_fu = SerializedBytes::from_password(&fu).to_savedata().unwrap();
+        assert_eq!(savedata_fu.canonical_password(), fu);
+        assert_eq!(savedata_fu.all_password_lengths(), 1..=Password::MAX_LEN);
+
+        for savedata in [
+            Savedata::default(),
+            Savedata {
+                xp: 1234,
+                inventory: [ItemId::MIN, ItemId::MAX].into_iter().collect(),
+                ..Savedata::default()
+            },
+            // インベントリが 0x3F で埋まっていれば、その部分は省略できる。
+            Savedata {
+                xp: 1234,
+                inventory: [ItemId::MAX; 8].into(),
+                ..Savedata::default()
+            },
+            // 不正な装備インデックス。
+            Savedata {
+                equipment: Equipment {
+                    weapon: WeaponIndex::new(14).unwrap(),
+                    ..Equipment::default()
+                },
+                ..Savedata::default()
+            },
+        ] {
+            let canonical = savedata.canonical_password();
+            assert_eq!(canonical.equivalence_key(), Some(savedata.normalize()));
+
+            let encoded = SerializedBytes::from_savedata(&savedata).to_password();
+            assert!(canonical.len() <= encoded.len());
+            assert_eq!(
+                savedata.all_password_lengths(),
+                canonical.len()..=Password::MAX_LEN
+            );
+
+            // 正規化後が同じなら、正規のパスワードも同じ。
+            assert_eq!(savedata.normalize().canonical_password(), canonical);
+        }
+
+        let full = Savedata {
+            xp: 1234,
+            inventory: [ItemId::MAX; 8].into(),
+            ..Savedata::default()
+        };
+        let canonical = full.canonical_password();
+        assert!(canonical.len() < SerializedBytes::from_savedata(&full).to_password().len());
+        // 1 文字削ったものは別のゲーム状態を表す。
+        let shorter = Password::new(&canonical[..canonical.len() - 1]).unwrap();
+        assert_ne!(shorter.equivalence_key(), Some(full.normalize()));
+    }
+
+    #[test]
+    fn test_from_savedata_minimal() {
+        let fu = Password::parse("ふ").unwrap();
+        let savedata_fu = SerializedBytes::from_password(&fu).to_savedata().unwrap();
+        assert_eq!(savedata_fu.shortest_password(), fu);
+
+        for savedata in [
+            Savedata::default(),
+            savedata_fu,
+            Savedata {
+                xp: 1234,
+                inventory: [ItemId::MIN, ItemId::MAX].into_iter().collect(),
+                ..Savedata::default()
+            },
+            Savedata {
+                xp: 1234,
+                inventory: [ItemId::MAX; 8].into(),
+                ..Savedata::default()
+            },
+            // 不正な装備インデックス。
+            Savedata {
+                equipment: Equipment {
+                    weapon: WeaponIndex::new(14).unwrap(),
+                    ..Equipment::default()
+                },
+                ..Savedata::default()
+            },
+        ] {
+            let target = savedata.normalize();
+            let bytes = SerializedBytes::from_savedata_minimal(&savedata);
+            assert!(bytes.checksum_is_ok());
+            assert_eq!(
+                bytes.to_savedata().map(|s| s.normalize()),
+                Some(target.clone())
+            );
+            assert!(bytes.len() <= SerializedBytes::from_savedata(&savedata).len());
+            assert_eq!(savedata.shortest_password(), bytes.to_password());
+            assert_eq!(savedata.canonical_password().len(), bytes.len());
+
+            // それより短くは表せない。
+            for equipment in equivalent_equipments(target.equipment) {
+                let savedata = Savedata {
+                    equipment,
+                    ..target.clone()
+                };
+                for len in Password::MIN_LEN..bytes.len() {
+                    assert!(SerializedBytes::from_savedata_with_len(&savedata, len).is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_charset_preference() {
+        let confusable = CharWeights::confusable();
+
+        for savedata in [
+            Savedata::default(),
+            Savedata {
+                xp: 1234,
+                inventory: [ItemId::MIN, ItemId::MAX].into_iter().collect(),
+                ..Savedata::default()
+            },
+            Savedata {
+                xp: 1234,
+                purse: 5000,
+                inventory: [ItemId::MAX; 8].into(),
+                ..Savedata::default()
+            },
+        ] {
+            let canonical = savedata.canonical_password();
+            let encoded = SerializedBytes::from_savedata(&savedata).to_password();
+
+            // 重みが全て 0 なら正規のパスワードと一致する。
+            assert_eq!(
+                Password::from_savedata_with_charset_preference(&savedata, &CharWeights::default()),
+                (canonical.clone(), 0)
+            );
+
+            // 取り違えやすい文字を避けられる場合は避ける。
+            let (password, score) =
+                Password::from_savedata_with_charset_preference(&savedata, &confusable);
+            assert_eq!(password.equivalence_key(), Some(savedata.normalize()));
+            assert_eq!(score, confusable.score(&password));
+            assert!(score < confusable.score(&canonical));
+            assert!(score < confusable.score(&encoded));
+        }
+
+        // 既定の状態を表すパスワードは取り違えやすい文字を含まずに作れる。
+        let savedata = Savedata::default();
+        let (password, score) =
+            Password::from_savedata_with_charset_preference(&savedata, &confusable);
+        assert_eq!(score, 0);
+        assert!(password.iter().all(|&pc| confusable.get(pc) == 0));
+
+        // 重みが全て等しければ最短のものを選ぶ。
+        let uniform = PasswordChar::iter().fold(CharWeights::default(), |w, pc| w.with(pc, 1));
+        let (password, score) =
+            Password::from_savedata_with_charset_preference(&savedata, &uniform);
+        assert_eq!(password.len(), savedata.canonical_password().len());
+        assert_eq!(score as usize, password.len());
+    }
+
+    #[test]
+    fn test_canonical_password_exhaustive() {
+        // 長さ 4 以下の有効なパスワードを全て調べ、同じゲーム状態を表すものの中で最短・辞書順最小のものと比べる。
+        let mut groups: HashMap<Savedata, Password> = HashMap::new();
+        for len in 1..=4 {
+            for password in Password::valid_passwords(len) {
+                let Some(key) = password.equivalence_key() else {
+                    continue;
+                };
+                // 長さの昇順、辞書順に列挙しているので、最初に現れたものが最小。
+                groups.entry(key).or_insert(password);
+            }
+        }
+
+        // 全て調べると時間がかかるので、長さ 4 のものは間引く。
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        groups.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+        for (i, (savedata, expected)) in groups.iter().enumerate() {
+            if expected.len() == 4 && i % 16 != 0 {
+                continue;
+            }
+            assert_eq!(&savedata.canonical_password(), expected, "{savedata:?}");
+        }
+    }
+}
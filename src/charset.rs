@@ -0,0 +1,163 @@
+use std::fmt::Write as _;
+
+use crate::password::{Password, PasswordChar};
+
+/// パスワードの各文字に対応する文字列の表(内部値順)。`Password::display_charset()` で使う。
+///
+/// 画像タイルのキーや独自の翻字など、任意の文字列でパスワードを表示するためのもの。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CharsetTable<'a>(pub [&'a str; 0x40]);
+
+impl CharsetTable<'static> {
+    /// ひらがな。`Password::display()` と同じ出力になる。
+    #[rustfmt::skip]
+    pub const HIRAGANA: Self = Self([
+        "あ", "い", "う", "え", "お",
+        "か", "き", "く", "け", "こ",
+        "さ", "し", "す", "せ", "そ",
+        "た", "ち", "つ", "て", "と",
+        "な", "に", "ぬ", "ね", "の",
+        "は", "ひ", "ふ", "へ", "ほ",
+        "ま", "み", "む", "め", "も",
+        "や", "ゆ", "よ",
+        "ら", "り", "る", "れ", "ろ",
+        "わ",
+        "が", "ぎ", "ぐ", "げ", "ご",
+        "ざ", "じ", "ず", "ぜ", "ぞ",
+        "ば", "び", "ぶ", "べ", "ぼ",
+        "ぱ", "ぴ", "ぷ", "ぺ", "ぽ",
+    ]);
+
+    /// ローマ字(ヘボン式、小文字)。`PasswordChar::to_romaji()` と同じ。
+    pub const ROMAJI: Self = {
+        let mut table = [""; 0x40];
+        let mut i = 0;
+        while i < table.len() {
+            table[i] = PasswordChar::ALL[i].to_romaji();
+            i += 1;
+        }
+        Self(table)
+    };
+
+    /// 内部値の 10 進表記。
+    ///
+    /// 内部値の順(あ, い, う, …, ぽ)に並べたタイル画像の番号としてそのまま使える。
+    #[rustfmt::skip]
+    pub const INDEX: Self = Self([
+        "0",  "1",  "2",  "3",  "4",
+        "5",  "6",  "7",  "8",  "9",
+        "10", "11", "12", "13", "14",
+        "15", "16", "17", "18", "19",
+        "20", "21", "22", "23", "24",
+        "25", "26", "27", "28", "29",
+        "30", "31", "32", "33", "34",
+        "35", "36", "37",
+        "38", "39", "40", "41", "42",
+        "43",
+        "44", "45", "46", "47", "48",
+        "49", "50", "51", "52", "53",
+        "54", "55", "56", "57", "58",
+        "59", "60", "61", "62", "63",
+    ]);
+}
+
+impl<'a> CharsetTable<'a> {
+    /// 文字 `pc` に対応する文字列を返す。
+    pub const fn get(&self, pc: PasswordChar) -> &'a str {
+        self.0[pc as usize]
+    }
+}
+
+impl Password {
+    /// パスワードの各文字を `table` の文字列に置き換えてフォーマットする `Display` オブジェクトを返す。
+    ///
+    /// 文字を区切るには `PasswordDisplayCharset::separated()` を使う。
+    pub fn display_charset<'a>(
+        &'a self,
+        table: &'a CharsetTable<'a>,
+    ) -> PasswordDisplayCharset<'a> {
+        PasswordDisplayCharset {
+            password: self,
+            table,
+            separator: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PasswordDisplayCharset<'a> {
+    password: &'a Password,
+    table: &'a CharsetTable<'a>,
+    separator: Option<char>,
+}
+
+impl PasswordDisplayCharset<'_> {
+    /// 文字の間に `separator` を挟むようにする。
+    pub fn separated(self, separator: char) -> Self {
+        Self {
+            separator: Some(separator),
+            ..self
+        }
+    }
+}
+
+impl std::fmt::Display for PasswordDisplayCharset<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, &pc) in self.password.iter().enumerate() {
+            if let Some(sep) = self.separator.filter(|_| i != 0) {
+                f.write_char(sep)?;
+            }
+            f.write_str(self.table.get(pc))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charset_table() {
+        for pc in PasswordChar::all() {
+            assert_eq!(CharsetTable::HIRAGANA.get(pc), pc.to_char().to_string());
+            assert_eq!(CharsetTable::ROMAJI.get(pc), pc.to_romaji());
+            assert_eq!(CharsetTable::INDEX.get(pc), pc.to_inner().to_string());
+        }
+    }
+
+    #[test]
+    fn test_password_display_charset() {
+        let password = Password::parse("おにのばか").unwrap();
+        assert_eq!(
+            password
+                .display_charset(&CharsetTable::HIRAGANA)
+                .to_string(),
+            password.display().to_string()
+        );
+        assert_eq!(
+            password.display_charset(&CharsetTable::ROMAJI).to_string(),
+            password.display_romaji().to_string()
+        );
+        assert_eq!(
+            password
+                .display_charset(&CharsetTable::INDEX)
+                .separated(',')
+                .to_string(),
+            "4,21,24,54,5"
+        );
+
+        // 独自の表。
+        let names: Vec<String> = (0..0x40).map(|i| format!("<tile{i:02}>")).collect();
+        let table = CharsetTable(std::array::from_fn(|i| names[i].as_str()));
+        assert_eq!(
+            password.display_charset(&table).to_string(),
+            "<tile04><tile21><tile24><tile54><tile05>"
+        );
+        assert_eq!(
+            password.display_charset(&table).separated(' ').to_string(),
+            "<tile04> <tile21> <tile24> <tile54> <tile05>"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use thiserror::Error;
+
 use crate::bounded::BoundedU8;
 
 /// mod 64 加算によるチェックサム。
@@ -29,3 +31,42 @@ impl Checksum {
         self.sum_xor
     }
 }
+
+/// パスワードのチェックサムの検査結果。`Password::checksum_report()` で得られる。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ChecksumReport {
+    /// バイト列に格納されたチェックサム。
+    pub embed: Checksum,
+    /// バイト列の内容から計算されたチェックサム。
+    pub calculated: Checksum,
+    /// 両者が一致するかどうか。
+    pub matches: bool,
+}
+
+/// バイト列に格納されたチェックサムと計算されたチェックサムが一致しないことを表すエラー。
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[error("checksum mismatch: embed={embed:?}, calculated={calculated:?}")]
+pub struct ChecksumMismatch {
+    /// バイト列に格納されたチェックサム。
+    pub embed: Checksum,
+    /// バイト列の内容から計算されたチェックサム。
+    pub calculated: Checksum,
+}
+
+/// `SerializedBytes::to_savedata_checked()` のエラー。チェックサムが一致しないことを表す。
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[error(
+    "checksum mismatch: embedded add={:#04X} xor={:#04X}, calculated add={:#04X} xor={:#04X}",
+    embed.sum_add(),
+    embed.sum_xor(),
+    calculated.sum_add(),
+    calculated.sum_xor()
+)]
+pub struct SavedataDecodeError {
+    /// バイト列に格納されたチェックサム。
+    pub embed: Checksum,
+    /// バイト列の内容から計算されたチェックサム。
+    pub calculated: Checksum,
+    /// バイト列の長さ。
+    pub len: usize,
+}
@@ -0,0 +1,226 @@
+//! ゲーム内で確認したパスワードとゲーム状態の対応(コーパス)。
+//!
+//! データは `DATA` にプレーンテキストで埋め込まれている。形式はデータ先頭のコメントを参照。
+//! 他言語への移植の際は、`DATA` を読んで同様に検証するとよい。
+//!
+//! **未完成**: 目標は短い・中程度・最大長のパスワードを含む 10〜20 件のゲームからの書き起こしだが、
+//! 現在のエントリはそのうち 2 件のみ(パスワード「ふ」と、それをロードしてすぐ神主に話しかけたときのパスワード)。
+//! どちらも `SerializedBytes` のテストで既に検証しているものである。
+//! ゲームで確認していないエントリは追加しないこと。
+
+use crate::password::Password;
+use crate::savedata::{Inventory, Savedata};
+use crate::serialized::SerializedBytes;
+
+/// コーパスのデータ。
+pub const DATA: &str = include_str!("corpus.txt");
+
+/// 各エントリが持つ列の名前。
+const COLUMNS: [&str; 6] = [
+    "password",
+    "xp",
+    "purse",
+    "events",
+    "equipment",
+    "inventory",
+];
+
+/// コーパスの検証に失敗したエントリ。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CorpusFailure {
+    /// `DATA` 内の行番号(1 始まり)。
+    pub line: usize,
+    /// パスワード。
+    pub password: String,
+    /// 一致しなかった列の名前。エントリ自体が不正な場合は `"format"`、ロードできない場合は `"password"`。
+    pub field: &'static str,
+    /// 期待値。
+    pub expected: String,
+    /// 実際の値。
+    pub actual: String,
+}
+
+impl std::fmt::Display for CorpusFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {}: {}: expected '{}', got '{}'",
+            self.line, self.password, self.field, self.expected, self.actual
+        )
+    }
+}
+
+/// コーパスの全エントリをデコードし、期待値と一致しなかったものを返す。
+pub fn verify() -> Vec<CorpusFailure> {
+    verify_data(DATA)
+}
+
+fn verify_data(data: &str) -> Vec<CorpusFailure> {
+    let mut failures = vec![];
+
+    for (i, line) in data.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let expected: Vec<_> = line.split_whitespace().collect();
+        let failure = |field, expected: &str, actual: String| CorpusFailure {
+            line: line_no,
+            password: line.split_whitespace().next().unwrap().to_owned(),
+            field,
+            expected: expected.to_owned(),
+            actual,
+        };
+
+        if expected.len() != COLUMNS.len() {
+            failures.push(failure(
+                "format",
+                &format!("{} columns", COLUMNS.len()),
+                format!("{} columns", expected.len()),
+            ));
+            continue;
+        }
+
+        let Some(savedata) = load(expected[0]) else {
+            failures.push(failure("password", expected[0], "(unloadable)".to_owned()));
+            continue;
+        };
+
+        for ((field, expected), actual) in COLUMNS
+            .into_iter()
+            .zip(expected.iter().copied())
+            .zip(render(expected[0], &savedata))
+            .skip(1)
+        {
+            if expected != actual {
+                failures.push(failure(field, expected, actual));
+            }
+        }
+    }
+
+    failures
+}
+
+/// パスワードをロードし、ロード後のゲーム状態を返す。
+fn load(s: &str) -> Option<Savedata> {
+    let password = Password::parse(s).ok()?;
+    let savedata = SerializedBytes::from_password(&password).to_savedata()?;
+    Some(savedata.normalize())
+}
+
+/// ゲーム状態をコーパスの各列の形式で表す。
+fn render(password: &str, savedata: &Savedata) -> [String; COLUMNS.len()] {
+    let events = savedata
+        .events
+        .flags()
+        .iter()
+        .map(|&(set, _)| if set { '1' } else { '0' })
+        .collect();
+
+    let eq = &savedata.equipment;
+    let equipment = [
+        eq.helm.get(),
+        eq.weapon.get(),
+        eq.armor.get(),
+        eq.shoes.get(),
+        eq.accessory0.get(),
+        eq.accessory1.get(),
+        eq.accessory2.get(),
+        eq.accessory3.get(),
+    ]
+    .map(|x| x.to_string())
+    .join("/");
+
+    [
+        password.to_owned(),
+        savedata.xp.to_string(),
+        savedata.purse.to_string(),
+        events,
+        equipment,
+        render_inventory(&savedata.inventory),
+    ]
+}
+
+fn render_inventory(inventory: &Inventory) -> String {
+    if inventory.is_empty() {
+        return "-".to_owned();
+    }
+
+    inventory
+        .iter()
+        .map(|item| format!("{:02X}", item.get()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let failures = verify();
+        assert!(
+            failures.is_empty(),
+            "{}",
+            failures
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_verify_failure() {
+        let data = "\
+# comment
+ふ 65535 65534 11111111 0/0/3/4/1/0/1/1 3F,3F,3F,3F,3F,3F,3F,3F
+
+おにのばき 0 0 00000000 0/0/0/0/0/0/0/0 -
+ふ 65535
+";
+        assert_eq!(
+            verify_data(data),
+            [
+                CorpusFailure {
+                    line: 2,
+                    password: "ふ".to_owned(),
+                    field: "purse",
+                    expected: "65534".to_owned(),
+                    actual: "65535".to_owned(),
+                },
+                CorpusFailure {
+                    line: 4,
+                    password: "おにのばき".to_owned(),
+                    field: "password",
+                    expected: "おにのばき".to_owned(),
+                    actual: "(unloadable)".to_owned(),
+                },
+                CorpusFailure {
+                    line: 5,
+                    password: "ふ".to_owned(),
+                    field: "format",
+                    expected: "6 columns".to_owned(),
+                    actual: "2 columns".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_inventory() {
+        assert_eq!(render_inventory(&Inventory::new()), "-");
+        assert_eq!(
+            render_inventory(
+                &[1, 0x3F]
+                    .map(|x| crate::savedata::ItemId::new(x).unwrap())
+                    .into_iter()
+                    .collect()
+            ),
+            "01,3F"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! 五十音順を仮定した文字表での `PasswordChar` の位置と、`EntryGrid` 上での入力コスト。
+//!
+//! **仮定**: ゲームのパスワード入力画面の配置は確認していない。ここでの文字表は五十音順に並べただけのもので、
+//! 位置はゲームのものではない。入力コストは呼び出し側が与える `EntryGrid` に対して計算する。
+
+use crate::movie::{shortest_move, EntryCell, EntryGrid};
+use crate::password::{Password, PasswordChar};
+
+/// 仮定した文字表の行数。
+pub const ASSUMED_GRID_ROWS: u8 = 14;
+
+/// 仮定した文字表の列数。
+pub const ASSUMED_GRID_COLS: u8 = 5;
+
+/// 五十音順を仮定した文字表。行は子音(あ行, か行, ..., ぱ行)、列は母音(あ段, い段, ..., お段)。
+///
+/// や行とわ行には空きマスがある。ゲームの入力画面の配置とは照合していない。
+#[rustfmt::skip]
+const ASSUMED_GRID: [[Option<PasswordChar>; ASSUMED_GRID_COLS as usize]; ASSUMED_GRID_ROWS as usize] = {
+    use PasswordChar::*;
+    [
+        [Some(A),  Some(I),  Some(U),  Some(E),  Some(O)],
+        [Some(Ka), Some(Ki), Some(Ku), Some(Ke), Some(Ko)],
+        [Some(Sa), Some(Si), Some(Su), Some(Se), Some(So)],
+        [Some(Ta), Some(Ti), Some(Tu), Some(Te), Some(To)],
+        [Some(Na), Some(Ni), Some(Nu), Some(Ne), Some(No)],
+        [Some(Ha), Some(Hi), Some(Hu), Some(He), Some(Ho)],
+        [Some(Ma), Some(Mi), Some(Mu), Some(Me), Some(Mo)],
+        [Some(Ya), None,     Some(Yu), None,     Some(Yo)],
+        [Some(Ra), Some(Ri), Some(Ru), Some(Re), Some(Ro)],
+        [Some(Wa), None,     None,     None,     None    ],
+        [Some(Ga), Some(Gi), Some(Gu), Some(Ge), Some(Go)],
+        [Some(Za), Some(Zi), Some(Zu), Some(Ze), Some(Zo)],
+        [Some(Ba), Some(Bi), Some(Bu), Some(Be), Some(Bo)],
+        [Some(Pa), Some(Pi), Some(Pu), Some(Pe), Some(Po)],
+    ]
+};
+
+/// 内部値から仮定した文字表での位置 (行, 列) への変換表。
+const ASSUMED_GRID_POS: [(u8, u8); 0x40] = {
+    let mut table = [(0, 0); 0x40];
+    let mut row = 0;
+    while row < ASSUMED_GRID.len() {
+        let mut col = 0;
+        while col < ASSUMED_GRID[row].len() {
+            if let Some(pc) = ASSUMED_GRID[row][col] {
+                table[pc as usize] = (row as u8, col as u8);
+            }
+            col += 1;
+        }
+        row += 1;
+    }
+    table
+};
+
+impl PasswordChar {
+    /// 五十音順を仮定した文字表での行(0 始まり)を返す。ゲームの入力画面での位置とは限らない。
+    pub const fn row(self) -> u8 {
+        ASSUMED_GRID_POS[self as usize].0
+    }
+
+    /// 五十音順を仮定した文字表での列(0 始まり)を返す。ゲームの入力画面での位置とは限らない。
+    pub const fn col(self) -> u8 {
+        ASSUMED_GRID_POS[self as usize].1
+    }
+
+    /// 五十音順を仮定した文字表の位置 (`row`, `col`) にある文字を返す。範囲外や空きマスに対しては `None` を返す。
+    pub const fn from_grid(row: u8, col: u8) -> Option<Self> {
+        if row >= ASSUMED_GRID_ROWS || col >= ASSUMED_GRID_COLS {
+            return None;
+        }
+        ASSUMED_GRID[row as usize][col as usize]
+    }
+}
+
+/// パスワード入力のコスト(ボタンを押す回数)のモデル。
+///
+/// カーソルは十字キー 1 回で `EntryGrid` の 1 マス移動し(空きマスも通れるものとする)、
+/// 文字の決定に A ボタンを 1 回押すものとする。最後の入力終了は含まない。
+/// 回り込みありの場合、移動は `Password::input_buttons()` と一致する。
+///
+/// 位置は `EntryGrid` と同じく `(x, y)` で表す。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EntryCostModel<'a> {
+    /// 入力画面の文字グリッド。
+    pub grid: &'a EntryGrid,
+    /// グリッドの端で反対側に回り込めるかどうか。
+    pub wrap: bool,
+    /// カーソルの初期位置 `(x, y)`。
+    pub start: (usize, usize),
+}
+
+impl<'a> EntryCostModel<'a> {
+    /// グリッドを指定して `EntryCostModel` を作る。回り込みあり、カーソルは左上から始まる。
+    pub const fn new(grid: &'a EntryGrid) -> Self {
+        Self {
+            grid,
+            wrap: true,
+            start: (0, 0),
+        }
+    }
+
+    /// パスワードの各文字の入力コスト(直前の位置からの移動回数 + 1)を返す。
+    ///
+    /// `start` がグリッドの範囲外の場合、`None` を返す。
+    pub fn step_costs(&self, password: &Password) -> Option<Vec<u32>> {
+        let mut pos = self.start;
+        password
+            .iter()
+            .map(|&pc| {
+                let next = self.grid.position(EntryCell::Char(pc));
+                let cost = self.distance(pos, next)? + 1;
+                pos = next;
+                Some(cost)
+            })
+            .collect()
+    }
+
+    /// パスワード全体の入力コストを返す。
+    ///
+    /// `start` がグリッドの範囲外の場合、`None` を返す。
+    pub fn cost(&self, password: &Password) -> Option<u32> {
+        self.step_costs(password)
+            .map(|costs| costs.into_iter().sum())
+    }
+
+    /// グリッドの 2 つの位置の間の移動回数を返す。どちらかが範囲外なら `None` を返す。
+    pub fn distance(&self, from: (usize, usize), to: (usize, usize)) -> Option<u32> {
+        let grid = self.grid;
+        grid.get(from.0, from.1)?;
+        grid.get(to.0, to.1)?;
+
+        let (_, dx) = shortest_move(from.0, to.0, grid.width(), self.wrap);
+        let (_, dy) = shortest_move(from.1, to.1, grid.height(), self.wrap);
+        Some(u32::try_from(dx + dy).unwrap())
+    }
+}
+
+/// `EntryCostModel::new(grid)` でのパスワードの入力コストを返す。
+pub fn entry_cost(password: &Password, grid: &EntryGrid) -> u32 {
+    EntryCostModel::new(grid)
+        .cost(password)
+        .expect("grid must contain the start position (0, 0)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        for pc in PasswordChar::iter() {
+            assert_eq!(PasswordChar::from_grid(pc.row(), pc.col()), Some(pc));
+        }
+
+        // 空きマスを除く全てのマスがちょうど 1 文字ずつに対応する。
+        let n_cells = (0..ASSUMED_GRID_ROWS)
+            .flat_map(|row| (0..ASSUMED_GRID_COLS).map(move |col| (row, col)))
+            .filter(|&(row, col)| PasswordChar::from_grid(row, col).is_some())
+            .count();
+        assert_eq!(n_cells, 0x40);
+
+        assert_eq!((PasswordChar::Yu.row(), PasswordChar::Yu.col()), (7, 2));
+        assert_eq!(PasswordChar::from_grid(7, 1), None);
+        assert_eq!(PasswordChar::from_grid(9, 0), Some(PasswordChar::Wa));
+        assert_eq!(PasswordChar::from_grid(9, 4), None);
+        assert_eq!(PasswordChar::from_grid(13, 4), Some(PasswordChar::Po));
+        assert_eq!(PasswordChar::from_grid(14, 0), None);
+        assert_eq!(PasswordChar::from_grid(0, 5), None);
+
+        // 清音と濁音/半濁音は同じ列にある。
+        for pc in PasswordChar::iter() {
+            assert_eq!(pc.col(), pc.base().col());
+        }
+    }
+
+    /// 仮定した文字表の下に入力終了の行を加えたグリッド(幅 5、高さ 15)。
+    fn grid() -> EntryGrid {
+        let mut cells = vec![];
+        for row in 0..ASSUMED_GRID_ROWS {
+            for col in 0..ASSUMED_GRID_COLS {
+                cells.push(
+                    PasswordChar::from_grid(row, col).map_or(EntryCell::Blank, EntryCell::Char),
+                );
+            }
+        }
+        cells.push(EntryCell::End);
+        cells.extend([EntryCell::Blank; 4]);
+
+        EntryGrid::new(usize::from(ASSUMED_GRID_COLS), &cells).unwrap()
+    }
+
+    fn pos(pc: PasswordChar) -> (usize, usize) {
+        (usize::from(pc.col()), usize::from(pc.row()))
+    }
+
+    #[test]
+    fn test_entry_cost() {
+        let grid = grid();
+        let model = EntryCostModel::new(&grid);
+        let no_wrap = EntryCostModel {
+            wrap: false,
+            ..model
+        };
+
+        // 同じ文字の連続は A ボタンのみ。
+        let password = Password::parse("ああああ").unwrap();
+        assert_eq!(entry_cost(&password, &grid), 4);
+        assert_eq!(no_wrap.step_costs(&password), Some(vec![1, 1, 1, 1]));
+
+        // 'お' は 'あ' の左に回り込める。
+        let password = Password::parse("おあ").unwrap();
+        assert_eq!(model.step_costs(&password), Some(vec![2, 2]));
+        assert_eq!(no_wrap.step_costs(&password), Some(vec![5, 5]));
+        // 'ぱ' は 'あ' の上に、入力終了の行を越えて回り込める。
+        let password = Password::parse("ぱあ").unwrap();
+        assert_eq!(model.step_costs(&password), Some(vec![3, 3]));
+        assert_eq!(no_wrap.step_costs(&password), Some(vec![14, 14]));
+        assert_eq!(no_wrap.cost(&password), Some(28));
+
+        // 'ぬ' は表の中央付近にあり、どちら回りでも遠い。
+        let cheap = Password::parse("あいうえお").unwrap();
+        let expensive = Password::parse("ぬあぬあぬ").unwrap();
+        assert_eq!(entry_cost(&cheap, &grid), 5 + 4);
+        assert_eq!(entry_cost(&expensive, &grid), 5 + 5 * 6);
+
+        let model_nu = EntryCostModel {
+            start: pos(PasswordChar::Nu),
+            ..model
+        };
+        assert_eq!(
+            model_nu.step_costs(&Password::parse("ぬ").unwrap()),
+            Some(vec![1])
+        );
+
+        // 回り込みありの移動は入力ムービーのボタン列と一致する。
+        for start in [(0, 0), (3, 2), (4, 14)] {
+            let model = EntryCostModel { start, ..model };
+            for password in [&cheap, &expensive] {
+                let last = pos(*password.iter().last().unwrap());
+                let to_end = model.distance(last, (0, 15 - 1)).unwrap();
+                assert_eq!(
+                    model.cost(password).unwrap() + to_end + 1,
+                    u32::try_from(password.input_buttons(&grid, start).unwrap().len()).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_entry_out_of_grid() {
+        let grid = grid();
+        let model = EntryCostModel::new(&grid);
+        let password = Password::parse("あ").unwrap();
+
+        assert_eq!(model.distance((5, 0), (0, 0)), None);
+        assert_eq!(model.distance((0, 0), (0, 15)), None);
+        assert_eq!(model.distance((4, 14), (0, 0)), Some(2));
+
+        for start in [(5, 0), (0, 15), (usize::MAX, usize::MAX)] {
+            let model = EntryCostModel { start, ..model };
+            assert_eq!(model.step_costs(&password), None);
+            assert_eq!(model.cost(&password), None);
+        }
+    }
+
+    #[test]
+    fn test_entry_distance() {
+        let grid = grid();
+        for wrap in [false, true] {
+            let model = EntryCostModel {
+                wrap,
+                ..EntryCostModel::new(&grid)
+            };
+            for a in PasswordChar::iter() {
+                for b in PasswordChar::iter() {
+                    let d = model.distance(pos(a), pos(b)).unwrap();
+                    assert_eq!(d, model.distance(pos(b), pos(a)).unwrap());
+                    assert_eq!(d == 0, a == b);
+                    if wrap {
+                        assert!(d <= u32::try_from(grid.width() / 2 + grid.height() / 2).unwrap());
+                    }
+                }
+            }
+        }
+    }
+}
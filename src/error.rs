@@ -0,0 +1,350 @@
+use arrayvec::CapacityError;
+use thiserror::Error;
+
+use crate::bounded::{BoundedIntegerParseError, BoundedIntegerRangeError, BoundedSliceError};
+use crate::checksum::{ChecksumMismatch, SavedataDecodeError};
+use crate::fields::SavedataFieldError;
+use crate::layout::FieldValueError;
+use crate::lua::LuaAddressError;
+use crate::movie::MovieOptionsError;
+use crate::password::{
+    PasswordCharFromCharError, PasswordCharFromInnerError, PasswordFromBytesError,
+    PasswordHexParseError, PasswordLengthError, PasswordParseError, PasswordSpannedParseError,
+};
+use crate::repair::FuzzyParseError;
+use crate::search::PatternParseError;
+use crate::serialized::{
+    BitCapacityError, EncodeLenError, LengthMismatch, SerializedBytesFromU8Error,
+};
+use crate::share::ShareCodeError;
+use crate::validate::LoadError;
+
+/// このクレートで発生しうるエラーをまとめた型。
+///
+/// 個別のエラー型からは `From` で変換できる。元のエラーは `source()` で得られ、
+/// `Display` には元のエラーのメッセージを含めない。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// 文字を `PasswordChar` に変換できなかった。
+    #[error("failed to convert char to password char")]
+    PasswordCharFromChar(#[from] PasswordCharFromCharError),
+
+    /// 値を `PasswordChar` に変換できなかった。
+    #[error("failed to convert value to password char")]
+    PasswordCharFromInner(#[from] PasswordCharFromInnerError),
+
+    /// パスワード文字列のパースに失敗した。
+    #[error("failed to parse password")]
+    PasswordParse(#[from] PasswordParseError),
+
+    /// パスワード文字列のパースに失敗した(位置情報付き)。
+    #[error("failed to parse password")]
+    PasswordSpannedParse(#[from] PasswordSpannedParseError),
+
+    /// 16 進ダンプ形式のパスワードのパースに失敗した。
+    #[error("failed to parse hex password")]
+    PasswordHexParse(#[from] PasswordHexParseError),
+
+    /// 内部値のスライスからパスワードを作れなかった。
+    #[error("failed to build password from bytes")]
+    PasswordFromBytes(#[from] PasswordFromBytesError),
+
+    /// `u8` のスライスから `SerializedBytes` を作れなかった。
+    #[error("failed to convert bytes to serialized bytes")]
+    SerializedBytesFromU8(#[from] SerializedBytesFromU8Error),
+
+    /// 指定された長さにエンコードできなかった。
+    #[error("failed to encode savedata in the requested length")]
+    EncodeLen(#[source] EncodeLenError),
+
+    /// `BitWriter` にビットを追加できなかった。
+    #[error("failed to push bits")]
+    BitCapacity(#[from] BitCapacityError),
+
+    /// コピー先の長さが正しくない。
+    #[error("buffer length mismatch")]
+    LengthMismatch(#[from] LengthMismatch),
+
+    /// 候補を列挙するパースに失敗した(パースの失敗は `PasswordParse` になる)。
+    #[error("failed to parse password fuzzily")]
+    FuzzyParse(#[source] FuzzyParseError),
+
+    /// パターン文字列のパースに失敗した。
+    #[error("failed to parse pattern")]
+    PatternParse(#[from] PatternParseError),
+
+    /// 値域が制限された整数のパースに失敗した。
+    #[error("failed to parse bounded integer")]
+    IntegerParse(#[from] BoundedIntegerParseError),
+
+    /// 共有コードのデコードに失敗した。
+    #[error("failed to decode share code")]
+    ShareCode(#[from] ShareCodeError),
+
+    /// チェックサムが一致しない。
+    #[error("password checksum is invalid")]
+    Checksum(#[from] ChecksumMismatch),
+
+    /// ゲーム状態へのデシリアライズに失敗した。
+    #[error("failed to decode savedata")]
+    SavedataDecode(#[from] SavedataDecodeError),
+
+    /// 長さが範囲外。
+    #[error("length {len} is out of range {min}..={max}")]
+    Length { len: usize, min: usize, max: usize },
+
+    /// 整数が値域外。
+    #[error("number is out of range")]
+    OutOfRange(#[from] BoundedIntegerRangeError),
+
+    /// スライスの要素が値域外。
+    #[error("slice contains an out-of-range number")]
+    SliceOutOfRange(#[from] BoundedSliceError),
+
+    /// セーブデータのフィールドの読み書きに失敗した。
+    #[error("failed to access savedata field")]
+    Field(#[from] SavedataFieldError),
+
+    /// `SerializedBytes` のフィールドに書き込む値が範囲外。
+    #[error("serialized field value is out of range")]
+    FieldValue(#[from] FieldValueError),
+
+    /// 入力ムービーの生成オプションが不正。
+    #[error("failed to generate input movie")]
+    MovieOptions(#[from] MovieOptionsError),
+
+    /// Lua スクリプトの書き込み先がアドレス空間に収まらない。
+    #[error("failed to generate lua script")]
+    LuaAddress(#[from] LuaAddressError),
+
+    /// 容量を超えて要素を追加しようとした。
+    #[error("capacity exceeded")]
+    Capacity(#[source] CapacityError),
+}
+
+impl<T> From<CapacityError<T>> for Error {
+    fn from(e: CapacityError<T>) -> Self {
+        Self::Capacity(e.simplify())
+    }
+}
+
+impl From<PasswordLengthError> for Error {
+    fn from(e: PasswordLengthError) -> Self {
+        Self::Length {
+            len: e.actual,
+            min: e.min,
+            max: e.max,
+        }
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(e: LoadError) -> Self {
+        match e {
+            LoadError::Parse(e) => e.into(),
+            LoadError::Checksum(e) => e.into(),
+        }
+    }
+}
+
+impl From<FuzzyParseError> for Error {
+    fn from(e: FuzzyParseError) -> Self {
+        match e {
+            FuzzyParseError::Parse(e) => e.into(),
+            e @ FuzzyParseError::TooManyCandidates { .. } => Self::FuzzyParse(e),
+        }
+    }
+}
+
+impl From<EncodeLenError> for Error {
+    fn from(e: EncodeLenError) -> Self {
+        match e {
+            EncodeLenError::Length(e) => e.into(),
+            e @ EncodeLenError::Truncated { .. } => Self::EncodeLen(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+    use crate::layout::FieldId;
+    use crate::password::Password;
+    use crate::savedata::{Inventory, ItemId, Savedata};
+    use crate::serialized::SerializedBytes;
+
+    fn checksum_mismatch() -> ChecksumMismatch {
+        let bytes = SerializedBytes::from_password(&Password::parse("おにのばき").unwrap());
+        bytes.decode_into(&mut Savedata::default()).unwrap_err()
+    }
+
+    /// `Display` が元のエラーのメッセージを含まず、`source()` が元のエラーを返すことを確かめる。
+    fn assert_chained(err: Error, inner: &dyn std::error::Error) {
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), inner.to_string());
+        assert!(!err.to_string().contains(&inner.to_string()));
+    }
+
+    #[test]
+    fn test_from() {
+        let e = PasswordParseError::InvalidChar { pos: 1, ch: 'x' };
+        assert_eq!(Error::from(e.clone()), Error::PasswordParse(e.clone()));
+        assert_chained(e.clone().into(), &e);
+
+        let e = PasswordSpannedParseError::InvalidLength {
+            char_len: 0,
+            byte_len: 0,
+        };
+        assert_eq!(
+            Error::from(e.clone()),
+            Error::PasswordSpannedParse(e.clone())
+        );
+        assert_chained(e.clone().into(), &e);
+
+        let e = PasswordCharFromCharError('x');
+        assert_eq!(Error::from(e), Error::PasswordCharFromChar(e));
+        assert_chained(e.into(), &e);
+
+        let e = PasswordCharFromInnerError(0x40);
+        assert_eq!(Error::from(e), Error::PasswordCharFromInner(e));
+        assert_chained(e.into(), &e);
+
+        let e = PasswordHexParseError::InvalidToken { index: 0 };
+        assert_eq!(Error::from(e.clone()), Error::PasswordHexParse(e.clone()));
+        assert_chained(e.clone().into(), &e);
+
+        let e = PasswordFromBytesError::InvalidLength { len: 0 };
+        assert_eq!(Error::from(e), Error::PasswordFromBytes(e));
+        assert_chained(e.into(), &e);
+
+        let e = SerializedBytesFromU8Error::OutOfRange {
+            index: 1,
+            value: 0x40,
+        };
+        assert_eq!(Error::from(e), Error::SerializedBytesFromU8(e));
+        assert_chained(e.into(), &e);
+
+        let e = LengthMismatch {
+            expected: 5,
+            actual: 6,
+        };
+        assert_eq!(Error::from(e), Error::LengthMismatch(e));
+        assert_chained(e.into(), &e);
+
+        let e = MovieOptionsError::FramesPerPressTooSmall(1);
+        assert_eq!(Error::from(e), Error::MovieOptions(e));
+        assert_chained(e.into(), &e);
+
+        let e = LuaAddressError {
+            base_addr: 0xFFFC,
+            len: 5,
+        };
+        assert_eq!(Error::from(e), Error::LuaAddress(e));
+        assert_chained(e.into(), &e);
+
+        let e = BitCapacityError::TooWide { n: 9, max: 8 };
+        assert_eq!(Error::from(e), Error::BitCapacity(e));
+        assert_chained(e.into(), &e);
+
+        let e = PatternParseError::InvalidLength;
+        assert_eq!(Error::from(e.clone()), Error::PatternParse(e.clone()));
+        assert_chained(e.clone().into(), &e);
+
+        let e = "x".parse::<ItemId>().unwrap_err();
+        assert_eq!(Error::from(e.clone()), Error::IntegerParse(e.clone()));
+        assert_chained(e.clone().into(), &e);
+
+        let e = ShareCodeError::Empty;
+        assert_eq!(Error::from(e.clone()), Error::ShareCode(e.clone()));
+        assert_chained(e.clone().into(), &e);
+
+        let e = checksum_mismatch();
+        assert_eq!(Error::from(e), Error::Checksum(e));
+        assert_chained(e.into(), &e);
+
+        let e = SerializedBytes::from_password(&Password::parse("おにのばき").unwrap())
+            .to_savedata_checked()
+            .unwrap_err();
+        assert_eq!(Error::from(e), Error::SavedataDecode(e));
+        assert_chained(e.into(), &e);
+
+        let e = BoundedIntegerRangeError::AboveMax;
+        assert_eq!(Error::from(e), Error::OutOfRange(e));
+        assert_chained(e.into(), &e);
+
+        let e = BoundedSliceError {
+            index: 2,
+            value: 0x40,
+        };
+        assert_eq!(Error::from(e), Error::SliceOutOfRange(e));
+        assert_chained(e.into(), &e);
+
+        let e = SavedataFieldError::UnknownField("foo".to_owned());
+        assert_eq!(Error::from(e.clone()), Error::Field(e.clone()));
+        assert_chained(e.clone().into(), &e);
+
+        let e = FieldValueError {
+            field: crate::layout::FieldId::Deposit,
+            value: 0x40,
+            max: 0x3F,
+        };
+        assert_eq!(Error::from(e), Error::FieldValue(e));
+        assert_chained(e.into(), &e);
+
+        let mut inventory = Inventory::from([ItemId::MAX; 8]);
+        let e = inventory.try_push(ItemId::MIN).unwrap_err();
+        assert_eq!(Error::from(e), Error::Capacity(e.simplify()));
+        assert_chained(e.into(), &e);
+
+        let e = Error::Length {
+            len: 0,
+            min: 1,
+            max: 38,
+        };
+        assert!(e.source().is_none());
+        assert_eq!(e.to_string(), "length 0 is out of range 1..=38");
+        assert_eq!(Error::from(PasswordLengthError::new(0)), e);
+    }
+
+    #[test]
+    fn test_from_load_error() {
+        let e = PasswordParseError::InvalidLength { len: 0 };
+        assert_eq!(
+            Error::from(LoadError::Parse(e.clone())),
+            Error::PasswordParse(e)
+        );
+
+        let e = checksum_mismatch();
+        assert_eq!(Error::from(LoadError::Checksum(e)), Error::Checksum(e));
+    }
+
+    #[test]
+    fn test_from_fuzzy_parse_error() {
+        let e = PasswordParseError::InvalidLength { len: 0 };
+        assert_eq!(
+            Error::from(FuzzyParseError::Parse(e.clone())),
+            Error::PasswordParse(e)
+        );
+
+        let e = FuzzyParseError::TooManyCandidates { count: 2, max: 1 };
+        assert_eq!(Error::from(e.clone()), Error::FuzzyParse(e.clone()));
+        assert_chained(e.clone().into(), &e);
+    }
+
+    #[test]
+    fn test_from_encode_len_error() {
+        let e = PasswordLengthError::new(0);
+        assert_eq!(Error::from(EncodeLenError::Length(e)), Error::from(e));
+
+        let e = EncodeLenError::Truncated {
+            len: 17,
+            field: FieldId::Events,
+            min_len: 18,
+        };
+        assert_eq!(Error::from(e), Error::EncodeLen(e));
+        assert_chained(e.into(), &e);
+    }
+}
@@ -0,0 +1,473 @@
+//! C 言語向けの FFI。
+//!
+//! 各関数は `MOMODEN_PASSWORD_OK` または負のエラーコードを返す。パニックが境界を越えることはない。
+//!
+//! ヘッダファイルは `include/momoden_password.h` (cbindgen で生成)。
+
+use std::ffi::{c_char, c_int, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::password::{Password, PasswordParseError};
+use crate::savedata::*;
+use crate::serialized::SerializedBytes;
+
+/// 成功。
+pub const MOMODEN_PASSWORD_OK: c_int = 0;
+
+/// 引数にヌルポインタが渡された。
+pub const MOMODEN_PASSWORD_ERR_NULL: c_int = -1;
+
+/// 文字列が UTF-8 として正しくない。
+pub const MOMODEN_PASSWORD_ERR_UTF8: c_int = -2;
+
+/// パスワードの文字数が正しくない。
+pub const MOMODEN_PASSWORD_ERR_INVALID_LENGTH: c_int = -3;
+
+/// パスワードに無効な文字が含まれている。
+pub const MOMODEN_PASSWORD_ERR_INVALID_CHAR: c_int = -4;
+
+/// パスワードのチェックサムが一致しない。
+pub const MOMODEN_PASSWORD_ERR_CHECKSUM: c_int = -5;
+
+/// セーブデータのフィールドが範囲外。
+pub const MOMODEN_PASSWORD_ERR_OUT_OF_RANGE: c_int = -6;
+
+/// 出力バッファが小さすぎる。
+pub const MOMODEN_PASSWORD_ERR_BUFFER_TOO_SMALL: c_int = -7;
+
+/// 内部エラー(パニック)。
+pub const MOMODEN_PASSWORD_ERR_PANIC: c_int = -100;
+
+/// `momoden_password_encode()` の出力に十分なバッファサイズ(終端の NUL を含む)。
+pub const MOMODEN_PASSWORD_ENCODE_BUF_LEN: usize = 115;
+
+// ひらがなは UTF-8 で 3 バイト。
+const _: () = assert!(MOMODEN_PASSWORD_ENCODE_BUF_LEN == 3 * Password::MAX_LEN + 1);
+
+/// `Savedata` の C 向け表現。
+///
+/// フラグ群はビットマスクで、`Savedata` 内の各構造体のフィールド順に bit0 から割り当てる。
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SavedataC {
+    /// 経験値。
+    pub xp: u16,
+    /// 所持金。
+    pub purse: u16,
+    /// 預金 (0..=0x3F)。
+    pub deposit: u8,
+    /// 年齢。
+    pub age: u8,
+    /// 加齢タイマー上位バイト。
+    pub age_timer_hi: u8,
+    /// 術習得状態 (bit0: きんたん, ..., bit7: ほうひ)。
+    pub spells: u8,
+    /// イベント進行状態 (bit0: hanasaka, ..., bit7: hohoemi)。
+    pub events: u8,
+    /// 宝物所持状態 (bit0: dragon, ..., bit4: swallow)。
+    pub treasures: u8,
+    /// お供存在状態 (bit0: 犬, bit1: キジ, bit2: 猿)。
+    pub minions: u8,
+    /// 復活地点ID (0..=0xF)。
+    pub respawn: u8,
+    /// ひえんブックマーク (bit0: 旅立ちの村, ..., bit9: 飛燕の城)。
+    pub bookmarks: u16,
+    /// 兜インデックス (0..=3)。
+    pub helm: u8,
+    /// 武器インデックス (0..=0xF)。
+    pub weapon: u8,
+    /// 鎧インデックス (0..=0xF)。
+    pub armor: u8,
+    /// 靴インデックス (0..=7)。
+    pub shoes: u8,
+    /// いでたち0インデックス (0..=3)。
+    pub accessory0: u8,
+    /// いでたち1インデックス (0..=3)。
+    pub accessory1: u8,
+    /// いでたち2インデックス (0..=1)。
+    pub accessory2: u8,
+    /// いでたち3インデックス (0..=1)。
+    pub accessory3: u8,
+    /// インベントリ内のアイテム数 (0..=8)。
+    pub inventory_len: u8,
+    /// インベントリ内のアイテムID (1..=0x3F)。先頭 `inventory_len` 個のみが有効。
+    pub inventory: [u8; 8],
+}
+
+/// パスワード文字列(NUL 終端 UTF-8)をデコードし、それに記録されたセーブデータを `out` に書き込む。
+///
+/// 書き込まれるのはパスワードに記録された生のセーブデータで、ロード時の装備の正規化は行わない。
+///
+/// # Safety
+///
+/// `password` は NUL 終端文字列を、`out` は書き込み可能な `SavedataC` を指していなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn momoden_password_decode(
+    password: *const c_char,
+    out: *mut SavedataC,
+) -> c_int {
+    guard(|| {
+        if out.is_null() {
+            return Err(MOMODEN_PASSWORD_ERR_NULL);
+        }
+
+        let password = unsafe { parse_password(password) }?;
+        let savedata = SerializedBytes::from_password(&password)
+            .to_savedata()
+            .ok_or(MOMODEN_PASSWORD_ERR_CHECKSUM)?;
+
+        unsafe { out.write(savedata_to_c(&savedata)) };
+
+        Ok(())
+    })
+}
+
+/// セーブデータをパスワード化し、NUL 終端 UTF-8 文字列として `buf` に書き込む。
+///
+/// `len` は `buf` のサイズ(バイト単位)。`MOMODEN_PASSWORD_ENCODE_BUF_LEN` 以上あれば常に十分。
+///
+/// # Safety
+///
+/// `savedata` は有効な `SavedataC` を、`buf` は `len` バイト書き込み可能な領域を指していなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn momoden_password_encode(
+    savedata: *const SavedataC,
+    buf: *mut c_char,
+    len: usize,
+) -> c_int {
+    guard(|| {
+        if savedata.is_null() || buf.is_null() {
+            return Err(MOMODEN_PASSWORD_ERR_NULL);
+        }
+
+        let savedata =
+            savedata_from_c(unsafe { &*savedata }).ok_or(MOMODEN_PASSWORD_ERR_OUT_OF_RANGE)?;
+        let password = SerializedBytes::from_savedata(&savedata).to_password();
+        let s = password.display().to_string();
+
+        if s.len() >= len {
+            return Err(MOMODEN_PASSWORD_ERR_BUFFER_TOO_SMALL);
+        }
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(s.as_ptr(), buf.cast::<u8>(), s.len());
+            buf.add(s.len()).write(0);
+        }
+
+        Ok(())
+    })
+}
+
+/// パスワード文字列(NUL 終端 UTF-8)が有効ならば `MOMODEN_PASSWORD_OK` を返す。
+///
+/// # Safety
+///
+/// `password` は NUL 終端文字列を指していなければならない。
+#[no_mangle]
+pub unsafe extern "C" fn momoden_password_validate(password: *const c_char) -> c_int {
+    guard(|| {
+        let password = unsafe { parse_password(password) }?;
+
+        if !password.is_valid() {
+            return Err(MOMODEN_PASSWORD_ERR_CHECKSUM);
+        }
+
+        Ok(())
+    })
+}
+
+/// `f` を実行し、結果をエラーコードに変換する。パニックした場合は `MOMODEN_PASSWORD_ERR_PANIC` を返す。
+fn guard(f: impl FnOnce() -> Result<(), c_int>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => MOMODEN_PASSWORD_OK,
+        Ok(Err(code)) => code,
+        Err(_) => MOMODEN_PASSWORD_ERR_PANIC,
+    }
+}
+
+/// # Safety
+///
+/// `password` はヌルポインタであるか、NUL 終端文字列を指していなければならない。
+unsafe fn parse_password(password: *const c_char) -> Result<Password, c_int> {
+    if password.is_null() {
+        return Err(MOMODEN_PASSWORD_ERR_NULL);
+    }
+
+    let s = unsafe { CStr::from_ptr(password) }
+        .to_str()
+        .map_err(|_| MOMODEN_PASSWORD_ERR_UTF8)?;
+
+    Password::parse(s).map_err(|e| match e {
+        PasswordParseError::InvalidLength { .. } => MOMODEN_PASSWORD_ERR_INVALID_LENGTH,
+        PasswordParseError::InvalidChar { .. } | PasswordParseError::InvalidChars(_) => {
+            MOMODEN_PASSWORD_ERR_INVALID_CHAR
+        }
+    })
+}
+
+/// フラグ群の構造体とビットマスクの相互変換関数を定義する。
+macro_rules! define_flags_conv {
+    ($to_bits:ident, $from_bits:ident, $ty:ident, $bits:ty, [$($field:ident),+ $(,)?]) => {
+        fn $to_bits(flags: $ty) -> $bits {
+            [$(flags.$field),+]
+                .into_iter()
+                .enumerate()
+                .fold(0, |acc, (i, b)| acc | (<$bits>::from(b) << i))
+        }
+
+        /// 未使用ビットが立っている場合は `None` を返す。
+        fn $from_bits(bits: $bits) -> Option<$ty> {
+            let mut i = 0;
+            let flags = $ty {
+                $($field: {
+                    let b = (bits >> i) & 1 != 0;
+                    i += 1;
+                    b
+                }),+
+            };
+            (bits.checked_shr(i).unwrap_or(0) == 0).then_some(flags)
+        }
+    };
+}
+
+define_flags_conv!(
+    spells_to_bits,
+    spells_from_bits,
+    Spells,
+    u8,
+    [kintan, rokkaku, inazuma, hien, mankintan, fuyuu, dadadidi, houhi]
+);
+define_flags_conv!(
+    events_to_bits,
+    events_from_bits,
+    Events,
+    u8,
+    [hanasaka, kintaro, urashima, netaro, murata, sarukani, dragon, hohoemi]
+);
+define_flags_conv!(
+    treasures_to_bits,
+    treasures_from_bits,
+    Treasures,
+    u8,
+    [dragon, fur, hotoke, hourai, swallow]
+);
+define_flags_conv!(
+    minions_to_bits,
+    minions_from_bits,
+    Minions,
+    u8,
+    [dog, pheasant, monkey]
+);
+define_flags_conv!(
+    bookmarks_to_bits,
+    bookmarks_from_bits,
+    Bookmarks,
+    u16,
+    [tabidachi, hanasaka, kintaro, urashima, netaro, kibou, sarukani, taketori, hohoemi, hien]
+);
+
+fn savedata_to_c(savedata: &Savedata) -> SavedataC {
+    let mut inventory = [0; 8];
+    for (dst, item) in inventory.iter_mut().zip(&savedata.inventory) {
+        *dst = item.get();
+    }
+
+    SavedataC {
+        xp: savedata.xp,
+        purse: savedata.purse,
+        deposit: savedata.deposit.get(),
+        age: savedata.age,
+        age_timer_hi: savedata.age_timer_hi,
+        spells: spells_to_bits(savedata.spells),
+        events: events_to_bits(savedata.events),
+        treasures: treasures_to_bits(savedata.treasures),
+        minions: minions_to_bits(savedata.minions),
+        respawn: savedata.respawn.get(),
+        bookmarks: bookmarks_to_bits(savedata.bookmarks),
+        helm: savedata.equipment.helm.get(),
+        weapon: savedata.equipment.weapon.get(),
+        armor: savedata.equipment.armor.get(),
+        shoes: savedata.equipment.shoes.get(),
+        accessory0: savedata.equipment.accessory0.get(),
+        accessory1: savedata.equipment.accessory1.get(),
+        accessory2: savedata.equipment.accessory2.get(),
+        accessory3: savedata.equipment.accessory3.get(),
+        inventory_len: savedata.inventory.len() as u8,
+        inventory,
+    }
+}
+
+/// 範囲外のフィールドがある場合は `None` を返す。
+fn savedata_from_c(c: &SavedataC) -> Option<Savedata> {
+    let items = c.inventory.get(..usize::from(c.inventory_len))?;
+    let inventory = items
+        .iter()
+        .map(|&item| ItemId::new(item))
+        .collect::<Option<Inventory>>()?;
+
+    Some(Savedata {
+        xp: c.xp,
+        purse: c.purse,
+        deposit: Deposit::new(c.deposit)?,
+        age: c.age,
+        age_timer_hi: c.age_timer_hi,
+        spells: spells_from_bits(c.spells)?,
+        events: events_from_bits(c.events)?,
+        treasures: treasures_from_bits(c.treasures)?,
+        minions: minions_from_bits(c.minions)?,
+        bookmarks: bookmarks_from_bits(c.bookmarks)?,
+        respawn: RespawnId::new(c.respawn)?,
+        equipment: Equipment {
+            helm: HelmIndex::new(c.helm)?,
+            weapon: WeaponIndex::new(c.weapon)?,
+            armor: ArmorIndex::new(c.armor)?,
+            shoes: ShoesIndex::new(c.shoes)?,
+            accessory0: Accessory0Index::new(c.accessory0)?,
+            accessory1: Accessory1Index::new(c.accessory1)?,
+            accessory2: Accessory2Index::new(c.accessory2)?,
+            accessory3: Accessory3Index::new(c.accessory3)?,
+        },
+        inventory,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::{align_of, offset_of, size_of};
+
+    use super::*;
+
+    // C ABI を経由して呼び出す。
+    const DECODE: unsafe extern "C" fn(*const c_char, *mut SavedataC) -> c_int =
+        momoden_password_decode;
+    const ENCODE: unsafe extern "C" fn(*const SavedataC, *mut c_char, usize) -> c_int =
+        momoden_password_encode;
+    const VALIDATE: unsafe extern "C" fn(*const c_char) -> c_int = momoden_password_validate;
+
+    fn decode(password: &CStr) -> Result<SavedataC, c_int> {
+        let mut out = SavedataC::default();
+        match unsafe { DECODE(password.as_ptr(), &mut out) } {
+            MOMODEN_PASSWORD_OK => Ok(out),
+            code => Err(code),
+        }
+    }
+
+    fn encode(savedata: &SavedataC, len: usize) -> Result<String, c_int> {
+        let mut buf = vec![0x7F as c_char; len];
+        match unsafe { ENCODE(savedata, buf.as_mut_ptr(), len) } {
+            MOMODEN_PASSWORD_OK => {
+                let s = unsafe { CStr::from_ptr(buf.as_ptr()) };
+                Ok(s.to_str().unwrap().to_owned())
+            }
+            code => Err(code),
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(size_of::<SavedataC>(), 32);
+        assert_eq!(align_of::<SavedataC>(), 2);
+
+        assert_eq!(offset_of!(SavedataC, xp), 0);
+        assert_eq!(offset_of!(SavedataC, purse), 2);
+        assert_eq!(offset_of!(SavedataC, deposit), 4);
+        assert_eq!(offset_of!(SavedataC, respawn), 11);
+        assert_eq!(offset_of!(SavedataC, bookmarks), 12);
+        assert_eq!(offset_of!(SavedataC, helm), 14);
+        assert_eq!(offset_of!(SavedataC, accessory3), 21);
+        assert_eq!(offset_of!(SavedataC, inventory_len), 22);
+        assert_eq!(offset_of!(SavedataC, inventory), 23);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let savedata = SerializedBytes::from_password(&Password::parse("おにのばか").unwrap())
+            .to_savedata()
+            .unwrap();
+
+        let c = decode(c"おにのばか").unwrap();
+        assert_eq!(c, savedata_to_c(&savedata));
+        assert_eq!(savedata_from_c(&c), Some(savedata.clone()));
+
+        let password = encode(&c, MOMODEN_PASSWORD_ENCODE_BUF_LEN).unwrap();
+        let password = std::ffi::CString::new(password).unwrap();
+        assert_eq!(decode(&password), Ok(c));
+
+        let full = Savedata {
+            spells: Spells::ALL,
+            events: Events::ALL,
+            treasures: Treasures::ALL,
+            minions: Minions::ALL,
+            bookmarks: Bookmarks::ALL,
+            inventory: [ItemId::MAX; 8].into(),
+            ..Default::default()
+        };
+        let c = savedata_to_c(&full);
+        assert_eq!(c.spells, 0xFF);
+        assert_eq!(c.treasures, 0x1F);
+        assert_eq!(c.bookmarks, 0x3FF);
+        assert_eq!(savedata_from_c(&c), Some(full));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            unsafe { VALIDATE(c"おにのばか".as_ptr()) },
+            MOMODEN_PASSWORD_OK
+        );
+        assert_eq!(
+            unsafe { VALIDATE(c"おにのばき".as_ptr()) },
+            MOMODEN_PASSWORD_ERR_CHECKSUM
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            unsafe { VALIDATE(std::ptr::null()) },
+            MOMODEN_PASSWORD_ERR_NULL
+        );
+        assert_eq!(
+            unsafe { DECODE(c"おにのばか".as_ptr(), std::ptr::null_mut()) },
+            MOMODEN_PASSWORD_ERR_NULL
+        );
+        assert_eq!(
+            unsafe { ENCODE(std::ptr::null(), std::ptr::null_mut(), 0) },
+            MOMODEN_PASSWORD_ERR_NULL
+        );
+
+        assert_eq!(decode(c"\xFF"), Err(MOMODEN_PASSWORD_ERR_UTF8));
+        assert_eq!(decode(c""), Err(MOMODEN_PASSWORD_ERR_INVALID_LENGTH));
+        assert_eq!(decode(c"おにのばx"), Err(MOMODEN_PASSWORD_ERR_INVALID_CHAR));
+        assert_eq!(decode(c"おにのばき"), Err(MOMODEN_PASSWORD_ERR_CHECKSUM));
+
+        let c = SavedataC::default();
+        let len = encode(&c, MOMODEN_PASSWORD_ENCODE_BUF_LEN).unwrap().len();
+        assert!(encode(&c, len + 1).is_ok());
+        assert_eq!(encode(&c, len), Err(MOMODEN_PASSWORD_ERR_BUFFER_TOO_SMALL));
+        assert_eq!(encode(&c, 0), Err(MOMODEN_PASSWORD_ERR_BUFFER_TOO_SMALL));
+
+        for invalid in [
+            SavedataC { deposit: 0x40, ..c },
+            SavedataC { helm: 4, ..c },
+            SavedataC { minions: 0x08, ..c },
+            SavedataC {
+                bookmarks: 0x400,
+                ..c
+            },
+            SavedataC {
+                inventory_len: 9,
+                ..c
+            },
+            SavedataC {
+                inventory_len: 1,
+                ..c
+            },
+        ] {
+            assert_eq!(
+                encode(&invalid, MOMODEN_PASSWORD_ENCODE_BUF_LEN),
+                Err(MOMODEN_PASSWORD_ERR_OUT_OF_RANGE)
+            );
+        }
+    }
+}
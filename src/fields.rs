@@ -0,0 +1,370 @@
+use thiserror::Error;
+
+use crate::savedata::*;
+
+/// `Savedata` のフィールド 1 つを名前で読み書きするための記述子。
+///
+/// 全てのフィールドは `u16` 値として読み書きする(真偽値は 0/1)。
+#[derive(Clone, Copy)]
+pub struct SavedataField {
+    name: &'static str,
+    kind: SavedataFieldKind,
+    get: fn(&Savedata) -> u16,
+    set: fn(&mut Savedata, u16) -> Result<(), SavedataFieldError>,
+}
+
+impl SavedataField {
+    /// フィールド名を返す(例: `"xp"`, `"spells.hien"`, `"inventory[0]"`)。
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// フィールドの種類を返す。
+    pub const fn kind(&self) -> SavedataFieldKind {
+        self.kind
+    }
+
+    /// フィールドの値を返す。
+    pub fn get(&self, savedata: &Savedata) -> u16 {
+        (self.get)(savedata)
+    }
+
+    /// フィールドに値を設定する。値が範囲外ならばエラーを返し、`savedata` は変更しない。
+    pub fn set(&self, savedata: &mut Savedata, value: u16) -> Result<(), SavedataFieldError> {
+        let (min, max) = self.kind.range();
+        if !(min..=max).contains(&value) {
+            return Err(SavedataFieldError::OutOfRange {
+                name: self.name,
+                value,
+                min,
+                max,
+            });
+        }
+
+        (self.set)(savedata, value)
+    }
+}
+
+impl std::fmt::Debug for SavedataField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SavedataField")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+/// `SavedataField` の種類。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SavedataFieldKind {
+    /// 真偽値 (0/1)。
+    Bool,
+
+    /// `min..=max` の範囲の整数。
+    Int { min: u16, max: u16 },
+
+    /// インベントリのスロット。0 は空(終端)を表し、それ以外はアイテムID。
+    Item,
+}
+
+impl SavedataFieldKind {
+    /// 値の範囲 `(min, max)` を返す。
+    pub const fn range(self) -> (u16, u16) {
+        match self {
+            Self::Bool => (0, 1),
+            Self::Int { min, max } => (min, max),
+            Self::Item => (0, ItemId::MAX_VALUE as u16),
+        }
+    }
+}
+
+/// `SavedataField` による読み書きで発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum SavedataFieldError {
+    /// 該当する名前のフィールドがない。
+    #[error("unknown field: {0}")]
+    UnknownField(String),
+
+    /// 値が範囲外。
+    #[error("{name}: value {value} is out of range {min}..={max}")]
+    OutOfRange {
+        name: &'static str,
+        value: u16,
+        min: u16,
+        max: u16,
+    },
+
+    /// インベントリに空きを作るような設定をしようとした。
+    #[error("inventory[{index}]: previous slot is empty")]
+    InventoryGap { index: usize },
+}
+
+macro_rules! field_bool {
+    ($name:literal, $($path:ident).+) => {
+        SavedataField {
+            name: $name,
+            kind: SavedataFieldKind::Bool,
+            get: |s| u16::from(s.$($path).+),
+            set: |s, v| {
+                s.$($path).+ = v != 0;
+                Ok(())
+            },
+        }
+    };
+}
+
+macro_rules! field_int {
+    ($name:literal, $ty:ty, $($path:ident).+) => {
+        SavedataField {
+            name: $name,
+            kind: SavedataFieldKind::Int {
+                min: <$ty>::MIN as u16,
+                max: <$ty>::MAX as u16,
+            },
+            get: |s| u16::from(s.$($path).+),
+            set: |s, v| {
+                s.$($path).+ = v as $ty;
+                Ok(())
+            },
+        }
+    };
+}
+
+macro_rules! field_bounded {
+    ($name:literal, $ty:ty, $($path:ident).+) => {
+        SavedataField {
+            name: $name,
+            kind: SavedataFieldKind::Int {
+                min: <$ty>::MIN_VALUE as u16,
+                max: <$ty>::MAX_VALUE as u16,
+            },
+            get: |s| u16::from(s.$($path).+.get()),
+            set: |s, v| {
+                // 範囲は呼び出し元で検査済み。
+                s.$($path).+ = <$ty>::new(v as u8).unwrap();
+                Ok(())
+            },
+        }
+    };
+}
+
+macro_rules! field_item {
+    ($name:literal, $idx:literal) => {
+        SavedataField {
+            name: $name,
+            kind: SavedataFieldKind::Item,
+            get: |s| {
+                s.inventory
+                    .get($idx)
+                    .map_or(0, |item| u16::from(item.get()))
+            },
+            set: |s, v| set_item(s, $idx, v),
+        }
+    };
+}
+
+/// インベントリのスロット `idx` に値を設定する。
+///
+/// 0 を設定するとそのスロット以降を空にする(ゲームでは 0 が終端なので)。
+fn set_item(savedata: &mut Savedata, idx: usize, value: u16) -> Result<(), SavedataFieldError> {
+    let inventory = &mut savedata.inventory;
+
+    match ItemId::new(value as u8) {
+        None => inventory.truncate(idx),
+        Some(item) if idx < inventory.len() => inventory[idx] = item,
+        Some(item) if idx == inventory.len() => inventory.push(item),
+        Some(_) => return Err(SavedataFieldError::InventoryGap { index: idx }),
+    }
+
+    Ok(())
+}
+
+impl Savedata {
+    /// 全フィールドの記述子。順序は `Savedata` の定義順。
+    pub const FIELDS: &'static [SavedataField] = &[
+        field_int!("xp", u16, xp),
+        field_int!("purse", u16, purse),
+        field_bounded!("deposit", Deposit, deposit),
+        field_int!("age", u8, age),
+        field_int!("age_timer_hi", u8, age_timer_hi),
+        field_bool!("spells.kintan", spells.kintan),
+        field_bool!("spells.rokkaku", spells.rokkaku),
+        field_bool!("spells.inazuma", spells.inazuma),
+        field_bool!("spells.hien", spells.hien),
+        field_bool!("spells.mankintan", spells.mankintan),
+        field_bool!("spells.fuyuu", spells.fuyuu),
+        field_bool!("spells.dadadidi", spells.dadadidi),
+        field_bool!("spells.houhi", spells.houhi),
+        field_bool!("events.hanasaka", events.hanasaka),
+        field_bool!("events.kintaro", events.kintaro),
+        field_bool!("events.urashima", events.urashima),
+        field_bool!("events.netaro", events.netaro),
+        field_bool!("events.murata", events.murata),
+        field_bool!("events.sarukani", events.sarukani),
+        field_bool!("events.dragon", events.dragon),
+        field_bool!("events.hohoemi", events.hohoemi),
+        field_bool!("treasures.dragon", treasures.dragon),
+        field_bool!("treasures.fur", treasures.fur),
+        field_bool!("treasures.hotoke", treasures.hotoke),
+        field_bool!("treasures.hourai", treasures.hourai),
+        field_bool!("treasures.swallow", treasures.swallow),
+        field_bool!("minions.dog", minions.dog),
+        field_bool!("minions.pheasant", minions.pheasant),
+        field_bool!("minions.monkey", minions.monkey),
+        field_bool!("bookmarks.tabidachi", bookmarks.tabidachi),
+        field_bool!("bookmarks.hanasaka", bookmarks.hanasaka),
+        field_bool!("bookmarks.kintaro", bookmarks.kintaro),
+        field_bool!("bookmarks.urashima", bookmarks.urashima),
+        field_bool!("bookmarks.netaro", bookmarks.netaro),
+        field_bool!("bookmarks.kibou", bookmarks.kibou),
+        field_bool!("bookmarks.sarukani", bookmarks.sarukani),
+        field_bool!("bookmarks.taketori", bookmarks.taketori),
+        field_bool!("bookmarks.hohoemi", bookmarks.hohoemi),
+        field_bool!("bookmarks.hien", bookmarks.hien),
+        field_bounded!("respawn", RespawnId, respawn),
+        field_bounded!("equipment.helm", HelmIndex, equipment.helm),
+        field_bounded!("equipment.weapon", WeaponIndex, equipment.weapon),
+        field_bounded!("equipment.armor", ArmorIndex, equipment.armor),
+        field_bounded!("equipment.shoes", ShoesIndex, equipment.shoes),
+        field_bounded!(
+            "equipment.accessory0",
+            Accessory0Index,
+            equipment.accessory0
+        ),
+        field_bounded!(
+            "equipment.accessory1",
+            Accessory1Index,
+            equipment.accessory1
+        ),
+        field_bounded!(
+            "equipment.accessory2",
+            Accessory2Index,
+            equipment.accessory2
+        ),
+        field_bounded!(
+            "equipment.accessory3",
+            Accessory3Index,
+            equipment.accessory3
+        ),
+        field_item!("inventory[0]", 0),
+        field_item!("inventory[1]", 1),
+        field_item!("inventory[2]", 2),
+        field_item!("inventory[3]", 3),
+        field_item!("inventory[4]", 4),
+        field_item!("inventory[5]", 5),
+        field_item!("inventory[6]", 6),
+        field_item!("inventory[7]", 7),
+    ];
+
+    /// 名前に対応するフィールドの記述子を返す。
+    pub fn field(name: &str) -> Option<&'static SavedataField> {
+        Self::FIELDS.iter().find(|field| field.name == name)
+    }
+
+    /// 名前で指定したフィールドの値を返す。
+    pub fn get_field(&self, name: &str) -> Option<u16> {
+        Self::field(name).map(|field| field.get(self))
+    }
+
+    /// 名前で指定したフィールドに値を設定する。
+    pub fn set_field(&mut self, name: &str, value: u16) -> Result<(), SavedataFieldError> {
+        let field =
+            Self::field(name).ok_or_else(|| SavedataFieldError::UnknownField(name.to_owned()))?;
+        field.set(self, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        let mut names: Vec<_> = Savedata::FIELDS.iter().map(SavedataField::name).collect();
+        assert_eq!(names.len(), 56);
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), 56);
+
+        assert_eq!(
+            Savedata::field("deposit").unwrap().kind(),
+            SavedataFieldKind::Int { min: 0, max: 0x3F }
+        );
+        assert_eq!(
+            Savedata::field("xp").unwrap().kind(),
+            SavedataFieldKind::Int {
+                min: 0,
+                max: 0xFFFF
+            }
+        );
+        assert!(Savedata::field("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut savedata = Savedata::default();
+
+        savedata.set_field("xp", 1234).unwrap();
+        savedata.set_field("spells.hien", 1).unwrap();
+        savedata.set_field("equipment.weapon", 13).unwrap();
+        assert_eq!(savedata.xp, 1234);
+        assert!(savedata.spells.hien);
+        assert_eq!(savedata.equipment.weapon.get(), 13);
+        assert_eq!(savedata.get_field("xp"), Some(1234));
+        assert_eq!(savedata.get_field("spells.hien"), Some(1));
+        assert_eq!(savedata.get_field("spells.kintan"), Some(0));
+
+        let orig = savedata.clone();
+        assert_eq!(
+            savedata.set_field("deposit", 0x40),
+            Err(SavedataFieldError::OutOfRange {
+                name: "deposit",
+                value: 0x40,
+                min: 0,
+                max: 0x3F
+            })
+        );
+        assert!(savedata.set_field("age", 0x100).is_err());
+        assert!(savedata.set_field("spells.hien", 2).is_err());
+        assert_eq!(
+            savedata.set_field("nonexistent", 0),
+            Err(SavedataFieldError::UnknownField("nonexistent".to_owned()))
+        );
+        assert_eq!(savedata, orig);
+
+        // 全フィールドについて、読んだ値を書き戻しても変化しない。
+        let savedata_full = Savedata {
+            spells: Spells::ALL,
+            inventory: [ItemId::MAX; 8].into(),
+            ..orig
+        };
+        let mut savedata = savedata_full.clone();
+        for field in Savedata::FIELDS {
+            field.set(&mut savedata, field.get(&savedata_full)).unwrap();
+        }
+        assert_eq!(savedata, savedata_full);
+    }
+
+    #[test]
+    fn test_inventory() {
+        let mut savedata = Savedata::default();
+
+        savedata.set_field("inventory[0]", 5).unwrap();
+        savedata.set_field("inventory[1]", 6).unwrap();
+        savedata.set_field("inventory[2]", 7).unwrap();
+        savedata.set_field("inventory[1]", 8).unwrap();
+        assert_eq!(savedata.get_field("inventory[1]"), Some(8));
+        assert_eq!(savedata.get_field("inventory[3]"), Some(0));
+        assert_eq!(savedata.inventory.len(), 3);
+
+        assert_eq!(
+            savedata.set_field("inventory[4]", 1),
+            Err(SavedataFieldError::InventoryGap { index: 4 })
+        );
+
+        // 0 を設定すると以降が空になる。
+        savedata.set_field("inventory[1]", 0).unwrap();
+        assert_eq!(savedata.inventory.len(), 1);
+        assert_eq!(savedata.get_field("inventory[2]"), Some(0));
+    }
+}
@@ -0,0 +1,290 @@
+//! プレイの記録として残したパスワード列の分析。
+//!
+//! セッションごとに控えたパスワードを順に並べて渡すと、各セッションでの変化
+//! (経験値、所持金、新たに達成したイベント、インベントリの出入り)をまとめる。
+
+use crate::password::Password;
+use crate::savedata::{ItemId, Savedata};
+use crate::serialized::SerializedBytes;
+use crate::validate::LoadError;
+
+/// パスワード列の分析結果。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct History {
+    /// 各パスワードの分析結果(入力と同じ順)。
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// 1 つのパスワードの分析結果。差分は直前のエントリとのもので、先頭のエントリでは全て空(0)となる。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryEntry {
+    /// パスワード。
+    pub password: Password,
+    /// ロード後のセーブデータ。
+    pub savedata: Savedata,
+    /// 経験値の増分。
+    pub xp_gained: i32,
+    /// 所持金の増分。
+    pub purse_delta: i32,
+    /// 新たに達成したイベントの名前。
+    pub new_events: Vec<&'static str>,
+    /// インベントリに増えたアイテム。
+    pub items_added: Vec<ItemId>,
+    /// インベントリから減ったアイテム。
+    pub items_removed: Vec<ItemId>,
+    /// 進行度が直前のエントリより小さい(順序が誤っている可能性がある)かどうか。
+    pub out_of_order: bool,
+    /// 同じセーブデータを持つ最初のエントリのインデックス(自身より前にあれば)。
+    pub duplicate_of: Option<usize>,
+}
+
+/// パスワード列を分析する。ロードできないパスワードがあればエラーを返す。
+pub fn analyze(passwords: &[Password]) -> Result<History, LoadError> {
+    let mut entries: Vec<HistoryEntry> = Vec::with_capacity(passwords.len());
+
+    for password in passwords {
+        let mut savedata = Savedata::default();
+        SerializedBytes::from_password(password).decode_into(&mut savedata)?;
+        let savedata = savedata.normalize();
+
+        let duplicate_of = entries.iter().position(|e| e.savedata == savedata);
+
+        let entry = match entries.last() {
+            None => HistoryEntry {
+                password: password.clone(),
+                savedata,
+                xp_gained: 0,
+                purse_delta: 0,
+                new_events: vec![],
+                items_added: vec![],
+                items_removed: vec![],
+                out_of_order: false,
+                duplicate_of,
+            },
+            Some(prev) => {
+                let prev = &prev.savedata;
+                let new_events = prev
+                    .events
+                    .flags()
+                    .into_iter()
+                    .zip(savedata.events.flags())
+                    .filter(|((before, _), (after, _))| !before && *after)
+                    .map(|(_, (_, name))| name)
+                    .collect();
+                HistoryEntry {
+                    password: password.clone(),
+                    xp_gained: i32::from(savedata.xp) - i32::from(prev.xp),
+                    purse_delta: i32::from(savedata.purse) - i32::from(prev.purse),
+                    new_events,
+                    items_added: multiset_sub(&savedata.inventory, &prev.inventory),
+                    items_removed: multiset_sub(&prev.inventory, &savedata.inventory),
+                    out_of_order: progression(&savedata) < progression(prev),
+                    duplicate_of,
+                    savedata,
+                }
+            }
+        };
+        entries.push(entry);
+    }
+
+    Ok(History { entries })
+}
+
+impl History {
+    /// 経験値の系列を返す。
+    pub fn xp_series(&self) -> Vec<u16> {
+        self.entries.iter().map(|e| e.savedata.xp).collect()
+    }
+
+    /// 所持金の系列を返す。
+    pub fn purse_series(&self) -> Vec<u16> {
+        self.entries.iter().map(|e| e.savedata.purse).collect()
+    }
+
+    /// 進行度(立っているフラグの総数)の系列を返す。
+    pub fn progression_series(&self) -> Vec<usize> {
+        self.entries
+            .iter()
+            .map(|e| progression(&e.savedata).0)
+            .collect()
+    }
+}
+
+/// 進行度。(術、イベント、宝物、お供、ひえんブックマークのうち立っているフラグの総数, 経験値)
+///
+/// これが減ることは通常のプレイではない。
+fn progression(savedata: &Savedata) -> (usize, u16) {
+    let n_flags = [
+        savedata.spells.flags().as_slice(),
+        &savedata.events.flags(),
+        &savedata.treasures.flags(),
+        &savedata.minions.flags(),
+        &savedata.bookmarks.flags(),
+    ]
+    .concat()
+    .into_iter()
+    .filter(|&(set, _)| set)
+    .count();
+
+    (n_flags, savedata.xp)
+}
+
+/// `lhs` から `rhs` の各要素を 1 つずつ取り除いた残りを返す。
+fn multiset_sub(lhs: &[ItemId], rhs: &[ItemId]) -> Vec<ItemId> {
+    let mut rhs = rhs.to_vec();
+    lhs.iter()
+        .copied()
+        .filter(|item| match rhs.iter().position(|x| x == item) {
+            Some(i) => {
+                rhs.swap_remove(i);
+                false
+            }
+            None => true,
+        })
+        .collect()
+}
+
+/// エントリごとに変化を列挙する。
+impl std::fmt::Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, e) in self.entries.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "#{} {}", i + 1, e.password.display_pretty())?;
+            writeln!(f, "  {}", e.savedata.summary())?;
+
+            if i != 0 {
+                writeln!(f, "  経験値 {:+}, 所持金 {:+}", e.xp_gained, e.purse_delta)?;
+            }
+            for name in &e.new_events {
+                writeln!(f, "  イベント: {name}")?;
+            }
+            for item in &e.items_added {
+                writeln!(f, "  アイテム: +0x{:02X}", item.get())?;
+            }
+            for item in &e.items_removed {
+                writeln!(f, "  アイテム: -0x{:02X}", item.get())?;
+            }
+            if e.out_of_order {
+                writeln!(f, "  警告: 直前のエントリより進行度が小さい")?;
+            }
+            if let Some(j) = e.duplicate_of {
+                writeln!(f, "  警告: #{} と同じ", j + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::savedata::{Events, Inventory};
+
+    fn items(ids: &[u8]) -> Inventory {
+        ids.iter().map(|&id| ItemId::new(id).unwrap()).collect()
+    }
+
+    fn password(savedata: &Savedata) -> Password {
+        SerializedBytes::from_savedata(savedata).to_password()
+    }
+
+    /// 3 セッション分のプレイ記録。3 つ目は 2 つ目より前のもの。
+    fn passwords() -> Vec<Password> {
+        let first = Savedata {
+            xp: 10,
+            purse: 100,
+            inventory: items(&[1, 2]),
+            ..Savedata::default()
+        };
+        let second = Savedata {
+            xp: 250,
+            purse: 40,
+            events: Events {
+                hanasaka: true,
+                netaro: true,
+                ..Events::default()
+            },
+            inventory: items(&[2, 3, 3]),
+            ..first.clone()
+        };
+        let third = Savedata {
+            xp: 120,
+            events: Events {
+                hanasaka: true,
+                ..Events::default()
+            },
+            ..second.clone()
+        };
+
+        [first, second, third].iter().map(password).collect()
+    }
+
+    #[test]
+    fn test_analyze() {
+        let history = analyze(&passwords()).unwrap();
+        let e = &history.entries;
+        assert_eq!(e.len(), 3);
+
+        assert_eq!(e[0].xp_gained, 0);
+        assert!(e[0].new_events.is_empty());
+        assert!(!e[0].out_of_order);
+
+        assert_eq!(e[1].xp_gained, 240);
+        assert_eq!(e[1].purse_delta, -60);
+        assert_eq!(
+            e[1].new_events,
+            ["花咲かの村で銀の鬼を倒した", "寝太郎を起こした"]
+        );
+        assert_eq!(e[1].items_added, items(&[3, 3]).as_slice());
+        assert_eq!(e[1].items_removed, items(&[1]).as_slice());
+        assert!(!e[1].out_of_order);
+
+        assert_eq!(e[2].xp_gained, -130);
+        assert!(e[2].new_events.is_empty());
+        assert!(e[2].out_of_order);
+        assert_eq!(e[2].duplicate_of, None);
+
+        assert_eq!(history.xp_series(), [10, 250, 120]);
+        assert_eq!(history.purse_series(), [100, 40, 40]);
+        assert_eq!(history.progression_series(), [0, 2, 1]);
+    }
+
+    #[test]
+    fn test_analyze_duplicate() {
+        let mut passwords = passwords();
+        passwords.push(passwords[0].clone());
+
+        let history = analyze(&passwords).unwrap();
+        assert_eq!(history.entries[3].duplicate_of, Some(0));
+        assert!(history.entries[..3]
+            .iter()
+            .all(|e| e.duplicate_of.is_none()));
+    }
+
+    #[test]
+    fn test_analyze_error() {
+        let passwords = [Password::parse("おにのばき").unwrap()];
+        assert!(matches!(analyze(&passwords), Err(LoadError::Checksum(_))));
+        assert_eq!(analyze(&[]).unwrap().entries, []);
+    }
+
+    #[test]
+    fn test_display() {
+        let history = analyze(&passwords()[..2]).unwrap();
+        let s = history.to_string();
+        let lines: Vec<_> = s.lines().collect();
+        assert!(lines[0].starts_with("#1 "));
+        assert!(lines.contains(&"  経験値 +240, 所持金 -60"));
+        assert!(lines.contains(&"  イベント: 寝太郎を起こした"));
+        assert!(lines.contains(&"  アイテム: +0x03"));
+        assert!(lines.contains(&"  アイテム: -0x01"));
+
+        let history = analyze(&passwords()).unwrap();
+        assert!(history
+            .to_string()
+            .ends_with("  警告: 直前のエントリより進行度が小さい\n"));
+    }
+}
@@ -0,0 +1,454 @@
+use std::io::{BufRead, Write};
+
+use thiserror::Error;
+
+use crate::password::Password;
+use crate::savedata::Savedata;
+use crate::serialized::SerializedBytes;
+use crate::validate::skip_line;
+
+/// `decode_lines()` のオプション。
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DecodeLinesOptions {
+    /// 無効な行があったら直ちにエラーを返すかどうか。
+    pub strict: bool,
+
+    /// 実際にロードした後の状態を出力するかどうか。
+    pub normalize: bool,
+}
+
+/// `encode_lines()` のオプション。
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct EncodeLinesOptions {
+    /// 無効な行があったら直ちにエラーを返すかどうか。
+    pub strict: bool,
+}
+
+/// 行単位処理の集計結果。空行は数えない。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LinesSummary {
+    /// 正常に処理できた行数。
+    pub ok: u64,
+
+    /// 形式は正しいが無効だった行数(チェックサム不一致、値の範囲外など)。
+    pub invalid: u64,
+
+    /// パースできなかった行数。
+    pub parse_error: u64,
+}
+
+impl LinesSummary {
+    /// 処理した行数の合計を返す。
+    pub fn total(&self) -> u64 {
+        self.ok + self.invalid + self.parse_error
+    }
+}
+
+/// 行単位処理で発生しうるエラー。
+#[derive(Debug, Error)]
+pub enum LinesError {
+    /// 入出力エラー。
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// strict モードで無効な行があった。
+    #[error("line {line}: {message}")]
+    Line { line: u64, message: String },
+}
+
+/// 1 行に 1 つずつ書かれたパスワードを読み、デコード結果を 1 行に 1 つずつ JSON で書き出す。
+///
+/// 出力する各オブジェクトは `line`, `password`, `valid` と、`savedata` または `error` を持つ。
+/// 空行は読み飛ばす。行は 1 つずつ読むので、メモリ使用量は `MAX_LINE_LEN` 程度に抑えられる。
+pub fn decode_lines<R: BufRead, W: Write>(
+    r: R,
+    mut w: W,
+    options: &DecodeLinesOptions,
+) -> Result<LinesSummary, LinesError> {
+    #[derive(serde::Serialize)]
+    struct Record<'a> {
+        line: u64,
+        password: &'a str,
+        valid: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        savedata: Option<Savedata>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    }
+
+    let mut summary = LinesSummary::default();
+
+    for_each_line(r, |line, s| {
+        let s = match s {
+            Ok(s) => s,
+            Err(msg) => {
+                summary.parse_error += 1;
+                check_strict(options.strict, line, msg)?;
+                let record = Record {
+                    line,
+                    password: "",
+                    valid: false,
+                    savedata: None,
+                    error: Some(msg.to_owned()),
+                };
+                return write_record(&mut w, &record);
+            }
+        };
+
+        let record = match Password::parse(s) {
+            Ok(password) => match SerializedBytes::from_password(&password).to_savedata() {
+                Some(savedata) => {
+                    summary.ok += 1;
+                    let savedata = if options.normalize {
+                        savedata.normalize()
+                    } else {
+                        savedata
+                    };
+                    Record {
+                        line,
+                        password: s,
+                        valid: true,
+                        savedata: Some(savedata),
+                        error: None,
+                    }
+                }
+                None => {
+                    summary.invalid += 1;
+                    check_strict(options.strict, line, "checksum mismatch")?;
+                    Record {
+                        line,
+                        password: s,
+                        valid: false,
+                        savedata: None,
+                        error: Some("checksum mismatch".to_owned()),
+                    }
+                }
+            },
+            Err(e) => {
+                summary.parse_error += 1;
+                check_strict(options.strict, line, &e)?;
+                Record {
+                    line,
+                    password: s,
+                    valid: false,
+                    savedata: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+
+        write_record(&mut w, &record)
+    })?;
+
+    w.flush()?;
+
+    Ok(summary)
+}
+
+/// 1 行に 1 つずつ JSON で書かれたセーブデータを読み、パスワード化した結果を 1 行に 1 つずつ JSON で書き出す。
+///
+/// 出力する各オブジェクトは `line` と、`password` または `error` を持つ。
+/// 空行は読み飛ばす。行は 1 つずつ読むので、メモリ使用量は `MAX_LINE_LEN` 程度に抑えられる。
+pub fn encode_lines<R: BufRead, W: Write>(
+    r: R,
+    mut w: W,
+    options: &EncodeLinesOptions,
+) -> Result<LinesSummary, LinesError> {
+    #[derive(serde::Serialize)]
+    struct Record {
+        line: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        password: Option<Password>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    }
+
+    let mut summary = LinesSummary::default();
+
+    for_each_line(r, |line, s| {
+        let s = match s {
+            Ok(s) => s,
+            Err(msg) => {
+                summary.parse_error += 1;
+                check_strict(options.strict, line, msg)?;
+                let record = Record {
+                    line,
+                    password: None,
+                    error: Some(msg.to_owned()),
+                };
+                return write_record(&mut w, &record);
+            }
+        };
+
+        let record = match serde_json::from_str::<Savedata>(s) {
+            Ok(savedata) => {
+                summary.ok += 1;
+                let password = SerializedBytes::from_savedata(&savedata).to_password();
+                Record {
+                    line,
+                    password: Some(password),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                // 構文は正しいが値が不正なものは invalid、それ以外はパースエラーとみなす。
+                if e.is_data() {
+                    summary.invalid += 1;
+                } else {
+                    summary.parse_error += 1;
+                }
+                check_strict(options.strict, line, &e)?;
+                Record {
+                    line,
+                    password: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+
+        write_record(&mut w, &record)
+    })?;
+
+    w.flush()?;
+
+    Ok(summary)
+}
+
+/// 1 行の最大バイト数。これを超える行はパースエラーとして扱う。
+pub const MAX_LINE_LEN: usize = 0x10000;
+
+/// 空でない各行について、行番号(1 始まり)と末尾の空白を除いた内容を `f` に渡す。
+///
+/// 行バッファは使い回す。UTF-8 として不正なバイトは U+FFFD に置換する。
+/// `MAX_LINE_LEN` を超える行については内容の代わりにエラーメッセージを渡す。
+fn for_each_line<R, F>(mut r: R, mut f: F) -> Result<(), LinesError>
+where
+    R: BufRead,
+    F: FnMut(u64, Result<&str, &str>) -> Result<(), LinesError>,
+{
+    let mut buf = Vec::<u8>::new();
+    let mut line = 0;
+
+    loop {
+        buf.clear();
+        let limit = MAX_LINE_LEN as u64 + 1;
+        if std::io::Read::take(&mut r, limit).read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        line += 1;
+
+        if buf.len() > MAX_LINE_LEN && buf.last() != Some(&b'\n') {
+            skip_line(&mut r)?;
+            f(line, Err("line too long"))?;
+            continue;
+        }
+
+        let s = String::from_utf8_lossy(&buf);
+        let s = s.trim_end();
+        if s.is_empty() {
+            continue;
+        }
+
+        f(line, Ok(s))?;
+    }
+
+    Ok(())
+}
+
+fn check_strict(strict: bool, line: u64, message: impl ToString) -> Result<(), LinesError> {
+    if strict {
+        return Err(LinesError::Line {
+            line,
+            message: message.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn write_record<W: Write>(w: &mut W, record: &impl serde::Serialize) -> Result<(), LinesError> {
+    serde_json::to_writer(&mut *w, record).map_err(std::io::Error::from)?;
+    w.write_all(b"\n")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(
+        input: &[u8],
+        options: &DecodeLinesOptions,
+    ) -> (LinesSummary, Vec<serde_json::Value>) {
+        let mut out = vec![];
+        let summary = decode_lines(input, &mut out, options).unwrap();
+        (summary, parse_output(&out))
+    }
+
+    fn encode(
+        input: &[u8],
+        options: &EncodeLinesOptions,
+    ) -> (LinesSummary, Vec<serde_json::Value>) {
+        let mut out = vec![];
+        let summary = encode_lines(input, &mut out, options).unwrap();
+        (summary, parse_output(&out))
+    }
+
+    fn parse_output(out: &[u8]) -> Vec<serde_json::Value> {
+        std::str::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_lines() {
+        let mut input = "おにのばか\n\nおにのばき\r\nabc\nふ\n".as_bytes().to_vec();
+        // 不正な UTF-8 を含む行。
+        input.extend_from_slice(b"\xFF\xFE\n");
+
+        let (summary, records) = decode(&input, &DecodeLinesOptions::default());
+        assert_eq!(
+            summary,
+            LinesSummary {
+                ok: 2,
+                invalid: 1,
+                parse_error: 2,
+            }
+        );
+        assert_eq!(summary.total(), 5);
+        assert_eq!(records.len(), 5);
+
+        assert_eq!(records[0]["line"], 1);
+        assert_eq!(records[0]["password"], "おにのばか");
+        assert_eq!(records[0]["valid"], true);
+        let savedata: Savedata = serde_json::from_value(records[0]["savedata"].clone()).unwrap();
+        assert_eq!(
+            Some(savedata),
+            SerializedBytes::from_password(&Password::parse("おにのばか").unwrap()).to_savedata()
+        );
+
+        assert_eq!(records[1]["line"], 3);
+        assert_eq!(records[1]["valid"], false);
+        assert_eq!(records[1]["error"], "checksum mismatch");
+        assert!(records[1].get("savedata").is_none());
+
+        assert_eq!(records[2]["line"], 4);
+        assert_eq!(records[2]["valid"], false);
+        assert!(records[2]["error"]
+            .as_str()
+            .unwrap()
+            .contains("invalid character"));
+
+        assert_eq!(records[3]["password"], "ふ");
+        assert_eq!(records[3]["valid"], true);
+
+        assert_eq!(records[4]["line"], 6);
+        assert_eq!(records[4]["password"], "\u{FFFD}\u{FFFD}");
+        assert_eq!(records[4]["valid"], false);
+    }
+
+    #[test]
+    fn test_long_line() {
+        let mut input = "?".repeat(MAX_LINE_LEN + 1).into_bytes();
+        input.extend_from_slice("\nおにのばか\n".as_bytes());
+        input.extend(std::iter::repeat_n(b'?', MAX_LINE_LEN * 3));
+
+        let (summary, records) = decode(&input, &DecodeLinesOptions::default());
+        assert_eq!(
+            summary,
+            LinesSummary {
+                ok: 1,
+                invalid: 0,
+                parse_error: 2,
+            }
+        );
+        assert_eq!(records[0]["line"], 1);
+        assert_eq!(records[0]["error"], "line too long");
+        assert_eq!(records[1]["line"], 2);
+        assert_eq!(records[1]["password"], "おにのばか");
+        assert_eq!(records[2]["line"], 3);
+    }
+
+    #[test]
+    fn test_decode_lines_strict() {
+        let input = "おにのばか\nおにのばき\nおにのばか\n".as_bytes();
+        let mut out = vec![];
+        let options = DecodeLinesOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let err = decode_lines(input, &mut out, &options).unwrap_err();
+        assert!(matches!(err, LinesError::Line { line: 2, .. }));
+        assert_eq!(parse_output(&out).len(), 1);
+    }
+
+    #[test]
+    fn test_encode_lines() {
+        let savedata = SerializedBytes::from_password(&Password::parse("おにのばか").unwrap())
+            .to_savedata()
+            .unwrap();
+        let mut big_deposit = serde_json::to_value(&savedata).unwrap();
+        big_deposit["deposit"] = 0x40.into();
+
+        let input = format!(
+            "{}\n\n{}\n{{\"xp\":\n{}\n",
+            serde_json::to_string(&savedata).unwrap(),
+            big_deposit,
+            serde_json::to_string(&Savedata::default()).unwrap(),
+        );
+
+        let (summary, records) = encode(input.as_bytes(), &EncodeLinesOptions::default());
+        assert_eq!(
+            summary,
+            LinesSummary {
+                ok: 2,
+                invalid: 1,
+                parse_error: 1,
+            }
+        );
+        assert_eq!(records.len(), 4);
+
+        let password = Password::parse(records[0]["password"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            SerializedBytes::from_password(&password).to_savedata(),
+            Some(savedata)
+        );
+        assert_eq!(records[1]["line"], 3);
+        assert!(records[1].get("password").is_none());
+        assert!(records[1]["error"].is_string());
+        assert_eq!(records[2]["line"], 4);
+        assert!(records[2]["error"].is_string());
+        assert_eq!(records[3]["line"], 5);
+
+        let options = EncodeLinesOptions { strict: true };
+        let err = encode_lines(input.as_bytes(), std::io::sink(), &options).unwrap_err();
+        assert!(matches!(err, LinesError::Line { line: 3, .. }));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = "おにのばか\nふ\n".as_bytes();
+        let mut decoded = vec![];
+        decode_lines(input, &mut decoded, &DecodeLinesOptions::default()).unwrap();
+
+        // デコード結果から savedata だけを取り出してエンコードする。
+        let savedata_lines: String = parse_output(&decoded)
+            .into_iter()
+            .map(|record| format!("{}\n", record["savedata"]))
+            .collect();
+        let (summary, records) = encode(savedata_lines.as_bytes(), &EncodeLinesOptions::default());
+        assert_eq!(summary.ok, 2);
+
+        for (record, orig) in records.iter().zip(["おにのばか", "ふ"]) {
+            let password = Password::parse(record["password"].as_str().unwrap()).unwrap();
+            let orig = Password::parse(orig).unwrap();
+            assert_eq!(
+                SerializedBytes::from_password(&password).to_savedata(),
+                SerializedBytes::from_password(&orig).to_savedata()
+            );
+        }
+    }
+}
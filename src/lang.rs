@@ -0,0 +1,72 @@
+//! 名前表の言語。
+//!
+//! 日本語名はゲーム内の表記に従う。英語名は非公式なもので、ファン翻訳とは照合していない。
+//! 英語名が定まっていない項目は未設定(`None`)のままにしてある。
+
+/// 表示言語。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Lang {
+    /// 日本語(ゲーム内の表記)。
+    #[default]
+    Ja,
+    /// 英語(非公式)。
+    En,
+}
+
+/// 名前表の 1 項目の名前。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FlagName {
+    ja: &'static str,
+    en: Option<&'static str>,
+}
+
+impl FlagName {
+    pub(crate) const fn new(ja: &'static str, en: Option<&'static str>) -> Self {
+        Self { ja, en }
+    }
+
+    /// 日本語名を返す。
+    pub const fn name_ja(self) -> &'static str {
+        self.ja
+    }
+
+    /// 非公式の英語名を返す。未設定なら `None` を返す。
+    pub const fn name_en(self) -> Option<&'static str> {
+        self.en
+    }
+
+    /// 指定した言語での名前を返す。英語名が未設定なら日本語名を返す。
+    pub const fn name_in(self, lang: Lang) -> &'static str {
+        match (lang, self.en) {
+            (Lang::En, Some(en)) => en,
+            _ => self.ja,
+        }
+    }
+}
+
+/// 値を指定した言語で表示するためのオブジェクト。`display_in()` で作る。
+#[derive(Debug)]
+pub struct DisplayIn<'a, T: ?Sized> {
+    pub(crate) value: &'a T,
+    pub(crate) lang: Lang,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_name() {
+        let name = FlagName::new("犬", Some("Dog"));
+        assert_eq!(name.name_ja(), "犬");
+        assert_eq!(name.name_en(), Some("Dog"));
+        assert_eq!(name.name_in(Lang::Ja), "犬");
+        assert_eq!(name.name_in(Lang::En), "Dog");
+
+        let name = FlagName::new("きんたん", None);
+        assert_eq!(name.name_en(), None);
+        assert_eq!(name.name_in(Lang::En), "きんたん");
+
+        assert_eq!(Lang::default(), Lang::Ja);
+    }
+}
@@ -0,0 +1,816 @@
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::checksum::SavedataDecodeError;
+use crate::macros::new_unchecked;
+use crate::savedata::Savedata;
+use crate::serialized::{SerializedByte, SerializedBytes};
+
+/// シリアライズ後のビット列におけるフィールドの識別子。
+///
+/// 並び順はビット列での出現順に等しい。
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FieldId {
+    AgeTimerHi,
+    PurseHi,
+    Age,
+    PurseLo,
+    XpLo,
+    Deposit,
+    XpHi,
+    Spells,
+    Treasures,
+    Respawn,
+    /// `Bookmarks` のうち `hien`, `hohoemi`。
+    Bookmarks1,
+    Minions,
+    /// `Bookmarks` のうち `Bookmarks1` 以外。
+    Bookmarks0,
+    Events,
+    Helm,
+    Weapon,
+    Armor,
+    Shoes,
+    Accessory0,
+    Accessory1,
+    Accessory2,
+    Accessory3,
+    /// インベントリの `n` 番目 (`n < 8`) のスロット。
+    InventorySlot(u8),
+}
+
+/// シリアライズ後のビット列における 1 フィールドの位置。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FieldSpec {
+    /// フィールドの識別子。
+    pub field: FieldId,
+    /// チェックサムを除いたビット列におけるオフセット。
+    pub bit_offset: usize,
+    /// ビット数。
+    pub bit_len: usize,
+}
+
+impl FieldSpec {
+    /// チェックサムを除いたビット列における範囲を返す。
+    pub const fn bits(&self) -> Range<usize> {
+        self.bit_offset..self.bit_offset + self.bit_len
+    }
+}
+
+const FIELD_LENS: [(FieldId, usize); 30] = [
+    (FieldId::AgeTimerHi, 8),
+    (FieldId::PurseHi, 8),
+    (FieldId::Age, 8),
+    (FieldId::PurseLo, 8),
+    (FieldId::XpLo, 8),
+    (FieldId::Deposit, 6),
+    (FieldId::XpHi, 8),
+    (FieldId::Spells, 8),
+    (FieldId::Treasures, 5),
+    (FieldId::Respawn, 4),
+    (FieldId::Bookmarks1, 2),
+    (FieldId::Minions, 3),
+    (FieldId::Bookmarks0, 8),
+    (FieldId::Events, 8),
+    (FieldId::Helm, 2),
+    (FieldId::Weapon, 4),
+    (FieldId::Armor, 4),
+    (FieldId::Shoes, 3),
+    (FieldId::Accessory0, 2),
+    (FieldId::Accessory1, 2),
+    (FieldId::Accessory2, 1),
+    (FieldId::Accessory3, 1),
+    (FieldId::InventorySlot(0), 6),
+    (FieldId::InventorySlot(1), 6),
+    (FieldId::InventorySlot(2), 6),
+    (FieldId::InventorySlot(3), 6),
+    (FieldId::InventorySlot(4), 6),
+    (FieldId::InventorySlot(5), 6),
+    (FieldId::InventorySlot(6), 6),
+    (FieldId::InventorySlot(7), 6),
+];
+
+/// シリアライズ後のビット列(チェックサムを除く)における全フィールドの位置。ビット列での出現順に並ぶ。
+///
+/// インベントリのスロットは最大 8 個全てを含むが、実際には最初の空きスロット(終端の 0)より後は読まれない。
+pub const SERIALIZED_LAYOUT: &[FieldSpec] = &{
+    let mut res = [FieldSpec {
+        field: FieldId::AgeTimerHi,
+        bit_offset: 0,
+        bit_len: 0,
+    }; FIELD_LENS.len()];
+
+    let mut offset = 0;
+    let mut i = 0;
+    while i < FIELD_LENS.len() {
+        let (field, bit_len) = FIELD_LENS[i];
+        res[i] = FieldSpec {
+            field,
+            bit_offset: offset,
+            bit_len,
+        };
+        offset += bit_len;
+        i += 1;
+    }
+
+    res
+};
+
+impl FieldId {
+    /// `SERIALIZED_LAYOUT` におけるこのフィールドの位置を返す。
+    ///
+    /// # Panics
+    ///
+    /// `InventorySlot(n)` で `n >= 8` ならパニックする。
+    pub fn spec(self) -> FieldSpec {
+        *SERIALIZED_LAYOUT
+            .iter()
+            .find(|spec| spec.field == self)
+            .unwrap_or_else(|| panic!("invalid field: {self:?}"))
+    }
+
+    /// チェックサムを除いたビット列の `pos` ビット目を含むフィールドを返す。範囲外なら `None` を返す。
+    pub fn at_bit(pos: usize) -> Option<Self> {
+        SERIALIZED_LAYOUT
+            .iter()
+            .find(|spec| spec.bits().contains(&pos))
+            .map(|spec| spec.field)
+    }
+
+    /// 表示用の名前を返す。インベントリのスロットは全て `"inventory"` となる。
+    const fn base_name(self) -> &'static str {
+        match self {
+            Self::AgeTimerHi => "age_timer_hi",
+            Self::PurseHi => "purse (high)",
+            Self::Age => "age",
+            Self::PurseLo => "purse (low)",
+            Self::XpLo => "xp (low)",
+            Self::Deposit => "deposit",
+            Self::XpHi => "xp (high)",
+            Self::Spells => "spells",
+            Self::Treasures => "treasures",
+            Self::Respawn => "respawn",
+            Self::Bookmarks1 => "bookmarks.hien/hohoemi",
+            Self::Minions => "minions",
+            Self::Bookmarks0 => "bookmarks (others)",
+            Self::Events => "events",
+            Self::Helm => "equipment.helm",
+            Self::Weapon => "equipment.weapon",
+            Self::Armor => "equipment.armor",
+            Self::Shoes => "equipment.shoes",
+            Self::Accessory0 => "equipment.accessory0",
+            Self::Accessory1 => "equipment.accessory1",
+            Self::Accessory2 => "equipment.accessory2",
+            Self::Accessory3 => "equipment.accessory3",
+            Self::InventorySlot(_) => "inventory",
+        }
+    }
+}
+
+/// `"events"`, `"equipment.helm"`, `"inventory[0]"` のような名前を表示する。
+impl std::fmt::Display for FieldId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.base_name())?;
+        if let Self::InventorySlot(i) = self {
+            write!(f, "[{i}]")?;
+        }
+        Ok(())
+    }
+}
+
+impl SerializedBytes {
+    /// チェックサムを除いた、全フィールド(インベントリ 8 スロットを含む)のビット数の合計。
+    pub const PAYLOAD_BITS: usize = {
+        let last = SERIALIZED_LAYOUT[SERIALIZED_LAYOUT.len() - 1];
+        last.bit_offset + last.bit_len
+    };
+
+    /// 全フィールドを格納するのに必要なバイト数(チェックサムの 2 バイトを含む)。
+    ///
+    /// これより長いバイト列の末尾は読まれない。
+    pub const FULL_LEN: usize = 2 + Self::PAYLOAD_BITS.div_ceil(6);
+
+    /// 長さ `len` のパスワード(バイト列)がフィールド `field` のビットを 1 つでも格納しているかどうかを返す。
+    ///
+    /// `false` なら、`field` の値は全て補われた 1 になる。
+    ///
+    /// # Panics
+    ///
+    /// `InventorySlot(n)` で `n >= 8` ならパニックする。
+    pub fn len_covers(len: usize, field: FieldId) -> bool {
+        field.spec().bit_offset < 6 * len.saturating_sub(2)
+    }
+
+    /// フィールド `field` のビットを含むバイトのインデックスの範囲を返す。
+    ///
+    /// パスワードの文字とデコード後のバイトは 1 対 1 に対応するので、これは `field` を表すパスワードの文字の範囲でもある
+    /// (ただし各文字のエンコード結果は直前の文字にも依存する)。
+    /// 先頭 2 バイトのチェックサムを含めたインデックスを返す。パスワードの長さが足りない場合を考慮しない。
+    ///
+    /// # Panics
+    ///
+    /// `InventorySlot(n)` で `n >= 8` ならパニックする。
+    pub fn chars_for_field(field: FieldId) -> Range<usize> {
+        let bits = field.spec().bits();
+        2 + bits.start / 6..2 + (bits.end - 1) / 6 + 1
+    }
+
+    /// フィールド `field` の値を、デシリアライズせずに直接読む。
+    ///
+    /// バイト列が足りない部分のビットは、デシリアライズ時と同様に 1 として読む。
+    /// チェックサムは検査しない。
+    ///
+    /// # Panics
+    ///
+    /// `InventorySlot(n)` で `n >= 8` ならパニックする。
+    pub fn get_field(&self, field: FieldId) -> u16 {
+        field.spec().bits().fold(0, |acc, i| {
+            let bit = self
+                .get(2 + i / 6)
+                .is_none_or(|b| (b.get() >> (5 - i % 6)) & 1 != 0);
+            (acc << 1) | u16::from(bit)
+        })
+    }
+
+    /// フィールド `field` の値を、デシリアライズせずに直接書き換え、`fix_checksum()` する。
+    ///
+    /// `field` 以外のビット(読まれないビットを含む)は変更しない。
+    /// バイト列が短くて `field` のビットを含まない場合、末尾に 0x3F を補ってから書き換える
+    /// (足りないビットは 1 として読まれるので、補ったバイトは他のフィールドの値を変えない)。
+    /// 値がフィールドのビット数に収まらなければエラーを返す(`self` は変更しない)。
+    ///
+    /// # Panics
+    ///
+    /// `InventorySlot(n)` で `n >= 8` ならパニックする。
+    pub fn set_field(&mut self, field: FieldId, value: u16) -> Result<(), FieldValueError> {
+        let spec = field.spec();
+        let max = (1_u16 << spec.bit_len) - 1;
+        if value > max {
+            return Err(FieldValueError { field, value, max });
+        }
+
+        let end = Self::chars_for_field(field).end;
+        if self.len() < end {
+            let mut buf = self.to_vec();
+            buf.resize(end, SerializedByte::MAX);
+            *self = Self::new(&buf).unwrap();
+        }
+
+        for (k, i) in spec.bits().enumerate() {
+            let bit = (value >> (spec.bit_len - 1 - k)) & 1;
+            let shift = 5 - i % 6;
+            let b = &mut self[2 + i / 6];
+            let new = (b.get() & !(1 << shift)) | ((bit as u8) << shift);
+            *b = new_unchecked!(SerializedByte, new);
+        }
+        self.fix_checksum();
+
+        Ok(())
+    }
+
+    /// `self` から `other` への差分を返す。
+    ///
+    /// 長さが異なってもよい。足りないバイトは(デシリアライズ時と同様に)全てのビットが 1、つまり 0x3F として比較する。
+    /// フィールドの値は `get_field()` で比べるので、インベントリの終端より後の読まれないスロットの差分も含む。
+    pub fn diff(&self, other: &Self) -> SerializedDiff {
+        let byte = |bytes: &Self, i: usize| bytes.get(i).copied().unwrap_or(SerializedByte::MAX);
+
+        let bytes = (0..self.len().max(other.len()))
+            .filter_map(|index| {
+                let (old, new) = (byte(self, index), byte(other, index));
+                (old != new).then_some(ByteDiff { index, old, new })
+            })
+            .collect();
+        let fields = SERIALIZED_LAYOUT
+            .iter()
+            .filter_map(|spec| {
+                let (old, new) = (self.get_field(spec.field), other.get_field(spec.field));
+                (old != new).then_some(FieldDiff {
+                    field: spec.field,
+                    old,
+                    new,
+                })
+            })
+            .collect();
+
+        SerializedDiff { bytes, fields }
+    }
+
+    /// `SerializedBytes` をゲーム状態にデシリアライズし、各フィールドがバイト列に格納されていたか、
+    /// 足りないビットとして 1 が補われたかを報告する。
+    ///
+    /// `to_savedata_checked()` と同じゲーム状態を返す。
+    /// 報告には実際に読まれたフィールドのみを含む(インベントリは終端のスロットまで)。
+    pub fn to_savedata_with_report(&self) -> Result<(Savedata, DecodeReport), SavedataDecodeError> {
+        let savedata = self.to_savedata_checked()?;
+
+        let n_avail = 6 * self.len().saturating_sub(2);
+        let n_slots = (savedata.inventory.len() + 1).min(8);
+        let fields = SERIALIZED_LAYOUT
+            .iter()
+            .filter(|spec| match spec.field {
+                FieldId::InventorySlot(n) => usize::from(n) < n_slots,
+                _ => true,
+            })
+            .map(|spec| {
+                let bits = spec.bits();
+                let n_present = bits.end.min(n_avail) - bits.start.min(n_avail);
+                let presence = match spec.bit_len - n_present {
+                    0 => FieldPresence::Present,
+                    n_padded if n_padded == spec.bit_len => FieldPresence::Padded,
+                    n_padded => FieldPresence::PartiallyPadded { n_padded },
+                };
+                (spec.field, presence)
+            })
+            .collect();
+
+        Ok((savedata, DecodeReport { fields }))
+    }
+}
+
+/// `SerializedBytes::to_savedata_with_report()` で得られる、各フィールドの出どころの報告。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodeReport {
+    /// 読まれた各フィールドとその出どころ。`SERIALIZED_LAYOUT` の順に並ぶ。
+    pub fields: Vec<(FieldId, FieldPresence)>,
+}
+
+impl DecodeReport {
+    /// フィールド `field` の出どころを返す。読まれなかったフィールドなら `None` を返す。
+    pub fn get(&self, field: FieldId) -> Option<FieldPresence> {
+        self.fields
+            .iter()
+            .find(|&&(f, _)| f == field)
+            .map(|&(_, presence)| presence)
+    }
+}
+
+/// デシリアライズしたフィールドの出どころ。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FieldPresence {
+    /// 全てのビットがバイト列に格納されていた。
+    Present,
+    /// 末尾の `n_padded` ビットが足りず、1 が補われた。
+    PartiallyPadded { n_padded: usize },
+    /// 全てのビットが足りず、1 が補われた。
+    Padded,
+}
+
+/// `SerializedBytes::diff()` の結果。
+///
+/// `Display` で 1 行 1 差分の形式に整形できる。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerializedDiff {
+    /// 値が異なるバイト。インデックスの昇順に並ぶ。
+    pub bytes: Vec<ByteDiff>,
+    /// 値が異なるフィールド。`SERIALIZED_LAYOUT` の順に並ぶ。
+    pub fields: Vec<FieldDiff>,
+}
+
+impl SerializedDiff {
+    /// 差分がないかどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+/// 1 バイト分の差分。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ByteDiff {
+    /// バイトのインデックス(0, 1 はチェックサム)。
+    pub index: usize,
+    /// 変更前の値(バイトがなければ 0x3F)。
+    pub old: SerializedByte,
+    /// 変更後の値(バイトがなければ 0x3F)。
+    pub new: SerializedByte,
+}
+
+/// 1 フィールド分の差分。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FieldDiff {
+    /// フィールドの識別子。
+    pub field: FieldId,
+    /// 変更前の値。
+    pub old: u16,
+    /// 変更後の値。
+    pub new: u16,
+}
+
+impl std::fmt::Display for SerializedDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no differences");
+        }
+
+        let mut first = true;
+        let mut newline = |f: &mut std::fmt::Formatter<'_>| {
+            if !std::mem::take(&mut first) {
+                f.write_str("\n")?;
+            }
+            Ok(())
+        };
+        for d in &self.bytes {
+            newline(f)?;
+            write!(f, "byte {:>2}: {:02X} -> {:02X}", d.index, d.old, d.new)?;
+            if d.index < 2 {
+                f.write_str(" (checksum)")?;
+            }
+        }
+        for d in &self.fields {
+            newline(f)?;
+            write!(f, "field {}: 0x{:02X} -> 0x{:02X}", d.field, d.old, d.new)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `SerializedBytes::set_field()` に渡された値がフィールドのビット数に収まらないことを表すエラー。
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[error("value {value} is too large for field {field} (max {max})")]
+pub struct FieldValueError {
+    /// 書き換えようとしたフィールド。
+    pub field: FieldId,
+    /// 渡された値。
+    pub value: u16,
+    /// フィールドの最大値。
+    pub max: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::Password;
+    use crate::savedata::*;
+    use crate::serialized::{BitReader, BitWriter};
+
+    #[test]
+    fn test_serialized_layout() {
+        // インベントリより前は 111bit。
+        assert_eq!(FieldId::InventorySlot(0).spec().bit_offset, 111);
+
+        for pair in SERIALIZED_LAYOUT.windows(2) {
+            assert_eq!(pair[0].bits().end, pair[1].bit_offset);
+            assert!(pair[0].field < pair[1].field);
+        }
+        let last = SERIALIZED_LAYOUT.last().unwrap();
+        assert_eq!(last.field, FieldId::InventorySlot(7));
+        assert_eq!(last.bits().end, SerializedBytes::PAYLOAD_BITS);
+        assert_eq!(SerializedBytes::PAYLOAD_BITS, 159);
+        assert!(last.bits().end <= BitWriter::CAPACITY);
+    }
+
+    #[test]
+    fn test_field_id_display() {
+        assert_eq!(FieldId::AgeTimerHi.to_string(), "age_timer_hi");
+        assert_eq!(FieldId::PurseHi.to_string(), "purse (high)");
+        assert_eq!(FieldId::Bookmarks1.to_string(), "bookmarks.hien/hohoemi");
+        assert_eq!(FieldId::Helm.to_string(), "equipment.helm");
+        assert_eq!(FieldId::InventorySlot(7).to_string(), "inventory[7]");
+
+        // 名前は全て異なる。
+        let names: std::collections::BTreeSet<_> = SERIALIZED_LAYOUT
+            .iter()
+            .map(|spec| spec.field.to_string())
+            .collect();
+        assert_eq!(names.len(), SERIALIZED_LAYOUT.len());
+    }
+
+    #[test]
+    fn test_field_id_at_bit() {
+        assert_eq!(FieldId::at_bit(0), Some(FieldId::AgeTimerHi));
+        assert_eq!(FieldId::at_bit(7), Some(FieldId::AgeTimerHi));
+        assert_eq!(FieldId::at_bit(8), Some(FieldId::PurseHi));
+        assert_eq!(FieldId::at_bit(110), Some(FieldId::Accessory3));
+        assert_eq!(FieldId::at_bit(111), Some(FieldId::InventorySlot(0)));
+        assert_eq!(
+            FieldId::at_bit(SerializedBytes::PAYLOAD_BITS - 1),
+            Some(FieldId::InventorySlot(7))
+        );
+        assert_eq!(FieldId::at_bit(SerializedBytes::PAYLOAD_BITS), None);
+        for spec in SERIALIZED_LAYOUT {
+            for pos in spec.bits() {
+                assert_eq!(FieldId::at_bit(pos), Some(spec.field));
+            }
+        }
+    }
+
+    #[test]
+    fn test_chars_for_field() {
+        assert_eq!(SerializedBytes::chars_for_field(FieldId::AgeTimerHi), 2..4);
+        assert_eq!(SerializedBytes::chars_for_field(FieldId::Deposit), 8..10);
+        assert_eq!(
+            SerializedBytes::chars_for_field(FieldId::Accessory3),
+            20..21
+        );
+        assert_eq!(
+            SerializedBytes::chars_for_field(FieldId::InventorySlot(0)),
+            20..22
+        );
+        assert_eq!(
+            SerializedBytes::chars_for_field(FieldId::InventorySlot(7)),
+            27..29
+        );
+    }
+
+    #[test]
+    fn test_full_len() {
+        // インベントリが満杯のときに最長になる。
+        let full = Savedata {
+            inventory: [ItemId::MAX; 8].into(),
+            ..Savedata::default()
+        };
+        assert_eq!(
+            SerializedBytes::from_savedata(&full).len(),
+            SerializedBytes::FULL_LEN
+        );
+        assert!(
+            SerializedBytes::from_savedata(&Savedata::default()).len() < SerializedBytes::FULL_LEN
+        );
+        assert_eq!(
+            SerializedBytes::chars_for_field(FieldId::InventorySlot(7)).end,
+            SerializedBytes::FULL_LEN
+        );
+
+        for spec in SERIALIZED_LAYOUT {
+            let chars = SerializedBytes::chars_for_field(spec.field);
+            for len in 1..=Password::MAX_LEN {
+                assert_eq!(
+                    SerializedBytes::len_covers(len, spec.field),
+                    len > chars.start,
+                    "{:?} {len}",
+                    spec.field
+                );
+            }
+        }
+        assert!(SerializedBytes::len_covers(3, FieldId::AgeTimerHi));
+        assert!(!SerializedBytes::len_covers(2, FieldId::AgeTimerHi));
+        assert!(SerializedBytes::len_covers(
+            SerializedBytes::FULL_LEN,
+            FieldId::InventorySlot(7)
+        ));
+        assert!(!SerializedBytes::len_covers(
+            SerializedBytes::FULL_LEN - 2,
+            FieldId::InventorySlot(7)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid field")]
+    fn test_chars_for_field_invalid() {
+        SerializedBytes::chars_for_field(FieldId::InventorySlot(8));
+    }
+
+    #[test]
+    fn test_bytes_get_set_field() {
+        let fu = SerializedBytes::from_password(&Password::parse("ふ").unwrap());
+        let savedata_fu = fu.to_savedata().unwrap();
+        for spec in SERIALIZED_LAYOUT {
+            assert_eq!(fu.get_field(spec.field), (1 << spec.bit_len) - 1);
+        }
+
+        // "ふ" のゲーム状態を表す最長のバイト列の経験値を書き換える。
+        let orig =
+            SerializedBytes::from_savedata_with_len(&savedata_fu, Password::MAX_LEN).unwrap();
+        let mut bytes = orig.clone();
+        bytes.set_field(FieldId::XpLo, 0x34).unwrap();
+        bytes.set_field(FieldId::XpHi, 0x12).unwrap();
+        assert!(bytes.checksum_is_ok());
+        assert_eq!(bytes.get_field(FieldId::XpLo), 0x34);
+        assert_eq!(bytes.get_field(FieldId::XpHi), 0x12);
+        assert_eq!(
+            bytes.to_savedata(),
+            Some(Savedata {
+                xp: 0x1234,
+                ..savedata_fu.clone()
+            })
+        );
+
+        // 変わるのはチェックサムと経験値を含むバイトのみ。
+        // (パスワードの文字は直前の文字に依存するので、最初に変わったバイト以降の文字は全て変わりうる。)
+        let xp_lo = SerializedBytes::chars_for_field(FieldId::XpLo);
+        let xp_hi = SerializedBytes::chars_for_field(FieldId::XpHi);
+        for i in 2..bytes.len() {
+            if !xp_lo.contains(&i) && !xp_hi.contains(&i) {
+                assert_eq!(bytes[i], orig[i], "{i}");
+            }
+        }
+        assert_ne!(bytes[..2], orig[..2]);
+
+        // 短いバイト列は 0x3F を補ってから書き換える。
+        let mut bytes = fu.clone();
+        bytes.set_field(FieldId::InventorySlot(7), 1).unwrap();
+        assert_eq!(
+            bytes.len(),
+            SerializedBytes::chars_for_field(FieldId::InventorySlot(7)).end
+        );
+        let mut expected = savedata_fu.clone();
+        expected.inventory[7] = ItemId::MIN;
+        assert_eq!(bytes.to_savedata(), Some(expected));
+
+        // 値がビット数に収まらない。
+        let mut bytes = orig.clone();
+        assert_eq!(
+            bytes.set_field(FieldId::Deposit, 0x40),
+            Err(FieldValueError {
+                field: FieldId::Deposit,
+                value: 0x40,
+                max: 0x3F
+            })
+        );
+        assert_eq!(bytes, orig);
+        assert_eq!(
+            bytes
+                .set_field(FieldId::Deposit, 0x40)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "value 64 is too large for field {} (max 63)",
+                FieldId::Deposit
+            )
+        );
+    }
+
+    #[test]
+    fn test_bytes_diff() {
+        let a = Savedata::default();
+        let b = Savedata {
+            purse: 0x1234,
+            ..Savedata::default()
+        };
+        let bytes_a = SerializedBytes::from_savedata(&a);
+        let bytes_b = SerializedBytes::from_savedata(&b);
+
+        let diff = bytes_a.diff(&bytes_b);
+        assert_eq!(
+            diff.fields,
+            [
+                FieldDiff {
+                    field: FieldId::PurseHi,
+                    old: 0,
+                    new: 0x12
+                },
+                FieldDiff {
+                    field: FieldId::PurseLo,
+                    old: 0,
+                    new: 0x34
+                },
+            ]
+        );
+        let purse_hi = SerializedBytes::chars_for_field(FieldId::PurseHi);
+        let purse_lo = SerializedBytes::chars_for_field(FieldId::PurseLo);
+        assert!(!diff.bytes.is_empty());
+        for d in &diff.bytes {
+            assert!(
+                d.index < 2 || purse_hi.contains(&d.index) || purse_lo.contains(&d.index),
+                "{d:?}"
+            );
+            assert_eq!((d.old, d.new), (bytes_a[d.index], bytes_b[d.index]));
+        }
+
+        assert!(bytes_a.diff(&bytes_a).is_empty());
+        assert_eq!(bytes_a.diff(&bytes_a).to_string(), "no differences");
+        let report = diff.to_string();
+        assert!(
+            report.contains("field purse (high): 0x00 -> 0x12"),
+            "{report}"
+        );
+        assert!(
+            report.contains("field purse (low): 0x00 -> 0x34"),
+            "{report}"
+        );
+        assert_eq!(report.lines().count(), diff.bytes.len() + diff.fields.len());
+
+        // 足りないバイトは 0x3F として比較する。
+        let fu = SerializedBytes::from_password(&Password::parse("ふ").unwrap());
+        let savedata_fu = fu.to_savedata().unwrap();
+        let mut longer = fu.clone();
+        longer.set_field(FieldId::InventorySlot(7), 0x3F).unwrap();
+        // データ部は全て 0x3F のまま。チェックサムはバイト数によって変わる。
+        let diff = fu.diff(&longer);
+        assert!(diff.fields.is_empty());
+        assert!(!diff.is_empty());
+        assert!(diff.bytes.iter().all(|d| d.index < 2));
+        let longest =
+            SerializedBytes::from_savedata_with_len(&savedata_fu, Password::MAX_LEN).unwrap();
+        let diff = fu.diff(&longest);
+        assert!(diff.fields.is_empty());
+        assert!(diff.bytes.iter().all(|d| d.index < 2 || d.index >= 28));
+    }
+
+    #[test]
+    fn test_bytes_to_savedata_with_report() {
+        // 13 文字のパスワードのデータ部は 11 バイト(66 ビット)。
+        let bytes =
+            SerializedBytes::from_password(&Password::parse("いいいいいいいいいいあえす").unwrap());
+        let (savedata, report) = bytes.to_savedata_with_report().unwrap();
+        assert_eq!(Some(savedata), bytes.to_savedata());
+
+        for field in [
+            FieldId::AgeTimerHi,
+            FieldId::PurseHi,
+            FieldId::Age,
+            FieldId::PurseLo,
+            FieldId::Spells,
+        ] {
+            assert_eq!(report.get(field), Some(FieldPresence::Present), "{field:?}");
+        }
+        assert_eq!(
+            report.get(FieldId::Treasures),
+            Some(FieldPresence::PartiallyPadded { n_padded: 1 })
+        );
+        assert_eq!(report.get(FieldId::Respawn), Some(FieldPresence::Padded));
+
+        // インベントリは 1 で補われて満杯になるので、8 スロット全てが読まれる。
+        for n in 0..8 {
+            assert_eq!(
+                report.get(FieldId::InventorySlot(n)),
+                Some(FieldPresence::Padded)
+            );
+        }
+        assert_eq!(report.fields.len(), SERIALIZED_LAYOUT.len());
+
+        // 読まれなかったスロットは含まない。
+        let bytes = SerializedBytes::from_savedata(&Savedata::default());
+        let (_, report) = bytes.to_savedata_with_report().unwrap();
+        assert!(report
+            .fields
+            .iter()
+            .all(|&(_, presence)| presence == FieldPresence::Present));
+        assert_eq!(
+            report.get(FieldId::InventorySlot(0)),
+            Some(FieldPresence::Present)
+        );
+        assert_eq!(report.get(FieldId::InventorySlot(1)), None);
+
+        let bytes = SerializedBytes::from_password(&Password::parse("あ").unwrap());
+        assert_eq!(
+            bytes.to_savedata_with_report().unwrap_err(),
+            bytes.to_savedata_checked().unwrap_err()
+        );
+    }
+
+    /// `src` のフィールド `field` の値を `dst` に写す。
+    fn copy_field(dst: &mut Savedata, src: &Savedata, field: FieldId) {
+        let bookmarks1 = |b: &mut Bookmarks, src: &Bookmarks| {
+            b.hien = src.hien;
+            b.hohoemi = src.hohoemi;
+        };
+        match field {
+            FieldId::AgeTimerHi => dst.age_timer_hi = src.age_timer_hi,
+            FieldId::PurseHi => dst.purse = (dst.purse & 0x00FF) | (src.purse & 0xFF00),
+            FieldId::Age => dst.age = src.age,
+            FieldId::PurseLo => dst.purse = (dst.purse & 0xFF00) | (src.purse & 0x00FF),
+            FieldId::XpLo => dst.xp = (dst.xp & 0xFF00) | (src.xp & 0x00FF),
+            FieldId::Deposit => dst.deposit = src.deposit,
+            FieldId::XpHi => dst.xp = (dst.xp & 0x00FF) | (src.xp & 0xFF00),
+            FieldId::Spells => dst.spells = src.spells,
+            FieldId::Treasures => dst.treasures = src.treasures,
+            FieldId::Respawn => dst.respawn = src.respawn,
+            FieldId::Bookmarks1 => bookmarks1(&mut dst.bookmarks, &src.bookmarks),
+            FieldId::Minions => dst.minions = src.minions,
+            FieldId::Bookmarks0 => {
+                let mut b = src.bookmarks;
+                bookmarks1(&mut b, &dst.bookmarks);
+                dst.bookmarks = b;
+            }
+            FieldId::Events => dst.events = src.events,
+            FieldId::Helm => dst.equipment.helm = src.equipment.helm,
+            FieldId::Weapon => dst.equipment.weapon = src.equipment.weapon,
+            FieldId::Armor => dst.equipment.armor = src.equipment.armor,
+            FieldId::Shoes => dst.equipment.shoes = src.equipment.shoes,
+            FieldId::Accessory0 => dst.equipment.accessory0 = src.equipment.accessory0,
+            FieldId::Accessory1 => dst.equipment.accessory1 = src.equipment.accessory1,
+            FieldId::Accessory2 => dst.equipment.accessory2 = src.equipment.accessory2,
+            FieldId::Accessory3 => dst.equipment.accessory3 = src.equipment.accessory3,
+            FieldId::InventorySlot(n) => {
+                dst.inventory[usize::from(n)] = src.inventory[usize::from(n)];
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialized_layout_matches_serializer() {
+        // 表の範囲のビットを反転すると、デシリアライズ結果は対応するフィールドのみが変わる。
+        let savedata = Savedata {
+            inventory: (1..=8).map(|id| ItemId::new(id).unwrap()).collect(),
+            ..Savedata::default()
+        };
+        let bytes = SerializedBytes::from_savedata(&savedata);
+        let n_bits = SERIALIZED_LAYOUT.last().unwrap().bits().end;
+
+        for spec in SERIALIZED_LAYOUT {
+            let mut reader = BitReader::new(&bytes);
+            let mut writer = BitWriter::new();
+            for i in 0..n_bits {
+                writer.push_bit(reader.read_bit() ^ spec.bits().contains(&i));
+            }
+
+            let mut flipped = writer.to_bytes().to_savedata().unwrap();
+            assert_ne!(flipped, savedata, "{:?}", spec.field);
+            copy_field(&mut flipped, &savedata, spec.field);
+            assert_eq!(flipped, savedata, "{:?}", spec.field);
+        }
+    }
+}
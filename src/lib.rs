@@ -1,14 +1,93 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
+// `ffi` モジュールは本質的に unsafe なので、`ffi` feature 有効時はそこだけを除外する。
+#![cfg_attr(
+    all(feature = "forbid-unsafe", not(feature = "ffi")),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(all(feature = "forbid-unsafe", feature = "ffi"), deny(unsafe_code))]
 
+mod attribution;
 mod bounded;
+mod canonical;
+mod charset;
 mod checksum;
+mod entry;
+mod error;
+mod fields;
+#[cfg(feature = "io")]
+mod io;
+mod lang;
+mod layout;
+mod lua;
 mod macros;
+mod movie;
 mod password;
+mod prefix;
+#[cfg(feature = "rand")]
+mod random;
+mod repair;
+mod romaji;
 mod savedata;
+mod search;
 mod serialized;
+mod share;
+#[cfg(feature = "proptest")]
+mod strategy;
+mod trace;
+mod validate;
+#[cfg(feature = "clap")]
+mod value_parser;
 
+pub mod corpus;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+pub mod history;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(any(test, feature = "reference"))]
+pub mod reference;
+pub mod report;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use self::attribution::*;
 pub use self::bounded::*;
+pub use self::canonical::*;
+pub use self::charset::*;
 pub use self::checksum::*;
+pub use self::entry::*;
+pub use self::error::*;
+pub use self::fields::*;
+#[cfg(feature = "io")]
+pub use self::io::*;
+pub use self::lang::*;
+pub use self::layout::*;
+pub use self::lua::*;
+pub use self::movie::*;
 pub use self::password::*;
+pub use self::prefix::*;
+#[cfg(feature = "rand")]
+pub use self::random::*;
+pub use self::repair::*;
+pub use self::romaji::*;
 pub use self::savedata::*;
+pub use self::search::*;
 pub use self::serialized::*;
+pub use self::share::*;
+#[cfg(feature = "proptest")]
+pub use self::strategy::*;
+pub use self::trace::*;
+pub use self::validate::*;
+#[cfg(feature = "clap")]
+pub use self::value_parser::*;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "arbitrary")]
+    pub use arbitrary;
+    #[cfg(feature = "schemars")]
+    pub use schemars;
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
@@ -0,0 +1,195 @@
+//! パスワードをエミュレータの RAM に書き込む Lua スクリプトの生成。
+//!
+//! パスワード入力バッファのアドレスはゲームで確認されていないので、既定値は設けていない。
+//! 呼び出し側が必ずアドレスを指定すること。
+
+use std::fmt::Write as _;
+
+use thiserror::Error;
+
+use crate::password::Password;
+
+/// パスワードを RAM に書き込む Lua スクリプトの生成オプション。
+///
+/// アドレスに既定値はないので、呼び出し側が指定しなければならない。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LuaScriptOptions {
+    /// パスワード入力バッファの先頭アドレス。各文字の内部値がここから連続して書き込まれる。
+    pub base_addr: u16,
+    /// 文字数を格納するアドレス。`None` なら文字数は書き込まない。
+    pub len_addr: Option<u16>,
+}
+
+impl LuaScriptOptions {
+    /// 先頭アドレスを指定して `LuaScriptOptions` を作る。文字数は書き込まない。
+    pub const fn new(base_addr: u16) -> Self {
+        Self {
+            base_addr,
+            len_addr: None,
+        }
+    }
+}
+
+/// 生成するスクリプトが用いる Lua API。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LuaApi {
+    Fceux,
+    Mesen,
+}
+
+impl LuaApi {
+    fn write_byte(self, addr: u16, value: u8) -> String {
+        match self {
+            Self::Fceux => format!("memory.writebyte(0x{addr:04X}, 0x{value:02X})"),
+            Self::Mesen => {
+                format!("emu.write(0x{addr:04X}, 0x{value:02X}, emu.memType.cpuDebug)")
+            }
+        }
+    }
+
+    fn print(self, msg: &str) -> String {
+        match self {
+            Self::Fceux => format!("print(\"{msg}\")"),
+            Self::Mesen => format!("emu.log(\"{msg}\")"),
+        }
+    }
+}
+
+/// パスワードの書き込み先がアドレス空間 (0x0000..=0xFFFF) に収まらないことを表すエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("password of {len} chars at ${base_addr:04X} exceeds the address space")]
+pub struct LuaAddressError {
+    /// 先頭アドレス。
+    pub base_addr: u16,
+    /// パスワードの文字数。
+    pub len: usize,
+}
+
+impl Password {
+    /// パスワードを `base_addr` 以降に書き込む FCEUX 用 Lua スクリプトを返す。
+    ///
+    /// 書き込み先アドレスが 0xFFFF を超える場合、エラーを返す。
+    pub fn to_fceux_lua(&self, base_addr: u16) -> Result<String, LuaAddressError> {
+        self.to_fceux_lua_with(&LuaScriptOptions::new(base_addr))
+    }
+
+    /// オプションを指定して FCEUX 用 Lua スクリプトを返す。
+    ///
+    /// 書き込み先アドレスが 0xFFFF を超える場合、エラーを返す。
+    pub fn to_fceux_lua_with(&self, options: &LuaScriptOptions) -> Result<String, LuaAddressError> {
+        self.to_lua(LuaApi::Fceux, options)
+    }
+
+    /// パスワードを `base_addr` 以降に書き込む Mesen 用 Lua スクリプトを返す。
+    ///
+    /// 書き込み先アドレスが 0xFFFF を超える場合、エラーを返す。
+    pub fn to_mesen_lua(&self, base_addr: u16) -> Result<String, LuaAddressError> {
+        self.to_mesen_lua_with(&LuaScriptOptions::new(base_addr))
+    }
+
+    /// オプションを指定して Mesen 用 Lua スクリプトを返す。
+    ///
+    /// 書き込み先アドレスが 0xFFFF を超える場合、エラーを返す。
+    pub fn to_mesen_lua_with(&self, options: &LuaScriptOptions) -> Result<String, LuaAddressError> {
+        self.to_lua(LuaApi::Mesen, options)
+    }
+
+    fn to_lua(&self, api: LuaApi, options: &LuaScriptOptions) -> Result<String, LuaAddressError> {
+        let base = options.base_addr;
+        let last =
+            u16::try_from(usize::from(base) + self.len() - 1).map_err(|_| LuaAddressError {
+                base_addr: base,
+                len: self.len(),
+            })?;
+
+        let mut buf = String::new();
+
+        writeln!(buf, "-- password: {}", self.display()).unwrap();
+        for (addr, pc) in (base..=last).zip(self.iter()) {
+            writeln!(buf, "{}", api.write_byte(addr, pc.to_inner())).unwrap();
+        }
+        if let Some(len_addr) = options.len_addr {
+            writeln!(buf, "{}", api.write_byte(len_addr, self.len() as u8)).unwrap();
+        }
+        let msg = format!(
+            "wrote password {} ({} chars) to ${base:04X}-${last:04X}",
+            self.display(),
+            self.len()
+        );
+        writeln!(buf, "{}", api.print(&msg)).unwrap();
+
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fceux_lua() {
+        let password = Password::parse("おにのばか").unwrap();
+
+        assert_eq!(
+            password.to_fceux_lua(0x0300).unwrap(),
+            "\
+-- password: おにのばか
+memory.writebyte(0x0300, 0x04)
+memory.writebyte(0x0301, 0x15)
+memory.writebyte(0x0302, 0x18)
+memory.writebyte(0x0303, 0x36)
+memory.writebyte(0x0304, 0x05)
+print(\"wrote password おにのばか (5 chars) to $0300-$0304\")
+"
+        );
+    }
+
+    #[test]
+    fn test_mesen_lua() {
+        let password = Password::parse("ふ").unwrap();
+        let options = LuaScriptOptions {
+            base_addr: 0x0300,
+            len_addr: Some(0x0340),
+        };
+
+        assert_eq!(
+            password.to_mesen_lua_with(&options).unwrap(),
+            "\
+-- password: ふ
+emu.write(0x0300, 0x1B, emu.memType.cpuDebug)
+emu.write(0x0340, 0x01, emu.memType.cpuDebug)
+emu.log(\"wrote password ふ (1 chars) to $0300-$0300\")
+"
+        );
+    }
+
+    #[test]
+    fn test_lua_buffer_end() {
+        let password = Password::parse("おにのばか").unwrap();
+
+        let script = password.to_fceux_lua(0xFFFB).unwrap();
+        assert!(script.contains("memory.writebyte(0xFFFF, 0x05)"));
+        assert!(script.contains("$FFFB-$FFFF"));
+    }
+
+    #[test]
+    fn test_lua_buffer_overflow() {
+        let password = Password::parse("おにのばか").unwrap();
+        let err = LuaAddressError {
+            base_addr: 0xFFFC,
+            len: 5,
+        };
+        assert_eq!(password.to_fceux_lua(0xFFFC), Err(err));
+        assert_eq!(password.to_mesen_lua(0xFFFC), Err(err));
+        let options = LuaScriptOptions {
+            base_addr: 0xFFFF,
+            len_addr: Some(0x0340),
+        };
+        assert!(password.to_fceux_lua_with(&options).is_err());
+        assert!(password.to_mesen_lua_with(&options).is_err());
+        assert_eq!(
+            err.to_string(),
+            "password of 5 chars at $FFFC exceeds the address space"
+        );
+    }
+}
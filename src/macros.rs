@@ -3,6 +3,9 @@
 ///
 /// デバッグビルドでは `assert!($cond)` と等価。
 /// リリースビルドでは `if !$cond { std::hint::unreachable_unchecked() }` と等価。
+///
+/// `unsafe fn` の中でのみ使うので、forbid-unsafe feature 有効時は定義しない。
+#[cfg(not(feature = "forbid-unsafe"))]
 macro_rules! assert_unchecked {
     ($cond:expr) => {{
         #[cfg(debug_assertions)]
@@ -19,25 +22,42 @@ macro_rules! assert_unchecked {
         }
     }};
 }
+#[cfg(not(feature = "forbid-unsafe"))]
 pub(crate) use assert_unchecked;
 
-/// このマクロの呼び出し位置に決して到達しないことを表明し、最適化を促進する。
-/// 表明が満たされない場合の挙動は未定義。
+/// `unsafe` な式 `$unchecked` を評価する。forbid-unsafe feature 有効時は代わりに安全な式 `$checked` を評価する。
 ///
-/// デバッグビルドでは `unreachable!()` と等価。
-/// リリースビルドでは `std::hint::unreachable_unchecked()` と等価。
-macro_rules! unreachable_unchecked {
-    () => {{
-        #[cfg(debug_assertions)]
-        {
-            const unsafe fn __needs_unsafe() {}
-            __needs_unsafe();
-            ::std::unreachable!();
-        }
-        #[cfg(not(debug_assertions))]
-        {
-            ::std::hint::unreachable_unchecked();
-        }
+/// `$unchecked` の前提条件は呼び出し側が保証しなければならない。
+macro_rules! unsafe_or {
+    ($unchecked:expr, $checked:expr $(,)?) => {{
+        #[cfg(not(feature = "forbid-unsafe"))]
+        #[allow(unused_unsafe)]
+        let res = unsafe { $unchecked };
+        #[cfg(feature = "forbid-unsafe")]
+        let res = $checked;
+        res
     }};
 }
-pub(crate) use unreachable_unchecked;
+pub(crate) use unsafe_or;
+
+/// 値域内にあることが分かっている内部値から `<$ty>::new_unchecked` で値を作る。
+///
+/// forbid-unsafe feature 有効時は `<$ty>::new` を用い、値域外なら panic する。
+macro_rules! new_unchecked {
+    ($ty:ty, $inner:expr) => {
+        $crate::macros::unsafe_or!(
+            <$ty>::new_unchecked($inner),
+            <$ty>::new($inner).expect("value out of range"),
+        )
+    };
+}
+pub(crate) use new_unchecked;
+
+/// `tracing` feature 有効時のみ `tracing::$level!(...)` に展開される。無効時は何も展開しない。
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($($arg)+);
+    };
+}
+pub(crate) use trace_event;
@@ -0,0 +1,422 @@
+use std::fmt::Write as _;
+
+use thiserror::Error;
+
+use crate::password::{Password, PasswordChar};
+
+/// パスワード入力画面のマス。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EntryCell {
+    /// 文字。
+    Char(PasswordChar),
+    /// 入力終了。
+    End,
+    /// 空白(選択しても何も起こらない)。
+    Blank,
+}
+
+/// パスワード入力画面の文字グリッド。
+///
+/// カーソルは上下左右に動き、端に達すると反対側に回り込む。
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntryGrid {
+    width: usize,
+    cells: Vec<EntryCell>,
+}
+
+impl EntryGrid {
+    /// 幅と行優先のマス列からグリッドを作る。
+    ///
+    /// マス数が幅の倍数でない場合、全文字がちょうど 1 回ずつ現れない場合、
+    /// 入力終了マスがちょうど 1 個でない場合は `None` を返す。
+    pub fn new(width: usize, cells: &[EntryCell]) -> Option<Self> {
+        if width == 0 || cells.is_empty() || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        let mut counts = [0_usize; 0x40];
+        let mut n_end = 0;
+        for cell in cells {
+            match cell {
+                EntryCell::Char(pc) => counts[usize::from(pc.to_inner())] += 1,
+                EntryCell::End => n_end += 1,
+                EntryCell::Blank => {}
+            }
+        }
+        if n_end != 1 || counts.iter().any(|&n| n != 1) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            cells: cells.to_vec(),
+        })
+    }
+
+    /// 幅を返す。
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// 高さを返す。
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    /// 位置 `(x, y)` のマスを返す。範囲外なら `None` を返す。
+    pub fn get(&self, x: usize, y: usize) -> Option<EntryCell> {
+        (x < self.width && y < self.height()).then(|| self.cells[self.width * y + x])
+    }
+
+    pub(crate) fn position(&self, cell: EntryCell) -> (usize, usize) {
+        let i = self.cells.iter().position(|&c| c == cell).unwrap();
+        (i % self.width, i / self.width)
+    }
+}
+
+/// 入力ムービーの形式。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MovieFormat {
+    /// FCEUX の FM2 (1P のみ、ヘッダなし)。
+    Fm2,
+    /// BizHawk の BK2 内 Input Log.txt (1P のみ、ヘッダなし)。
+    Bk2,
+}
+
+/// 入力ムービーの生成オプション。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MovieOptions<'a> {
+    /// 入力画面の文字グリッド。
+    pub grid: &'a EntryGrid,
+    /// ボタン 1 回の入力に要するフレーム数。1 フレーム押した後、残りのフレームは離す。2 以上。
+    pub frames_per_press: usize,
+    /// カーソルの初期位置 `(x, y)`。
+    pub start: (usize, usize),
+}
+
+/// 入力ムービーの生成オプションが不正であることを表すエラー。
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum MovieOptionsError {
+    /// カーソルの初期位置がグリッドの範囲外。
+    #[error("start position ({x}, {y}) is out of the {width}x{height} grid")]
+    StartOutOfGrid {
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    },
+
+    /// ボタン 1 回の入力に要するフレーム数が 2 未満。
+    #[error("frames_per_press must be >= 2 (got {0})")]
+    FramesPerPressTooSmall(usize),
+}
+
+impl<'a> MovieOptions<'a> {
+    /// グリッドを指定して `MovieOptions` を作る。2 フレームごとに入力し、カーソルは左上から始まる。
+    pub const fn new(grid: &'a EntryGrid) -> Self {
+        Self {
+            grid,
+            frames_per_press: 2,
+            start: (0, 0),
+        }
+    }
+
+    fn validate(&self) -> Result<(), MovieOptionsError> {
+        check_start(self.grid, self.start)?;
+        // 同じボタンを連続して押すには、間に離すフレームが必要。
+        if self.frames_per_press < 2 {
+            return Err(MovieOptionsError::FramesPerPressTooSmall(
+                self.frames_per_press,
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn check_start(grid: &EntryGrid, (x, y): (usize, usize)) -> Result<(), MovieOptionsError> {
+    if grid.get(x, y).is_none() {
+        return Err(MovieOptionsError::StartOutOfGrid {
+            x,
+            y,
+            width: grid.width(),
+            height: grid.height(),
+        });
+    }
+    Ok(())
+}
+
+/// 入力ボタン。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Button {
+    Up,
+    Down,
+    Left,
+    Right,
+    A,
+}
+
+impl Button {
+    fn to_fm2(self) -> &'static str {
+        // RLDUTSBA
+        match self {
+            Self::Up => "...U....",
+            Self::Down => "..D.....",
+            Self::Left => ".L......",
+            Self::Right => "R.......",
+            Self::A => ".......A",
+        }
+    }
+
+    fn to_bk2(self) -> &'static str {
+        // UDLRSsBA
+        match self {
+            Self::Up => "U.......",
+            Self::Down => ".D......",
+            Self::Left => "..L.....",
+            Self::Right => "...R....",
+            Self::A => ".......A",
+        }
+    }
+}
+
+impl MovieFormat {
+    fn frame(self, button: Option<Button>) -> String {
+        match self {
+            Self::Fm2 => format!("|0|{}|||", button.map_or("........", Button::to_fm2)),
+            Self::Bk2 => format!("|..|{}|", button.map_or("........", Button::to_bk2)),
+        }
+    }
+}
+
+impl Password {
+    /// 入力画面でこのパスワードを入力するボタン列を返す(最後の入力終了を含む)。
+    ///
+    /// 各文字について、回り込みを考慮した最短の横移動、縦移動、A ボタンの順に押す。
+    ///
+    /// `start` がグリッドの範囲外の場合、エラーを返す。
+    pub fn input_buttons(
+        &self,
+        grid: &EntryGrid,
+        start: (usize, usize),
+    ) -> Result<Vec<Button>, MovieOptionsError> {
+        check_start(grid, start)?;
+
+        let targets = self
+            .iter()
+            .map(|&pc| grid.position(EntryCell::Char(pc)))
+            .chain(std::iter::once(grid.position(EntryCell::End)));
+
+        let mut res = vec![];
+        let (mut x, mut y) = start;
+        for (tx, ty) in targets {
+            push_moves(&mut res, x, tx, grid.width(), Button::Left, Button::Right);
+            push_moves(&mut res, y, ty, grid.height(), Button::Up, Button::Down);
+            res.push(Button::A);
+            (x, y) = (tx, ty);
+        }
+
+        Ok(res)
+    }
+
+    /// 入力画面でこのパスワードを入力するのに要するフレーム数を返す。
+    ///
+    /// `opts` が不正な場合、エラーを返す。
+    pub fn input_cost(&self, opts: &MovieOptions<'_>) -> Result<usize, MovieOptionsError> {
+        opts.validate()?;
+        Ok(self.input_buttons(opts.grid, opts.start)?.len() * opts.frames_per_press)
+    }
+
+    /// 入力画面でこのパスワードを入力するムービーの入力部分を返す。1 行が 1 フレームに対応する。
+    ///
+    /// `opts.start` がグリッドの範囲外の場合、および `opts.frames_per_press` が 2 未満の場合、エラーを返す。
+    pub fn to_input_movie(
+        &self,
+        format: MovieFormat,
+        opts: &MovieOptions<'_>,
+    ) -> Result<String, MovieOptionsError> {
+        opts.validate()?;
+
+        let mut buf = String::new();
+
+        for button in self.input_buttons(opts.grid, opts.start)? {
+            writeln!(buf, "{}", format.frame(Some(button))).unwrap();
+            for _ in 1..opts.frames_per_press {
+                writeln!(buf, "{}", format.frame(None)).unwrap();
+            }
+        }
+
+        Ok(buf)
+    }
+}
+
+/// 長さ `len` の循環軸上で `from` から `to` へ移動するボタンを追加する。同距離なら回り込まない方向を選ぶ。
+fn push_moves(
+    buttons: &mut Vec<Button>,
+    from: usize,
+    to: usize,
+    len: usize,
+    dec: Button,
+    inc: Button,
+) {
+    let (is_inc, n) = shortest_move(from, to, len, true);
+    let button = if is_inc { inc } else { dec };
+    buttons.extend(std::iter::repeat_n(button, n));
+}
+
+/// 長さ `len` の軸上で `from` から `to` へ移動する最短の方向(増加方向なら `true`)と回数を返す。
+///
+/// `wrap` なら端で反対側に回り込める。同距離なら回り込まない方向を選ぶ。
+pub(crate) fn shortest_move(from: usize, to: usize, len: usize, wrap: bool) -> (bool, usize) {
+    if !wrap {
+        return (to > from, from.abs_diff(to));
+    }
+
+    let n_inc = (to + len - from) % len;
+    let n_dec = (from + len - to) % len;
+
+    if n_inc < n_dec || (n_inc == n_dec && to > from) {
+        (true, n_inc)
+    } else {
+        (false, n_dec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 内部値順に 8 文字ずつ並べ、最後の行に入力終了を置いたグリッド。
+    fn grid() -> EntryGrid {
+        let mut cells: Vec<_> = PasswordChar::all().map(EntryCell::Char).to_vec();
+        cells.push(EntryCell::End);
+        cells.extend([EntryCell::Blank; 7]);
+
+        EntryGrid::new(8, &cells).unwrap()
+    }
+
+    #[test]
+    fn test_entry_grid() {
+        let grid = grid();
+        assert_eq!(grid.width(), 8);
+        assert_eq!(grid.height(), 9);
+        assert_eq!(grid.get(1, 0), Some(EntryCell::Char(PasswordChar::I)));
+        assert_eq!(grid.get(0, 8), Some(EntryCell::End));
+        assert_eq!(grid.get(8, 0), None);
+
+        // 文字の欠落、入力終了の欠落、幅の不整合。
+        assert_eq!(EntryGrid::new(8, &[EntryCell::End; 8]), None);
+        let cells: Vec<_> = PasswordChar::all().map(EntryCell::Char).to_vec();
+        assert_eq!(EntryGrid::new(8, &cells), None);
+        let mut cells = cells;
+        cells.push(EntryCell::End);
+        assert_eq!(EntryGrid::new(8, &cells), None);
+    }
+
+    #[test]
+    fn test_input_buttons() {
+        use Button::*;
+
+        let grid = grid();
+
+        // "お" = (4, 0), "ぽ" = (7, 7)。入力終了 = (0, 8)。
+        let password = Password::parse("おぽ").unwrap();
+        assert_eq!(
+            password.input_buttons(&grid, (0, 0)).unwrap(),
+            [
+                Right, Right, Right, Right, A, // お
+                Right, Right, Right, Up, Up, A, // ぽ (上に回り込む)
+                Right, Down, A, // 入力終了 (右に回り込む)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_cost() {
+        let grid = grid();
+        let password = Password::parse("おにのばか").unwrap();
+
+        for frames_per_press in [2, 3, 5] {
+            let opts = MovieOptions {
+                frames_per_press,
+                start: (3, 2),
+                ..MovieOptions::new(&grid)
+            };
+            for format in [MovieFormat::Fm2, MovieFormat::Bk2] {
+                let movie = password.to_input_movie(format, &opts).unwrap();
+                assert_eq!(movie.lines().count(), password.input_cost(&opts).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_input_movie() {
+        let grid = grid();
+        let opts = MovieOptions::new(&grid);
+
+        // "い" = (1, 0)。
+        let password = Password::parse("い").unwrap();
+
+        assert_eq!(
+            password.to_input_movie(MovieFormat::Fm2, &opts).unwrap(),
+            "\
+|0|R.......|||
+|0|........|||
+|0|.......A|||
+|0|........|||
+|0|.L......|||
+|0|........|||
+|0|...U....|||
+|0|........|||
+|0|.......A|||
+|0|........|||
+"
+        );
+        assert_eq!(
+            password.to_input_movie(MovieFormat::Bk2, &opts).unwrap(),
+            "\
+|..|...R....|
+|..|........|
+|..|.......A|
+|..|........|
+|..|..L.....|
+|..|........|
+|..|U.......|
+|..|........|
+|..|.......A|
+|..|........|
+"
+        );
+    }
+
+    #[test]
+    fn test_movie_options_error() {
+        let grid = grid();
+        let password = Password::parse("い").unwrap();
+
+        let err = MovieOptionsError::StartOutOfGrid {
+            x: 8,
+            y: 0,
+            width: 8,
+            height: 9,
+        };
+        assert_eq!(password.input_buttons(&grid, (8, 0)), Err(err));
+        let opts = MovieOptions {
+            start: (8, 0),
+            ..MovieOptions::new(&grid)
+        };
+        assert_eq!(password.input_cost(&opts), Err(err));
+        assert_eq!(password.to_input_movie(MovieFormat::Fm2, &opts), Err(err));
+        assert_eq!(
+            err.to_string(),
+            "start position (8, 0) is out of the 8x9 grid"
+        );
+
+        let opts = MovieOptions {
+            frames_per_press: 1,
+            ..MovieOptions::new(&grid)
+        };
+        let err = MovieOptionsError::FramesPerPressTooSmall(1);
+        assert_eq!(password.input_cost(&opts), Err(err));
+        assert_eq!(password.to_input_movie(MovieFormat::Bk2, &opts), Err(err));
+    }
+}
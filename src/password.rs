@@ -1,14 +1,26 @@
 use std::fmt::Write as _;
 
-use arrayvec::ArrayVec;
+use arrayvec::{ArrayString, ArrayVec};
 use thiserror::Error;
 
+#[cfg(not(feature = "forbid-unsafe"))]
 use crate::macros::assert_unchecked;
+use crate::macros::unsafe_or;
 use crate::serialized::SerializedBytes;
 
 /// `Password` の内部バッファ。
 pub type PasswordInner = ArrayVec<PasswordChar, { Password::MAX_LEN }>;
 
+/// `Password::to_array_string()` の戻り値の型。ひらがなは UTF-8 で 3 バイトなので、`Password::MAX_LEN * 3` バイト。
+pub type PasswordArrayString = ArrayString<{ Password::MAX_LEN * 3 }>;
+
+/// `Password::to_array_string_pretty()` の戻り値の型。区切りの空白の分だけ `PasswordArrayString` より長い。
+pub type PasswordArrayStringPretty =
+    ArrayString<{ Password::MAX_LEN * 3 + PRETTY_CHUNK_LENS.len() - 1 }>;
+
+/// `display_pretty()` で空白区切りする文字数。
+const PRETTY_CHUNK_LENS: &[usize] = &[5, 7, 5, 7, 7, 7];
+
 /// パスワード。
 #[repr(transparent)]
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -33,10 +45,24 @@ impl Password {
         &[Su, Be, Te, No, Te, Ki, Ga, Mi, Ta, I, Na]
     };
 
+    /// `parse_lenient()` が読み飛ばす区切り記号。
+    pub const LENIENT_SEPARATORS: &[char] = &['・', '･', '-', '_', ',', '.', '、', '。', '/'];
+
     /// `PasswordChar` のスライスから `Password` を作る。文字数が範囲外なら `None` を返す。
     pub fn new(chars: &[PasswordChar]) -> Option<Self> {
-        matches!(chars.len(), Self::MIN_LEN..=Self::MAX_LEN)
-            .then(|| unsafe { Self::new_unchecked(chars) })
+        Self::new_checked(chars).ok()
+    }
+
+    /// `PasswordChar` のスライスから `Password` を作る。文字数が範囲外ならエラーを返す。
+    pub fn new_checked(chars: &[PasswordChar]) -> Result<Self, PasswordLengthError> {
+        if !matches!(chars.len(), Self::MIN_LEN..=Self::MAX_LEN) {
+            return Err(PasswordLengthError::new(chars.len()));
+        }
+
+        Ok(unsafe_or!(
+            Self::new_unchecked(chars),
+            Self(chars.iter().copied().collect())
+        ))
     }
 
     /// `PasswordChar` のスライスから `Password` を作る。
@@ -44,30 +70,260 @@ impl Password {
     /// # Safety
     ///
     /// 文字数は `Password::MIN_LEN..=Password::MAX_LEN` の範囲内になければならない。
+    #[cfg(not(feature = "forbid-unsafe"))]
     pub unsafe fn new_unchecked(chars: &[PasswordChar]) -> Self {
         assert_unchecked!(matches!(chars.len(), Self::MIN_LEN..=Self::MAX_LEN));
         Self(chars.iter().copied().collect())
     }
 
+    /// `PasswordChar` のスライスから `Password` を作る。
+    ///
+    /// `forbid-unsafe` feature 有効時は安全な関数となり、文字数が範囲外ならパニックする。
+    #[cfg(feature = "forbid-unsafe")]
+    pub fn new_unchecked(chars: &[PasswordChar]) -> Self {
+        Self::new_checked(chars).expect("password length out of range")
+    }
+
+    /// 内部値のスライスから `Password` を作る。文字数や内部値が範囲外ならエラーを返す。
+    ///
+    /// エミュレータの RAM から読み出したパスワードバッファをそのまま渡すためのもの。
+    pub fn from_inner_bytes(bytes: &[u8]) -> Result<Self, PasswordFromBytesError> {
+        if !matches!(bytes.len(), Self::MIN_LEN..=Self::MAX_LEN) {
+            return Err(PasswordFromBytesError::InvalidLength { len: bytes.len() });
+        }
+
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                PasswordChar::from_inner(value)
+                    .ok_or(PasswordFromBytesError::OutOfRange { index, value })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// 各文字の内部値を返す。`from_inner_bytes()` の逆。
+    pub fn to_inner_bytes(&self) -> ArrayVec<u8, { Self::MAX_LEN }> {
+        self.iter().map(|pc| pc.to_inner()).collect()
+    }
+
+    /// `PasswordChar` の列から `Password` を作る。文字数が範囲外ならエラーを返す。
+    ///
+    /// 最大文字数を超えた時点で消費をやめるので、無限イテレータを渡してもよい。
+    pub fn from_iter_checked<I>(iter: I) -> Result<Self, PasswordParseError>
+    where
+        I: IntoIterator<Item = PasswordChar>,
+    {
+        let mut builder = PasswordBuilder::new();
+        builder.extend(iter);
+        builder.build()
+    }
+
     /// ひらがな文字列をパースして `Password` を作る。
     pub fn parse(s: &str) -> Result<Self, PasswordParseError> {
-        let mut inner = PasswordInner::new();
+        Self::parse_chars(s.chars().enumerate())
+    }
 
+    /// `parse()` と同じくパースし、エラーには元の文字列における位置(文字位置とバイト位置)を含める。
+    ///
+    /// エディタ上での強調表示など、UTF-8 のバイト位置が必要な場合に使う。
+    pub fn parse_spanned(s: &str) -> Result<Self, PasswordSpannedParseError> {
+        Self::parse(s).map_err(|e| match e {
+            PasswordParseError::InvalidChar { pos, ch } => {
+                let (byte_offset, _) = s.char_indices().nth(pos).unwrap();
+                PasswordSpannedParseError::InvalidChar {
+                    ch,
+                    char_pos: pos,
+                    byte_offset,
+                    byte_len: ch.len_utf8(),
+                }
+            }
+            PasswordParseError::InvalidLength { .. } => PasswordSpannedParseError::InvalidLength {
+                char_len: s.chars().count(),
+                byte_len: s.len(),
+            },
+            PasswordParseError::InvalidChars(_) => unreachable!(),
+        })
+    }
+
+    /// ひらがな文字列をパースして `Password` を作る。無効な文字があれば、それらを全て含むエラーを返す。
+    ///
+    /// 無効な文字があれば、その位置と文字を全て列挙した `InvalidChars` エラーを返す(文字数の検査より優先する)。
+    /// 全て有効な文字であれば、文字数が正しくない場合に `InvalidLength` エラーを返す。
+    pub fn parse_all_errors(s: &str) -> Result<Self, PasswordParseError> {
+        let mut chars = vec![];
+        let mut invalids = vec![];
         for (i, c) in s.chars().enumerate() {
+            match PasswordChar::from_char(c) {
+                Some(pc) => chars.push(pc),
+                None => invalids.push((i, c)),
+            }
+        }
+
+        if !invalids.is_empty() {
+            return Err(PasswordParseError::InvalidChars(invalids));
+        }
+
+        Self::new_checked(&chars).map_err(|e| PasswordParseError::InvalidLength { len: e.actual })
+    }
+
+    /// 空白と区切り記号を読み飛ばしつつ、ひらがな文字列をパースして `Password` を作る。
+    ///
+    /// `display_pretty()` の出力や掲示板からコピーした文字列などをそのまま受け付ける。
+    /// 読み飛ばすのは Unicode の空白(全角空白や改行を含む)と `LENIENT_SEPARATORS` の文字。
+    /// また、NFD で分解された濁点/半濁点(U+3099, U+309A)を直前の文字と合成する。
+    /// エラーの位置は元の文字列における文字位置。
+    pub fn parse_lenient(s: &str) -> Result<Self, PasswordParseError> {
+        let mut chars = s.chars().enumerate().peekable();
+        let chars = std::iter::from_fn(move || {
+            let (i, c) = chars.next()?;
+            // 合成できない結合文字はそのまま残し、無効な文字としてエラーにする。
+            match chars.peek().and_then(|&(_, mark)| compose_voiced(c, mark)) {
+                Some(composed) => {
+                    chars.next();
+                    Some((i, composed))
+                }
+                None => Some((i, c)),
+            }
+        });
+
+        Self::parse_chars(
+            chars.filter(|&(_, c)| !(c.is_whitespace() || Self::LENIENT_SEPARATORS.contains(&c))),
+        )
+    }
+
+    /// (元の文字列における位置, 文字) の列をパースする。
+    fn parse_chars(
+        chars: impl IntoIterator<Item = (usize, char)>,
+    ) -> Result<Self, PasswordParseError> {
+        let mut inner = PasswordInner::new();
+
+        let mut chars = chars.into_iter();
+        while let Some((i, c)) = chars.next() {
             let pc = PasswordChar::from_char(c)
                 .ok_or(PasswordParseError::InvalidChar { pos: i, ch: c })?;
-            inner
-                .try_push(pc)
-                .map_err(|_| PasswordParseError::InvalidLength)?;
+            if inner.try_push(pc).is_err() {
+                // 残りの文字は検査せず、文字数のみ数える。
+                let len = inner.len() + 1 + chars.count();
+                return Err(PasswordParseError::InvalidLength { len });
+            }
         }
 
         if inner.is_empty() {
-            return Err(PasswordParseError::InvalidLength);
+            return Err(PasswordParseError::InvalidLength { len: 0 });
         }
 
         Ok(Self(inner))
     }
 
+    /// 同じ長さのパスワードとの間で、文字が異なる位置の個数を返す。長さが異なれば `None` を返す。
+    pub fn hamming_distance(&self, other: &Password) -> Option<usize> {
+        (self.len() == other.len()).then(|| self.differs_at(other).count())
+    }
+
+    /// 先頭から一致する文字数を返す。
+    pub fn common_prefix_len(&self, other: &Password) -> usize {
+        self.iter()
+            .zip(other.iter())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    /// 文字が異なる位置を昇順に返す。長さが異なる場合、一方にしかない位置も含む。
+    pub fn differs_at<'a>(&'a self, other: &'a Password) -> impl Iterator<Item = usize> + 'a {
+        (0..self.len().max(other.len())).filter(|&i| self.get(i) != other.get(i))
+    }
+
+    /// 文字の列 `needle` が現れる最初の位置を返す。現れなければ `None` を返す。
+    ///
+    /// `needle` が空なら `Some(0)` を返す。メモリ確保は行わない。
+    /// 先頭/末尾との一致は(スライスの) `starts_with()`/`ends_with()` で調べられる。
+    pub fn find_seq(&self, needle: &[PasswordChar]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+        self.windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    /// 文字の列 `needle` を含むかどうかを返す。`needle` が空なら `true` を返す。
+    pub fn contains_seq(&self, needle: &[PasswordChar]) -> bool {
+        self.find_seq(needle).is_some()
+    }
+
+    /// `self` の後に `other` を連結したパスワードを返す。文字数が最大文字数を超えるならエラーを返す。
+    pub fn concat(&self, other: &Password) -> Result<Password, PasswordLengthError> {
+        self.with_suffix(other)
+    }
+
+    /// `self` の後に `suffix` を連結したパスワードを返す。文字数が最大文字数を超えるならエラーを返す。
+    pub fn with_suffix(&self, suffix: &[PasswordChar]) -> Result<Password, PasswordLengthError> {
+        let len = self.len() + suffix.len();
+        if len > Self::MAX_LEN {
+            return Err(PasswordLengthError::new(len));
+        }
+
+        let mut res = self.clone();
+        res.0.extend(suffix.iter().copied());
+        Ok(res)
+    }
+
+    /// 末尾に文字を追加する。既に最大文字数ならエラーを返す。
+    pub fn try_push(&mut self, pc: PasswordChar) -> Result<(), PasswordLengthError> {
+        self.0
+            .try_push(pc)
+            .map_err(|_| PasswordLengthError::new(self.len() + 1))
+    }
+
+    /// 末尾の文字を取り除いて返す。既に最小文字数ならエラーを返す。
+    pub fn pop(&mut self) -> Result<PasswordChar, PasswordLengthError> {
+        self.check_shrink(1)?;
+        Ok(self.0.pop().unwrap())
+    }
+
+    /// `idx` の位置に文字を挿入する。既に最大文字数ならエラーを返す。
+    ///
+    /// # Panics
+    ///
+    /// `idx > self.len()` ならパニックする。
+    pub fn insert(&mut self, idx: usize, pc: PasswordChar) -> Result<(), PasswordLengthError> {
+        assert!(idx <= self.len(), "insertion index {idx} is out of bounds");
+        self.0
+            .try_insert(idx, pc)
+            .map_err(|_| PasswordLengthError::new(self.len() + 1))
+    }
+
+    /// `idx` の位置の文字を取り除いて返す。既に最小文字数ならエラーを返す。
+    ///
+    /// # Panics
+    ///
+    /// `idx >= self.len()` ならパニックする。
+    pub fn remove(&mut self, idx: usize) -> Result<PasswordChar, PasswordLengthError> {
+        assert!(idx < self.len(), "removal index {idx} is out of bounds");
+        self.check_shrink(1)?;
+        Ok(self.0.remove(idx))
+    }
+
+    /// 文字数を `new_len` に切り詰める。`new_len` が最小文字数未満ならエラーを返す。
+    ///
+    /// `new_len` が現在の文字数以上なら何もしない。
+    pub fn truncate(&mut self, new_len: usize) -> Result<(), PasswordLengthError> {
+        if new_len < Self::MIN_LEN {
+            return Err(PasswordLengthError::new(new_len));
+        }
+        self.0.truncate(new_len);
+        Ok(())
+    }
+
+    /// 文字数を `n` 減らしても最小文字数以上であることを確かめる。
+    fn check_shrink(&self, n: usize) -> Result<(), PasswordLengthError> {
+        match self.len().checked_sub(n) {
+            Some(len) if len >= Self::MIN_LEN => Ok(()),
+            len => Err(PasswordLengthError::new(len.unwrap_or(0))),
+        }
+    }
+
     /// 内部バッファを返す。
     pub fn into_inner(self) -> PasswordInner {
         self.0
@@ -85,27 +341,151 @@ impl Password {
     }
 
     /// パスワードをひらがな文字列(空白区切りなし)としてフォーマットする `Display` オブジェクトを返す。
-    pub fn display(&self) -> PasswordDisplay {
+    pub fn display(&self) -> PasswordDisplay<'_> {
         PasswordDisplay { password: self }
     }
 
     /// パスワードをひらがな文字列(空白区切りあり)としてフォーマットする `Display` オブジェクトを返す。
-    pub fn display_pretty(&self) -> PasswordDisplayPretty {
+    pub fn display_pretty(&self) -> PasswordDisplayPretty<'_> {
         PasswordDisplayPretty { password: self }
     }
 
+    /// `display()` と同じ文字列をヒープ確保なしで返す。
+    pub fn to_array_string(&self) -> PasswordArrayString {
+        let mut res = PasswordArrayString::new();
+        for &pc in self {
+            res.push(pc.to_char());
+        }
+        res
+    }
+
+    /// `display_pretty()` と同じ文字列をヒープ確保なしで返す。
+    pub fn to_array_string_pretty(&self) -> PasswordArrayStringPretty {
+        let mut res = PasswordArrayStringPretty::new();
+        for (i, chunk) in self.pretty_chunks().enumerate() {
+            if i != 0 {
+                res.push(' ');
+            }
+            for &pc in chunk {
+                res.push(pc.to_char());
+            }
+        }
+        res
+    }
+
+    /// パスワードを、文字数 `groups` ごとに区切ってフォーマットする `Display` オブジェクトを返す。
+    ///
+    /// 区切りは空白で、`PasswordDisplayGrouped::separated()` で変えられる。
+    /// `groups` の合計がパスワードの文字数に満たなければ、残りを最後の 1 グループとする。
+    /// 0 のグループは無視される。
+    pub fn display_grouped<'a>(&'a self, groups: &'a [usize]) -> PasswordDisplayGrouped<'a> {
+        PasswordDisplayGrouped {
+            password: self,
+            groups,
+            separator: ' ',
+        }
+    }
+
+    /// パスワードを、1 行が `width` 桁を超えないように改行してフォーマットする `Display` オブジェクトを返す。
+    ///
+    /// 既定では文字を 1 桁として空白区切りなしで並べ、末尾に改行は付けない。
+    /// `PasswordDisplayWrapped::double_width()` で文字を 2 桁と数え、
+    /// `PasswordDisplayWrapped::respect_groups()` で `display_pretty()` の単位を途中で改行しないようにする。
+    /// 1 文字も収まらない `width` でも、1 行に 1 文字は出力する。
+    pub fn display_wrapped(&self, width: usize) -> PasswordDisplayWrapped<'_> {
+        PasswordDisplayWrapped {
+            password: self,
+            width,
+            double_width: false,
+            respect_groups: false,
+        }
+    }
+
+    /// `display_pretty()` で空白区切りされる単位ごとに分割する。
+    fn pretty_chunks(&self) -> impl Iterator<Item = &[PasswordChar]> {
+        self.grouped_chunks(PRETTY_CHUNK_LENS)
+    }
+
+    /// `display_grouped()` で区切られる単位ごとに分割する。
+    fn grouped_chunks<'a>(
+        &'a self,
+        groups: &'a [usize],
+    ) -> impl Iterator<Item = &'a [PasswordChar]> {
+        let mut rest = self.as_slice();
+        groups
+            .iter()
+            .filter(|&&len| len > 0)
+            .map(Some)
+            .chain([None])
+            .map_while(move |len| {
+                if rest.is_empty() {
+                    return None;
+                }
+                let len = len.map_or(rest.len(), |&len| len.min(rest.len()));
+                let (chunk, tail) = rest.split_at(len);
+                rest = tail;
+                Some(chunk)
+            })
+    }
+
     /// パスワードを内部値の 16 進ダンプとしてフォーマットする `Display` オブジェクトを返す。
     ///
     /// 結果の文字列は Mesen や FCEUX のメモリエディタにそのまま貼り付け可能。
-    pub fn display_hex(&self) -> PasswordDisplayHex {
-        PasswordDisplayHex { password: self }
+    pub fn display_hex(&self) -> PasswordDisplayHex<'_> {
+        self.display_hex_with(HexDisplayOptions::default())
+    }
+
+    /// パスワードを内部値の 16 進ダンプとして、`options` に従ってフォーマットする `Display` オブジェクトを返す。
+    pub fn display_hex_with<'a>(
+        &'a self,
+        options: HexDisplayOptions<'a>,
+    ) -> PasswordDisplayHex<'a> {
+        PasswordDisplayHex {
+            password: self,
+            options,
+        }
     }
 
-    /// パスワードが有効(ゲーム状態としてロードできる)かどうかを返す。
+    /// `display_hex()` の形式(空白区切りの 2 桁の 16 進数)の文字列をパースして `Password` を作る。
+    ///
+    /// 小文字や連続する空白、末尾の改行も受け付ける。
+    pub fn parse_hex(s: &str) -> Result<Self, PasswordHexParseError> {
+        let mut inner = PasswordInner::new();
+
+        for (i, token) in s.split_whitespace().enumerate() {
+            // `from_str_radix()` は符号を受け付けるので、桁を自前で検査する。
+            let value = (token.len() == 2 && token.bytes().all(|b| b.is_ascii_hexdigit()))
+                .then(|| u8::from_str_radix(token, 16).unwrap())
+                .ok_or(PasswordHexParseError::InvalidToken { index: i })?;
+            let pc = PasswordChar::from_inner(value)
+                .ok_or(PasswordHexParseError::OutOfRange { index: i, value })?;
+            inner
+                .try_push(pc)
+                .map_err(|_| PasswordHexParseError::InvalidLength)?;
+        }
+
+        if inner.is_empty() {
+            return Err(PasswordHexParseError::InvalidLength);
+        }
+
+        Ok(Self(inner))
+    }
+
+    /// パスワードが有効(チェックサムが一致する)かどうかを返す。
+    ///
+    /// ゲームは特殊パスワードをロード前に処理するので、実際にロードできるかどうかは `is_loadable()` で判定する。
     pub fn is_valid(&self) -> bool {
         SerializedBytes::from_password(self).checksum_is_ok()
     }
 
+    /// パスワードをゲーム状態としてロードできる(有効であり、かつ特殊パスワードでない)かどうかを返す。
+    ///
+    /// 現状、特殊パスワードの先頭部分には有効なものはないので `is_valid()` と一致するが、
+    /// ロードできることを確かめたい場合はこちらを使う。
+    pub fn is_loadable(&self) -> bool {
+        self.is_valid() && !self.is_special()
+    }
+
     /// パスワードの 2 文字目のみを見たとき、それが有効なパスワードになりえないかどうかを返す。
     pub fn is_invalid_second_char(pc_second: PasswordChar) -> bool {
         // 一般に add と xor の偶奇は一致するので、
@@ -127,12 +507,27 @@ impl Password {
         //
         // よって、prefix[1] が偶数ならば有効なパスワードにはなりえない。
 
-        pc_second.to_inner() % 2 == 0
+        pc_second.to_inner().is_multiple_of(2)
     }
 
     /// 特殊パスワード(音楽室/美術室)かどうかを返す。
     pub fn is_special(&self) -> bool {
-        self.is_special_audio() || self.is_special_enemy()
+        self.special_kind().is_some()
+    }
+
+    /// 特殊パスワードならその種類を返す。
+    ///
+    /// 両方の特殊パスワードに共通する先頭部分("す" から "すべての" まで)に対しては `Audio` を返す
+    /// (ゲーム内での扱いを確かめたものではなく、判定順による)。
+    /// 個別の判定には `is_special_audio()`, `is_special_enemy()` を使う。
+    pub fn special_kind(&self) -> Option<SpecialPassword> {
+        if self.is_special_audio() {
+            Some(SpecialPassword::Audio)
+        } else if self.is_special_enemy() {
+            Some(SpecialPassword::Enemy)
+        } else {
+            None
+        }
     }
 
     /// 音楽室に入る特殊パスワードかどうかを返す。
@@ -184,6 +579,114 @@ impl<'a> IntoIterator for &'a Password {
     }
 }
 
+/// ひらがな文字列としてシリアライズする。
+#[cfg(feature = "serde")]
+impl serde::Serialize for Password {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_array_string())
+    }
+}
+
+/// ひらがな文字列から `Password::parse()` でデシリアライズする。
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Password {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Password;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a hiragana password string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Password::parse(v).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// 文字数を `MIN_LEN..=MAX_LEN` から選び、各文字を選ぶ。
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Password {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(Self::MIN_LEN..=Self::MAX_LEN)?;
+        (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()
+            .map(Self)
+    }
+
+    /// 残りのバイト数(`MIN_LEN..=MAX_LEN` に丸める)を文字数とする。
+    fn arbitrary_take_rest(mut u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.len().clamp(Self::MIN_LEN, Self::MAX_LEN);
+        (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()
+            .map(Self)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1 + Self::MIN_LEN, Some(1 + Self::MAX_LEN))
+    }
+}
+
+/// `Password` を 1 文字ずつ組み立てるためのビルダー。
+///
+/// 最大文字数を超えて追加された文字は捨てられ、`build()` がエラーを返すようになる。
+#[derive(Clone, Debug, Default)]
+pub struct PasswordBuilder {
+    inner: PasswordInner,
+    overflow: bool,
+}
+
+impl PasswordBuilder {
+    /// 空の `PasswordBuilder` を返す。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 末尾に文字を追加する。
+    pub fn push(&mut self, pc: PasswordChar) {
+        if self.inner.try_push(pc).is_err() {
+            self.overflow = true;
+        }
+    }
+
+    /// これまでに追加された文字数が範囲外かどうかを返す。
+    pub fn is_overflow(&self) -> bool {
+        self.overflow
+    }
+
+    /// `Password` を作る。文字数が範囲外ならエラーを返す。
+    pub fn build(self) -> Result<Password, PasswordParseError> {
+        if self.overflow || self.inner.is_empty() {
+            let len = self.inner.len() + usize::from(self.overflow);
+            return Err(PasswordParseError::InvalidLength { len });
+        }
+
+        Ok(Password(self.inner))
+    }
+}
+
+impl Extend<PasswordChar> for PasswordBuilder {
+    /// 最大文字数を超えた時点で消費をやめる。
+    fn extend<I: IntoIterator<Item = PasswordChar>>(&mut self, iter: I) {
+        if self.overflow {
+            return;
+        }
+        for pc in iter {
+            self.push(pc);
+            if self.overflow {
+                break;
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PasswordDisplay<'a> {
     password: &'a Password,
@@ -206,18 +709,103 @@ pub struct PasswordDisplayPretty<'a> {
 
 impl std::fmt::Display for PasswordDisplayPretty<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        const LENS: &[usize] = &[5, 7, 5, 7, 7, 7];
+        self.password.display_grouped(PRETTY_CHUNK_LENS).fmt(f)
+    }
+}
+
+#[derive(Debug)]
+pub struct PasswordDisplayGrouped<'a> {
+    password: &'a Password,
+    groups: &'a [usize],
+    separator: char,
+}
 
-        let mut pcs = self.password.iter().peekable();
-        let pcs = pcs.by_ref();
+impl PasswordDisplayGrouped<'_> {
+    /// グループの間に `separator` を挟むようにする。
+    pub fn separated(self, separator: char) -> Self {
+        Self { separator, ..self }
+    }
+}
 
-        for &len in LENS {
-            let chunk = pcs.take(len);
+impl std::fmt::Display for PasswordDisplayGrouped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, chunk) in self.password.grouped_chunks(self.groups).enumerate() {
+            if i != 0 {
+                f.write_char(self.separator)?;
+            }
             for &pc in chunk {
                 f.write_char(pc.to_char())?;
             }
-            if pcs.peek().is_some() {
-                f.write_char(' ')?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct PasswordDisplayWrapped<'a> {
+    password: &'a Password,
+    width: usize,
+    double_width: bool,
+    respect_groups: bool,
+}
+
+impl PasswordDisplayWrapped<'_> {
+    /// 文字を 2 桁として数えるようにする(端末の全角文字に合わせる)。
+    pub fn double_width(self) -> Self {
+        Self {
+            double_width: true,
+            ..self
+        }
+    }
+
+    /// `display_pretty()` と同様に空白で区切った単位を、途中で改行しないようにする。
+    ///
+    /// 空白は 1 桁と数える。1 行に収まらない単位は、収まる文字数ごとに分割して別の行とする。
+    pub fn respect_groups(self) -> Self {
+        Self {
+            respect_groups: true,
+            ..self
+        }
+    }
+}
+
+impl std::fmt::Display for PasswordDisplayWrapped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let char_width = if self.double_width { 2 } else { 1 };
+        let line_len = (self.width / char_width).max(1);
+
+        if !self.respect_groups {
+            for (i, line) in self.password.chunks(line_len).enumerate() {
+                if i != 0 {
+                    f.write_char('\n')?;
+                }
+                for &pc in line {
+                    f.write_char(pc.to_char())?;
+                }
+            }
+            return Ok(());
+        }
+
+        // 現在の行の桁数(行頭なら `None`)。
+        let mut col: Option<usize> = None;
+        for group in self.password.pretty_chunks() {
+            for piece in group.chunks(line_len) {
+                let piece_width = char_width * piece.len();
+                col = match col {
+                    Some(col) if col + 1 + piece_width <= self.width => {
+                        f.write_char(' ')?;
+                        Some(col + 1 + piece_width)
+                    }
+                    Some(_) => {
+                        f.write_char('\n')?;
+                        Some(piece_width)
+                    }
+                    None => Some(piece_width),
+                };
+                for &pc in piece {
+                    f.write_char(pc.to_char())?;
+                }
             }
         }
 
@@ -225,18 +813,69 @@ impl std::fmt::Display for PasswordDisplayPretty<'_> {
     }
 }
 
+/// 特殊パスワードの種類。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SpecialPassword {
+    /// 音楽室に入る特殊パスワード(`Password::SPECIAL_AUDIO`)。
+    Audio,
+    /// 美術室に入る特殊パスワード(`Password::SPECIAL_ENEMY`)。
+    Enemy,
+}
+
+/// `Password::display_hex_with()` の書式。
+///
+/// `Default` は `display_hex()` と同じ書式 (`00 36 3F`)。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HexDisplayOptions<'a> {
+    /// 値の区切り。
+    pub separator: &'a str,
+    /// 16 進数の英字を小文字にするかどうか。
+    pub lowercase: bool,
+    /// 各値に `0x` を前置するかどうか。
+    pub prefix: bool,
+    /// 1 行の値の個数。`Some` なら、この個数ごとに区切りの末尾の空白を除いて改行する(0 なら改行しない)。
+    pub width: Option<usize>,
+}
+
+impl Default for HexDisplayOptions<'_> {
+    fn default() -> Self {
+        Self {
+            separator: " ",
+            lowercase: false,
+            prefix: false,
+            width: None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PasswordDisplayHex<'a> {
     password: &'a Password,
+    options: HexDisplayOptions<'a>,
 }
 
 impl std::fmt::Display for PasswordDisplayHex<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let options = &self.options;
+
         for (i, &pc) in self.password.iter().enumerate() {
             if i != 0 {
-                f.write_char(' ')?;
+                match options.width {
+                    Some(width) if width > 0 && i % width == 0 => {
+                        f.write_str(options.separator.trim_end())?;
+                        f.write_char('\n')?;
+                    }
+                    _ => f.write_str(options.separator)?,
+                }
+            }
+            if options.prefix {
+                f.write_str("0x")?;
+            }
+            if options.lowercase {
+                write!(f, "{:02x}", pc.to_inner())?;
+            } else {
+                write!(f, "{:02X}", pc.to_inner())?;
             }
-            write!(f, "{:02X}", pc.to_inner())?;
         }
 
         Ok(())
@@ -314,10 +953,35 @@ pub enum PasswordChar {
 }
 
 impl PasswordChar {
+    /// 全ての文字(内部値の昇順)。
+    #[rustfmt::skip]
+    pub const ALL: [Self; 0x40] = {
+        use PasswordChar::*;
+        [
+            A,  I,  U,  E,  O,
+            Ka, Ki, Ku, Ke, Ko,
+            Sa, Si, Su, Se, So,
+            Ta, Ti, Tu, Te, To,
+            Na, Ni, Nu, Ne, No,
+            Ha, Hi, Hu, He, Ho,
+            Ma, Mi, Mu, Me, Mo,
+            Ya, Yu, Yo,
+            Ra, Ri, Ru, Re, Ro,
+            Wa,
+            Ga, Gi, Gu, Ge, Go,
+            Za, Zi, Zu, Ze, Zo,
+            Ba, Bi, Bu, Be, Bo,
+            Pa, Pi, Pu, Pe, Po,
+        ]
+    };
+
     /// 内部値から `PasswordChar` を作る。無効値に対しては `None` を返す。
     pub const fn from_inner(inner: u8) -> Option<Self> {
         if matches!(inner, 0..=0x3F) {
-            Some(unsafe { Self::from_inner_unchecked(inner) })
+            Some(unsafe_or!(
+                Self::from_inner_unchecked(inner),
+                Self::ALL[inner as usize]
+            ))
         } else {
             None
         }
@@ -328,80 +992,62 @@ impl PasswordChar {
     /// # Safety
     ///
     /// `inner` は有効値、即ち `0..=0x3F` でなければならない。
+    #[cfg(not(feature = "forbid-unsafe"))]
     pub const unsafe fn from_inner_unchecked(inner: u8) -> Self {
         assert_unchecked!(matches!(inner, 0..=0x3F));
         std::mem::transmute(inner)
     }
 
+    /// 内部値から `PasswordChar` を作る。
+    ///
+    /// `forbid-unsafe` feature 有効時は安全な関数となり、`inner` が無効値ならパニックする。
+    #[cfg(feature = "forbid-unsafe")]
+    pub const fn from_inner_unchecked(inner: u8) -> Self {
+        match Self::from_inner(inner) {
+            Some(pc) => pc,
+            None => panic!("invalid password char"),
+        }
+    }
+
     /// ひらがな文字を `PasswordChar` に変換する。無効な文字に対しては `None` を返す。
     pub const fn from_char(c: char) -> Option<Self> {
-        match c {
-            'あ' => Some(Self::A),
-            'い' => Some(Self::I),
-            'う' => Some(Self::U),
-            'え' => Some(Self::E),
-            'お' => Some(Self::O),
-            'か' => Some(Self::Ka),
-            'き' => Some(Self::Ki),
-            'く' => Some(Self::Ku),
-            'け' => Some(Self::Ke),
-            'こ' => Some(Self::Ko),
-            'さ' => Some(Self::Sa),
-            'し' => Some(Self::Si),
-            'す' => Some(Self::Su),
-            'せ' => Some(Self::Se),
-            'そ' => Some(Self::So),
-            'た' => Some(Self::Ta),
-            'ち' => Some(Self::Ti),
-            'つ' => Some(Self::Tu),
-            'て' => Some(Self::Te),
-            'と' => Some(Self::To),
-            'な' => Some(Self::Na),
-            'に' => Some(Self::Ni),
-            'ぬ' => Some(Self::Nu),
-            'ね' => Some(Self::Ne),
-            'の' => Some(Self::No),
-            'は' => Some(Self::Ha),
-            'ひ' => Some(Self::Hi),
-            'ふ' => Some(Self::Hu),
-            'へ' => Some(Self::He),
-            'ほ' => Some(Self::Ho),
-            'ま' => Some(Self::Ma),
-            'み' => Some(Self::Mi),
-            'む' => Some(Self::Mu),
-            'め' => Some(Self::Me),
-            'も' => Some(Self::Mo),
-            'や' => Some(Self::Ya),
-            'ゆ' => Some(Self::Yu),
-            'よ' => Some(Self::Yo),
-            'ら' => Some(Self::Ra),
-            'り' => Some(Self::Ri),
-            'る' => Some(Self::Ru),
-            'れ' => Some(Self::Re),
-            'ろ' => Some(Self::Ro),
-            'わ' => Some(Self::Wa),
-            'が' => Some(Self::Ga),
-            'ぎ' => Some(Self::Gi),
-            'ぐ' => Some(Self::Gu),
-            'げ' => Some(Self::Ge),
-            'ご' => Some(Self::Go),
-            'ざ' => Some(Self::Za),
-            'じ' => Some(Self::Zi),
-            'ず' => Some(Self::Zu),
-            'ぜ' => Some(Self::Ze),
-            'ぞ' => Some(Self::Zo),
-            'ば' => Some(Self::Ba),
-            'び' => Some(Self::Bi),
-            'ぶ' => Some(Self::Bu),
-            'べ' => Some(Self::Be),
-            'ぼ' => Some(Self::Bo),
-            'ぱ' => Some(Self::Pa),
-            'ぴ' => Some(Self::Pi),
-            'ぷ' => Some(Self::Pu),
-            'ぺ' => Some(Self::Pe),
-            'ぽ' => Some(Self::Po),
-            _ => None,
+        let Some(i) = (c as u32).checked_sub(FROM_CHAR_BASE as u32) else {
+            return None;
+        };
+        if i as usize >= FROM_CHAR_TABLE.len() {
+            return None;
+        }
+
+        Self::from_inner(FROM_CHAR_TABLE[i as usize])
+    }
+
+    /// ひらがな文字列を `PasswordChar` の列に変換する。空文字列も受け付ける。
+    ///
+    /// `Password::find_seq()` などに渡す列を作るためのもの。
+    /// 無効な文字があれば、最初のものを指す `PasswordParseError::InvalidChar` を返す。
+    pub fn seq_from_str(s: &str) -> Result<Vec<Self>, PasswordParseError> {
+        s.chars()
+            .enumerate()
+            .map(|(pos, ch)| Self::from_char(ch).ok_or(PasswordParseError::InvalidChar { pos, ch }))
+            .collect()
+    }
+
+    /// 無効な文字 `c` に対する修正候補を、もっともらしい順に返す。候補がなければ空を返す。
+    ///
+    /// カタカナはひらがなに読み替えた上で、小書きの仮名、'ゔ' などの文字集合にない仮名、および
+    /// OCR で取り違えやすい漢字(`'力'` など)を似た文字に対応させる。`c` が有効な文字ならそれ自身のみを返す。
+    pub fn suggest(c: char) -> Vec<Self> {
+        // カタカナ ァ..=ヶ はひらがな ぁ..=ゖ と 0x60 ずれている。
+        let c = match c {
+            'ァ'..='ヶ' => char::from_u32(u32::from(c) - 0x60).unwrap(),
+            _ => c,
+        };
+
+        if let Some(&(_, pcs)) = SUGGEST_TABLE.iter().find(|&&(k, _)| k == c) {
+            return pcs.to_vec();
         }
+
+        Self::from_char(c).into_iter().collect()
     }
 
     /// 内部値を返す。
@@ -411,114 +1057,336 @@ impl PasswordChar {
 
     /// 対応するひらがな文字を返す。
     pub const fn to_char(self) -> char {
-        match self {
-            Self::A => 'あ',
-            Self::I => 'い',
-            Self::U => 'う',
-            Self::E => 'え',
-            Self::O => 'お',
-            Self::Ka => 'か',
-            Self::Ki => 'き',
-            Self::Ku => 'く',
-            Self::Ke => 'け',
-            Self::Ko => 'こ',
-            Self::Sa => 'さ',
-            Self::Si => 'し',
-            Self::Su => 'す',
-            Self::Se => 'せ',
-            Self::So => 'そ',
-            Self::Ta => 'た',
-            Self::Ti => 'ち',
-            Self::Tu => 'つ',
-            Self::Te => 'て',
-            Self::To => 'と',
-            Self::Na => 'な',
-            Self::Ni => 'に',
-            Self::Nu => 'ぬ',
-            Self::Ne => 'ね',
-            Self::No => 'の',
-            Self::Ha => 'は',
-            Self::Hi => 'ひ',
-            Self::Hu => 'ふ',
-            Self::He => 'へ',
-            Self::Ho => 'ほ',
-            Self::Ma => 'ま',
-            Self::Mi => 'み',
-            Self::Mu => 'む',
-            Self::Me => 'め',
-            Self::Mo => 'も',
-            Self::Ya => 'や',
-            Self::Yu => 'ゆ',
-            Self::Yo => 'よ',
-            Self::Ra => 'ら',
-            Self::Ri => 'り',
-            Self::Ru => 'る',
-            Self::Re => 'れ',
-            Self::Ro => 'ろ',
-            Self::Wa => 'わ',
-            Self::Ga => 'が',
-            Self::Gi => 'ぎ',
-            Self::Gu => 'ぐ',
-            Self::Ge => 'げ',
-            Self::Go => 'ご',
-            Self::Za => 'ざ',
-            Self::Zi => 'じ',
-            Self::Zu => 'ず',
-            Self::Ze => 'ぜ',
-            Self::Zo => 'ぞ',
-            Self::Ba => 'ば',
-            Self::Bi => 'び',
-            Self::Bu => 'ぶ',
-            Self::Be => 'べ',
-            Self::Bo => 'ぼ',
-            Self::Pa => 'ぱ',
-            Self::Pi => 'ぴ',
-            Self::Pu => 'ぷ',
-            Self::Pe => 'ぺ',
-            Self::Po => 'ぽ',
-        }
-    }
-
-    /// 全ての文字を昇順で返す。
-    pub const fn all() -> [Self; 0x40] {
-        use PasswordChar::*;
+        TO_CHAR_TABLE[self as usize]
+    }
 
-        #[rustfmt::skip]
-        const ALL: [PasswordChar; 0x40] = [
-            A,  I,  U,  E,  O,
-            Ka, Ki, Ku, Ke, Ko,
-            Sa, Si, Su, Se, So,
-            Ta, Ti, Tu, Te, To,
-            Na, Ni, Nu, Ne, No,
-            Ha, Hi, Hu, He, Ho,
-            Ma, Mi, Mu, Me, Mo,
-            Ya, Yu, Yo,
-            Ra, Ri, Ru, Re, Ro,
-            Wa,
-            Ga, Gi, Gu, Ge, Go,
-            Za, Zi, Zu, Ze, Zo,
-            Ba, Bi, Bu, Be, Bo,
-            Pa, Pi, Pu, Pe, Po,
-        ];
+    /// 濁点/半濁点を除いた文字(清音)を返す。清音に対してはそれ自身を返す。
+    pub const fn base(self) -> Self {
+        let inner = self.to_inner();
+        let base = match inner {
+            // が行, ざ行 -> か行, さ行
+            0x2C..=0x35 => inner - 0x27,
+            // ば行 -> は行
+            0x36..=0x3A => inner - 0x1D,
+            // ぱ行 -> は行
+            0x3B..=0x3F => inner - 0x22,
+            _ => inner,
+        };
+        Self::ALL[base as usize]
+    }
+
+    /// 濁音かどうかを返す。
+    pub const fn is_dakuten(self) -> bool {
+        matches!(self.to_inner(), 0x2C..=0x3A)
+    }
 
-        ALL
+    /// 半濁音かどうかを返す。
+    pub const fn is_handakuten(self) -> bool {
+        matches!(self.to_inner(), 0x3B..=0x3F)
+    }
+
+    /// 清音 `self.base()` に濁点を付けた文字を返す。パスワードに使われる文字になければ `None` を返す。
+    pub const fn with_dakuten(self) -> Option<Self> {
+        let base = self.base().to_inner();
+        match base {
+            0x05..=0x0E => Some(Self::ALL[base as usize + 0x27]),
+            0x19..=0x1D => Some(Self::ALL[base as usize + 0x1D]),
+            _ => None,
+        }
+    }
+
+    /// 清音 `self.base()` に半濁点を付けた文字を返す。パスワードに使われる文字になければ `None` を返す。
+    pub const fn with_handakuten(self) -> Option<Self> {
+        let base = self.base().to_inner();
+        match base {
+            0x19..=0x1D => Some(Self::ALL[base as usize + 0x22]),
+            _ => None,
+        }
+    }
+
+    /// 全ての文字を昇順で返す。`PasswordChar::ALL` と同じ。
+    pub const fn all() -> [Self; 0x40] {
+        Self::ALL
+    }
+
+    /// 全ての文字を昇順で列挙するイテレータを返す。
+    pub fn iter() -> std::array::IntoIter<Self, 0x40> {
+        Self::ALL.into_iter()
     }
 }
 
+/// 内部値を一様に選ぶ。
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PasswordChar {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.int_in_range(0..=0x3F_u8)
+            .map(|x| Self::ALL[usize::from(x)])
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(1))
+    }
+}
+
+/// `PasswordChar::from_char()` と同じ。
+impl TryFrom<char> for PasswordChar {
+    type Error = PasswordCharFromCharError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or(PasswordCharFromCharError(c))
+    }
+}
+
+/// `PasswordChar::from_inner()` と同じ。
+impl TryFrom<u8> for PasswordChar {
+    type Error = PasswordCharFromInnerError;
+
+    fn try_from(inner: u8) -> Result<Self, Self::Error> {
+        Self::from_inner(inner).ok_or(PasswordCharFromInnerError(inner))
+    }
+}
+
+/// `PasswordChar::to_char()` と同じ。
+impl From<PasswordChar> for char {
+    fn from(pc: PasswordChar) -> Self {
+        pc.to_char()
+    }
+}
+
+/// `PasswordChar::to_inner()` と同じ。
+impl From<PasswordChar> for u8 {
+    fn from(pc: PasswordChar) -> Self {
+        pc.to_inner()
+    }
+}
+
+/// 内部値からひらがな文字への変換表。
+#[rustfmt::skip]
+const TO_CHAR_TABLE: [char; 0x40] = [
+    'あ', 'い', 'う', 'え', 'お',
+    'か', 'き', 'く', 'け', 'こ',
+    'さ', 'し', 'す', 'せ', 'そ',
+    'た', 'ち', 'つ', 'て', 'と',
+    'な', 'に', 'ぬ', 'ね', 'の',
+    'は', 'ひ', 'ふ', 'へ', 'ほ',
+    'ま', 'み', 'む', 'め', 'も',
+    'や', 'ゆ', 'よ',
+    'ら', 'り', 'る', 'れ', 'ろ',
+    'わ',
+    'が', 'ぎ', 'ぐ', 'げ', 'ご',
+    'ざ', 'じ', 'ず', 'ぜ', 'ぞ',
+    'ば', 'び', 'ぶ', 'べ', 'ぼ',
+    'ぱ', 'ぴ', 'ぷ', 'ぺ', 'ぽ',
+];
+
+/// `FROM_CHAR_TABLE` の先頭に対応する文字。パスワードに使われる文字は全て 'あ'..='わ' にある。
+const FROM_CHAR_BASE: char = 'あ';
+
+/// ひらがな文字から内部値への変換表。`FROM_CHAR_BASE` からのオフセットで引く。無効な文字は 0xFF。
+const FROM_CHAR_TABLE: [u8; 'わ' as usize - FROM_CHAR_BASE as usize + 1] = {
+    let mut table = [0xFF; 'わ' as usize - FROM_CHAR_BASE as usize + 1];
+    let mut i = 0;
+    while i < TO_CHAR_TABLE.len() {
+        table[TO_CHAR_TABLE[i] as usize - FROM_CHAR_BASE as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// 清音 `base` と結合文字 `mark` (濁点 U+3099 / 半濁点 U+309A)を合成した文字を返す。
+/// 合成結果がパスワードに使われる文字でなければ `None` を返す。
+fn compose_voiced(base: char, mark: char) -> Option<char> {
+    let base = PasswordChar::from_char(base).filter(|&pc| pc.base() == pc)?;
+    let pc = match mark {
+        '\u{3099}' => base.with_dakuten(),
+        '\u{309A}' => base.with_handakuten(),
+        _ => None,
+    };
+
+    pc.map(PasswordChar::to_char)
+}
+
+/// `PasswordChar::suggest()` 用の、文字集合にない文字から修正候補への対応表。
+const SUGGEST_TABLE: &[(char, &[PasswordChar])] = {
+    use PasswordChar::*;
+    &[
+        // 小書きの仮名。
+        ('ぁ', &[A]),
+        ('ぃ', &[I]),
+        ('ぅ', &[U]),
+        ('ぇ', &[E]),
+        ('ぉ', &[O]),
+        ('ゕ', &[Ka]),
+        ('ゖ', &[Ke]),
+        ('っ', &[Tu]),
+        ('ゃ', &[Ya]),
+        ('ゅ', &[Yu]),
+        ('ょ', &[Yo]),
+        ('ゎ', &[Wa]),
+        // 文字集合にない仮名(読みの近いもの)。
+        ('ゔ', &[Bu, U]),
+        ('ゐ', &[I]),
+        ('ゑ', &[E]),
+        ('を', &[O, Wa]),
+        // カタカナと字形の似た漢字。
+        ('力', &[Ka]),
+        ('工', &[E]),
+        ('夕', &[Ta]),
+        ('卜', &[To]),
+        ('二', &[Ni]),
+        ('八', &[Ha]),
+        ('口', &[Ro]),
+    ]
+};
+
 /// パスワードのパース時に発生しうるエラー。
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
 pub enum PasswordParseError {
     /// パスワードの文字数が正しくない。
+    ///
+    /// `len` は入力の文字数。ただし、最大文字数を超えた時点で入力の消費をやめる場合
+    /// (`Password::from_iter_checked()`, `PasswordBuilder` など)は `Password::MAX_LEN + 1` となる。
     #[error(
-        "password must contain {}..={} chars",
+        "password must contain {}..={} chars, got {len}",
         Password::MIN_LEN,
         Password::MAX_LEN
     )]
-    InvalidLength,
+    InvalidLength { len: usize },
 
     /// パスワードに無効な文字が含まれている。
-    #[error("password contains an invalid character '{ch}' at position {pos}")]
+    ///
+    /// 表示には `PasswordChar::suggest()` による最有力の修正候補があれば含まれる。
+    #[error(
+        "password contains an invalid character '{ch}' at position {pos}{}",
+        display_suggestion(*ch)
+    )]
     InvalidChar { pos: usize, ch: char },
+
+    /// パスワードに無効な文字が含まれている(`Password::parse_all_errors()` で全て列挙したもの)。
+    #[error("password contains invalid characters: {}", display_invalid_chars(.0))]
+    InvalidChars(Vec<(usize, char)>),
+}
+
+fn display_suggestion(ch: char) -> String {
+    PasswordChar::suggest(ch)
+        .first()
+        .map_or_else(String::new, |pc| {
+            format!(" (did you mean '{}'?)", pc.to_char())
+        })
+}
+
+/// `PasswordParseError::InvalidChars` の表示に含める文字数の上限。
+const INVALID_CHARS_DISPLAY_MAX: usize = 5;
+
+fn display_invalid_chars(invalids: &[(usize, char)]) -> String {
+    let mut s = invalids
+        .iter()
+        .take(INVALID_CHARS_DISPLAY_MAX)
+        .map(|(pos, ch)| format!("'{ch}' at position {pos}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if invalids.len() > INVALID_CHARS_DISPLAY_MAX {
+        s.push_str(&format!(
+            " and {} more",
+            invalids.len() - INVALID_CHARS_DISPLAY_MAX
+        ));
+    }
+    s
+}
+
+/// 16 進ダンプ形式のパスワードのパース時に発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum PasswordHexParseError {
+    /// パスワードの文字数が正しくない。
+    #[error(
+        "password must contain {}..={} bytes",
+        Password::MIN_LEN,
+        Password::MAX_LEN
+    )]
+    InvalidLength,
+
+    /// 2 桁の 16 進数でないトークンがある。
+    #[error("invalid hex token at index {index}")]
+    InvalidToken { index: usize },
+
+    /// 値が内部値の範囲(0x00..=0x3F)外。
+    #[error("byte 0x{value:02X} at index {index} is out of range")]
+    OutOfRange { index: usize, value: u8 },
+}
+
+/// 文字を `PasswordChar` に変換できなかった。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("'{0}' is not a password character")]
+pub struct PasswordCharFromCharError(pub char);
+
+/// 値を `PasswordChar` に変換できなかった(内部値の範囲 0x00..=0x3F 外)。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("0x{0:02X} is not a valid password character value")]
+pub struct PasswordCharFromInnerError(pub u8);
+
+/// `Password` の操作の結果、文字数が範囲外になる。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("password length {actual} is out of range {min}..={max}")]
+pub struct PasswordLengthError {
+    /// 実際の(操作後の)文字数。
+    pub actual: usize,
+    /// 最小文字数 (`Password::MIN_LEN`)。
+    pub min: usize,
+    /// 最大文字数 (`Password::MAX_LEN`)。
+    pub max: usize,
+}
+
+impl PasswordLengthError {
+    /// 実際の文字数が `actual` であるエラーを返す。
+    pub(crate) const fn new(actual: usize) -> Self {
+        Self {
+            actual,
+            min: Password::MIN_LEN,
+            max: Password::MAX_LEN,
+        }
+    }
+}
+
+/// `Password::parse_spanned()` で発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum PasswordSpannedParseError {
+    /// パスワードの文字数が正しくない。`char_len`, `byte_len` は入力全体の文字数とバイト数。
+    #[error(
+        "password must contain {}..={} chars, got {char_len} chars ({byte_len} bytes)",
+        Password::MIN_LEN,
+        Password::MAX_LEN
+    )]
+    InvalidLength { char_len: usize, byte_len: usize },
+
+    /// パスワードに無効な文字が含まれている。
+    ///
+    /// `char_pos` は文字位置、`byte_offset` と `byte_len` はその文字の UTF-8 でのバイト位置とバイト数。
+    #[error(
+        "password contains an invalid character '{ch}' at position {char_pos} (byte offset {byte_offset}){}",
+        display_suggestion(*ch)
+    )]
+    InvalidChar {
+        ch: char,
+        char_pos: usize,
+        byte_offset: usize,
+        byte_len: usize,
+    },
+}
+
+/// 内部値のスライスから `Password` を作る際に発生しうるエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum PasswordFromBytesError {
+    /// バイト数が正しくない。
+    #[error(
+        "password must contain {}..={} bytes, got {len}",
+        Password::MIN_LEN,
+        Password::MAX_LEN
+    )]
+    InvalidLength { len: usize },
+
+    /// 値が内部値の範囲(0x00..=0x3F)外。
+    #[error("byte 0x{value:02X} at index {index} is out of range")]
+    OutOfRange { index: usize, value: u8 },
 }
 
 #[cfg(test)]
@@ -527,6 +1395,168 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_password_char_conversion() {
+        for pc in PasswordChar::all() {
+            assert_eq!(PasswordChar::from_char(pc.to_char()), Some(pc));
+        }
+
+        // 全ての文字が異なる。
+        let chars: std::collections::BTreeSet<_> =
+            PasswordChar::all().map(PasswordChar::to_char).into();
+        assert_eq!(chars.len(), 0x40);
+
+        // 範囲外の文字や、範囲内でもパスワードに使われない文字は受け付けない。
+        for c in [
+            '\0',
+            'a',
+            '?',
+            'ぁ',
+            'ぃ',
+            'っ',
+            'ゃ',
+            'ゎ',
+            'を',
+            'ん',
+            'ゔ',
+            'カ',
+            '\u{10FFFF}',
+        ] {
+            assert_eq!(PasswordChar::from_char(c), None, "{c:?}");
+        }
+        // const 文脈でも使える。
+        const KA: Option<PasswordChar> = PasswordChar::from_char('か');
+        const KA_CHAR: char = PasswordChar::Ka.to_char();
+        assert_eq!(KA, Some(PasswordChar::Ka));
+        assert_eq!(KA_CHAR, 'か');
+
+        let n_valid = ('\0'..='\u{FFFF}')
+            .filter(|&c| PasswordChar::from_char(c).is_some())
+            .count();
+        assert_eq!(n_valid, 0x40);
+    }
+
+    #[test]
+    fn test_password_char_all() {
+        assert!(PasswordChar::ALL
+            .iter()
+            .map(|pc| pc.to_inner())
+            .eq(0..=0x3F));
+        assert_eq!(PasswordChar::all(), PasswordChar::ALL);
+
+        let iter = PasswordChar::iter();
+        assert_eq!(iter.len(), 0x40);
+        assert!(iter.map(PasswordChar::to_inner).eq(0..=0x3F));
+        assert!(PasswordChar::iter()
+            .rev()
+            .map(PasswordChar::to_inner)
+            .eq((0..=0x3F).rev()));
+
+        let mut iter = PasswordChar::iter();
+        assert_eq!(iter.next(), Some(PasswordChar::A));
+        assert_eq!(iter.next_back(), Some(PasswordChar::Po));
+        assert_eq!(iter.len(), 0x3E);
+
+        // const 文脈でも使える。
+        const WA: PasswordChar = PasswordChar::ALL[0x2B];
+        assert_eq!(WA, PasswordChar::Wa);
+    }
+
+    #[test]
+    fn test_password_char_dakuten() {
+        use PasswordChar::*;
+
+        assert_eq!(Ga.base(), Ka);
+        assert_eq!(Zo.base(), So);
+        assert_eq!(Ba.base(), Ha);
+        assert_eq!(Pa.base(), Ha);
+        assert_eq!(Po.base(), Ho);
+        assert_eq!(Ta.base(), Ta);
+
+        assert_eq!(Ka.with_dakuten(), Some(Ga));
+        assert_eq!(Si.with_dakuten(), Some(Zi));
+        assert_eq!(Hu.with_dakuten(), Some(Bu));
+        assert_eq!(Pu.with_dakuten(), Some(Bu));
+        assert_eq!(Hu.with_handakuten(), Some(Pu));
+        assert_eq!(Bu.with_handakuten(), Some(Pu));
+        assert_eq!(Ta.with_dakuten(), None);
+        assert_eq!(U.with_dakuten(), None);
+        assert_eq!(Ka.with_handakuten(), None);
+
+        for pc in PasswordChar::iter() {
+            let base = pc.base();
+            assert_eq!(base.base(), base);
+            assert!(!base.is_dakuten() && !base.is_handakuten());
+            assert!(!(pc.is_dakuten() && pc.is_handakuten()));
+
+            if pc.is_dakuten() {
+                assert_eq!(base.with_dakuten(), Some(pc), "{pc:?}");
+            } else if pc.is_handakuten() {
+                assert_eq!(base.with_handakuten(), Some(pc), "{pc:?}");
+            } else {
+                assert_eq!(base, pc);
+            }
+            // 濁音/半濁音の清音への変換は Unicode の並びとも一致する。
+            if let Some(voiced) = pc.with_dakuten() {
+                assert_eq!(voiced.to_char() as u32, base.to_char() as u32 + 1);
+                assert!(voiced.is_dakuten());
+            }
+            if let Some(voiced) = pc.with_handakuten() {
+                assert_eq!(voiced.to_char() as u32, base.to_char() as u32 + 2);
+                assert!(voiced.is_handakuten());
+            }
+        }
+
+        let n_dakuten = PasswordChar::iter().filter(|pc| pc.is_dakuten()).count();
+        let n_handakuten = PasswordChar::iter().filter(|pc| pc.is_handakuten()).count();
+        assert_eq!((n_dakuten, n_handakuten), (15, 5));
+        let n_base = PasswordChar::iter().filter(|pc| pc.base() == *pc).count();
+        assert_eq!(n_base, 0x40 - 20);
+    }
+
+    #[test]
+    fn test_password_char_try_from() {
+        for pc in PasswordChar::all() {
+            assert_eq!(PasswordChar::try_from(pc.to_char()), Ok(pc));
+            assert_eq!(char::from(pc), pc.to_char());
+            assert_eq!(PasswordChar::try_from(pc.to_inner()), Ok(pc));
+            assert_eq!(u8::from(pc), pc.to_inner());
+        }
+        for c in ('\0'..='\u{FFFF}').step_by(7) {
+            assert_eq!(
+                PasswordChar::try_from(c).ok(),
+                PasswordChar::from_char(c),
+                "{c:?}"
+            );
+        }
+        for inner in 0..=u8::MAX {
+            assert_eq!(
+                PasswordChar::try_from(inner).ok(),
+                PasswordChar::from_inner(inner)
+            );
+        }
+
+        assert_eq!(
+            PasswordChar::try_from('ん'),
+            Err(PasswordCharFromCharError('ん'))
+        );
+        assert_eq!(
+            PasswordChar::try_from(0x40),
+            Err(PasswordCharFromInnerError(0x40))
+        );
+
+        let chars: Result<Vec<_>, _> = "おにのばか".chars().map(PasswordChar::try_from).collect();
+        assert_equal(chars.unwrap(), Password::parse("おにのばか").unwrap());
+        let chars: Result<Vec<_>, _> = "おにのばかん".chars().map(PasswordChar::try_from).collect();
+        assert_eq!(chars, Err(PasswordCharFromCharError('ん')));
+        let s: String = Password::parse("おにのばか")
+            .unwrap()
+            .into_iter()
+            .map(char::from)
+            .collect();
+        assert_eq!(s, "おにのばか");
+    }
+
     #[test]
     fn test_password_new() {
         assert_equal(
@@ -545,6 +1575,102 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "forbid-unsafe")]
+    #[should_panic(expected = "password length out of range")]
+    fn test_password_new_unchecked_out_of_range() {
+        Password::new_unchecked(&[]);
+    }
+
+    #[test]
+    #[cfg(feature = "forbid-unsafe")]
+    #[should_panic(expected = "invalid password char")]
+    fn test_password_char_from_inner_unchecked_out_of_range() {
+        PasswordChar::from_inner_unchecked(0x40);
+    }
+
+    #[test]
+    fn test_password_new_checked() {
+        assert_eq!(
+            Password::new_checked(&[PasswordChar::A]),
+            Ok(Password::parse("あ").unwrap())
+        );
+        assert_eq!(
+            Password::new_checked(&[PasswordChar::Po; Password::MAX_LEN]).map(|p| p.len()),
+            Ok(Password::MAX_LEN)
+        );
+
+        let e = Password::new_checked(&[]).unwrap_err();
+        assert_eq!(
+            e,
+            PasswordLengthError {
+                actual: 0,
+                min: Password::MIN_LEN,
+                max: Password::MAX_LEN
+            }
+        );
+        assert_eq!(e.to_string(), "password length 0 is out of range 1..=38");
+
+        let e = Password::new_checked(&[PasswordChar::A; Password::MAX_LEN + 1]).unwrap_err();
+        assert_eq!(
+            e,
+            PasswordLengthError {
+                actual: Password::MAX_LEN + 1,
+                min: Password::MIN_LEN,
+                max: Password::MAX_LEN
+            }
+        );
+        assert_eq!(e.to_string(), "password length 39 is out of range 1..=38");
+    }
+
+    #[test]
+    fn test_password_from_iter_checked() {
+        assert_eq!(
+            Password::from_iter_checked([]),
+            Err(PasswordParseError::InvalidLength { len: 0 })
+        );
+        assert_eq!(
+            Password::from_iter_checked([PasswordChar::Hu]),
+            Ok(Password::parse("ふ").unwrap())
+        );
+        assert_eq!(
+            Password::from_iter_checked(std::iter::repeat_n(PasswordChar::Po, Password::MAX_LEN)),
+            Ok(Password::new(&[PasswordChar::Po; Password::MAX_LEN]).unwrap())
+        );
+
+        // 最大文字数 + 1 文字まで消費したらやめる。
+        let mut iter = PasswordChar::all().into_iter().cycle();
+        assert_eq!(
+            Password::from_iter_checked(iter.by_ref()),
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+        assert_eq!(
+            iter.next(),
+            PasswordChar::from_inner((Password::MAX_LEN + 1) as u8)
+        );
+    }
+
+    #[test]
+    fn test_password_builder() {
+        let mut builder = PasswordBuilder::new();
+        builder.push(PasswordChar::O);
+        builder.extend(Password::parse("にのばか").unwrap());
+        assert!(!builder.is_overflow());
+        assert_eq!(builder.build(), Password::parse("おにのばか"));
+
+        let mut builder = PasswordBuilder::new();
+        builder.extend([PasswordChar::A; Password::MAX_LEN + 1]);
+        assert!(builder.is_overflow());
+        assert_eq!(
+            builder.build(),
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+    }
+
     #[test]
     fn test_password_parse() {
         assert_equal(Password::parse("ふ").unwrap(), [PasswordChar::Hu]);
@@ -559,10 +1685,20 @@ mod tests {
             ],
         );
 
-        assert_eq!(Password::parse(""), Err(PasswordParseError::InvalidLength));
+        assert_eq!(
+            Password::parse(""),
+            Err(PasswordParseError::InvalidLength { len: 0 })
+        );
         assert_eq!(
             Password::parse(&"あ".repeat(Password::MAX_LEN + 1)),
-            Err(PasswordParseError::InvalidLength)
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+        // 最大文字数を超えた後の文字は検査せず、文字数のみ数える。
+        assert_eq!(
+            Password::parse(&format!("{}?", "あ".repeat(50))),
+            Err(PasswordParseError::InvalidLength { len: 51 })
         );
         assert_eq!(
             Password::parse("あいう?えお"),
@@ -570,6 +1706,527 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_password_char_suggest() {
+        use PasswordChar::*;
+
+        // カタカナ。
+        assert_eq!(PasswordChar::suggest('カ'), [Ka]);
+        assert_eq!(PasswordChar::suggest('パ'), [Pa]);
+        assert_eq!(PasswordChar::suggest('ッ'), [Tu]);
+        assert_eq!(PasswordChar::suggest('ヶ'), [Ke]);
+        assert_eq!(PasswordChar::suggest('ヴ'), [Bu, U]);
+
+        assert_eq!(PasswordChar::suggest('ぁ'), [A]);
+        assert_eq!(PasswordChar::suggest('ゔ'), [Bu, U]);
+        assert_eq!(PasswordChar::suggest('を'), [O, Wa]);
+        assert_eq!(PasswordChar::suggest('力'), [Ka]);
+        assert_eq!(PasswordChar::suggest('ば'), [Ba]);
+
+        // 候補なし。
+        assert_eq!(PasswordChar::suggest('ん'), []);
+        assert_eq!(PasswordChar::suggest('ン'), []);
+        assert_eq!(PasswordChar::suggest('x'), []);
+        assert_eq!(PasswordChar::suggest('\u{309A}'), []);
+
+        // 表の文字はいずれも文字集合にない。
+        for &(c, pcs) in SUGGEST_TABLE {
+            assert_eq!(PasswordChar::from_char(c), None, "{c}");
+            assert!(!pcs.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_password_parse_error_display() {
+        assert_eq!(
+            Password::parse("おにのバか").unwrap_err().to_string(),
+            "password contains an invalid character 'バ' at position 3 (did you mean 'ば'?)"
+        );
+        assert_eq!(
+            Password::parse("をにのばか").unwrap_err().to_string(),
+            "password contains an invalid character 'を' at position 0 (did you mean 'お'?)"
+        );
+        assert_eq!(
+            Password::parse("おにのばx").unwrap_err().to_string(),
+            "password contains an invalid character 'x' at position 4"
+        );
+    }
+
+    #[test]
+    fn test_password_parse_all_errors() {
+        for s in [
+            "ふ",
+            "おにのばか",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            assert_eq!(Password::parse_all_errors(s), Password::parse(s));
+        }
+
+        let e = Password::parse_all_errors("おにxのばかyあzい").unwrap_err();
+        assert_eq!(
+            e,
+            PasswordParseError::InvalidChars(vec![(2, 'x'), (6, 'y'), (8, 'z')])
+        );
+        assert_eq!(
+            e.to_string(),
+            "password contains invalid characters: 'x' at position 2, 'y' at position 6, 'z' at position 8"
+        );
+
+        // 表示する文字数には上限がある。
+        let e = Password::parse_all_errors("abcdefgh").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "password contains invalid characters: 'a' at position 0, 'b' at position 1, \
+             'c' at position 2, 'd' at position 3, 'e' at position 4 and 3 more"
+        );
+
+        // 無効な文字があれば文字数の検査より優先する。
+        assert_eq!(
+            Password::parse_all_errors(&format!("{}?", "あ".repeat(Password::MAX_LEN))),
+            Err(PasswordParseError::InvalidChars(vec![(
+                Password::MAX_LEN,
+                '?'
+            )]))
+        );
+        assert_eq!(
+            Password::parse_all_errors(""),
+            Err(PasswordParseError::InvalidLength { len: 0 })
+        );
+        assert_eq!(
+            Password::parse_all_errors(&"あ".repeat(Password::MAX_LEN + 1)),
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_password_parse_spanned() {
+        assert_eq!(
+            Password::parse_spanned("おにのばか"),
+            Ok(Password::parse("おにのばか").unwrap())
+        );
+
+        // 位置は無効な文字をちょうど指す。
+        for s in ["あいう?えお", "あいうカえお", "あいう😀えお"] {
+            let Err(PasswordSpannedParseError::InvalidChar {
+                ch,
+                char_pos,
+                byte_offset,
+                byte_len,
+            }) = Password::parse_spanned(s)
+            else {
+                panic!("{s}");
+            };
+            assert_eq!(char_pos, 3);
+            assert_eq!(byte_offset, "あいう".len());
+            assert_eq!(&s[byte_offset..byte_offset + byte_len], ch.to_string());
+        }
+        assert_eq!(
+            Password::parse_spanned("?"),
+            Err(PasswordSpannedParseError::InvalidChar {
+                ch: '?',
+                char_pos: 0,
+                byte_offset: 0,
+                byte_len: 1
+            })
+        );
+
+        assert_eq!(
+            Password::parse_spanned(""),
+            Err(PasswordSpannedParseError::InvalidLength {
+                char_len: 0,
+                byte_len: 0
+            })
+        );
+        assert_eq!(
+            Password::parse_spanned(&"あ".repeat(Password::MAX_LEN + 1)),
+            Err(PasswordSpannedParseError::InvalidLength {
+                char_len: Password::MAX_LEN + 1,
+                byte_len: 3 * (Password::MAX_LEN + 1)
+            })
+        );
+        assert_eq!(
+            Password::parse_spanned("ア").unwrap_err().to_string(),
+            "password contains an invalid character 'ア' at position 0 (byte offset 0) (did you mean 'あ'?)"
+        );
+    }
+
+    #[test]
+    fn test_password_parse_lenient() {
+        let expected =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        for s in [
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+            "おしぼひま きびねとしぼひ まきびねと ひげがけちめい かほがすざ",
+            "おしぼひま\nきびねとしぼひ\r\nまきびねと\n\nひげがけちめい\nかほがすざ\n",
+            "  おしぼひま　きびねとしぼひ　まきびねと　ひげがけちめい　かほがすざ \t",
+            "おしぼひま・きびねとしぼひ・まきびねと・ひげがけちめい・かほがすざ",
+            "おしぼひま-きびねとしぼひ-まきびねと-ひげがけちめい-かほがすざ。",
+        ] {
+            assert_eq!(Password::parse_lenient(s).as_ref(), Ok(&expected), "{s:?}");
+        }
+        let pretty = expected.display_pretty().to_string();
+        assert_eq!(Password::parse_lenient(&pretty), Ok(expected));
+
+        // エラーの位置は元の文字列におけるもの。
+        assert_eq!(
+            Password::parse_lenient("おにの\n ば?か"),
+            Err(PasswordParseError::InvalidChar { pos: 6, ch: '?' })
+        );
+        assert_eq!(
+            Password::parse_lenient(" \n　・"),
+            Err(PasswordParseError::InvalidLength { len: 0 })
+        );
+        assert_eq!(
+            Password::parse_lenient(&"あ ".repeat(Password::MAX_LEN + 1)),
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+        assert_eq!(
+            Password::parse_lenient(&"あ ".repeat(Password::MAX_LEN)),
+            Ok(Password::new(&[PasswordChar::A; Password::MAX_LEN]).unwrap())
+        );
+    }
+
+    /// 濁音/半濁音を NFD で分解する。
+    fn decompose(s: &str) -> String {
+        s.chars()
+            .flat_map(|c| {
+                let pc = PasswordChar::from_char(c).unwrap().to_inner();
+                match pc {
+                    // が..=ぼ
+                    0x2C..=0x3A => {
+                        vec![char::from_u32(c as u32 - 1).unwrap(), '\u{3099}']
+                    }
+                    // ぱ..=ぽ
+                    0x3B..=0x3F => {
+                        vec![char::from_u32(c as u32 - 2).unwrap(), '\u{309A}']
+                    }
+                    _ => vec![c],
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_password_parse_lenient_nfd() {
+        assert_eq!(
+            Password::parse_lenient("おにのは\u{3099}か"),
+            Password::parse("おにのばか")
+        );
+        assert_eq!(
+            Password::parse_lenient("あは\u{3099}ほ\u{309A}"),
+            Password::parse("あばぽ")
+        );
+        assert_eq!(decompose("あばぽ"), "あは\u{3099}ほ\u{309A}");
+
+        // 全ての濁音/半濁音。
+        let voiced: String = PasswordChar::all()[PasswordChar::Ga as usize..]
+            .iter()
+            .map(|pc| pc.to_char())
+            .collect();
+        assert_eq!(
+            Password::parse_lenient(&decompose(&voiced)),
+            Password::parse(&voiced)
+        );
+
+        for s in [
+            "ふ",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+            "すべてのきよくがききたいな",
+            "すべてのてきがみたいな",
+        ] {
+            let nfd = decompose(s);
+            let nfc = Password::parse(s).unwrap();
+            let password = Password::parse_lenient(&nfd).unwrap();
+            assert_eq!(password, nfc);
+            assert_eq!(
+                SerializedBytes::from_password(&password).to_savedata(),
+                SerializedBytes::from_password(&nfc).to_savedata()
+            );
+        }
+
+        // 空白区切りと併用できる。
+        assert_eq!(
+            Password::parse_lenient("おにの は\u{3099}か\n"),
+            Password::parse("おにのばか")
+        );
+
+        // 単独の結合文字や、合成できない文字に続く結合文字はその位置でエラーになる。
+        for (s, pos, ch) in [
+            ("\u{3099}あ", 0, '\u{3099}'),
+            ("あ\u{3099}", 1, '\u{3099}'),
+            ("おに\u{309A}", 2, '\u{309A}'),
+            ("か\u{309A}", 1, '\u{309A}'),
+            ("ば\u{3099}", 1, '\u{3099}'),
+            ("と\u{3099}", 1, '\u{3099}'),
+            ("は \u{3099}", 2, '\u{3099}'),
+            ("は\u{3099}\u{3099}", 2, '\u{3099}'),
+        ] {
+            assert_eq!(
+                Password::parse_lenient(s),
+                Err(PasswordParseError::InvalidChar { pos, ch }),
+                "{s:?}"
+            );
+        }
+        // 無効な文字が先にあればそちらが報告される。
+        assert_eq!(
+            Password::parse_lenient("?あ\u{3099}"),
+            Err(PasswordParseError::InvalidChar { pos: 0, ch: '?' })
+        );
+
+        // `parse()` は結合文字を受け付けない。
+        assert_eq!(
+            Password::parse("は\u{3099}"),
+            Err(PasswordParseError::InvalidChar {
+                pos: 1,
+                ch: '\u{3099}'
+            })
+        );
+    }
+
+    #[test]
+    fn test_password_push_pop() {
+        let mut password = Password::parse("あ").unwrap();
+        assert_eq!(password.pop(), Err(PasswordLengthError::new(0)));
+        assert_eq!(password.try_push(PasswordChar::Ba), Ok(()));
+        assert_eq!(password, Password::parse("あば").unwrap());
+        assert_eq!(password.pop(), Ok(PasswordChar::Ba));
+        assert_eq!(password, Password::parse("あ").unwrap());
+
+        let mut password = Password::new(&[PasswordChar::A; Password::MAX_LEN]).unwrap();
+        assert_eq!(
+            password.try_push(PasswordChar::Ba),
+            Err(PasswordLengthError::new(Password::MAX_LEN + 1))
+        );
+        assert_eq!(password.len(), Password::MAX_LEN);
+        assert_eq!(password.pop(), Ok(PasswordChar::A));
+        assert_eq!(password.len(), Password::MAX_LEN - 1);
+    }
+
+    #[test]
+    fn test_password_insert_remove() {
+        let mut password = Password::parse("おのか").unwrap();
+        password.insert(1, PasswordChar::Ni).unwrap();
+        password.insert(3, PasswordChar::Ba).unwrap();
+        assert_eq!(password, Password::parse("おにのばか").unwrap());
+        password.insert(5, PasswordChar::Po).unwrap();
+        password.insert(0, PasswordChar::A).unwrap();
+        assert_eq!(password, Password::parse("あおにのばかぽ").unwrap());
+
+        assert_eq!(password.remove(0), Ok(PasswordChar::A));
+        assert_eq!(password.remove(5), Ok(PasswordChar::Po));
+        assert_eq!(password.remove(2), Ok(PasswordChar::No));
+        assert_eq!(password, Password::parse("おにばか").unwrap());
+
+        let mut password = Password::parse("あ").unwrap();
+        assert_eq!(password.remove(0), Err(PasswordLengthError::new(0)));
+        assert_eq!(password, Password::parse("あ").unwrap());
+
+        let mut password = Password::new(&[PasswordChar::A; Password::MAX_LEN]).unwrap();
+        assert_eq!(
+            password.insert(0, PasswordChar::Ba),
+            Err(PasswordLengthError::new(Password::MAX_LEN + 1))
+        );
+        assert_eq!(
+            password,
+            Password::new(&[PasswordChar::A; Password::MAX_LEN]).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_password_insert_out_of_bounds() {
+        let _ = Password::parse("あ").unwrap().insert(2, PasswordChar::A);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_password_remove_out_of_bounds() {
+        let _ = Password::parse("あい").unwrap().remove(2);
+    }
+
+    #[test]
+    fn test_password_truncate() {
+        let mut password = Password::parse("おにのばか").unwrap();
+        assert_eq!(password.truncate(10), Ok(()));
+        assert_eq!(password.len(), 5);
+        assert_eq!(password.truncate(3), Ok(()));
+        assert_eq!(password, Password::parse("おにの").unwrap());
+        assert_eq!(password.truncate(0), Err(PasswordLengthError::new(0)));
+        assert_eq!(password.truncate(1), Ok(()));
+        assert_eq!(password, Password::parse("お").unwrap());
+
+        let mut password = Password::new(&[PasswordChar::A; Password::MAX_LEN]).unwrap();
+        assert_eq!(password.truncate(Password::MAX_LEN), Ok(()));
+        assert_eq!(password.len(), Password::MAX_LEN);
+    }
+
+    #[test]
+    fn test_password_find_seq() {
+        let seq = |s: &str| PasswordChar::seq_from_str(s).unwrap();
+        let password = Password::parse("おにのばかばかばか").unwrap();
+
+        assert_eq!(password.find_seq(&seq("ばか")), Some(3));
+        assert_eq!(password.find_seq(&seq("かば")), Some(4));
+        // 重なる出現。
+        assert_eq!(password.find_seq(&seq("ばかばか")), Some(3));
+        assert!(password.contains_seq(&seq("かばかばか")));
+        assert!(!password.contains_seq(&seq("ばばか")));
+
+        assert_eq!(password.find_seq(&[]), Some(0));
+        assert!(password.contains_seq(&[]));
+        // 検索対象より長い。
+        assert_eq!(password.find_seq(&seq("おにのばかばかばかば")), None);
+        assert_eq!(password.find_seq(&seq("おにのばかばかばか")), Some(0));
+
+        assert!(password.starts_with(&seq("おにの")));
+        assert!(password.ends_with(&seq("ばか")));
+        assert!(!password.ends_with(&seq("おにのばかばかばかばか")));
+
+        assert_eq!(PasswordChar::seq_from_str(""), Ok(vec![]));
+        assert_eq!(
+            PasswordChar::seq_from_str("ばかん"),
+            Err(PasswordParseError::InvalidChar { pos: 2, ch: 'ん' })
+        );
+    }
+
+    #[test]
+    fn test_password_distance() {
+        let a = Password::parse("おにのばか").unwrap();
+        let b = Password::parse("おにのばき").unwrap();
+        let c = Password::parse("あいうえお").unwrap();
+        let d = Password::parse("おにの").unwrap();
+
+        // 同一。
+        assert_eq!(a.hamming_distance(&a), Some(0));
+        assert_eq!(a.common_prefix_len(&a), 5);
+        assert_eq!(a.differs_at(&a).count(), 0);
+
+        assert_eq!(a.hamming_distance(&b), Some(1));
+        assert_eq!(a.common_prefix_len(&b), 4);
+        assert_equal(a.differs_at(&b), [4]);
+
+        // 全て異なる。
+        assert_eq!(a.hamming_distance(&c), Some(5));
+        assert_eq!(a.common_prefix_len(&c), 0);
+        assert_equal(a.differs_at(&c), 0..5);
+
+        // 長さが異なる。
+        assert_eq!(a.hamming_distance(&d), None);
+        assert_eq!(a.common_prefix_len(&d), 3);
+        assert_eq!(d.common_prefix_len(&a), 3);
+        assert_equal(a.differs_at(&d), [3, 4]);
+        assert_equal(d.differs_at(&a), [3, 4]);
+        assert_equal(c.differs_at(&d), 0..5);
+
+        // ひらがなではなく文字単位で比べる("ば" と "は" は別の文字)。
+        let e = Password::parse("おにのはか").unwrap();
+        assert_eq!(a.hamming_distance(&e), Some(1));
+        assert_equal(a.differs_at(&e), [3]);
+    }
+
+    #[test]
+    fn test_password_concat() {
+        let prefix = Password::parse("おにの").unwrap();
+        let suffix = Password::parse("ばか").unwrap();
+        assert_eq!(
+            prefix.concat(&suffix),
+            Ok(Password::parse("おにのばか").unwrap())
+        );
+        assert_eq!(
+            prefix.with_suffix(&[PasswordChar::Ba, PasswordChar::Ka]),
+            Ok(Password::parse("おにのばか").unwrap())
+        );
+        assert_eq!(prefix.with_suffix(&[]), Ok(prefix.clone()));
+
+        let half = Password::new(&[PasswordChar::A; Password::MAX_LEN / 2]).unwrap();
+        assert_eq!(
+            half.concat(&half),
+            Ok(Password::new(&[PasswordChar::A; Password::MAX_LEN]).unwrap())
+        );
+        let longer = half.with_suffix(&[PasswordChar::I]).unwrap();
+        assert_eq!(
+            half.concat(&longer),
+            Err(PasswordLengthError::new(Password::MAX_LEN + 1))
+        );
+        assert_eq!(
+            longer.with_suffix(&half),
+            Err(PasswordLengthError::new(Password::MAX_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn test_password_concat_roundtrip() {
+        use crate::savedata::Savedata;
+
+        // 有効なパスワードを任意の位置で分割して連結し直しても、同じセーブデータになる。
+        let savedata = Savedata {
+            xp: 1234,
+            purse: 5678,
+            ..Savedata::default()
+        };
+        let password = SerializedBytes::from_savedata(&savedata).to_password();
+        for i in 1..password.len() {
+            let prefix = Password::new(&password[..i]).unwrap();
+            let joined = prefix.with_suffix(&password[i..]).unwrap();
+            assert_eq!(joined, password);
+            assert_eq!(
+                SerializedBytes::from_password(&joined).to_savedata(),
+                Some(savedata.clone())
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_password_serde() {
+        let password = Password::parse("おにのばか").unwrap();
+        assert_eq!(serde_json::to_string(&password).unwrap(), r#""おにのばか""#);
+        assert_eq!(
+            serde_json::from_str::<Password>(r#""おにのばか""#).unwrap(),
+            password
+        );
+
+        let passwords = vec![password.clone(), Password::parse("ふ").unwrap()];
+        let json = serde_json::to_string(&passwords).unwrap();
+        assert_eq!(json, r#"["おにのばか","ふ"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<Password>>(&json).unwrap(),
+            passwords
+        );
+
+        let map = std::collections::BTreeMap::from([(password.clone(), 1)]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"おにのばか":1}"#);
+        assert_eq!(
+            serde_json::from_str::<std::collections::BTreeMap<Password, i32>>(&json).unwrap(),
+            map
+        );
+
+        // 文字列として書かれるので、自己記述的でない形式でも使える。
+        let bytes = bincode::serialize(&passwords).unwrap();
+        assert_eq!(
+            bincode::deserialize::<Vec<Password>>(&bytes).unwrap(),
+            passwords
+        );
+        assert_eq!(
+            bincode::serialize(&password).unwrap(),
+            bincode::serialize("おにのばか").unwrap()
+        );
+
+        let err = serde_json::from_str::<Password>(r#""あいう?えお""#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&PasswordParseError::InvalidChar { pos: 3, ch: '?' }.to_string()));
+        assert!(serde_json::from_str::<Password>(r#""""#).is_err());
+        assert!(serde_json::from_str::<Password>("5").is_err());
+        let bytes = bincode::serialize("おにの?").unwrap();
+        assert!(bincode::deserialize::<Password>(&bytes).is_err());
+    }
+
     #[test]
     fn test_password_display() {
         assert_eq!(
@@ -633,6 +2290,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_password_display_wrapped() {
+        let password = Password::new(&PasswordChar::ALL[..Password::MAX_LEN]).unwrap();
+        let wrapped = |width: usize| password.display_wrapped(width).to_string();
+
+        assert_eq!(
+            wrapped(5),
+            "あいうえお\nかきくけこ\nさしすせそ\nたちつてと\nなにぬねの\nはひふへほ\nまみむめも\nやゆよ"
+        );
+        assert_eq!(
+            wrapped(7),
+            "あいうえおかき\nくけこさしすせ\nそたちつてとな\nにぬねのはひふ\nへほまみむめも\nやゆよ"
+        );
+        assert_eq!(
+            wrapped(20),
+            "あいうえおかきくけこさしすせそたちつてと\nなにぬねのはひふへほまみむめもやゆよ"
+        );
+
+        let wrapped = |width: usize| password.display_wrapped(width).respect_groups().to_string();
+        // 5 桁に収まらない 7 文字の単位は分割する。
+        assert_eq!(
+            wrapped(5),
+            "あいうえお\nかきくけこ\nさし\nすせそたち\nつてとなに\nぬね\nのはひふへ\nほま\nみむめもや\nゆよ"
+        );
+        assert_eq!(
+            wrapped(7),
+            "あいうえお\nかきくけこさし\nすせそたち\nつてとなにぬね\nのはひふへほま\nみむめもやゆよ"
+        );
+        assert_eq!(
+            wrapped(20),
+            "あいうえお かきくけこさし すせそたち\nつてとなにぬね のはひふへほま\nみむめもやゆよ"
+        );
+
+        let wrapped = |width: usize| {
+            password
+                .display_wrapped(width)
+                .double_width()
+                .respect_groups()
+                .to_string()
+        };
+        assert_eq!(
+            wrapped(20),
+            "あいうえお\nかきくけこさし\nすせそたち\nつてとなにぬね\nのはひふへほま\nみむめもやゆよ"
+        );
+        assert_eq!(
+            wrapped(25),
+            "あいうえお かきくけこさし\nすせそたち つてとなにぬね\nのはひふへほま\nみむめもやゆよ"
+        );
+
+        // 幅より短ければ改行しない。
+        let password = Password::parse("おにのばか").unwrap();
+        assert_eq!(password.display_wrapped(5).to_string(), "おにのばか");
+        assert_eq!(
+            password.display_wrapped(20).respect_groups().to_string(),
+            "おにのばか"
+        );
+        // 1 文字も収まらない幅。
+        assert_eq!(
+            password.display_wrapped(1).double_width().to_string(),
+            "お\nに\nの\nば\nか"
+        );
+        assert_eq!(
+            password.display_wrapped(0).respect_groups().to_string(),
+            "お\nに\nの\nば\nか"
+        );
+    }
+
+    #[test]
+    fn test_password_display_grouped() {
+        let password = |len: usize| Password::new(&PasswordChar::ALL[..len]).unwrap();
+        let grouped =
+            |len: usize, groups: &[usize]| password(len).display_grouped(groups).to_string();
+
+        // 最初のグループより短い。
+        assert_eq!(grouped(3, &[5, 5]), "あいう");
+        // ちょうどグループを覆う。
+        assert_eq!(grouped(10, &[5, 5]), "あいうえお かきくけこ");
+        // グループより長い分は最後の 1 グループになる。
+        assert_eq!(grouped(13, &[5, 5]), "あいうえお かきくけこ さしす");
+        assert_eq!(grouped(7, &[5]), "あいうえお かき");
+        assert_eq!(grouped(7, &[]), "あいうえおかき");
+        assert_eq!(grouped(7, &[0, 2, 0, 3]), "あい うえお かき");
+
+        assert_eq!(
+            password(Password::MAX_LEN)
+                .display_grouped(&[7, 7, 7, 7, 7, 3])
+                .separated('/')
+                .to_string(),
+            "あいうえおかき/くけこさしすせ/そたちつてとな/にぬねのはひふ/へほまみむめも/やゆよ"
+        );
+        assert_eq!(
+            password(12)
+                .display_grouped(&[5; 8])
+                .separated('-')
+                .to_string(),
+            "あいうえお-かきくけこ-さし"
+        );
+
+        for len in Password::MIN_LEN..=Password::MAX_LEN {
+            let password = password(len);
+            assert_eq!(
+                password.display_grouped(PRETTY_CHUNK_LENS).to_string(),
+                password.display_pretty().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_password_to_array_string() {
+        let mut state: u32 = 1;
+        for len in Password::MIN_LEN..=Password::MAX_LEN {
+            for _ in 0..10 {
+                let password = Password::from_iter_checked((0..len).map(|_| {
+                    state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                    PasswordChar::from_inner(((state >> 16) & 0x3F) as u8).unwrap()
+                }))
+                .unwrap();
+
+                assert_eq!(
+                    password.to_array_string().as_str(),
+                    password.display().to_string()
+                );
+                assert_eq!(
+                    password.to_array_string_pretty().as_str(),
+                    password.display_pretty().to_string()
+                );
+            }
+        }
+
+        let password = Password::new(&[PasswordChar::Po; Password::MAX_LEN]).unwrap();
+        assert!(password.to_array_string().is_full());
+        assert!(password.to_array_string_pretty().is_full());
+    }
+
     #[test]
     fn test_password_display_hex() {
         assert_eq!(
@@ -641,6 +2432,195 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_password_display_hex_with() {
+        let password = Password::parse("あばぽかき").unwrap();
+        let hex = |options| password.display_hex_with(options).to_string();
+
+        assert_eq!(hex(HexDisplayOptions::default()), "00 36 3F 05 06");
+        assert_eq!(
+            hex(HexDisplayOptions::default()),
+            password.display_hex().to_string()
+        );
+        for len in Password::MIN_LEN..=Password::MAX_LEN {
+            let password = Password::new(&PasswordChar::ALL[..len]).unwrap();
+            let expected: Vec<_> = password
+                .iter()
+                .map(|pc| format!("{:02X}", pc.to_inner()))
+                .collect();
+            assert_eq!(password.display_hex().to_string(), expected.join(" "));
+        }
+
+        assert_eq!(
+            hex(HexDisplayOptions {
+                separator: "",
+                lowercase: true,
+                ..HexDisplayOptions::default()
+            }),
+            "00363f0506"
+        );
+        assert_eq!(
+            hex(HexDisplayOptions {
+                separator: ", ",
+                prefix: true,
+                ..HexDisplayOptions::default()
+            }),
+            "0x00, 0x36, 0x3F, 0x05, 0x06"
+        );
+        assert_eq!(
+            hex(HexDisplayOptions {
+                separator: ", ",
+                lowercase: true,
+                prefix: true,
+                width: Some(2),
+            }),
+            "0x00, 0x36,\n0x3f, 0x05,\n0x06"
+        );
+        assert_eq!(
+            hex(HexDisplayOptions {
+                width: Some(3),
+                ..HexDisplayOptions::default()
+            }),
+            "00 36 3F\n05 06"
+        );
+        assert_eq!(
+            hex(HexDisplayOptions {
+                width: Some(5),
+                ..HexDisplayOptions::default()
+            }),
+            "00 36 3F 05 06"
+        );
+        assert_eq!(
+            hex(HexDisplayOptions {
+                width: Some(0),
+                ..HexDisplayOptions::default()
+            }),
+            "00 36 3F 05 06"
+        );
+
+        // 折り返した出力も `parse_hex()` で読める。
+        let wrapped = hex(HexDisplayOptions {
+            width: Some(2),
+            lowercase: true,
+            ..HexDisplayOptions::default()
+        });
+        assert_eq!(Password::parse_hex(&wrapped), Ok(password.clone()));
+    }
+
+    #[test]
+    fn test_password_parse_hex() {
+        assert_eq!(
+            Password::parse_hex("00 36 3F"),
+            Ok(Password::parse("あばぽ").unwrap())
+        );
+        assert_eq!(
+            Password::parse_hex("  00  36\t3f\n"),
+            Ok(Password::parse("あばぽ").unwrap())
+        );
+        assert_eq!(
+            Password::parse_hex("1B\r\n"),
+            Ok(Password::parse("ふ").unwrap())
+        );
+
+        for s in [
+            "ふ",
+            "おにのばか",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            let password = Password::parse(s).unwrap();
+            let hex = password.display_hex().to_string();
+            assert_eq!(Password::parse_hex(&hex), Ok(password.clone()));
+            assert_eq!(Password::parse_hex(&format!("{hex}\n")), Ok(password));
+        }
+        let password = Password::new(&PasswordChar::all()[..Password::MAX_LEN]).unwrap();
+        assert_eq!(
+            Password::parse_hex(&password.display_hex().to_string()),
+            Ok(password)
+        );
+    }
+
+    #[test]
+    fn test_password_parse_hex_error() {
+        for s in ["", " \n", &"00 ".repeat(Password::MAX_LEN + 1)] {
+            assert_eq!(
+                Password::parse_hex(s),
+                Err(PasswordHexParseError::InvalidLength),
+                "{s:?}"
+            );
+        }
+        for (s, index) in [
+            ("0", 0),
+            ("00 003", 1),
+            ("00 3G", 1),
+            ("+1", 0),
+            ("00,01", 0),
+            ("00 あ", 1),
+        ] {
+            assert_eq!(
+                Password::parse_hex(s),
+                Err(PasswordHexParseError::InvalidToken { index }),
+                "{s:?}"
+            );
+        }
+        assert_eq!(
+            Password::parse_hex("00 3F 40"),
+            Err(PasswordHexParseError::OutOfRange {
+                index: 2,
+                value: 0x40
+            })
+        );
+        assert_eq!(
+            Password::parse_hex("FF").unwrap_err().to_string(),
+            "byte 0xFF at index 0 is out of range"
+        );
+    }
+
+    #[test]
+    fn test_password_from_inner_bytes() {
+        assert_eq!(
+            Password::from_inner_bytes(&[0x00, 0x36, 0x3F]),
+            Ok(Password::parse("あばぽ").unwrap())
+        );
+
+        for s in [
+            "ふ",
+            "おにのばか",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            let password = Password::parse(s).unwrap();
+            let bytes = password.to_inner_bytes();
+            assert_eq!(Password::from_inner_bytes(&bytes), Ok(password.clone()));
+
+            let hex: Vec<_> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+            assert_eq!(hex.join(" "), password.display_hex().to_string());
+        }
+
+        assert_eq!(
+            Password::from_inner_bytes(&[]),
+            Err(PasswordFromBytesError::InvalidLength { len: 0 })
+        );
+        assert_eq!(
+            Password::from_inner_bytes(&[0; Password::MAX_LEN + 1]),
+            Err(PasswordFromBytesError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+        assert_eq!(
+            Password::from_inner_bytes(&[0x00, 0x40]),
+            Err(PasswordFromBytesError::OutOfRange {
+                index: 1,
+                value: 0x40
+            })
+        );
+        assert_eq!(
+            Password::from_inner_bytes(&[0x3F, 0x00, 0xFF, 0x40]),
+            Err(PasswordFromBytesError::OutOfRange {
+                index: 2,
+                value: 0xFF
+            })
+        );
+    }
+
     #[test]
     fn test_password_is_invalid_second_char() {
         fn naive(prefix: [PasswordChar; 2]) -> bool {
@@ -675,4 +2655,90 @@ mod tests {
             .unwrap()
             .is_special());
     }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_password_char_arbitrary() {
+        use arbitrary::{Arbitrary as _, Unstructured};
+
+        // 全ての文字が現れる。
+        let buf: Vec<u8> = (0..=u8::MAX).collect();
+        let mut u = Unstructured::new(&buf);
+        let mut seen = [false; 0x40];
+        while !u.is_empty() {
+            seen[PasswordChar::arbitrary(&mut u).unwrap() as usize] = true;
+        }
+        assert!(seen.iter().all(|&b| b));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    proptest::proptest! {
+        #[test]
+        fn test_password_arbitrary(buf in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..64)) {
+            use arbitrary::{Arbitrary as _, Unstructured};
+
+            let password = Password::arbitrary(&mut Unstructured::new(&buf)).unwrap();
+            proptest::prop_assert!((Password::MIN_LEN..=Password::MAX_LEN).contains(&password.len()));
+
+            // 残りのバイト数が文字数になる。
+            let password = Password::arbitrary_take_rest(Unstructured::new(&buf)).unwrap();
+            proptest::prop_assert_eq!(
+                password.len(),
+                buf.len().clamp(Password::MIN_LEN, Password::MAX_LEN)
+            );
+        }
+    }
+
+    #[test]
+    fn test_password_is_loadable() {
+        assert!(Password::parse("ふ").unwrap().is_loadable());
+        assert!(!Password::parse("あ").unwrap().is_loadable());
+
+        // 特殊パスワードの先頭部分には、チェックサムが一致するものはない。
+        for special in [Password::SPECIAL_AUDIO, Password::SPECIAL_ENEMY] {
+            for len in 1..=special.len() {
+                let password = Password::new(&special[..len]).unwrap();
+                assert!(!password.is_valid(), "{password:?}");
+                assert!(!password.is_loadable(), "{password:?}");
+            }
+        }
+
+        for password in Password::valid_passwords(3) {
+            assert!(password.is_loadable());
+        }
+    }
+
+    #[test]
+    fn test_password_special_kind() {
+        let kind = |s: &str| Password::parse(s).unwrap().special_kind();
+
+        // 共通の先頭部分は Audio とする。
+        assert_eq!(kind("す"), Some(SpecialPassword::Audio));
+        assert_eq!(kind("すべての"), Some(SpecialPassword::Audio));
+        assert!(Password::parse("すべての").unwrap().is_special_enemy());
+
+        assert_eq!(kind("すべてのき"), Some(SpecialPassword::Audio));
+        assert_eq!(
+            kind("すべてのきよくがききたいな"),
+            Some(SpecialPassword::Audio)
+        );
+        assert_eq!(kind("すべてのて"), Some(SpecialPassword::Enemy));
+        assert_eq!(kind("すべてのてき"), Some(SpecialPassword::Enemy));
+        assert_eq!(kind("すべてのてきがみたいな"), Some(SpecialPassword::Enemy));
+
+        assert_eq!(kind("あ"), None);
+        assert_eq!(kind("すべてのきよくがききたいなあ"), None);
+        assert_eq!(kind("すべてのてきがみたいなあ"), None);
+
+        for s in [
+            "す",
+            "すべてのき",
+            "すべてのてき",
+            "あ",
+            "すべてのてきがみたいなあ",
+        ] {
+            let password = Password::parse(s).unwrap();
+            assert_eq!(password.special_kind().is_some(), password.is_special());
+        }
+    }
 }
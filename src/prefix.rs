@@ -0,0 +1,600 @@
+use std::sync::OnceLock;
+
+use crate::password::{Password, PasswordChar, PasswordInner};
+use crate::savedata::Savedata;
+use crate::serialized::SerializedBytes;
+
+/// パスワードの先頭部分(prefix)から決まる、チェックサムに関する状態。
+///
+/// 1 文字ずつ `push()` できるので、先頭から文字を決めていく深さ優先探索の枝刈りに使える。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PrefixState {
+    len: usize,
+    /// 直前の文字の内部値(先頭では 0x1F)。
+    prev: u8,
+    /// 格納された加算チェックサム(デコード後の先頭バイト)。
+    embed_add: u8,
+    /// 格納された XOR チェックサム(デコード後の 2 バイト目)。
+    embed_xor: u8,
+    /// デコード後の 3 バイト目以降の和(mod 64)。
+    sum_add: u8,
+    /// デコード後の 3 バイト目以降の XOR。
+    sum_xor: u8,
+}
+
+impl Password {
+    /// パスワードの先頭部分 `prefix` から `PrefixState` を作る。
+    ///
+    /// # Panics
+    ///
+    /// `prefix.len() > Password::MAX_LEN` ならパニックする。
+    pub fn prefix_state(prefix: &[PasswordChar]) -> PrefixState {
+        assert!(
+            prefix.len() <= Self::MAX_LEN,
+            "prefix length {} is out of range",
+            prefix.len()
+        );
+
+        let mut state = PrefixState::new();
+        for &pc in prefix {
+            state.push(pc);
+        }
+        state
+    }
+
+    /// `prefix` の後に文字を追加して得られる、長さ `target_len` の有効なパスワードを全て返す。
+    /// 結果は辞書順(内部値順)に並ぶ。
+    ///
+    /// 最後の 1 文字はチェックサムから直接求め、それ以外の追加文字は `PrefixState` で枝刈りしつつ列挙する。
+    /// よって追加文字数を m とすると、結果はたかだか 64^(m-1) 個で、計算量もそれに比例する。
+    ///
+    /// `target_len` が `prefix.len()` 未満、または `Password::MIN_LEN..=Password::MAX_LEN` の範囲外なら空を返す。
+    pub fn complete(prefix: &[PasswordChar], target_len: usize) -> Vec<Password> {
+        if prefix.len() > target_len || !matches!(target_len, Self::MIN_LEN..=Self::MAX_LEN) {
+            return vec![];
+        }
+
+        let mut res = vec![];
+        let mut buf: PasswordInner = prefix.iter().copied().collect();
+        complete_dfs(&mut buf, Self::prefix_state(prefix), target_len, &mut res);
+        res
+    }
+
+    /// 長さ `len` の有効なパスワードを辞書順(内部値順)に全て列挙するイテレータを返す。
+    ///
+    /// `complete()` と同様に枝刈りしつつ遅延評価で列挙する。長さ 3 以上の有効なパスワードは 64^(`len`-2) 個あるので、
+    /// 全て列挙できるのは長さ 5 (約 1677 万個)程度まで。
+    ///
+    /// `len` が `Password::MIN_LEN..=Password::MAX_LEN` の範囲外なら何も返さない。
+    pub fn valid_passwords(len: usize) -> ValidPasswords {
+        ValidPasswords::new(len)
+    }
+
+    /// 長さ `max_len` 以下の有効なパスワードを、長さの昇順、同じ長さの中では辞書順(内部値順)に全て返す。
+    ///
+    /// `valid_passwords()` で長さごとに列挙する。長さ 3 以上の有効なパスワードは 64^(長さ-2) 個あるので、
+    /// 現実的なのは `max_len` が 3 程度まで。
+    pub fn all_valid_short(max_len: usize) -> Vec<Password> {
+        (Self::MIN_LEN..=max_len.min(Self::MAX_LEN))
+            .flat_map(Self::valid_passwords)
+            .collect()
+    }
+}
+
+/// 長さ 2 以下の有効なパスワードと、それをデコードして `normalize()` したゲーム状態の表を返す。
+///
+/// 長さの昇順、同じ長さの中では辞書順に並ぶ。初回の呼び出し時に `Password::all_valid_short()` で作る。
+pub fn valid_short_table() -> &'static [(Password, Savedata)] {
+    static TABLE: OnceLock<Vec<(Password, Savedata)>> = OnceLock::new();
+
+    TABLE.get_or_init(|| {
+        Password::all_valid_short(2)
+            .into_iter()
+            .map(|password| {
+                let savedata = SerializedBytes::from_password(&password)
+                    .to_savedata()
+                    .unwrap()
+                    .normalize();
+                (password, savedata)
+            })
+            .collect()
+    })
+}
+
+fn complete_dfs(
+    buf: &mut PasswordInner,
+    state: PrefixState,
+    target_len: usize,
+    res: &mut Vec<Password>,
+) {
+    if !state.can_be_valid_with_any_suffix(target_len) {
+        return;
+    }
+
+    match target_len - buf.len() {
+        0 => res.push(Password::new(buf).unwrap()),
+        1 => {
+            let pc = state.valid_last_char().unwrap();
+            buf.push(pc);
+            res.push(Password::new(buf).unwrap());
+            buf.pop();
+        }
+        _ => {
+            for pc in PasswordChar::iter() {
+                let mut next = state;
+                next.push(pc);
+                buf.push(pc);
+                complete_dfs(buf, next, target_len, res);
+                buf.pop();
+            }
+        }
+    }
+}
+
+/// `Password::valid_passwords()` が返すイテレータ。
+#[derive(Clone, Debug)]
+pub struct ValidPasswords {
+    len: usize,
+    buf: PasswordInner,
+    /// `buf[..i]` に対する状態と、位置 `i` で次に試す文字の内部値。
+    stack: Vec<(PrefixState, u8)>,
+}
+
+impl ValidPasswords {
+    fn new(len: usize) -> Self {
+        let root = PrefixState::new();
+        let stack = if root.can_be_valid_with_any_suffix(len) {
+            vec![(root, 0)]
+        } else {
+            vec![]
+        };
+
+        Self {
+            len,
+            buf: PasswordInner::new(),
+            stack,
+        }
+    }
+}
+
+impl Iterator for ValidPasswords {
+    type Item = Password;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // スタックに積まれる状態は全て有効なパスワードに延長できる。
+        loop {
+            let (state, next) = self.stack.last_mut()?;
+
+            if self.len - self.buf.len() == 1 {
+                let mut chars = self.buf.clone();
+                chars.push(state.valid_last_char().unwrap());
+                self.stack.pop();
+                self.buf.pop();
+                return Some(Password::new(&chars).unwrap());
+            }
+
+            if usize::from(*next) == PasswordChar::ALL.len() {
+                self.stack.pop();
+                self.buf.pop();
+                continue;
+            }
+
+            let pc = PasswordChar::ALL[usize::from(*next)];
+            *next += 1;
+            let mut child = *state;
+            child.push(pc);
+            if child.can_be_valid_with_any_suffix(self.len) {
+                self.buf.push(pc);
+                self.stack.push((child, 0));
+            }
+        }
+    }
+}
+
+impl Default for PrefixState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrefixState {
+    /// 空の prefix に対する状態を返す。
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            prev: 0x1F,
+            embed_add: 0,
+            embed_xor: 0,
+            sum_add: 0,
+            sum_xor: 0,
+        }
+    }
+
+    /// prefix の文字数を返す。
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// prefix が空かどうかを返す。
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// prefix の末尾に文字を追加する。
+    pub fn push(&mut self, pc: PasswordChar) {
+        let b = self.byte_of(pc);
+        match self.len {
+            0 => self.embed_add = b,
+            1 => self.embed_xor = b,
+            _ => {
+                self.sum_add = (self.sum_add + b) & 0x3F;
+                self.sum_xor ^= b;
+            }
+        }
+        self.prev = pc.to_inner();
+        self.len += 1;
+    }
+
+    /// prefix の後に続けて、長さ `final_len` の有効なパスワードを作れるかどうかを返す。
+    ///
+    /// `final_len` が prefix の文字数未満、または `Password::MIN_LEN..=Password::MAX_LEN` の範囲外なら `false` を返す。
+    pub fn can_be_valid_with_any_suffix(&self, final_len: usize) -> bool {
+        if final_len < self.len || !matches!(final_len, Password::MIN_LEN..=Password::MAX_LEN) {
+            return false;
+        }
+
+        // 2 文字以下のパスワードでは、格納されたチェックサムが共に 0x3F でなければならない
+        //(1 文字なら XOR チェックサムは 0x3F とみなされる)。
+        if final_len <= 2 {
+            return (self.len < 1 || self.embed_add == 0x3F)
+                && (self.len < 2 || self.embed_xor == 0x3F);
+        }
+
+        // 格納されたチェックサムが未定なら、それを合わせればよい。
+        // 加算チェックサムのみ決まっている場合も、3 バイト目以降が 1 つ以上あるので合わせられる。
+        if self.len < 2 {
+            return true;
+        }
+
+        // 残りの m バイト y_1, ..., y_m は任意に選べる。
+        // Σy ≡ t (mod 64) かつ ⊕y = u を満たす y があるかどうかを調べる。
+        let (t, u) = self.remaining_targets();
+        match final_len - self.len {
+            0 => t == 0 && u == 0,
+            1 => t == u,
+            2 => {
+                // y_1 + y_2 = (y_1 ^ y_2) + 2 * (y_1 & y_2) なので、a = y_1 & y_2 は
+                // 2a ≡ t - u (mod 64) かつ a & u == 0 を満たす。逆にそのような a があれば y_1 = a | u, y_2 = a とできる。
+                let d = t.wrapping_sub(u) & 0x3F;
+                d % 2 == 0 && [d / 2, d / 2 + 0x20].iter().any(|a| a & u == 0)
+            }
+            // y_3 = u とおけば、残りは t' = t - u, u' = 0 の 2 バイトの場合に帰着する。
+            _ => (t.wrapping_sub(u) & 0x3F) % 2 == 0,
+        }
+    }
+
+    /// prefix の後に 1 文字追加して有効なパスワードになるなら、その文字を返す。
+    ///
+    /// そのような文字はたかだか 1 つしかない。
+    pub fn valid_last_char(&self) -> Option<PasswordChar> {
+        let final_len = self.len + 1;
+        if !self.can_be_valid_with_any_suffix(final_len) {
+            return None;
+        }
+
+        // 追加する文字のデコード後のバイト。
+        let b = match final_len {
+            1 | 2 => 0x3F,
+            _ => self.remaining_targets().0,
+        };
+        Some(self.char_of(b))
+    }
+
+    /// 残りのバイトが満たすべき加算チェックサムと XOR チェックサムを返す。prefix が 2 文字以上のときのみ意味を持つ。
+    fn remaining_targets(&self) -> (u8, u8) {
+        (
+            self.embed_add.wrapping_sub(self.sum_add) & 0x3F,
+            self.embed_xor ^ self.sum_xor,
+        )
+    }
+
+    /// 次の位置に文字 `pc` を置いたときのデコード後のバイトを返す。
+    fn byte_of(&self, pc: PasswordChar) -> u8 {
+        (pc.to_inner() ^ self.prev).wrapping_sub(Self::add_table(self.len)) & 0x3F
+    }
+
+    /// 次の位置のデコード後のバイトが `b` になる文字を返す。
+    fn char_of(&self, b: u8) -> PasswordChar {
+        let c = (b.wrapping_add(Self::add_table(self.len)) & 0x3F) ^ self.prev;
+        PasswordChar::from_inner(c).unwrap()
+    }
+
+    fn add_table(pos: usize) -> u8 {
+        SerializedBytes::ENCODE_ADD_TABLE[pos % 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn password(chars: &[PasswordChar]) -> Password {
+        Password::new(chars).unwrap()
+    }
+
+    /// `prefix` の後に `n_free` 文字を追加して有効なパスワードにできるかどうかを総当たりで調べる。
+    fn naive_completable(prefix: &[PasswordChar], n_free: usize) -> bool {
+        let mut buf = prefix.to_vec();
+        fn rec(buf: &mut Vec<PasswordChar>, n_free: usize) -> bool {
+            if n_free == 0 {
+                return password(buf).is_valid();
+            }
+            PasswordChar::iter().any(|pc| {
+                buf.push(pc);
+                let res = rec(buf, n_free - 1);
+                buf.pop();
+                res
+            })
+        }
+        rec(&mut buf, n_free)
+    }
+
+    #[test]
+    fn test_prefix_state_len4() {
+        // 長さ 4 のパスワードの先頭 3 文字全てについて、枝刈りの判定が総当たりと一致する。
+        let last_byte: Vec<Vec<u8>> = PasswordChar::iter()
+            .map(|c| {
+                PasswordChar::iter()
+                    .map(|d| SerializedBytes::from_password(&password(&[c, c, c, d]))[3].get())
+                    .collect()
+            })
+            .collect();
+        // 表を使った判定が `is_valid()` と一致することを一部で確かめる。
+        for (a, b, c, d) in itertools::iproduct!(
+            PasswordChar::iter().step_by(5),
+            PasswordChar::iter().step_by(3),
+            PasswordChar::iter().step_by(7),
+            PasswordChar::iter()
+        ) {
+            let bytes = SerializedBytes::from_password(&password(&[a, b, c]));
+            let [b0, b1, b2] = [0, 1, 2].map(|i| bytes[i].get());
+            let b3 = last_byte[c as usize][d as usize];
+            assert_eq!(
+                b0 == (b2 + b3) & 0x3F && b1 == b2 ^ b3,
+                password(&[a, b, c, d]).is_valid()
+            );
+        }
+
+        let mut n_completable = 0;
+        for a in PasswordChar::iter() {
+            for b in PasswordChar::iter() {
+                for c in PasswordChar::iter() {
+                    let prefix = [a, b, c];
+                    let state = Password::prefix_state(&prefix);
+
+                    // 4 文字目のデコード後のバイトは 3 文字目と 4 文字目のみで決まるので、表を引く。
+                    let bytes = SerializedBytes::from_password(&password(&prefix));
+                    let [b0, b1, b2] = [0, 1, 2].map(|i| bytes[i].get());
+                    let valid_last: Vec<_> = PasswordChar::iter()
+                        .filter(|&d| {
+                            let b3 = last_byte[c as usize][d as usize];
+                            b0 == (b2 + b3) & 0x3F && b1 == b2 ^ b3
+                        })
+                        .collect();
+                    assert!(valid_last.len() <= 1);
+                    assert_eq!(
+                        state.can_be_valid_with_any_suffix(4),
+                        !valid_last.is_empty(),
+                        "{prefix:?}"
+                    );
+                    assert_eq!(state.valid_last_char(), valid_last.first().copied());
+
+                    n_completable += usize::from(!valid_last.is_empty());
+                }
+            }
+        }
+
+        // 2 文字目の偶奇による枝刈りより強い。
+        let n_odd_second = 64 * 32 * 64;
+        assert!(n_completable < n_odd_second);
+    }
+
+    #[test]
+    fn test_prefix_state_short() {
+        for len in 0..=2 {
+            for final_len in len.max(1)..=4 {
+                let n_free = final_len - len;
+                let prefixes: Vec<Vec<PasswordChar>> = match len {
+                    0 => vec![vec![]],
+                    1 => PasswordChar::iter().map(|a| vec![a]).collect(),
+                    _ => PasswordChar::iter()
+                        .flat_map(|a| PasswordChar::iter().map(move |b| vec![a, b]))
+                        .step_by(if n_free >= 2 { 37 } else { 1 })
+                        .collect(),
+                };
+                for prefix in prefixes {
+                    let state = Password::prefix_state(&prefix);
+                    assert_eq!(
+                        state.can_be_valid_with_any_suffix(final_len),
+                        naive_completable(&prefix, n_free),
+                        "{prefix:?} {final_len}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_state_long() {
+        // 残り 2 文字、3 文字の場合を疑似乱数で選んだ prefix について確かめる。
+        let mut state: u32 = 1;
+        let mut rand = move || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            PasswordChar::from_inner(((state >> 16) & 0x3F) as u8).unwrap()
+        };
+        for n_free in 2..=3 {
+            let mut n_completable = 0;
+            for _ in 0..if n_free == 2 { 300 } else { 8 } {
+                let prefix: Vec<_> = (0..4).map(|_| rand()).collect();
+                let expected = naive_completable(&prefix, n_free);
+                assert_eq!(
+                    Password::prefix_state(&prefix).can_be_valid_with_any_suffix(4 + n_free),
+                    expected,
+                    "{prefix:?}"
+                );
+                n_completable += usize::from(expected);
+            }
+            assert!(n_completable > 0);
+        }
+    }
+
+    #[test]
+    fn test_prefix_state_push() {
+        let password =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        let mut state = PrefixState::new();
+        assert!(state.is_empty());
+        for (i, &pc) in password.iter().enumerate() {
+            assert_eq!(state, Password::prefix_state(&password[..i]));
+            assert!(state.can_be_valid_with_any_suffix(password.len()));
+            state.push(pc);
+        }
+        assert_eq!(state.len(), password.len());
+        assert!(state.can_be_valid_with_any_suffix(password.len()));
+        assert!(!state.can_be_valid_with_any_suffix(password.len() - 1));
+
+        let state = Password::prefix_state(&password[..password.len() - 1]);
+        assert_eq!(state.valid_last_char(), password.last().copied());
+
+        assert!(!PrefixState::new().can_be_valid_with_any_suffix(0));
+        assert!(!PrefixState::new().can_be_valid_with_any_suffix(Password::MAX_LEN + 1));
+        assert_eq!(
+            PrefixState::new().valid_last_char(),
+            Password::parse("ふ").unwrap().first().copied()
+        );
+    }
+
+    #[test]
+    fn test_complete() {
+        use crate::search::{search, Pattern, SearchOptions};
+
+        // 総当たり検索の結果と一致する。
+        for (prefix, target_len) in [
+            ("", 1),
+            ("", 2),
+            ("", 3),
+            ("あ", 3),
+            ("あい", 3),
+            ("あい", 4),
+            ("あば", 4),
+            ("おにの", 4),
+            ("おにの", 5),
+            ("おにのば", 5),
+            ("おにのばか", 5),
+            ("おにのばか", 7),
+        ] {
+            let prefix = if prefix.is_empty() {
+                vec![]
+            } else {
+                Password::parse(prefix).unwrap().to_vec()
+            };
+            let res = Password::complete(&prefix, target_len);
+            assert!(res.iter().all(|p| p.is_valid() && p.starts_with(&prefix)));
+
+            let pattern: Vec<_> = (0..target_len).map(|i| prefix.get(i).copied()).collect();
+            let expected = search(&Pattern::new(&pattern).unwrap(), &SearchOptions::default());
+            assert_eq!(res, expected, "{prefix:?} {target_len}");
+        }
+
+        // 末尾 2 文字以外を固定する。
+        let password =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        let res = Password::complete(&password[..password.len() - 2], password.len());
+        assert!(res.contains(&password));
+        assert!((1..=64).contains(&res.len()));
+        assert!(res.iter().all(Password::is_valid));
+
+        assert_eq!(
+            Password::complete(&password, password.len()),
+            std::slice::from_ref(&password)
+        );
+        assert_eq!(Password::complete(&password, password.len() - 1), []);
+        assert_eq!(Password::complete(&[], 0), []);
+        assert_eq!(Password::complete(&[], Password::MAX_LEN + 1), []);
+    }
+
+    #[test]
+    fn test_valid_passwords() {
+        use itertools::Itertools as _;
+
+        // 総当たりの結果と一致する。
+        for len in [1, 2] {
+            let expected: Vec<_> = itertools::repeat_n(PasswordChar::iter(), len)
+                .multi_cartesian_product()
+                .map(|chars| Password::new(&chars).unwrap())
+                .filter(Password::is_valid)
+                .collect();
+            itertools::assert_equal(Password::valid_passwords(len), expected);
+        }
+        itertools::assert_equal(
+            Password::valid_passwords(1),
+            [Password::parse("ふ").unwrap()],
+        );
+
+        for len in [3, 4] {
+            itertools::assert_equal(Password::valid_passwords(len), Password::complete(&[], len));
+        }
+        assert_eq!(Password::valid_passwords(3).count(), 64);
+
+        // 長いパスワードでも先頭から遅延評価で得られる。
+        let res: Vec<_> = Password::valid_passwords(Password::MAX_LEN)
+            .take(100)
+            .collect();
+        assert!(res.iter().all(Password::is_valid));
+        assert!(res.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(Password::valid_passwords(0).next(), None);
+        assert_eq!(
+            Password::valid_passwords(Password::MAX_LEN + 1).next(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_all_valid_short() {
+        use itertools::Itertools as _;
+
+        // 総当たりの結果と一致する。
+        let expected: Vec<_> = (1..=2)
+            .flat_map(|len| {
+                itertools::repeat_n(PasswordChar::iter(), len)
+                    .multi_cartesian_product()
+                    .map(|chars| Password::new(&chars).unwrap())
+                    .filter(Password::is_valid)
+            })
+            .collect();
+        assert_eq!(Password::all_valid_short(2), expected);
+
+        let res = Password::all_valid_short(3);
+        assert_eq!(res.len(), 1 + 1 + 64);
+        assert_eq!(res[..2], expected);
+        assert!(res
+            .windows(2)
+            .all(|w| (w[0].len(), &w[0]) < (w[1].len(), &w[1])));
+
+        assert_eq!(Password::all_valid_short(0), []);
+
+        let table = valid_short_table();
+        assert_eq!(
+            table
+                .iter()
+                .map(|(password, _)| password.clone())
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(table[0].0, Password::parse("ふ").unwrap());
+        for (password, savedata) in table {
+            assert_eq!(password.equivalence_key().as_ref(), Some(savedata));
+        }
+    }
+}
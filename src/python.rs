@@ -0,0 +1,196 @@
+//! PyO3 による Python バインディング。
+//!
+//! maturin develop --features=python (pyproject.toml を参照)
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::password::Password;
+use crate::savedata::Savedata;
+use crate::search::{Pattern, SearchOptions};
+use crate::serialized::SerializedBytes;
+
+create_exception!(
+    momoden_password,
+    PasswordError,
+    PyValueError,
+    "パスワードまたはパターンが不正である。"
+);
+
+/// パスワード。
+#[pyclass(
+    name = "Password",
+    module = "momoden_password",
+    frozen,
+    eq,
+    hash,
+    ord,
+    skip_from_py_object
+)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PyPassword(Password);
+
+#[pymethods]
+impl PyPassword {
+    /// ひらがな文字列をパースしてパスワードを作る。
+    #[staticmethod]
+    fn parse(s: &str) -> PyResult<Self> {
+        Password::parse(s)
+            .map(Self)
+            .map_err(|e| PasswordError::new_err(e.to_string()))
+    }
+
+    /// パスワードが有効(ゲーム状態としてロードできる)かどうかを返す。
+    fn is_valid(&self) -> bool {
+        self.0.is_valid()
+    }
+
+    /// 特殊パスワード(音楽室/美術室)かどうかを返す。
+    fn is_special(&self) -> bool {
+        self.0.is_special()
+    }
+
+    /// パスワードに記録されたセーブデータを返す。無効なパスワードならば `PasswordError` を送出する。
+    fn savedata(&self) -> PyResult<PySavedata> {
+        let bytes = SerializedBytes::from_password(&self.0);
+        bytes.to_savedata().map(PySavedata).ok_or_else(|| {
+            PasswordError::new_err(format!(
+                "checksum mismatch: embed={:?}, calculated={:?}",
+                bytes.checksum_embed(),
+                bytes.checksum_calculated()
+            ))
+        })
+    }
+
+    /// 空白区切りの文字列を返す。
+    fn pretty(&self) -> String {
+        self.0.display_pretty().to_string()
+    }
+
+    /// 内部値の 16 進ダンプを返す。
+    fn hex(&self) -> String {
+        self.0.display_hex().to_string()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.display().to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Password('{}')", self.0.display())
+    }
+}
+
+/// セーブデータ。読み取り専用の dict のように振る舞う。
+#[pyclass(
+    name = "Savedata",
+    module = "momoden_password",
+    frozen,
+    eq,
+    skip_from_py_object
+)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PySavedata(Savedata);
+
+#[pymethods]
+impl PySavedata {
+    /// dict からセーブデータを作る。省略時はデフォルト値。
+    #[new]
+    #[pyo3(signature = (data=None))]
+    fn new(data: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        match data {
+            Some(data) => Self::from_dict(data),
+            None => Ok(Self(Savedata::default())),
+        }
+    }
+
+    /// dict からセーブデータを作る。
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        pythonize::depythonize(data)
+            .map(Self)
+            .map_err(|e| PyValueError::new_err(format!("invalid savedata: {e}")))
+    }
+
+    /// dict に変換する。
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let obj = pythonize::pythonize(py, &self.0)?;
+        Ok(obj.cast_into::<PyDict>()?)
+    }
+
+    /// パスワード化する。
+    fn to_password(&self) -> PyPassword {
+        PyPassword(SerializedBytes::from_savedata(&self.0).to_password())
+    }
+
+    /// 実際にロードした後の状態を返す。
+    fn normalize(&self) -> Self {
+        Self(self.0.normalize())
+    }
+
+    fn keys<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(self.to_dict(py)?.keys().into_any())
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        self.to_dict(py)?
+            .get_item(key)?
+            .ok_or_else(|| PyKeyError::new_err(key.to_owned()))
+    }
+
+    fn __contains__(&self, py: Python<'_>, key: &str) -> PyResult<bool> {
+        self.to_dict(py)?.contains(key)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(self.keys(py)?.try_iter()?.into_any())
+    }
+
+    fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+        Ok(self.to_dict(py)?.len())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("Savedata({})", self.to_dict(py)?.repr()?))
+    }
+}
+
+/// パターン('?' はワイルドカード)にマッチする有効なパスワードを辞書順に返す。探索中は GIL を解放する。
+#[pyfunction]
+#[pyo3(signature = (pattern, limit=None, exclude_special=false, threads=1))]
+fn search(
+    py: Python<'_>,
+    pattern: &str,
+    limit: Option<usize>,
+    exclude_special: bool,
+    threads: usize,
+) -> PyResult<Vec<PyPassword>> {
+    let pattern = Pattern::parse(pattern).map_err(|e| PasswordError::new_err(e.to_string()))?;
+    let options = SearchOptions {
+        limit,
+        exclude_special,
+        threads,
+        ..Default::default()
+    };
+
+    let passwords = py.detach(|| crate::search::search(&pattern, &options));
+
+    Ok(passwords.into_iter().map(PyPassword).collect())
+}
+
+#[pymodule]
+#[pyo3(name = "momoden_password")]
+fn momoden_password_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPassword>()?;
+    m.add_class::<PySavedata>()?;
+    m.add_function(wrap_pyfunction!(search, m)?)?;
+    m.add("PasswordError", m.py().get_type::<PasswordError>())?;
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use rand::Rng;
+
+use crate::password::Password;
+use crate::savedata::Savedata;
+use crate::serialized::{SerializedByte, SerializedBytes, SerializedBytesInner};
+
+impl Password {
+    /// 長さ `len` の有効なパスワードを一様ランダムに生成する。
+    ///
+    /// パスワードとデコード後のバイト列は 1 対 1 に対応し、有効なパスワードは
+    /// 3 バイト目以降(長さ 2 以下なら空)を任意に選び、先頭 2 バイトをそのチェックサムとしたものに限られる。
+    /// よって 3 バイト目以降を一様に選べば、有効なパスワード全体から一様に選ぶことになる。
+    ///
+    /// なお、パスワードの文字を直接選んでから先頭 2 文字を合わせる方式では、
+    /// 2 文字目が 3 バイト目のデコード結果にも影響するため、このようにはならない。
+    ///
+    /// # Panics
+    ///
+    /// `len` が `Password::MIN_LEN..=Password::MAX_LEN` の範囲外ならパニックする。
+    pub fn random_valid<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Self {
+        assert!(
+            matches!(len, Self::MIN_LEN..=Self::MAX_LEN),
+            "password length {len} is out of range"
+        );
+
+        let mut inner = SerializedBytesInner::new();
+        for _ in 0..len {
+            inner.push(SerializedByte::new(rng.random_range(0..=0x3F)).unwrap());
+        }
+        SerializedBytes::new(&inner)
+            .unwrap()
+            .with_fixed_checksum()
+            .to_password()
+    }
+}
+
+/// `Password::random_valid()` で生成したパスワードをデコードしたゲーム状態を返す。
+///
+/// # Panics
+///
+/// `len` が `Password::MIN_LEN..=Password::MAX_LEN` の範囲外ならパニックする。
+pub fn random_valid_savedata<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Savedata {
+    SerializedBytes::from_password(&Password::random_valid(rng, len))
+        .to_savedata()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng as _;
+
+    use super::*;
+
+    #[test]
+    fn test_random_valid() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for len in Password::MIN_LEN..=Password::MAX_LEN {
+            for _ in 0..100 {
+                let password = Password::random_valid(&mut rng, len);
+                assert_eq!(password.len(), len);
+                assert!(password.is_valid());
+            }
+        }
+
+        // 長さ 2 以下の有効なパスワードは 1 つしかない。
+        assert_eq!(
+            Password::random_valid(&mut rng, 1),
+            Password::parse("ふ").unwrap()
+        );
+
+        let savedata = random_valid_savedata(&mut rng, Password::MAX_LEN);
+        assert_eq!(
+            SerializedBytes::from_savedata(&savedata)
+                .to_savedata()
+                .unwrap(),
+            savedata
+        );
+    }
+
+    #[test]
+    fn test_random_valid_uniform() {
+        // 長さ 3, 4 の有効なパスワードはそれぞれ 64, 64^2 個ある。先頭の文字はどちらでも一様に分布する。
+        let mut rng = StdRng::seed_from_u64(2);
+        for len in [3, 4] {
+            const N: usize = 64 * 100;
+            let mut counts = [0_usize; 0x40];
+            for _ in 0..N {
+                let password = Password::random_valid(&mut rng, len);
+                counts[password[0] as usize] += 1;
+            }
+
+            // 自由度 63 のカイ二乗分布の上側 0.1% 点は約 103.4。
+            let expected = (N / 0x40) as f64;
+            let chi2: f64 = counts
+                .iter()
+                .map(|&c| (c as f64 - expected).powi(2) / expected)
+                .sum();
+            assert!(chi2 < 103.4, "len={len} chi2={chi2}");
+        }
+
+        // 長さ 3 の有効なパスワードは全て現れる。
+        let passwords: std::collections::BTreeSet<_> = (0..2000)
+            .map(|_| Password::random_valid(&mut rng, 3))
+            .collect();
+        assert_eq!(passwords.len(), 64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_random_valid_empty() {
+        Password::random_valid(&mut StdRng::seed_from_u64(0), 0);
+    }
+}
@@ -0,0 +1,444 @@
+//! 素朴な参照実装。
+//!
+//! 本体の実装(変換表、バッファの使い回し、ビット列操作など)の回帰を検出するため、
+//! アルゴリズムをそのまま書き下したもの。速度は考慮しない。他言語への移植の参考にもなる。
+//!
+//! テスト時、または `reference` feature 有効時のみコンパイルされる。
+
+use crate::savedata::Savedata;
+
+/// パスワードに使われる文字。`n` 文字目の内部値は `n`。
+pub const CHARS: &str = "あいうえおかきくけこさしすせそたちつてとなにぬねのはひふへほまみむめもやゆよらりるれろわがぎぐげござじずぜぞばびぶべぼぱぴぷぺぽ";
+
+/// エンコード時の加算値。`i` 文字目には `ADD[i % 4]` を加える。
+pub const ADD: [u8; 4] = [0x05, 0x19, 0x32, 0x21];
+
+/// ゲーム状態のビット列(チェックサムを除く)の長さ。パスワードが短ければ 1 で補われる。
+pub const BITS_LEN: usize = 162;
+
+/// ゲーム状態のビット列におけるフィールドの並び。(フィールド名, ビット数, 値の中での位置)
+///
+/// 各フィールドは上位ビットから格納される。`xp`, `purse` は上位/下位バイトに分かれている。
+/// インベントリはこの後に 6bit ずつ、最大 8 個続く。
+pub const LAYOUT: &[(&str, usize, usize)] = &[
+    ("age_timer_hi", 8, 0),
+    ("purse", 8, 8),
+    ("age", 8, 0),
+    ("purse", 8, 0),
+    ("xp", 8, 0),
+    ("deposit", 6, 0),
+    ("xp", 8, 8),
+    ("spells.houhi", 1, 0),
+    ("spells.dadadidi", 1, 0),
+    ("spells.fuyuu", 1, 0),
+    ("spells.mankintan", 1, 0),
+    ("spells.hien", 1, 0),
+    ("spells.inazuma", 1, 0),
+    ("spells.rokkaku", 1, 0),
+    ("spells.kintan", 1, 0),
+    ("treasures.swallow", 1, 0),
+    ("treasures.hourai", 1, 0),
+    ("treasures.hotoke", 1, 0),
+    ("treasures.fur", 1, 0),
+    ("treasures.dragon", 1, 0),
+    ("respawn", 4, 0),
+    ("bookmarks.hien", 1, 0),
+    ("bookmarks.hohoemi", 1, 0),
+    ("minions.monkey", 1, 0),
+    ("minions.pheasant", 1, 0),
+    ("minions.dog", 1, 0),
+    ("bookmarks.taketori", 1, 0),
+    ("bookmarks.sarukani", 1, 0),
+    ("bookmarks.kibou", 1, 0),
+    ("bookmarks.netaro", 1, 0),
+    ("bookmarks.urashima", 1, 0),
+    ("bookmarks.kintaro", 1, 0),
+    ("bookmarks.hanasaka", 1, 0),
+    ("bookmarks.tabidachi", 1, 0),
+    ("events.hohoemi", 1, 0),
+    ("events.dragon", 1, 0),
+    ("events.sarukani", 1, 0),
+    ("events.murata", 1, 0),
+    ("events.netaro", 1, 0),
+    ("events.urashima", 1, 0),
+    ("events.kintaro", 1, 0),
+    ("events.hanasaka", 1, 0),
+    ("equipment.helm", 2, 0),
+    ("equipment.weapon", 4, 0),
+    ("equipment.armor", 4, 0),
+    ("equipment.shoes", 3, 0),
+    ("equipment.accessory0", 2, 0),
+    ("equipment.accessory1", 2, 0),
+    ("equipment.accessory2", 1, 0),
+    ("equipment.accessory3", 1, 0),
+];
+
+/// 文字を内部値に変換する。
+pub fn char_to_code(c: char) -> Option<u8> {
+    CHARS.chars().position(|x| x == c).map(|i| i as u8)
+}
+
+/// 内部値を文字に変換する。
+pub fn code_to_char(code: u8) -> Option<char> {
+    CHARS.chars().nth(usize::from(code))
+}
+
+/// パスワード(内部値の列)をバイト列にデコードする。
+pub fn decode(codes: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![];
+    for i in 0..codes.len() {
+        let prev = if i == 0 { 0x1F } else { codes[i - 1] };
+        let x = codes[i] ^ prev;
+        bytes.push((x + 0x40 - ADD[i % 4]) % 0x40);
+    }
+    bytes
+}
+
+/// バイト列をパスワード(内部値の列)にエンコードする。
+pub fn encode(bytes: &[u8]) -> Vec<u8> {
+    let mut codes: Vec<u8> = vec![];
+    for i in 0..bytes.len() {
+        let x = (bytes[i] + ADD[i % 4]) % 0x40;
+        let prev = if i == 0 { 0x1F } else { codes[i - 1] };
+        codes.push(x ^ prev);
+    }
+    codes
+}
+
+/// バイト列に埋め込まれたチェックサム (add, xor) を返す。2 バイト目がなければ 0x3F とする。
+pub fn checksum_embed(bytes: &[u8]) -> (u8, u8) {
+    (bytes[0], bytes.get(1).copied().unwrap_or(0x3F))
+}
+
+/// バイト列の 3 バイト目以降から計算したチェックサム (add, xor) を返す。
+/// 3 バイト目がなければ、0x3F が 1 つあるものとして扱う。
+pub fn checksum_calculated(bytes: &[u8]) -> (u8, u8) {
+    if bytes.len() <= 2 {
+        return (0x3F, 0x3F);
+    }
+
+    let mut sum_add = 0;
+    let mut sum_xor = 0;
+    for &b in &bytes[2..] {
+        sum_add = (sum_add + u32::from(b)) % 0x40;
+        sum_xor ^= b;
+    }
+    (sum_add as u8, sum_xor)
+}
+
+/// バイト列の 3 バイト目以降をビット列に変換し、長さ `BITS_LEN` まで 1 で補う(超える分は捨てる)。
+pub fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    let mut bits = vec![];
+    for &b in bytes.iter().skip(2) {
+        for i in (0..6).rev() {
+            bits.push((b >> i) & 1 == 1);
+        }
+    }
+    bits.resize(BITS_LEN, true);
+    bits.truncate(BITS_LEN);
+    bits
+}
+
+/// バイト列をゲーム状態にデシリアライズする。チェックサムが一致しなければ `None` を返す。
+pub fn deserialize(bytes: &[u8]) -> Option<Savedata> {
+    if checksum_embed(bytes) != checksum_calculated(bytes) {
+        return None;
+    }
+
+    let bits = bytes_to_bits(bytes);
+    let mut pos = 0;
+    let mut read = |n: usize| {
+        let mut x = 0_u16;
+        for _ in 0..n {
+            x = (x << 1) | u16::from(bits[pos]);
+            pos += 1;
+        }
+        x
+    };
+
+    let mut values: Vec<(&str, u16)> = vec![];
+    for &(name, n_bits, shift) in LAYOUT {
+        let x = read(n_bits) << shift;
+        match values.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, value)) => *value |= x,
+            None => values.push((name, x)),
+        }
+    }
+
+    let mut savedata = Savedata::default();
+    for (name, value) in values {
+        savedata.set_field(name, value).unwrap();
+    }
+    for i in 0..8 {
+        let item = read(6);
+        if item == 0 {
+            break;
+        }
+        savedata
+            .set_field(&format!("inventory[{i}]"), item)
+            .unwrap();
+    }
+
+    Some(savedata)
+}
+
+/// ゲーム状態をバイト列にシリアライズする。先頭 2 バイトはチェックサム。
+pub fn serialize(savedata: &Savedata) -> Vec<u8> {
+    let mut bits = vec![];
+    let mut write = |n: usize, x: u16| {
+        for i in (0..n).rev() {
+            bits.push((x >> i) & 1 == 1);
+        }
+    };
+
+    for &(name, n_bits, shift) in LAYOUT {
+        let value = savedata.get_field(name).unwrap();
+        write(n_bits, value >> shift);
+    }
+    for &item in &savedata.inventory {
+        write(6, u16::from(item.get()));
+    }
+    if savedata.inventory.len() < 8 {
+        write(6, 0);
+    }
+
+    // 6bit 単位に 0 で補う。
+    while bits.len() % 6 != 0 {
+        bits.push(false);
+    }
+
+    let mut bytes = vec![0, 0];
+    for chunk in bits.chunks(6) {
+        let mut b = 0;
+        for &bit in chunk {
+            b = (b << 1) | u8::from(bit);
+        }
+        bytes.push(b);
+    }
+
+    let (sum_add, sum_xor) = checksum_calculated(&bytes);
+    bytes[0] = sum_add;
+    bytes[1] = sum_xor;
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::{Password, PasswordChar};
+    use crate::serialized::SerializedBytes;
+    use crate::validate::validate_stream;
+
+    /// 固定シードの疑似乱数列。
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            self.0 >> 33
+        }
+
+        fn codes(&mut self) -> Vec<u8> {
+            let len = 1 + (self.next() as usize) % Password::MAX_LEN;
+            (0..len).map(|_| (self.next() % 0x40) as u8).collect()
+        }
+    }
+
+    fn password(codes: &[u8]) -> Password {
+        Password::from_iter_checked(codes.iter().map(|&c| PasswordChar::from_inner(c).unwrap()))
+            .unwrap()
+    }
+
+    fn raw(bytes: &SerializedBytes) -> Vec<u8> {
+        bytes.iter().map(|b| b.get()).collect()
+    }
+
+    /// 長さ 2 以下の全ての内部値の列と、固定シードの乱数で生成した内部値の列。
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        let short = (1..=2_u32).flat_map(|len| {
+            (0..0x40_u32.pow(len)).map(move |mut x| {
+                (0..len)
+                    .map(|_| {
+                        let c = (x % 0x40) as u8;
+                        x /= 0x40;
+                        c
+                    })
+                    .collect()
+            })
+        });
+        let mut rng = Rng(1);
+        let random = (0..20000).map(move |_| rng.codes());
+        short.chain(random)
+    }
+
+    #[test]
+    fn test_chars() {
+        for c in '\0'..='\u{FFFF}' {
+            assert_eq!(
+                PasswordChar::from_char(c).map(PasswordChar::to_inner),
+                char_to_code(c)
+            );
+        }
+        for pc in PasswordChar::all() {
+            assert_eq!(Some(pc.to_char()), code_to_char(pc.to_inner()));
+        }
+    }
+
+    #[test]
+    fn test_codec() {
+        let mut buf = SerializedBytes::from_savedata(&Savedata::default());
+        let mut savedata_buf = Savedata::default();
+
+        for codes in inputs() {
+            let password = password(&codes);
+
+            // デコード。
+            let decoded = SerializedBytes::from_password(&password);
+            assert_eq!(raw(&decoded), decode(&codes), "{codes:?}");
+            password.decode_into(&mut buf);
+            assert_eq!(buf, decoded);
+
+            // エンコード。
+            assert_eq!(
+                decoded
+                    .to_password()
+                    .iter()
+                    .map(|pc| pc.to_inner())
+                    .collect::<Vec<_>>(),
+                encode(&raw(&decoded))
+            );
+
+            // チェックサム。
+            let embed = decoded.checksum_embed();
+            let calculated = decoded.checksum_calculated();
+            assert_eq!(
+                (embed.sum_add().get(), embed.sum_xor().get()),
+                checksum_embed(&raw(&decoded))
+            );
+            assert_eq!(
+                (calculated.sum_add().get(), calculated.sum_xor().get()),
+                checksum_calculated(&raw(&decoded))
+            );
+            assert_eq!(password.is_valid(), embed == calculated);
+
+            // デシリアライズ。
+            let expected = deserialize(&raw(&decoded));
+            assert_eq!(decoded.to_savedata(), expected);
+            assert_eq!(
+                decoded
+                    .decode_into(&mut savedata_buf)
+                    .ok()
+                    .map(|()| savedata_buf.clone()),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut buf = SerializedBytes::from_savedata(&Savedata::default());
+
+        // チェックサムを合わせた乱数のバイト列からロードしたゲーム状態と、それを乱数で変更したもの。
+        let mut rng = Rng(2);
+        for _ in 0..2000 {
+            let mut bytes = rng.codes();
+            if bytes.len() >= 2 {
+                (bytes[0], bytes[1]) = checksum_calculated(&bytes);
+            }
+            let Some(mut savedata) = deserialize(&bytes) else {
+                continue;
+            };
+
+            for _ in 0..4 {
+                let expected = serialize(&savedata);
+                assert_eq!(raw(&SerializedBytes::from_savedata(&savedata)), expected);
+                savedata.encode_into(&mut buf);
+                assert_eq!(raw(&buf), expected);
+                assert_eq!(deserialize(&expected).as_ref(), Some(&savedata));
+
+                let name = Savedata::FIELDS[rng.next() as usize % Savedata::FIELDS.len()].name();
+                let _ = savedata.set_field(name, rng.next() as u16);
+            }
+        }
+
+        // 往復すると元に戻る。
+        let savedata = deserialize(&decode(&[0x1B])).unwrap();
+        assert_eq!(deserialize(&serialize(&savedata)), Some(savedata));
+    }
+
+    #[test]
+    fn test_search() {
+        use crate::search::{search, Pattern, SearchOptions};
+
+        // 全ての候補を辞書順に調べ、チェックサムが一致するものを集める。
+        for (pattern, fixed) in [("??", &[][..]), ("???", &[]), ("あ??", &[0])] {
+            let len = pattern.chars().count();
+            let n_wild = len - fixed.len();
+            let expected: Vec<_> = (0..0x40_u32.pow(n_wild as u32))
+                .map(|mut x| {
+                    let mut codes = fixed.to_vec();
+                    let mut wild: Vec<_> = (0..n_wild)
+                        .map(|_| {
+                            let c = (x % 0x40) as u8;
+                            x /= 0x40;
+                            c
+                        })
+                        .collect();
+                    wild.reverse();
+                    codes.extend(wild);
+                    codes
+                })
+                .filter(|codes| {
+                    let bytes = decode(codes);
+                    checksum_embed(&bytes) == checksum_calculated(&bytes)
+                })
+                .map(|codes| password(&codes))
+                .collect();
+
+            for threads in [1, 4] {
+                let options = SearchOptions {
+                    threads,
+                    exclude_special: false,
+                    ..SearchOptions::default()
+                };
+                assert_eq!(
+                    search(&Pattern::parse(pattern).unwrap(), &options),
+                    expected,
+                    "{pattern}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_stream() {
+        let mut rng = Rng(3);
+        let lines: Vec<_> = (0..2000)
+            .map(|_| {
+                let codes = rng.codes();
+                codes
+                    .iter()
+                    .map(|&c| code_to_char(c).unwrap())
+                    .collect::<String>()
+            })
+            .collect();
+        let input = lines.join("\n");
+
+        let mut n = 0;
+        validate_stream(input.as_bytes(), |line, s, res| {
+            let codes: Vec<_> = s.chars().map(|c| char_to_code(c).unwrap()).collect();
+            let bytes = decode(&codes);
+            assert_eq!(
+                res.is_ok(),
+                checksum_embed(&bytes) == checksum_calculated(&bytes),
+                "line {line}"
+            );
+            n += 1;
+        })
+        .unwrap();
+        assert_eq!(n, lines.len());
+    }
+}
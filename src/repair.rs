@@ -0,0 +1,432 @@
+use thiserror::Error;
+
+use crate::password::{Password, PasswordChar, PasswordParseError};
+use crate::savedata::Savedata;
+use crate::serialized::SerializedBytes;
+
+impl Password {
+    /// たかだか `max_edits` 文字の置換で有効になるパスワードを全て返す。
+    ///
+    /// 結果は置換した文字数の昇順、同じ文字数の中では辞書順(内部値順)に並ぶ。
+    /// `self` が有効なら先頭は `self` となる。
+    ///
+    /// 文字を 1 つ置換するとデコード後のバイトは 2 つしか変わらないので、チェックサムを差分更新しながら
+    /// 候補を調べる。候補数は置換位置の組み合わせ数 × 63^`max_edits` なので、`max_edits` は 2 程度までが現実的。
+    pub fn repair(&self, max_edits: usize) -> Vec<Password> {
+        let alternatives = self
+            .iter()
+            .map(|&orig| PasswordChar::iter().filter(|&pc| pc != orig).collect())
+            .collect();
+        Repairer::new(self, alternatives).run(max_edits)
+    }
+
+    /// `Password::parse_fuzzy()` が調べる候補の個数の上限。
+    pub const FUZZY_CANDIDATES_MAX: u64 = 1 << 24;
+
+    /// 濁点/半濁点の誤認識を考慮してひらがな文字列をパースし、有効なパスワードの候補を全て返す。
+    ///
+    /// 各文字は、その文字自身か、清音・濁音・半濁音のうち同じ清音を持つ他の文字(例: 'ば' なら 'は', 'ぱ')の
+    /// いずれかとみなす。たかだか `max_substitutions` 文字を置き換えて有効になるものを、
+    /// 置き換えた文字数の昇順、同じ文字数の中では辞書順(内部値順)に返す。
+    /// OCR などで 'ば' と 'ぱ' を取り違えたパスワードから元のパスワードを復元するのに使う。
+    ///
+    /// 文字列をパースできなければ `FuzzyParseError::Parse` を返す。
+    /// 調べる候補の個数が `Password::FUZZY_CANDIDATES_MAX` を超えるなら `FuzzyParseError::TooManyCandidates` を返す。
+    pub fn parse_fuzzy(
+        s: &str,
+        max_substitutions: usize,
+    ) -> Result<Vec<Password>, FuzzyParseError> {
+        let password = Self::parse(s)?;
+        let alternatives: Vec<Vec<PasswordChar>> =
+            password.iter().map(|&pc| siblings(pc)).collect();
+
+        // dp[k]: ちょうど k 文字を置き換える候補の個数。
+        let max_substitutions = max_substitutions.min(password.len());
+        let mut dp = vec![0_u64; max_substitutions + 1];
+        dp[0] = 1;
+        for alts in &alternatives {
+            for k in (1..=max_substitutions).rev() {
+                dp[k] = dp[k].saturating_add(dp[k - 1].saturating_mul(alts.len() as u64));
+            }
+        }
+        let count = dp.iter().fold(0_u64, |acc, &x| acc.saturating_add(x));
+        if count > Self::FUZZY_CANDIDATES_MAX {
+            return Err(FuzzyParseError::TooManyCandidates {
+                count,
+                max: Self::FUZZY_CANDIDATES_MAX,
+            });
+        }
+
+        Ok(Repairer::new(&password, alternatives).run(max_substitutions))
+    }
+}
+
+/// `pc` と同じ清音を持つ、`pc` 以外の文字を返す。
+fn siblings(pc: PasswordChar) -> Vec<PasswordChar> {
+    let base = pc.base();
+    [Some(base), base.with_dakuten(), base.with_handakuten()]
+        .into_iter()
+        .flatten()
+        .filter(|&sibling| sibling != pc)
+        .collect()
+}
+
+/// `Password::parse_fuzzy()` で発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum FuzzyParseError {
+    /// 文字列をパスワードとしてパースできない。
+    #[error(transparent)]
+    Parse(#[from] PasswordParseError),
+
+    /// 調べる候補の個数が上限を超えている。
+    #[error("too many candidates ({count} > {max})")]
+    TooManyCandidates { count: u64, max: u64 },
+}
+
+impl Password {
+    /// ちょうど 1 文字だけ異なるパスワード(`self.len()` × 63 個)を列挙するイテレータを返す。
+    ///
+    /// 位置の昇順、同じ位置の中では文字の内部値順に並ぶ。`self` 自身は含まない。
+    pub fn single_edit_variants(&self) -> SingleEditVariants {
+        SingleEditVariants {
+            password: self.clone(),
+            pos: 0,
+            next: 0,
+        }
+    }
+
+    /// ちょうど 1 文字だけ異なる有効なパスワードと、それをデコードしたゲーム状態を全て返す。
+    ///
+    /// 並び順は `single_edit_variants()` と同じ。
+    pub fn typo_collisions(&self) -> Vec<(Password, Savedata)> {
+        self.single_edit_variants()
+            .filter_map(|password| {
+                let savedata = SerializedBytes::from_password(&password).to_savedata()?;
+                Some((password, savedata))
+            })
+            .collect()
+    }
+}
+
+/// `Password::single_edit_variants()` が返すイテレータ。
+#[derive(Clone, Debug)]
+pub struct SingleEditVariants {
+    password: Password,
+    /// 変更する位置。
+    pos: usize,
+    /// 次に試す文字の内部値。
+    next: u8,
+}
+
+impl Iterator for SingleEditVariants {
+    type Item = Password;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.password.len() {
+            let pos = self.pos;
+            let pc = PasswordChar::ALL[usize::from(self.next)];
+            if self.next == 0x3F {
+                self.pos += 1;
+                self.next = 0;
+            } else {
+                self.next += 1;
+            }
+
+            if pc != self.password[pos] {
+                let mut inner = self.password.clone().into_inner();
+                inner[pos] = pc;
+                return Some(Password::new(&inner).unwrap());
+            }
+        }
+
+        None
+    }
+}
+
+/// 置換候補を列挙しつつ、デコード後のバイト列とチェックサムを差分更新する。
+struct Repairer {
+    chars: Vec<PasswordChar>,
+    /// 各位置で置換先として試す文字。
+    alternatives: Vec<Vec<PasswordChar>>,
+    bytes: Vec<u8>,
+    /// 3 バイト目以降の和(mod 64)。
+    sum_add: u8,
+    /// 3 バイト目以降の XOR。
+    sum_xor: u8,
+}
+
+impl Repairer {
+    fn new(password: &Password, alternatives: Vec<Vec<PasswordChar>>) -> Self {
+        let bytes = SerializedBytes::from_password(password).to_u8_vec();
+        let data = bytes.get(2..).unwrap_or_default();
+
+        Self {
+            chars: password.to_vec(),
+            alternatives,
+            sum_add: data.iter().fold(0_u8, |acc, &b| acc.wrapping_add(b)) & 0x3F,
+            sum_xor: data.iter().fold(0, |acc, &b| acc ^ b),
+            bytes,
+        }
+    }
+
+    /// たかだか `max_edits` 文字を置換した有効なパスワードを、置換した文字数の昇順、同じ文字数の中では辞書順に返す。
+    fn run(&mut self, max_edits: usize) -> Vec<Password> {
+        let mut res = vec![];
+
+        if self.is_valid() {
+            res.push(Password::new(&self.chars).unwrap());
+        }
+        for n_edits in 1..=max_edits.min(self.chars.len()) {
+            let start = res.len();
+            self.dfs(0, n_edits, &mut res);
+            res[start..].sort_unstable();
+        }
+
+        res
+    }
+
+    /// `pos` 以降の位置から `n_edits` 個を選んで置換した有効なパスワードを `res` に追加する。
+    fn dfs(&mut self, pos: usize, n_edits: usize, res: &mut Vec<Password>) {
+        if n_edits == 0 {
+            if self.is_valid() {
+                res.push(Password::new(&self.chars).unwrap());
+            }
+            return;
+        }
+
+        for i in pos..=self.chars.len() - n_edits {
+            let orig = self.chars[i];
+            for k in 0..self.alternatives[i].len() {
+                let pc = self.alternatives[i][k];
+                self.set(i, pc);
+                self.dfs(i + 1, n_edits - 1, res);
+            }
+            self.set(i, orig);
+        }
+    }
+
+    /// `i` 文字目を `pc` に変え、影響を受けるバイト(`i` と `i + 1`)とチェックサムを更新する。
+    fn set(&mut self, i: usize, pc: PasswordChar) {
+        self.chars[i] = pc;
+        for j in i..(i + 2).min(self.chars.len()) {
+            let prev = if j == 0 {
+                0x1F
+            } else {
+                self.chars[j - 1].to_inner()
+            };
+            let b = (self.chars[j].to_inner() ^ prev)
+                .wrapping_sub(SerializedBytes::ENCODE_ADD_TABLE[j % 4])
+                & 0x3F;
+            if j >= 2 {
+                let old = self.bytes[j];
+                self.sum_add = self.sum_add.wrapping_sub(old).wrapping_add(b) & 0x3F;
+                self.sum_xor ^= old ^ b;
+            }
+            self.bytes[j] = b;
+        }
+    }
+
+    /// `SerializedBytes::checksum_is_ok()` と同じ判定を行う。
+    fn is_valid(&self) -> bool {
+        let embed_xor = self.bytes.get(1).copied().unwrap_or(0x3F);
+        if self.bytes.len() <= 2 {
+            return (self.bytes[0], embed_xor) == (0x3F, 0x3F);
+        }
+
+        (self.bytes[0], embed_xor) == (self.sum_add, self.sum_xor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools as _;
+
+    use super::*;
+
+    /// 総当たりで求めた、ちょうど `n_edits` 文字の置換で有効になるパスワード(辞書順)。
+    fn naive(password: &Password, n_edits: usize) -> Vec<Password> {
+        let mut res: Vec<Password> = itertools::repeat_n(PasswordChar::iter(), password.len())
+            .multi_cartesian_product()
+            .map(|chars| Password::new(&chars).unwrap())
+            .filter(|cand| {
+                cand.iter()
+                    .zip(password.iter())
+                    .filter(|(a, b)| a != b)
+                    .count()
+                    == n_edits
+                    && cand.is_valid()
+            })
+            .collect();
+        res.sort_unstable();
+        res
+    }
+
+    #[test]
+    fn test_repair_naive() {
+        for s in ["ふ", "あ", "あい", "ふぽ", "おにの"] {
+            let password = Password::parse(s).unwrap();
+            let mut expected = naive(&password, 0);
+            expected.extend(naive(&password, 1));
+            expected.extend(naive(&password, 2));
+            assert_eq!(password.repair(2), expected, "{s}");
+            assert_eq!(password.repair(5), password.repair(password.len()));
+        }
+    }
+
+    #[test]
+    fn test_single_edit_variants() {
+        for s in [
+            "ふ",
+            "おにのばか",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            let password = Password::parse(s).unwrap();
+            let variants: Vec<_> = password.single_edit_variants().collect();
+            assert_eq!(variants.len(), password.len() * 63);
+            assert!(!variants.contains(&password));
+            assert!(variants.iter().all(|v| {
+                v.len() == password.len()
+                    && v.iter()
+                        .zip(password.iter())
+                        .filter(|(a, b)| a != b)
+                        .count()
+                        == 1
+            }));
+
+            // 全て異なる。
+            let set: std::collections::BTreeSet<_> = variants.iter().collect();
+            assert_eq!(set.len(), variants.len());
+        }
+    }
+
+    #[test]
+    fn test_typo_collisions() {
+        for s in [
+            "ふ",
+            "おにのばか",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            let password = Password::parse(s).unwrap();
+            let collisions = password.typo_collisions();
+
+            // repair(1) から自身を除いたものと(順序を除いて)一致する。
+            let mut expected = password.repair(1);
+            expected.retain(|p| *p != password);
+            let mut actual: Vec<_> = collisions.iter().map(|(p, _)| p.clone()).collect();
+            actual.sort_unstable();
+            assert_eq!(actual, expected);
+
+            for (p, savedata) in &collisions {
+                assert_eq!(
+                    SerializedBytes::from_password(p).to_savedata().as_ref(),
+                    Some(savedata)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzzy() {
+        let original = "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ";
+        assert_eq!(
+            Password::parse_fuzzy(original, 0).unwrap(),
+            [Password::parse(original).unwrap()]
+        );
+
+        // 'ば' を 'ぱ' と誤認識したものから元に戻せる。
+        let res = Password::parse_fuzzy("おにのぱかへ", 1).unwrap();
+        assert_eq!(res[0], Password::parse("おにのばかへ").unwrap());
+        assert!(!Password::parse("おにのぱかへ").unwrap().is_valid());
+
+        // 'び' を 'ぴ' と誤認識したもの。
+        let corrupted = Password::parse(&original.replacen('び', "ぴ", 1)).unwrap();
+        assert!(!corrupted.is_valid());
+        let res = Password::parse_fuzzy(&corrupted.display().to_string(), 1).unwrap();
+        assert_eq!(res[0], Password::parse(original).unwrap());
+
+        // 置換は濁点/半濁点の違いに限られ、置換した文字数の昇順、同じ文字数の中では辞書順に並ぶ。
+        let res = Password::parse_fuzzy(&corrupted.display().to_string(), 3).unwrap();
+        assert!(res.iter().all(|p| p.is_valid()
+            && p.iter()
+                .zip(corrupted.iter())
+                .all(|(a, b)| a.base() == b.base())));
+        let n_edits = |p: &Password| p.hamming_distance(&corrupted).unwrap();
+        assert!(res
+            .windows(2)
+            .all(|w| (n_edits(&w[0]), &w[0]) < (n_edits(&w[1]), &w[1])));
+        assert_eq!(res[0], Password::parse(original).unwrap());
+
+        // 濁点/半濁点を持たない文字は置換されない。
+        assert_eq!(Password::parse_fuzzy("あ", 1).unwrap(), []);
+        assert_eq!(
+            Password::parse_fuzzy("ぷ", 1).unwrap(),
+            [Password::parse("ふ").unwrap()]
+        );
+
+        assert_eq!(
+            Password::parse_fuzzy("x", 1),
+            Err(FuzzyParseError::Parse(PasswordParseError::InvalidChar {
+                pos: 0,
+                ch: 'x'
+            }))
+        );
+        assert!(matches!(
+            Password::parse_fuzzy(&"ぱ".repeat(Password::MAX_LEN), Password::MAX_LEN),
+            Err(FuzzyParseError::TooManyCandidates { count, max })
+                if count == 3_u64.pow(Password::MAX_LEN as u32) && max == Password::FUZZY_CANDIDATES_MAX
+        ));
+    }
+
+    #[test]
+    fn test_repair() {
+        let original =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        assert_eq!(original.repair(0), std::slice::from_ref(&original));
+        let res = original.repair(1);
+        assert_eq!(res[0], original);
+        assert!(res.iter().all(Password::is_valid));
+
+        // 1 文字を変えたものは 1 文字の置換で元に戻せる。
+        let mut state: u32 = 1;
+        let mut rand = move |n: usize| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 16) as usize % n
+        };
+        for _ in 0..20 {
+            let mut chars = original.to_vec();
+            let i = rand(chars.len());
+            chars[i] = PasswordChar::ALL[(chars[i] as usize + 1 + rand(63)) % 64];
+            let corrupted = Password::new(&chars).unwrap();
+
+            let res = corrupted.repair(1);
+            assert!(res.contains(&original));
+            assert!(res.iter().all(Password::is_valid));
+            assert!(!res.contains(&corrupted));
+        }
+
+        // 2 文字を変えたものは 2 文字の置換で元に戻せる。
+        for _ in 0..3 {
+            let mut chars = original.to_vec();
+            let i = rand(chars.len());
+            let j = (i + 1 + rand(chars.len() - 1)) % chars.len();
+            chars[i] = PasswordChar::ALL[(chars[i] as usize + 1 + rand(63)) % 64];
+            chars[j] = PasswordChar::ALL[(chars[j] as usize + 1 + rand(63)) % 64];
+            let corrupted = Password::new(&chars).unwrap();
+
+            let res = corrupted.repair(2);
+            assert!(res.contains(&original));
+            assert!(res.iter().all(Password::is_valid));
+
+            // 置換した文字数の昇順、同じ文字数の中では辞書順に並ぶ。
+            let n_edits = |p: &Password| {
+                p.iter()
+                    .zip(corrupted.iter())
+                    .filter(|(a, b)| a != b)
+                    .count()
+            };
+            assert!(res
+                .windows(2)
+                .all(|w| (n_edits(&w[0]), &w[0]) < (n_edits(&w[1]), &w[1])));
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! パスワードに関する情報をまとめたプレーンテキストのレポート。
+//!
+//! CLI や Web など、複数の出力先で同じ内容を表示するためのもの。出力形式は安定させる。
+
+use std::fmt::Write as _;
+
+use crate::fields::SavedataFieldKind;
+use crate::layout::{FieldId, SERIALIZED_LAYOUT};
+use crate::password::{Password, SpecialPassword};
+use crate::savedata::Savedata;
+use crate::serialized::SerializedBytes;
+
+/// パスワードのレポートを返す。
+///
+/// 表記、分類、チェックサム、生/ロード後のセーブデータ、パディングされたフィールド、警告を含む。
+pub fn render(password: &Password) -> String {
+    let mut buf = String::new();
+    write_report(&mut buf, password).unwrap();
+    buf
+}
+
+/// セーブデータのレポートを返す。セーブデータをエンコードしたパスワードについて `render` を呼ぶ。
+pub fn render_savedata(savedata: &Savedata) -> String {
+    render(&SerializedBytes::from_savedata(savedata).to_password())
+}
+
+fn write_report(w: &mut String, password: &Password) -> std::fmt::Result {
+    let bytes = SerializedBytes::from_password(password);
+    let savedata = bytes.to_savedata();
+
+    writeln!(w, "password: {}", password.display_pretty())?;
+    writeln!(w, "hex:      {}", password.display_hex())?;
+    writeln!(w, "length:   {}/{}", password.len(), Password::MAX_LEN)?;
+    writeln!(w, "status:   {}", classify(password, savedata.is_some()))?;
+
+    let embed = bytes.checksum_embed();
+    let calculated = bytes.checksum_calculated();
+    writeln!(w)?;
+    writeln!(w, "checksum:   add  xor")?;
+    writeln!(
+        w,
+        "  embed       {:02X}   {:02X}",
+        embed.sum_add().get(),
+        embed.sum_xor().get()
+    )?;
+    writeln!(
+        w,
+        "  calculated  {:02X}   {:02X}",
+        calculated.sum_add().get(),
+        calculated.sum_xor().get()
+    )?;
+
+    let mut warnings = vec![];
+    if password.is_special() {
+        warnings
+            .push("special password: the game enters a special room instead of loading".to_owned());
+    }
+
+    writeln!(w)?;
+    match savedata {
+        Some(savedata) => {
+            let normalized = savedata.normalize();
+            write_savedata(w, &savedata, &normalized)?;
+            write_padding(w, &padded_fields(&bytes, &savedata))?;
+            warnings.extend(savedata_warnings(password, &savedata, &normalized));
+        }
+        None => writeln!(w, "savedata: (checksum mismatch)")?,
+    }
+
+    writeln!(w)?;
+    if warnings.is_empty() {
+        writeln!(w, "warnings: none")?;
+    } else {
+        writeln!(w, "warnings:")?;
+        for warning in warnings {
+            writeln!(w, "  - {warning}")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_savedata(w: &mut String, savedata: &Savedata, normalized: &Savedata) -> std::fmt::Result {
+    writeln!(w, "savedata:")?;
+    writeln!(w, "  {:<24} {:>6} {:>6}", "field", "raw", "loaded")?;
+    for field in Savedata::FIELDS {
+        let raw = field.get(savedata);
+        let loaded = field.get(normalized);
+        writeln!(
+            w,
+            "  {:<24} {:>6} {:>6}{}",
+            field.name(),
+            format_value(field.kind(), raw),
+            format_value(field.kind(), loaded),
+            if raw == loaded { "" } else { "  *" }
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_padding(w: &mut String, padded: &[(String, usize, usize)]) -> std::fmt::Result {
+    writeln!(w)?;
+    if padded.is_empty() {
+        writeln!(w, "padding: none")?;
+    } else {
+        writeln!(w, "padding (missing bits are read as 1):")?;
+        for (name, n_padded, n_bits) in padded {
+            writeln!(w, "  {name:<24} {n_padded}/{n_bits} bits")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn classify(password: &Password, valid: bool) -> &'static str {
+    match password.special_kind() {
+        Some(SpecialPassword::Audio) => "special (audio room)",
+        Some(SpecialPassword::Enemy) => "special (enemy room)",
+        None if valid => "valid",
+        None => "invalid (checksum mismatch)",
+    }
+}
+
+fn format_value(kind: SavedataFieldKind, value: u16) -> String {
+    match kind {
+        SavedataFieldKind::Bool => if value != 0 { "true" } else { "false" }.to_owned(),
+        SavedataFieldKind::Item if value == 0 => "-".to_owned(),
+        SavedataFieldKind::Item => format!("0x{value:02X}"),
+        SavedataFieldKind::Int { .. } => value.to_string(),
+    }
+}
+
+/// パスワードの長さが足りず、ビットが補われたフィールドを (名前, 補われたビット数, ビット数) として返す。
+fn padded_fields(bytes: &SerializedBytes, savedata: &Savedata) -> Vec<(String, usize, usize)> {
+    // 先頭 2 バイトはチェックサム。
+    let n_avail = 6 * bytes.len().saturating_sub(2);
+
+    // インベントリは終端の 0 を含めて読まれる(満杯なら終端はない)。
+    let n_slots = (savedata.inventory.len() + 1).min(savedata.inventory.capacity());
+
+    let mut res = vec![];
+    for spec in SERIALIZED_LAYOUT {
+        if matches!(spec.field, FieldId::InventorySlot(i) if usize::from(i) >= n_slots) {
+            break;
+        }
+        let range = spec.bits();
+        let n_padded = range.end - range.end.min(n_avail.max(range.start));
+        if n_padded > 0 {
+            res.push((spec.field.to_string(), n_padded, spec.bit_len));
+        }
+    }
+
+    res
+}
+
+/// ロード時の装備の変化、未知または未検証の復活地点、および正規形でないことに関する警告を返す。
+fn savedata_warnings(
+    password: &Password,
+    savedata: &Savedata,
+    normalized: &Savedata,
+) -> Vec<String> {
+    let mut res = vec![];
+
+    for field in Savedata::FIELDS {
+        let raw = field.get(savedata);
+        let loaded = field.get(normalized);
+        if raw != loaded {
+            res.push(format!(
+                "{} changes on load: {} -> {}",
+                field.name(),
+                format_value(field.kind(), raw),
+                format_value(field.kind(), loaded)
+            ));
+        }
+    }
+
+    match savedata.respawn.is_valid_location() {
+        Some(true) => {}
+        Some(false) => res.push(format!(
+            "respawn 0x{:X} is not a known location",
+            savedata.respawn
+        )),
+        None => res.push(format!(
+            "respawn 0x{:X} is not verified as a location",
+            savedata.respawn
+        )),
+    }
+
+    let canonical = SerializedBytes::from_savedata(savedata).to_password();
+    if &canonical != password {
+        res.push(format!(
+            "not canonical: re-encoding gives {}",
+            canonical.display_pretty()
+        ));
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_golden(password: &str, golden: &str) {
+        let password = Password::parse(password).unwrap();
+        assert_eq!(render(&password), golden);
+    }
+
+    #[test]
+    fn test_render_valid() {
+        assert_golden("おにのばか", include_str!("../testdata/report/valid.txt"));
+    }
+
+    #[test]
+    fn test_render_invalid() {
+        assert_golden("おにのばき", include_str!("../testdata/report/invalid.txt"));
+    }
+
+    #[test]
+    fn test_render_special() {
+        assert_golden(
+            "すべてのきよくがききたいな",
+            include_str!("../testdata/report/special.txt"),
+        );
+    }
+
+    #[test]
+    fn test_render_savedata() {
+        let savedata = Savedata::default();
+        let report = render_savedata(&savedata);
+
+        assert!(report.contains("status:   valid\n"));
+        assert!(report.contains("padding: none\n"));
+        // 既定値の復活地点 0x0 は未検証。
+        assert!(report.contains("warnings:\n  - respawn 0x0 is not verified as a location\n"));
+
+        let savedata = Savedata {
+            respawn: crate::savedata::RespawnId::MAX,
+            ..Savedata::default()
+        };
+        assert!(render_savedata(&savedata).contains("warnings: none\n"));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::password::{Password, PasswordBuilder, PasswordChar, PasswordParseError};
+
+/// 内部値からローマ字(ヘボン式)への変換表。
+#[rustfmt::skip]
+const TO_ROMAJI_TABLE: [&str; 0x40] = [
+    "a",  "i",   "u",   "e",  "o",
+    "ka", "ki",  "ku",  "ke", "ko",
+    "sa", "shi", "su",  "se", "so",
+    "ta", "chi", "tsu", "te", "to",
+    "na", "ni",  "nu",  "ne", "no",
+    "ha", "hi",  "fu",  "he", "ho",
+    "ma", "mi",  "mu",  "me", "mo",
+    "ya", "yu",  "yo",
+    "ra", "ri",  "ru",  "re", "ro",
+    "wa",
+    "ga", "gi",  "gu",  "ge", "go",
+    "za", "ji",  "zu",  "ze", "zo",
+    "ba", "bi",  "bu",  "be", "bo",
+    "pa", "pi",  "pu",  "pe", "po",
+];
+
+/// ヘボン式以外に受け付けるつづり(訓令式)。
+const ROMAJI_ALIASES: &[(&str, PasswordChar)] = &[
+    ("si", PasswordChar::Si),
+    ("ti", PasswordChar::Ti),
+    ("tu", PasswordChar::Tu),
+    ("hu", PasswordChar::Hu),
+    ("zi", PasswordChar::Zi),
+];
+
+impl PasswordChar {
+    /// 対応するローマ字(ヘボン式、小文字)を返す。
+    pub const fn to_romaji(self) -> &'static str {
+        TO_ROMAJI_TABLE[self as usize]
+    }
+
+    /// ローマ字 1 音節(小文字)を `PasswordChar` に変換する。無効なつづりに対しては `None` を返す。
+    fn from_romaji(s: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|pc| pc.to_romaji() == s)
+            .or_else(|| {
+                ROMAJI_ALIASES
+                    .iter()
+                    .find(|&&(alias, _)| alias == s)
+                    .map(|&(_, pc)| pc)
+            })
+    }
+}
+
+impl Password {
+    /// ローマ字文字列をパースして `Password` を作る。
+    ///
+    /// ヘボン式と訓令式のつづり("shi"/"si" など)を受け付け、大文字小文字は区別しない。
+    /// 無効な音節があれば、その先頭のバイト位置を `pos` とする `InvalidChar` エラーを返す
+    /// (それより前は全て ASCII なので、文字位置とも一致する)。
+    pub fn parse_romaji(s: &str) -> Result<Self, PasswordParseError> {
+        let mut builder = PasswordBuilder::new();
+
+        let mut rest = s;
+        while let Some(ch) = rest.chars().next() {
+            let pos = s.len() - rest.len();
+            let err = PasswordParseError::InvalidChar { pos, ch };
+
+            // 全ての音節は母音で終わり、母音はその 1 文字のみなので、最初の母音までが 1 音節。
+            let len = rest
+                .find(|c: char| matches!(c.to_ascii_lowercase(), 'a' | 'i' | 'u' | 'e' | 'o'))
+                .ok_or_else(|| err.clone())?
+                + 1;
+            let syllable = rest[..len].to_ascii_lowercase();
+            let pc = PasswordChar::from_romaji(&syllable).ok_or(err)?;
+
+            builder.push(pc);
+            if builder.is_overflow() {
+                break;
+            }
+            rest = &rest[len..];
+        }
+
+        builder.build()
+    }
+
+    /// パスワードをローマ字(ヘボン式、小文字)としてフォーマットする `Display` オブジェクトを返す。
+    ///
+    /// 音節を区切るには `PasswordDisplayRomaji::separated()` を使う。
+    pub fn display_romaji(&self) -> PasswordDisplayRomaji<'_> {
+        PasswordDisplayRomaji {
+            password: self,
+            separator: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PasswordDisplayRomaji<'a> {
+    password: &'a Password,
+    separator: Option<char>,
+}
+
+impl PasswordDisplayRomaji<'_> {
+    /// 音節の間に `separator` を挟むようにする。
+    pub fn separated(self, separator: char) -> Self {
+        Self {
+            separator: Some(separator),
+            ..self
+        }
+    }
+}
+
+impl std::fmt::Display for PasswordDisplayRomaji<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, pc) in self.password.iter().enumerate() {
+            if let Some(sep) = self.separator.filter(|_| i != 0) {
+                write!(f, "{sep}")?;
+            }
+            f.write_str(pc.to_romaji())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::assert_equal;
+
+    use super::*;
+
+    #[test]
+    fn test_password_char_romaji() {
+        for pc in PasswordChar::all() {
+            assert_eq!(PasswordChar::from_romaji(pc.to_romaji()), Some(pc));
+            assert_equal(
+                Password::parse_romaji(&pc.to_romaji().to_ascii_uppercase()).unwrap(),
+                [pc],
+            );
+        }
+
+        // 全てのつづりが異なる。
+        let romajis: std::collections::BTreeSet<_> =
+            PasswordChar::all().map(PasswordChar::to_romaji).into();
+        assert_eq!(romajis.len(), 0x40);
+
+        assert_eq!(PasswordChar::Si.to_romaji(), "shi");
+        assert_eq!(PasswordChar::Ti.to_romaji(), "chi");
+        assert_eq!(PasswordChar::Tu.to_romaji(), "tsu");
+        assert_eq!(PasswordChar::Hu.to_romaji(), "fu");
+        assert_eq!(PasswordChar::Zi.to_romaji(), "ji");
+    }
+
+    #[test]
+    fn test_password_parse_romaji() {
+        assert_eq!(
+            Password::parse_romaji("oninobaka"),
+            Password::parse("おにのばか")
+        );
+        assert_eq!(
+            Password::parse_romaji("OniNoBaka"),
+            Password::parse("おにのばか")
+        );
+        assert_eq!(
+            Password::parse_romaji("sishitichitutsuhufuziji"),
+            Password::parse("ししちちつつふふじじ")
+        );
+    }
+
+    #[test]
+    fn test_password_display_romaji() {
+        #[rustfmt::skip]
+        const EXPECTED: [&str; 0x40] = [
+            "a",  "i",   "u",   "e",  "o",
+            "ka", "ki",  "ku",  "ke", "ko",
+            "sa", "shi", "su",  "se", "so",
+            "ta", "chi", "tsu", "te", "to",
+            "na", "ni",  "nu",  "ne", "no",
+            "ha", "hi",  "fu",  "he", "ho",
+            "ma", "mi",  "mu",  "me", "mo",
+            "ya", "yu",  "yo",
+            "ra", "ri",  "ru",  "re", "ro",
+            "wa",
+            "ga", "gi",  "gu",  "ge", "go",
+            "za", "ji",  "zu",  "ze", "zo",
+            "ba", "bi",  "bu",  "be", "bo",
+            "pa", "pi",  "pu",  "pe", "po",
+        ];
+        for (pc, expected) in PasswordChar::all().into_iter().zip(EXPECTED) {
+            let password = Password::new(&[pc]).unwrap();
+            assert_eq!(password.display_romaji().to_string(), expected, "{pc:?}");
+        }
+
+        let password = Password::parse("おにのばか").unwrap();
+        assert_eq!(password.display_romaji().to_string(), "oninobaka");
+        assert_eq!(
+            password.display_romaji().separated('-').to_string(),
+            "o-ni-no-ba-ka"
+        );
+        assert_eq!(
+            password.display_romaji().separated(' ').to_string(),
+            "o ni no ba ka"
+        );
+        assert_eq!(
+            Password::parse("ふ")
+                .unwrap()
+                .display_romaji()
+                .separated('-')
+                .to_string(),
+            "fu"
+        );
+    }
+
+    #[test]
+    fn test_password_romaji_roundtrip() {
+        for s in [
+            "ふ",
+            "おにのばか",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            let password = Password::parse(s).unwrap();
+            let romaji = password.display_romaji().to_string();
+            assert_eq!(Password::parse_romaji(&romaji), Ok(password.clone()));
+        }
+
+        // 全ての文字を含むパスワード。
+        for chunk in PasswordChar::all().chunks(Password::MAX_LEN) {
+            let password = Password::new(chunk).unwrap();
+            let romaji = password.display_romaji().to_string();
+            assert_eq!(Password::parse_romaji(&romaji), Ok(password));
+        }
+    }
+
+    #[test]
+    fn test_password_parse_romaji_error() {
+        assert_eq!(
+            Password::parse_romaji(""),
+            Err(PasswordParseError::InvalidLength { len: 0 })
+        );
+        assert_eq!(
+            Password::parse_romaji(&"a".repeat(Password::MAX_LEN + 1)),
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+
+        // 存在しない音節。
+        assert_eq!(
+            Password::parse_romaji("onikyabaka"),
+            Err(PasswordParseError::InvalidChar { pos: 3, ch: 'k' })
+        );
+        assert_eq!(
+            Password::parse_romaji("oniwo"),
+            Err(PasswordParseError::InvalidChar { pos: 3, ch: 'w' })
+        );
+        // 末尾が母音で終わっていない。
+        assert_eq!(
+            Password::parse_romaji("oninobak"),
+            Err(PasswordParseError::InvalidChar { pos: 7, ch: 'k' })
+        );
+        assert_eq!(
+            Password::parse_romaji("onin"),
+            Err(PasswordParseError::InvalidChar { pos: 3, ch: 'n' })
+        );
+        // ASCII 以外の文字。
+        assert_eq!(
+            Password::parse_romaji("oniのbaka"),
+            Err(PasswordParseError::InvalidChar { pos: 3, ch: 'の' })
+        );
+        assert_eq!(
+            Password::parse_romaji("oni baka"),
+            Err(PasswordParseError::InvalidChar { pos: 3, ch: ' ' })
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use arrayvec::ArrayVec;
 
-use crate::bounded::BoundedU8;
-use crate::macros::unreachable_unchecked;
+use crate::define_bounded;
+use crate::lang::{DisplayIn, FlagName, Lang};
+use crate::macros::new_unchecked;
 
 /// パスワードに記録されるゲーム状態。
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Savedata {
     /// 経験値。
     pub xp: u16,
@@ -31,9 +35,17 @@ pub struct Savedata {
     /// 装備。
     pub equipment: Equipment,
     /// インベントリ。
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_inventory))]
     pub inventory: Inventory,
 }
 
+/// 長さ 0..=8 のインベントリを作る。
+#[cfg(feature = "arbitrary")]
+fn arbitrary_inventory(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Inventory> {
+    let len = u.int_in_range(0..=Inventory::new().capacity())?;
+    (0..len).map(|_| u.arbitrary()).collect()
+}
+
 impl Savedata {
     /// このセーブデータを実際にロードした後の状態を返す。
     ///
@@ -45,13 +57,115 @@ impl Savedata {
             ..*self
         }
     }
+
+    /// 主要なフィールドを 1 行にまとめて表示するためのオブジェクトを返す。
+    ///
+    /// 例: `Lv? xp=65535 金=65535/預63 年齢255 術8/8 宝5/5 供3/3 旅10/10 復活F 荷8`
+    ///
+    /// フィールドの順序は `Savedata` のフィールド順(JSON 出力と同じ)で、各フィールドは固定幅なので
+    /// 複数行を並べると桁が揃う。経験値とレベルの対応表はまだないので、レベルは常に `?` となる。
+    pub fn summary(&self) -> SavedataSummary<'_> {
+        SavedataSummary { savedata: self }
+    }
+}
+
+#[derive(Debug)]
+pub struct SavedataSummary<'a> {
+    savedata: &'a Savedata,
+}
+
+impl std::fmt::Display for SavedataSummary<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// 立っているフラグの数を、フラグの総数と同じ桁数に右詰めして書き込む。
+        fn write_count<const N: usize>(
+            f: &mut std::fmt::Formatter<'_>,
+            flags: [(bool, &str); N],
+        ) -> std::fmt::Result {
+            let n = flags.iter().filter(|(set, _)| *set).count();
+            let width = N.to_string().len();
+            write!(f, "{n:width$}/{N}")
+        }
+
+        let s = self.savedata;
+        write!(
+            f,
+            "Lv? xp={:5} 金={:5}/預{:2} 年齢{:3}",
+            s.xp,
+            s.purse,
+            s.deposit.get(),
+            s.age
+        )?;
+        f.write_str(" 術")?;
+        write_count(f, s.spells.flags())?;
+        f.write_str(" 宝")?;
+        write_count(f, s.treasures.flags())?;
+        f.write_str(" 供")?;
+        write_count(f, s.minions.flags())?;
+        f.write_str(" 旅")?;
+        write_count(f, s.bookmarks.flags())?;
+        write!(f, " 復活{:X} 荷{}", s.respawn.get(), s.inventory.len())
+    }
 }
 
-/// 預金 (6bit)。
-pub type Deposit = BoundedU8<0, 0x3F>;
+/// フィールドごとに 1 行ずつ表示する(末尾に改行は付かない)。
+impl std::fmt::Display for Savedata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "経験値: {}", self.xp)?;
+        writeln!(f, "所持金: {}", self.purse)?;
+        writeln!(f, "預金: {}", self.deposit)?;
+        writeln!(f, "年齢: {}", self.age)?;
+        writeln!(f, "加齢タイマー上位バイト: {}", self.age_timer_hi)?;
+        writeln!(f, "術: {}", self.spells)?;
+        writeln!(f, "イベント: {}", self.events)?;
+        writeln!(f, "宝物: {}", self.treasures)?;
+        writeln!(f, "お供: {}", self.minions)?;
+        writeln!(f, "ひえんブックマーク: {}", self.bookmarks)?;
+        writeln!(f, "復活地点ID: {}", self.respawn)?;
+        writeln!(f, "装備: {}", self.equipment)?;
+        write!(f, "インベントリ: {}", display_inventory(&self.inventory))
+    }
+}
+
+/// 立っているフラグの名前を `", "` 区切りで書き込む。1 つも立っていなければ `"なし"` (英語では `"none"`) と書き込む。
+fn write_flags(
+    f: &mut std::fmt::Formatter<'_>,
+    flags: &[(bool, &str)],
+    lang: Lang,
+) -> std::fmt::Result {
+    let mut names = flags.iter().filter(|(set, _)| *set).map(|(_, name)| name);
+
+    let Some(first) = names.next() else {
+        return f.write_str(match lang {
+            Lang::Ja => "なし",
+            Lang::En => "none",
+        });
+    };
+    f.write_str(first)?;
+    for name in names {
+        write!(f, ", {name}")?;
+    }
+
+    Ok(())
+}
+
+/// `Savedata` をシリアライズした JSON の JSON Schema を返す。
+///
+/// 値域制限付き整数は `minimum`/`maximum` で、フラグ群は真偽値のオブジェクトで表される。
+#[cfg(feature = "schemars")]
+pub fn schema_for_savedata() -> serde_json::Value {
+    schemars::schema_for!(Savedata).to_value()
+}
+
+define_bounded! {
+    /// 預金 (6bit)。
+    pub struct Deposit: u8 in 0..=0x3F;
+}
 
 /// 術習得状態。
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Spells {
     /// きんたん
     pub kintan: bool,
@@ -97,8 +211,63 @@ impl Spells {
     };
 }
 
+/// 覚えた術の名前を列挙する。
+impl Spells {
+    /// 各フラグの名前(フィールド順)。
+    pub const NAMES: [FlagName; 8] = [
+        FlagName::new("きんたん", None),
+        FlagName::new("ろっかく", None),
+        FlagName::new("いなずま", None),
+        FlagName::new("ひえん", None),
+        FlagName::new("まんきんたん", None),
+        FlagName::new("ふゆう", None),
+        FlagName::new("だだぢぢ", None),
+        FlagName::new("ほうひ", None),
+    ];
+
+    /// (フラグ, 日本語名) の配列を返す。
+    pub(crate) fn flags(&self) -> [(bool, &'static str); 8] {
+        self.flags_in(Lang::Ja)
+    }
+
+    /// (フラグ, 指定した言語での名前) の配列を返す。
+    pub(crate) fn flags_in(&self, lang: Lang) -> [(bool, &'static str); 8] {
+        let values = [
+            self.kintan,
+            self.rokkaku,
+            self.inazuma,
+            self.hien,
+            self.mankintan,
+            self.fuyuu,
+            self.dadadidi,
+            self.houhi,
+        ];
+        std::array::from_fn(|i| (values[i], Self::NAMES[i].name_in(lang)))
+    }
+
+    /// 指定した言語で表示するためのオブジェクトを返す。`Display` は日本語で表示する。
+    pub fn display_in(&self, lang: Lang) -> DisplayIn<'_, Self> {
+        DisplayIn { value: self, lang }
+    }
+}
+
+impl std::fmt::Display for Spells {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.display_in(Lang::Ja), f)
+    }
+}
+
+impl std::fmt::Display for DisplayIn<'_, Spells> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_flags(f, &self.value.flags_in(self.lang), self.lang)
+    }
+}
+
 /// イベント進行状態。
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Events {
     /// 花咲かの村で銀の鬼を倒した
     pub hanasaka: bool,
@@ -144,8 +313,81 @@ impl Events {
     };
 }
 
+/// 終えたイベントを列挙する。
+impl Events {
+    /// 各フラグの名前(フィールド順)。
+    pub const NAMES: [FlagName; 8] = [
+        FlagName::new(
+            "花咲かの村で銀の鬼を倒した",
+            Some("Defeated the Silver Oni in Hanasaka Village"),
+        ),
+        FlagName::new(
+            "金太郎の村で金の鬼を倒した",
+            Some("Defeated the Gold Oni in Kintaro Village"),
+        ),
+        FlagName::new(
+            "浦島の村でパールの鬼を倒した",
+            Some("Defeated the Pearl Oni in Urashima Village"),
+        ),
+        FlagName::new("寝太郎を起こした", Some("Woke Netaro")),
+        FlagName::new(
+            "寝太郎の村で村田の情報を聞いた",
+            Some("Heard about Murata in Netaro Village"),
+        ),
+        FlagName::new("やまんばを倒した", Some("Defeated Yamanba")),
+        FlagName::new(
+            "寝太郎の村でリュウのくびかざりを盗まれた",
+            Some("Had the Dragon Necklace stolen in Netaro Village"),
+        ),
+        FlagName::new(
+            "微笑みの村の通行許可を得た",
+            Some("Got permission to enter Hohoemi Village"),
+        ),
+    ];
+
+    /// (フラグ, 日本語名) の配列を返す。
+    pub(crate) fn flags(&self) -> [(bool, &'static str); 8] {
+        self.flags_in(Lang::Ja)
+    }
+
+    /// (フラグ, 指定した言語での名前) の配列を返す。
+    pub(crate) fn flags_in(&self, lang: Lang) -> [(bool, &'static str); 8] {
+        let values = [
+            self.hanasaka,
+            self.kintaro,
+            self.urashima,
+            self.netaro,
+            self.murata,
+            self.sarukani,
+            self.dragon,
+            self.hohoemi,
+        ];
+        std::array::from_fn(|i| (values[i], Self::NAMES[i].name_in(lang)))
+    }
+
+    /// 指定した言語で表示するためのオブジェクトを返す。`Display` は日本語で表示する。
+    pub fn display_in(&self, lang: Lang) -> DisplayIn<'_, Self> {
+        DisplayIn { value: self, lang }
+    }
+}
+
+impl std::fmt::Display for Events {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.display_in(Lang::Ja), f)
+    }
+}
+
+impl std::fmt::Display for DisplayIn<'_, Events> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_flags(f, &self.value.flags_in(self.lang), self.lang)
+    }
+}
+
 /// 宝物所持状態。
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Treasures {
     /// リュウのくびかざり
     pub dragon: bool,
@@ -179,8 +421,57 @@ impl Treasures {
     };
 }
 
+/// 持っている宝物の名前を列挙する。
+impl Treasures {
+    /// 各フラグの名前(フィールド順)。
+    pub const NAMES: [FlagName; 5] = [
+        FlagName::new("リュウのくびかざり", Some("Dragon Necklace")),
+        FlagName::new("キンいろのけがわ", Some("Golden Fur")),
+        FlagName::new("ホトケのおはち", Some("Buddha's Bowl")),
+        FlagName::new("ホウライのタマ", Some("Hourai Jewel")),
+        FlagName::new("ツバメのこやすがい", Some("Swallow's Cowrie")),
+    ];
+
+    /// (フラグ, 日本語名) の配列を返す。
+    pub(crate) fn flags(&self) -> [(bool, &'static str); 5] {
+        self.flags_in(Lang::Ja)
+    }
+
+    /// (フラグ, 指定した言語での名前) の配列を返す。
+    pub(crate) fn flags_in(&self, lang: Lang) -> [(bool, &'static str); 5] {
+        let values = [
+            self.dragon,
+            self.fur,
+            self.hotoke,
+            self.hourai,
+            self.swallow,
+        ];
+        std::array::from_fn(|i| (values[i], Self::NAMES[i].name_in(lang)))
+    }
+
+    /// 指定した言語で表示するためのオブジェクトを返す。`Display` は日本語で表示する。
+    pub fn display_in(&self, lang: Lang) -> DisplayIn<'_, Self> {
+        DisplayIn { value: self, lang }
+    }
+}
+
+impl std::fmt::Display for Treasures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.display_in(Lang::Ja), f)
+    }
+}
+
+impl std::fmt::Display for DisplayIn<'_, Treasures> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_flags(f, &self.value.flags_in(self.lang), self.lang)
+    }
+}
+
 /// お供存在状態。
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Minions {
     /// 犬
     pub dog: bool,
@@ -206,8 +497,49 @@ impl Minions {
     };
 }
 
+/// 連れているお供を列挙する。
+impl Minions {
+    /// 各フラグの名前(フィールド順)。
+    pub const NAMES: [FlagName; 3] = [
+        FlagName::new("犬", Some("Dog")),
+        FlagName::new("キジ", Some("Pheasant")),
+        FlagName::new("猿", Some("Monkey")),
+    ];
+
+    /// (フラグ, 日本語名) の配列を返す。
+    pub(crate) fn flags(&self) -> [(bool, &'static str); 3] {
+        self.flags_in(Lang::Ja)
+    }
+
+    /// (フラグ, 指定した言語での名前) の配列を返す。
+    pub(crate) fn flags_in(&self, lang: Lang) -> [(bool, &'static str); 3] {
+        let values = [self.dog, self.pheasant, self.monkey];
+        std::array::from_fn(|i| (values[i], Self::NAMES[i].name_in(lang)))
+    }
+
+    /// 指定した言語で表示するためのオブジェクトを返す。`Display` は日本語で表示する。
+    pub fn display_in(&self, lang: Lang) -> DisplayIn<'_, Self> {
+        DisplayIn { value: self, lang }
+    }
+}
+
+impl std::fmt::Display for Minions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.display_in(Lang::Ja), f)
+    }
+}
+
+impl std::fmt::Display for DisplayIn<'_, Minions> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_flags(f, &self.value.flags_in(self.lang), self.lang)
+    }
+}
+
 /// ひえんブックマーク。
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Bookmarks {
     /// 旅立ちの村
     pub tabidachi: bool,
@@ -261,11 +593,93 @@ impl Bookmarks {
     };
 }
 
-/// 復活地点ID (4bit)。
-pub type RespawnId = BoundedU8<0, 0xF>;
+/// ブックマークした場所を列挙する。
+impl Bookmarks {
+    /// 各フラグの名前(フィールド順)。
+    pub const NAMES: [FlagName; 10] = [
+        FlagName::new("旅立ちの村", Some("Tabidachi Village")),
+        FlagName::new("花咲かの村", Some("Hanasaka Village")),
+        FlagName::new("金太郎の村", Some("Kintaro Village")),
+        FlagName::new("浦島の村", Some("Urashima Village")),
+        FlagName::new("寝太郎の村", Some("Netaro Village")),
+        FlagName::new("希望の都", Some("Kibou Capital")),
+        FlagName::new("猿蟹の村", Some("Sarukani Village")),
+        FlagName::new("竹取の村", Some("Taketori Village")),
+        FlagName::new("微笑みの村", Some("Hohoemi Village")),
+        FlagName::new("飛燕の城", Some("Hien Castle")),
+    ];
+
+    /// (フラグ, 日本語名) の配列を返す。
+    pub(crate) fn flags(&self) -> [(bool, &'static str); 10] {
+        self.flags_in(Lang::Ja)
+    }
+
+    /// (フラグ, 指定した言語での名前) の配列を返す。
+    pub(crate) fn flags_in(&self, lang: Lang) -> [(bool, &'static str); 10] {
+        let values = [
+            self.tabidachi,
+            self.hanasaka,
+            self.kintaro,
+            self.urashima,
+            self.netaro,
+            self.kibou,
+            self.sarukani,
+            self.taketori,
+            self.hohoemi,
+            self.hien,
+        ];
+        std::array::from_fn(|i| (values[i], Self::NAMES[i].name_in(lang)))
+    }
+
+    /// 指定した言語で表示するためのオブジェクトを返す。`Display` は日本語で表示する。
+    pub fn display_in(&self, lang: Lang) -> DisplayIn<'_, Self> {
+        DisplayIn { value: self, lang }
+    }
+}
+
+impl std::fmt::Display for Bookmarks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.display_in(Lang::Ja), f)
+    }
+}
+
+impl std::fmt::Display for DisplayIn<'_, Bookmarks> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_flags(f, &self.value.flags_in(self.lang), self.lang)
+    }
+}
+
+define_bounded! {
+    /// 復活地点ID (4bit)。
+    ///
+    /// ロード時に値は変化しない(`Savedata::normalize()` もこれに倣う)。
+    /// 全て 0xF のパスワード「ふ」でロードしてすぐ神主に話しかけると、得られるパスワードの復活地点IDも 0xF のまま。
+    pub struct RespawnId: u8 in 0..=0xF;
+}
+
+impl RespawnId {
+    /// ゲームが復活地点として受け付ける値かどうかを返す。
+    ///
+    /// 受け付けると確認された値なら `Some(true)`、受け付けないと確認された値なら `Some(false)`、
+    /// 未確認なら `None` を返す。
+    ///
+    /// **未検証**: 実際の復活地点の一覧はまだ確かめられていない。
+    /// 0xF はロードしてもそのまま保たれ、神主に話しかけても同じ値のパスワードが得られることだけが分かっているので、
+    /// 現状は 0xF に対して `Some(true)`、それ以外に対して `None` を返す。
+    pub const fn is_valid_location(self) -> Option<bool> {
+        if self.get() == 0xF {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
 
 /// 装備。
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Equipment {
     pub helm: HelmIndex,
     pub weapon: WeaponIndex,
@@ -280,76 +694,576 @@ pub struct Equipment {
 impl Equipment {
     /// このセーブデータ内装備を実際にロードした後の装備を返す。
     ///
-    /// 装備品のインデックスが不正な場合、装備が変化する。
+    /// 装備品のインデックスが不正な場合、装備が変化する。各スロットの変化は `normalize_*()` を参照。
+    /// 不正なインデックスが次のスロットの装備に変化する場合、次のスロット自身のインデックスが正常ならばそちらが優先される。
     pub fn normalize(&self) -> Self {
-        let mut res = Self::default();
+        let (weapon, armor_from_weapon) = Self::normalize_weapon(self.weapon);
+        let (armor, shoes_from_armor) = Self::normalize_armor(self.armor);
+        let (shoes, accessory0_from_shoes) = Self::normalize_shoes(self.shoes);
 
-        match self.helm.get() {
-            0..=2 => res.helm = self.helm,
-            3 => {}
-            4.. => unsafe { unreachable_unchecked!() },
+        Self {
+            helm: Self::normalize_helm(self.helm).unwrap_or_default(),
+            weapon: weapon.unwrap_or_default(),
+            armor: armor.or(armor_from_weapon).unwrap_or_default(),
+            shoes: shoes.or(shoes_from_armor).unwrap_or_default(),
+            accessory0: Self::normalize_accessory0(self.accessory0)
+                .or(accessory0_from_shoes)
+                .unwrap_or_default(),
+            accessory1: Self::normalize_accessory1(self.accessory1).unwrap_or_default(),
+            accessory2: self.accessory2,
+            accessory3: self.accessory3,
         }
-        match self.weapon.get() {
-            0..=10 => res.weapon = self.weapon,
-            11..=12 => {}
-            x @ 13..=15 => res.armor = unsafe { ArmorIndex::new_unchecked(x - 12) },
-            16.. => unsafe { unreachable_unchecked!() },
+    }
+
+    /// 兜インデックスをロードした結果を返す。0..=2 はそのまま、3 は `None` (装備なし)。
+    pub fn normalize_helm(helm: HelmIndex) -> Option<HelmIndex> {
+        match helm.get() {
+            0..=2 => Some(helm),
+            3 => None,
+            4.. => unreachable!(),
         }
-        match self.armor.get() {
-            0..=9 => res.armor = self.armor,
-            10..=11 => {}
-            x @ 12..=15 => res.shoes = unsafe { ShoesIndex::new_unchecked(x - 11) },
-            16.. => unsafe { unreachable_unchecked!() },
+    }
+
+    /// 武器インデックスをロードした結果を (武器, 変化先の鎧) として返す。
+    ///
+    /// 0..=10 はそのまま、11..=12 は装備なし、13..=15 は武器なしで鎧 1..=3 に変化する。
+    pub fn normalize_weapon(weapon: WeaponIndex) -> (Option<WeaponIndex>, Option<ArmorIndex>) {
+        match weapon.get() {
+            0..=10 => (Some(weapon), None),
+            11..=12 => (None, None),
+            x @ 13..=15 => (None, Some(new_unchecked!(ArmorIndex, x - 12))),
+            16.. => unreachable!(),
         }
-        match self.shoes.get() {
-            0..=4 => res.shoes = self.shoes,
-            5..=6 => {}
-            7 => res.accessory0 = unsafe { Accessory0Index::new_unchecked(1) },
-            8.. => unsafe { unreachable_unchecked!() },
+    }
+
+    /// 鎧インデックスをロードした結果を (鎧, 変化先の靴) として返す。
+    ///
+    /// 0..=9 はそのまま、10..=11 は装備なし、12..=15 は鎧なしで靴 1..=4 に変化する。
+    pub fn normalize_armor(armor: ArmorIndex) -> (Option<ArmorIndex>, Option<ShoesIndex>) {
+        match armor.get() {
+            0..=9 => (Some(armor), None),
+            10..=11 => (None, None),
+            x @ 12..=15 => (None, Some(new_unchecked!(ShoesIndex, x - 11))),
+            16.. => unreachable!(),
         }
-        match self.accessory0.get() {
-            0..=2 => res.accessory0 = self.accessory0,
-            3 => {}
-            4.. => unsafe { unreachable_unchecked!() },
+    }
+
+    /// 靴インデックスをロードした結果を (靴, 変化先のいでたち0) として返す。
+    ///
+    /// 0..=4 はそのまま、5..=6 は装備なし、7 は靴なしでいでたち0 の 1 に変化する。
+    pub fn normalize_shoes(shoes: ShoesIndex) -> (Option<ShoesIndex>, Option<Accessory0Index>) {
+        match shoes.get() {
+            0..=4 => (Some(shoes), None),
+            5..=6 => (None, None),
+            7 => (None, Some(new_unchecked!(Accessory0Index, 1))),
+            8.. => unreachable!(),
         }
-        match self.accessory1.get() {
-            0..=2 => res.accessory1 = self.accessory1,
-            3 => {}
-            4.. => unsafe { unreachable_unchecked!() },
+    }
+
+    /// いでたち0インデックスをロードした結果を返す。0..=2 はそのまま、3 は `None` (装備なし)。
+    pub fn normalize_accessory0(accessory0: Accessory0Index) -> Option<Accessory0Index> {
+        match accessory0.get() {
+            0..=2 => Some(accessory0),
+            3 => None,
+            4.. => unreachable!(),
         }
-        res.accessory2 = self.accessory2;
-        res.accessory3 = self.accessory3;
+    }
 
-        res
+    /// いでたち1インデックスをロードした結果を返す。0..=2 はそのまま、3 は `None` (装備なし)。
+    pub fn normalize_accessory1(accessory1: Accessory1Index) -> Option<Accessory1Index> {
+        match accessory1.get() {
+            0..=2 => Some(accessory1),
+            3 => None,
+            4.. => unreachable!(),
+        }
     }
 }
 
-/// 兜インデックス (2bit)。
-pub type HelmIndex = BoundedU8<0, 3>;
+/// 各スロットのインデックスを `スロット: インデックス` の形で列挙する。
+impl std::fmt::Display for Equipment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "兜: {}, 武器: {}, 鎧: {}, 靴: {}, いでたち0: {}, いでたち1: {}, いでたち2: {}, いでたち3: {}",
+            self.helm,
+            self.weapon,
+            self.armor,
+            self.shoes,
+            self.accessory0,
+            self.accessory1,
+            self.accessory2,
+            self.accessory3,
+        )
+    }
+}
 
-/// 武器インデックス (4bit)。
-pub type WeaponIndex = BoundedU8<0, 0xF>;
+define_bounded! {
+    /// 兜インデックス (2bit)。
+    pub struct HelmIndex: u8 in 0..=3;
 
-/// 鎧インデックス (4bit)。
-pub type ArmorIndex = BoundedU8<0, 0xF>;
+    /// 武器インデックス (4bit)。
+    pub struct WeaponIndex: u8 in 0..=0xF;
 
-/// 靴インデックス (3bit)。
-pub type ShoesIndex = BoundedU8<0, 7>;
+    /// 鎧インデックス (4bit)。
+    pub struct ArmorIndex: u8 in 0..=0xF;
 
-/// いでたち0インデックス (2bit)。
-pub type Accessory0Index = BoundedU8<0, 3>;
+    /// 靴インデックス (3bit)。
+    pub struct ShoesIndex: u8 in 0..=7;
 
-/// いでたち1インデックス (2bit)。
-pub type Accessory1Index = BoundedU8<0, 3>;
+    /// いでたち0インデックス (2bit)。
+    pub struct Accessory0Index: u8 in 0..=3;
 
-/// いでたち2インデックス (1bit)。
-pub type Accessory2Index = BoundedU8<0, 1>;
+    /// いでたち1インデックス (2bit)。
+    pub struct Accessory1Index: u8 in 0..=3;
 
-/// いでたち3インデックス (1bit)。
-pub type Accessory3Index = BoundedU8<0, 1>;
+    /// いでたち2インデックス (1bit)。
+    pub struct Accessory2Index: u8 in 0..=1;
+
+    /// いでたち3インデックス (1bit)。
+    pub struct Accessory3Index: u8 in 0..=1;
+}
 
 /// インベントリ。
 pub type Inventory = ArrayVec<ItemId, 8>;
 
-/// アイテムID (nonzero, 6bit)。
-pub type ItemId = BoundedU8<1, 0x3F>;
+/// インベントリを表示するためのオブジェクトを返す。
+///
+/// アイテムIDを `0x3F` の形で `", "` 区切りで列挙する。空なら `"なし"` と表示する。
+pub fn display_inventory(inventory: &Inventory) -> InventoryDisplay<'_> {
+    InventoryDisplay { inventory }
+}
+
+#[derive(Debug)]
+pub struct InventoryDisplay<'a> {
+    inventory: &'a Inventory,
+}
+
+impl std::fmt::Display for InventoryDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.inventory.is_empty() {
+            return f.write_str("なし");
+        }
+
+        for (i, item) in self.inventory.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "0x{:02X}", item.get())?;
+        }
+
+        Ok(())
+    }
+}
+
+define_bounded! {
+    /// アイテムID (nonzero, 6bit)。
+    pub struct ItemId: u8 in 1..=0x3F;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::Password;
+    use crate::serialized::SerializedBytes;
+
+    #[test]
+    fn test_normalize_slots() {
+        for helm in HelmIndex::all() {
+            let expected = (helm.get() <= 2).then_some(helm);
+            assert_eq!(Equipment::normalize_helm(helm), expected);
+        }
+
+        for weapon in WeaponIndex::all() {
+            let expected = match weapon.get() {
+                0..=10 => (Some(weapon), None),
+                11 | 12 => (None, None),
+                x => (None, Some(ArmorIndex::new(x - 12).unwrap())),
+            };
+            assert_eq!(Equipment::normalize_weapon(weapon), expected);
+        }
+
+        for armor in ArmorIndex::all() {
+            let expected = match armor.get() {
+                0..=9 => (Some(armor), None),
+                10 | 11 => (None, None),
+                x => (None, Some(ShoesIndex::new(x - 11).unwrap())),
+            };
+            assert_eq!(Equipment::normalize_armor(armor), expected);
+        }
+
+        for shoes in ShoesIndex::all() {
+            let expected = match shoes.get() {
+                0..=4 => (Some(shoes), None),
+                5 | 6 => (None, None),
+                _ => (None, Some(Accessory0Index::new(1).unwrap())),
+            };
+            assert_eq!(Equipment::normalize_shoes(shoes), expected);
+        }
+
+        for accessory0 in Accessory0Index::all() {
+            let expected = (accessory0.get() <= 2).then_some(accessory0);
+            assert_eq!(Equipment::normalize_accessory0(accessory0), expected);
+        }
+
+        for accessory1 in Accessory1Index::all() {
+            let expected = (accessory1.get() <= 2).then_some(accessory1);
+            assert_eq!(Equipment::normalize_accessory1(accessory1), expected);
+        }
+    }
+
+    #[test]
+    fn test_normalize_precedence() {
+        let equipment = |weapon, armor, shoes, accessory0| Equipment {
+            weapon: WeaponIndex::new(weapon).unwrap(),
+            armor: ArmorIndex::new(armor).unwrap(),
+            shoes: ShoesIndex::new(shoes).unwrap(),
+            accessory0: Accessory0Index::new(accessory0).unwrap(),
+            ..Equipment::default()
+        };
+
+        // 変化先のスロットが空なら、変化した装備が入る。
+        assert_eq!(equipment(13, 10, 5, 3).normalize(), equipment(0, 1, 0, 0));
+        assert_eq!(equipment(0, 15, 7, 3).normalize(), equipment(0, 0, 4, 1));
+
+        // 変化先のスロット自身のインデックスが正常なら(0 であっても)そちらが優先される。
+        assert_eq!(equipment(15, 0, 0, 0).normalize(), equipment(0, 0, 0, 0));
+        assert_eq!(equipment(15, 9, 5, 2).normalize(), equipment(0, 9, 0, 2));
+        assert_eq!(equipment(0, 12, 4, 0).normalize(), equipment(0, 0, 4, 0));
+        assert_eq!(equipment(0, 0, 7, 2).normalize(), equipment(0, 0, 0, 2));
+    }
+
+    #[test]
+    fn test_normalize_fixture() {
+        // パスワード「ふ」(全スロットが最大値)でロードし、すぐ神主に話しかけたときの装備。
+        let equipment = Equipment {
+            helm: HelmIndex::MAX,
+            weapon: WeaponIndex::MAX,
+            armor: ArmorIndex::MAX,
+            shoes: ShoesIndex::MAX,
+            accessory0: Accessory0Index::MAX,
+            accessory1: Accessory1Index::MAX,
+            accessory2: Accessory2Index::MAX,
+            accessory3: Accessory3Index::MAX,
+        };
+        let expected = Equipment {
+            armor: ArmorIndex::new(3).unwrap(),
+            shoes: ShoesIndex::new(4).unwrap(),
+            accessory0: Accessory0Index::new(1).unwrap(),
+            accessory2: Accessory2Index::MAX,
+            accessory3: Accessory3Index::MAX,
+            ..Equipment::default()
+        };
+        assert_eq!(equipment.normalize(), expected);
+
+        // ゲームが実際に出力したパスワードの装備と一致する。
+        let password =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        let savedata = SerializedBytes::from_password(&password)
+            .to_savedata()
+            .unwrap();
+        assert_eq!(savedata.equipment, expected);
+    }
+
+    #[test]
+    fn test_normalize_respawn() {
+        for respawn in RespawnId::all() {
+            let savedata = Savedata {
+                respawn,
+                ..Default::default()
+            };
+            assert_eq!(savedata.normalize().respawn, respawn);
+        }
+
+        // パスワード「ふ」でロードしてすぐ神主に話しかけたときのパスワード。
+        let password =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        let savedata = SerializedBytes::from_password(&password)
+            .to_savedata()
+            .unwrap();
+        assert_eq!(savedata.respawn, RespawnId::MAX);
+        assert_eq!(savedata.respawn.is_valid_location(), Some(true));
+    }
+
+    #[test]
+    fn test_respawn_is_valid_location() {
+        // 0xF 以外は未検証。
+        for respawn in RespawnId::all() {
+            let expected = (respawn == RespawnId::MAX).then_some(true);
+            assert_eq!(respawn.is_valid_location(), expected, "{respawn:?}");
+        }
+    }
+
+    #[test]
+    fn test_name_tables() {
+        let tables: [&[FlagName]; 5] = [
+            &Spells::NAMES,
+            &Events::NAMES,
+            &Treasures::NAMES,
+            &Minions::NAMES,
+            &Bookmarks::NAMES,
+        ];
+
+        let mut untranslated = vec![];
+        for names in tables {
+            for (i, name) in names.iter().enumerate() {
+                assert!(!name.name_ja().is_empty());
+                match name.name_en() {
+                    Some(en) => assert!(!en.is_empty() && en.is_ascii(), "{en}"),
+                    None => untranslated.push(name.name_ja()),
+                }
+
+                // 同じ表の中に重複する名前はない。
+                for other in &names[..i] {
+                    assert_ne!(name.name_ja(), other.name_ja());
+                    if name.name_en().is_some() {
+                        assert_ne!(name.name_en(), other.name_en());
+                    }
+                }
+            }
+        }
+
+        // 英語名が定まっていないのは術のみ。
+        assert_eq!(
+            untranslated,
+            Spells::NAMES.map(FlagName::name_ja).as_slice()
+        );
+    }
+
+    #[test]
+    fn test_display_in() {
+        assert_eq!(
+            Minions::ALL.display_in(Lang::Ja).to_string(),
+            Minions::ALL.to_string()
+        );
+        assert_eq!(
+            Minions::ALL.display_in(Lang::En).to_string(),
+            "Dog, Pheasant, Monkey"
+        );
+        assert_eq!(Minions::NONE.display_in(Lang::En).to_string(), "none");
+
+        let bookmarks = Bookmarks {
+            tabidachi: true,
+            hien: true,
+            ..Bookmarks::NONE
+        };
+        assert_eq!(
+            bookmarks.display_in(Lang::En).to_string(),
+            "Tabidachi Village, Hien Castle"
+        );
+        let treasures = Treasures {
+            hourai: true,
+            ..Treasures::NONE
+        };
+        assert_eq!(treasures.display_in(Lang::En).to_string(), "Hourai Jewel");
+        let events = Events {
+            netaro: true,
+            ..Events::NONE
+        };
+        assert_eq!(events.display_in(Lang::En).to_string(), "Woke Netaro");
+
+        // 英語名が未設定なら日本語名で表示する。
+        let spells = Spells {
+            kintan: true,
+            hien: true,
+            ..Spells::NONE
+        };
+        assert_eq!(spells.display_in(Lang::En).to_string(), "きんたん, ひえん");
+    }
+
+    #[test]
+    fn test_display_flags() {
+        assert_eq!(Spells::NONE.to_string(), "なし");
+        assert_eq!(
+            Spells::ALL.to_string(),
+            "きんたん, ろっかく, いなずま, ひえん, まんきんたん, ふゆう, だだぢぢ, ほうひ"
+        );
+        let spells = Spells {
+            kintan: true,
+            hien: true,
+            ..Spells::NONE
+        };
+        assert_eq!(spells.to_string(), "きんたん, ひえん");
+
+        assert_eq!(Events::NONE.to_string(), "なし");
+        assert_eq!(
+            Events::ALL.to_string(),
+            "花咲かの村で銀の鬼を倒した, 金太郎の村で金の鬼を倒した, 浦島の村でパールの鬼を倒した, \
+寝太郎を起こした, 寝太郎の村で村田の情報を聞いた, やまんばを倒した, \
+寝太郎の村でリュウのくびかざりを盗まれた, 微笑みの村の通行許可を得た"
+        );
+        let events = Events {
+            netaro: true,
+            hohoemi: true,
+            ..Events::NONE
+        };
+        assert_eq!(
+            events.to_string(),
+            "寝太郎を起こした, 微笑みの村の通行許可を得た"
+        );
+
+        assert_eq!(Treasures::NONE.to_string(), "なし");
+        assert_eq!(
+            Treasures::ALL.to_string(),
+            "リュウのくびかざり, キンいろのけがわ, ホトケのおはち, ホウライのタマ, ツバメのこやすがい"
+        );
+        let treasures = Treasures {
+            fur: true,
+            ..Treasures::NONE
+        };
+        assert_eq!(treasures.to_string(), "キンいろのけがわ");
+
+        assert_eq!(Minions::NONE.to_string(), "なし");
+        assert_eq!(Minions::ALL.to_string(), "犬, キジ, 猿");
+        let minions = Minions {
+            dog: true,
+            monkey: true,
+            ..Minions::NONE
+        };
+        assert_eq!(minions.to_string(), "犬, 猿");
+
+        assert_eq!(Bookmarks::NONE.to_string(), "なし");
+        assert_eq!(
+            Bookmarks::ALL.to_string(),
+            "旅立ちの村, 花咲かの村, 金太郎の村, 浦島の村, 寝太郎の村, 希望の都, 猿蟹の村, 竹取の村, 微笑みの村, 飛燕の城"
+        );
+        let bookmarks = Bookmarks {
+            tabidachi: true,
+            hien: true,
+            ..Bookmarks::NONE
+        };
+        assert_eq!(bookmarks.to_string(), "旅立ちの村, 飛燕の城");
+    }
+
+    #[test]
+    fn test_display_equipment() {
+        assert_eq!(
+            Equipment::default().to_string(),
+            "兜: 0, 武器: 0, 鎧: 0, 靴: 0, いでたち0: 0, いでたち1: 0, いでたち2: 0, いでたち3: 0"
+        );
+        let equipment = Equipment {
+            helm: HelmIndex::MAX,
+            weapon: WeaponIndex::MAX,
+            armor: ArmorIndex::MAX,
+            shoes: ShoesIndex::MAX,
+            accessory0: Accessory0Index::MAX,
+            accessory1: Accessory1Index::MAX,
+            accessory2: Accessory2Index::MAX,
+            accessory3: Accessory3Index::MAX,
+        };
+        assert_eq!(
+            equipment.to_string(),
+            "兜: 3, 武器: 15, 鎧: 15, 靴: 7, いでたち0: 3, いでたち1: 3, いでたち2: 1, いでたち3: 1"
+        );
+        let equipment = Equipment {
+            weapon: WeaponIndex::new(10).unwrap(),
+            shoes: ShoesIndex::new(4).unwrap(),
+            ..Equipment::default()
+        };
+        assert_eq!(
+            equipment.to_string(),
+            "兜: 0, 武器: 10, 鎧: 0, 靴: 4, いでたち0: 0, いでたち1: 0, いでたち2: 0, いでたち3: 0"
+        );
+    }
+
+    #[test]
+    fn test_display_inventory() {
+        assert_eq!(display_inventory(&Inventory::new()).to_string(), "なし");
+        assert_eq!(
+            display_inventory(&Inventory::from([ItemId::MAX; 8])).to_string(),
+            "0x3F, 0x3F, 0x3F, 0x3F, 0x3F, 0x3F, 0x3F, 0x3F"
+        );
+        let inventory: Inventory = [1, 0x20]
+            .into_iter()
+            .map(|x| ItemId::new(x).unwrap())
+            .collect();
+        assert_eq!(display_inventory(&inventory).to_string(), "0x01, 0x20");
+    }
+
+    #[test]
+    fn test_display_savedata() {
+        let savedata = Savedata {
+            xp: 1234,
+            spells: Spells {
+                kintan: true,
+                ..Spells::NONE
+            },
+            minions: Minions::ALL,
+            inventory: Inventory::from_iter([ItemId::MIN]),
+            ..Savedata::default()
+        };
+        assert_eq!(
+            savedata.to_string(),
+            "\
+経験値: 1234
+所持金: 0
+預金: 0
+年齢: 0
+加齢タイマー上位バイト: 0
+術: きんたん
+イベント: なし
+宝物: なし
+お供: 犬, キジ, 猿
+ひえんブックマーク: なし
+復活地点ID: 0
+装備: 兜: 0, 武器: 0, 鎧: 0, 靴: 0, いでたち0: 0, いでたち1: 0, いでたち2: 0, いでたち3: 0
+インベントリ: 0x01"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            Savedata::default().summary().to_string(),
+            "Lv? xp=    0 金=    0/預 0 年齢  0 術0/8 宝0/5 供0/3 旅 0/10 復活0 荷0"
+        );
+
+        let savedata = SerializedBytes::from_password(&Password::parse("ふ").unwrap())
+            .to_savedata()
+            .unwrap();
+        assert_eq!(
+            savedata.summary().to_string(),
+            "Lv? xp=65535 金=65535/預63 年齢255 術8/8 宝5/5 供3/3 旅10/10 復活F 荷8"
+        );
+
+        // 桁が揃う。
+        let savedata = Savedata {
+            xp: 1234,
+            inventory: Inventory::from_iter([ItemId::MIN]),
+            ..Savedata::default()
+        };
+        let width = |s: &Savedata| s.summary().to_string().chars().count();
+        assert_eq!(width(&savedata), width(&Savedata::default()));
+    }
+
+    #[cfg(all(feature = "schemars", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_schema_for_savedata() {
+        let schema = schema_for_savedata();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        // 全フィールドが最大値のセーブデータ。
+        let savedata = SerializedBytes::from_password(&Password::parse("ふ").unwrap())
+            .to_savedata()
+            .unwrap();
+        let mut value = serde_json::to_value(&savedata).unwrap();
+        assert!(validator.is_valid(&value));
+
+        value["deposit"] = 0x40.into();
+        assert!(!validator.is_valid(&value));
+        value["deposit"] = 0x3F.into();
+
+        value["inventory"][0] = 0.into();
+        assert!(!validator.is_valid(&value));
+        value["inventory"][0] = 1.into();
+
+        value["inventory"] = serde_json::to_value([1; 9]).unwrap();
+        assert!(!validator.is_valid(&value));
+        value["inventory"] = serde_json::to_value([1; 8]).unwrap();
+
+        value["spells"]["kintan"] = 1.into();
+        assert!(!validator.is_valid(&value));
+        value["spells"]["kintan"] = true.into();
+
+        assert!(validator.is_valid(&value));
+    }
+}
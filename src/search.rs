@@ -0,0 +1,590 @@
+use std::ops::ControlFlow;
+
+use arrayvec::ArrayVec;
+use thiserror::Error;
+
+use crate::macros::{trace_event, unsafe_or};
+use crate::password::{Password, PasswordChar};
+
+/// `Pattern` の内部バッファ。
+pub type PatternInner = ArrayVec<Option<PasswordChar>, { Password::MAX_LEN }>;
+
+/// パスワード検索用のパターン。各要素は固定文字またはワイルドカード (`None`)。
+#[repr(transparent)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Pattern(PatternInner);
+
+impl Pattern {
+    /// パターン文字列内でワイルドカードを表す文字。
+    pub const WILDCARD: char = '?';
+
+    /// 要素のスライスから `Pattern` を作る。文字数が範囲外なら `None` を返す。
+    pub fn new(elems: &[Option<PasswordChar>]) -> Option<Self> {
+        matches!(elems.len(), Password::MIN_LEN..=Password::MAX_LEN)
+            .then(|| Self(elems.iter().copied().collect()))
+    }
+
+    /// ひらがなとワイルドカード `?` からなる文字列をパースして `Pattern` を作る。
+    pub fn parse(s: &str) -> Result<Self, PatternParseError> {
+        let mut inner = PatternInner::new();
+
+        for (i, c) in s.chars().enumerate() {
+            let elem = if c == Self::WILDCARD {
+                None
+            } else {
+                let pc = PasswordChar::from_char(c)
+                    .ok_or(PatternParseError::InvalidChar { pos: i, ch: c })?;
+                Some(pc)
+            };
+            inner
+                .try_push(elem)
+                .map_err(|_| PatternParseError::InvalidLength)?;
+        }
+
+        if inner.is_empty() {
+            return Err(PatternParseError::InvalidLength);
+        }
+
+        Ok(Self(inner))
+    }
+
+    /// パターン全体を含むスライスを返す。
+    pub fn as_slice(&self) -> &[Option<PasswordChar>] {
+        self.0.as_slice()
+    }
+
+    /// 文字数を返す。
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// ワイルドカードの個数を返す。
+    pub fn wildcard_count(&self) -> usize {
+        self.0.iter().filter(|elem| elem.is_none()).count()
+    }
+
+    /// パスワードがこのパターンにマッチするかどうかを返す。
+    pub fn matches(&self, password: &Password) -> bool {
+        self.len() == password.len()
+            && self
+                .0
+                .iter()
+                .zip(password.iter())
+                .all(|(elem, &pc)| elem.is_none_or(|x| x == pc))
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write as _;
+
+        for elem in &self.0 {
+            f.write_char(elem.map_or(Self::WILDCARD, PasswordChar::to_char))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Pattern {
+    type Err = PatternParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// パターンのパース時に発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum PatternParseError {
+    /// パターンの文字数が正しくない。
+    #[error(
+        "pattern must contain {}..={} chars",
+        Password::MIN_LEN,
+        Password::MAX_LEN
+    )]
+    InvalidLength,
+
+    /// パターンに無効な文字が含まれている。
+    #[error("pattern contains an invalid character '{ch}' at position {pos}")]
+    InvalidChar { pos: usize, ch: char },
+}
+
+/// パスワード検索のオプション。
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SearchOptions {
+    /// 見つけるパスワードの最大個数。`None` なら無制限。
+    pub limit: Option<usize>,
+
+    /// 特殊パスワード(音楽室/美術室)を結果から除外するかどうか。
+    pub exclude_special: bool,
+
+    /// 探索に用いるスレッド数。0 または 1 ならば呼び出し元のスレッドのみで探索する。
+    ///
+    /// `search_with()` では無視される。
+    pub threads: usize,
+
+    /// 探索ステップ数(探索木のノード訪問回数)の上限。`None` なら無制限。
+    ///
+    /// 上限に達すると探索を打ち切る。指定された場合、`search()` は単一スレッドで探索する。
+    pub max_steps: Option<u64>,
+}
+
+/// `search_with()` の終了状態。
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SearchStatus {
+    /// 探索空間を全て調べ終えた。
+    Completed,
+
+    /// `options.limit` 個のパスワードを見つけた、またはコールバックが `Break` を返した。
+    Stopped,
+
+    /// 探索ステップ数が `options.max_steps` に達した。
+    BudgetExhausted,
+}
+
+/// パターンにマッチする有効なパスワードを全て求める。結果は辞書順(内部値順)に並ぶ。
+///
+/// `options.limit` が指定されている場合、辞書順で先頭からその個数までを返す。
+pub fn search(pattern: &Pattern, options: &SearchOptions) -> Vec<Password> {
+    if options.threads <= 1 || options.max_steps.is_some() {
+        let mut res = vec![];
+        search_with(pattern, options, |password| {
+            res.push(password.clone());
+            ControlFlow::Continue(())
+        });
+        return res;
+    }
+
+    // 最初のワイルドカードを固定した部分問題たちに分割し、スレッドたちに割り振る。
+    // 部分問題を辞書順に連結すれば全体の結果も辞書順になる。
+    let Some(pos) = pattern.0.iter().position(Option::is_none) else {
+        return search(
+            pattern,
+            &SearchOptions {
+                threads: 1,
+                ..options.clone()
+            },
+        );
+    };
+
+    let subpatterns: Vec<Pattern> = PasswordChar::all()
+        .into_iter()
+        .map(|pc| {
+            let mut sub = pattern.clone();
+            sub.0[pos] = Some(pc);
+            sub
+        })
+        .collect();
+    let sub_options = SearchOptions {
+        threads: 1,
+        ..options.clone()
+    };
+
+    let chunk_len = subpatterns.len().div_ceil(options.threads);
+    let mut res: Vec<Password> = std::thread::scope(|scope| {
+        let handles: Vec<_> = subpatterns
+            .chunks(chunk_len)
+            .map(|chunk| {
+                let sub_options = &sub_options;
+                scope.spawn(move || {
+                    let mut res = vec![];
+                    for sub in chunk {
+                        let remain = sub_options.limit.map(|limit| limit - res.len().min(limit));
+                        if remain == Some(0) {
+                            break;
+                        }
+                        res.extend(search(
+                            sub,
+                            &SearchOptions {
+                                limit: remain,
+                                ..sub_options.clone()
+                            },
+                        ));
+                    }
+                    res
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("search thread panicked"))
+            .collect()
+    });
+
+    if let Some(limit) = options.limit {
+        res.truncate(limit);
+    }
+
+    res
+}
+
+/// パターンにマッチする有効なパスワードを辞書順(内部値順)に列挙し、それぞれについて `f` を呼ぶ。
+///
+/// `f` が `ControlFlow::Break` を返すと探索を打ち切る。
+/// `options.limit` 個のパスワードを見つけた場合、および探索ステップ数が `options.max_steps` に達した場合も
+/// 探索を打ち切る。
+pub fn search_with<F>(pattern: &Pattern, options: &SearchOptions, f: F) -> SearchStatus
+where
+    F: FnMut(&Password) -> ControlFlow<()>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("search", %pattern, ?options).entered();
+
+    let mut searcher = Searcher {
+        pattern,
+        options,
+        f,
+        buf: ArrayVec::new(),
+        found: 0,
+        steps: 0,
+        exhausted: false,
+    };
+
+    let status = match searcher.dfs() {
+        ControlFlow::Continue(()) => SearchStatus::Completed,
+        ControlFlow::Break(()) if searcher.exhausted => SearchStatus::BudgetExhausted,
+        ControlFlow::Break(()) => SearchStatus::Stopped,
+    };
+
+    trace_event!(
+        info,
+        steps = searcher.steps,
+        found = searcher.found,
+        ?status,
+        "search finished"
+    );
+
+    status
+}
+
+/// 探索の進捗イベントを発行する間隔(探索ステップ数)。
+#[cfg(feature = "tracing")]
+const PROGRESS_INTERVAL: u64 = 1 << 20;
+
+struct Searcher<'a, F> {
+    pattern: &'a Pattern,
+    options: &'a SearchOptions,
+    f: F,
+    buf: ArrayVec<PasswordChar, { Password::MAX_LEN }>,
+    found: usize,
+    steps: u64,
+    exhausted: bool,
+}
+
+impl<F> Searcher<'_, F>
+where
+    F: FnMut(&Password) -> ControlFlow<()>,
+{
+    fn dfs(&mut self) -> ControlFlow<()> {
+        if self.options.max_steps.is_some_and(|max| self.steps >= max) {
+            self.exhausted = true;
+            return ControlFlow::Break(());
+        }
+        self.steps += 1;
+
+        #[cfg(feature = "tracing")]
+        if self.steps.is_multiple_of(PROGRESS_INTERVAL) {
+            tracing::debug!(steps = self.steps, found = self.found, "search progress");
+        }
+
+        let pos = self.buf.len();
+
+        // 全ての文字が決まったら有効かどうかチェックして戻る。
+        if pos == self.pattern.len() {
+            return self.visit();
+        }
+
+        // 枝刈り: 2 文字目が無効なら直ちに却下。
+        if pos == 2 && Password::is_invalid_second_char(self.buf[1]) {
+            trace_event!(trace, rule = "invalid_second_char", depth = pos, "pruned");
+            return ControlFlow::Continue(());
+        }
+
+        match self.pattern.0[pos] {
+            Some(pc) => self.dfs_push(pc)?,
+            None => {
+                for pc in PasswordChar::all() {
+                    self.dfs_push(pc)?;
+                }
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn dfs_push(&mut self, pc: PasswordChar) -> ControlFlow<()> {
+        self.buf.push(pc);
+        let res = self.dfs();
+        self.buf.pop().unwrap();
+        res
+    }
+
+    fn visit(&mut self) -> ControlFlow<()> {
+        if self.options.limit.is_some_and(|limit| self.found >= limit) {
+            return ControlFlow::Break(());
+        }
+
+        let password = unsafe_or!(
+            Password::new_unchecked(&self.buf),
+            Password::new(&self.buf).unwrap()
+        );
+        if !password.is_valid() || (self.options.exclude_special && password.is_special()) {
+            return ControlFlow::Continue(());
+        }
+
+        self.found += 1;
+        (self.f)(&password)?;
+
+        if self.options.limit.is_some_and(|limit| self.found >= limit) {
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::iproduct;
+
+    use super::*;
+
+    fn naive(pattern: &Pattern) -> Vec<Password> {
+        let cands: Vec<Vec<PasswordChar>> = pattern
+            .as_slice()
+            .iter()
+            .map(|elem| elem.map_or_else(|| PasswordChar::all().to_vec(), |pc| vec![pc]))
+            .collect();
+
+        itertools::Itertools::multi_cartesian_product(cands.into_iter())
+            .map(|pcs| Password::new(&pcs).unwrap())
+            .filter(Password::is_valid)
+            .collect()
+    }
+
+    #[test]
+    fn test_pattern_parse() {
+        let pattern = Pattern::parse("お?の").unwrap();
+        assert_eq!(
+            pattern.as_slice(),
+            [Some(PasswordChar::O), None, Some(PasswordChar::No)]
+        );
+        assert_eq!(pattern.wildcard_count(), 1);
+        assert_eq!(pattern.to_string(), "お?の");
+
+        assert_eq!(Pattern::parse(""), Err(PatternParseError::InvalidLength));
+        assert_eq!(
+            Pattern::parse(&"?".repeat(Password::MAX_LEN + 1)),
+            Err(PatternParseError::InvalidLength)
+        );
+        assert_eq!(
+            Pattern::parse("あ?x"),
+            Err(PatternParseError::InvalidChar { pos: 2, ch: 'x' })
+        );
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        let pattern = Pattern::parse("お?の").unwrap();
+        assert!(pattern.matches(&Password::parse("おにの").unwrap()));
+        assert!(!pattern.matches(&Password::parse("あにの").unwrap()));
+        assert!(!pattern.matches(&Password::parse("おにのば").unwrap()));
+    }
+
+    #[test]
+    fn test_search() {
+        for s in ["?", "??", "ふ?", "?ふ"] {
+            let pattern = Pattern::parse(s).unwrap();
+            assert_eq!(search(&pattern, &SearchOptions::default()), naive(&pattern));
+        }
+
+        let pattern = Pattern::parse("おにのば?").unwrap();
+        assert!(search(&pattern, &SearchOptions::default())
+            .contains(&Password::parse("おにのばか").unwrap()));
+    }
+
+    #[test]
+    fn test_search_limit() {
+        let pattern = Pattern::parse("??").unwrap();
+        let all = search(&pattern, &SearchOptions::default());
+
+        for limit in [0, 1, 5, all.len(), all.len() + 1] {
+            let options = SearchOptions {
+                limit: Some(limit),
+                ..Default::default()
+            };
+            assert_eq!(search(&pattern, &options), all[..limit.min(all.len())]);
+        }
+    }
+
+    #[test]
+    fn test_search_threads() {
+        for (s, threads, limit) in iproduct!(["??", "あ??", "ふ"], [2, 3, 64, 100], [None, Some(7)])
+        {
+            let pattern = Pattern::parse(s).unwrap();
+            let single = SearchOptions {
+                limit,
+                ..Default::default()
+            };
+            let multi = SearchOptions {
+                limit,
+                threads,
+                ..Default::default()
+            };
+            assert_eq!(search(&pattern, &multi), search(&pattern, &single));
+        }
+    }
+
+    #[test]
+    fn test_search_exclude_special() {
+        let pattern = Pattern::parse("?").unwrap();
+        let options = SearchOptions {
+            exclude_special: true,
+            ..Default::default()
+        };
+        let res = search(&pattern, &options);
+        assert!(res.iter().all(|password| !password.is_special()));
+        assert_eq!(
+            res,
+            naive(&pattern)
+                .into_iter()
+                .filter(|password| !password.is_special())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_search_with_break() {
+        let pattern = Pattern::parse("あ???").unwrap();
+        let mut found = vec![];
+        let status = search_with(&pattern, &SearchOptions::default(), |password| {
+            found.push(password.clone());
+            if found.len() == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(status, SearchStatus::Stopped);
+        assert_eq!(found, search(&pattern, &SearchOptions::default())[..3]);
+    }
+
+    #[test]
+    fn test_search_max_steps() {
+        let pattern = Pattern::parse("あ???").unwrap();
+        let all = search(&pattern, &SearchOptions::default());
+
+        for max_steps in [0, 1, 100, 10000] {
+            let options = SearchOptions {
+                max_steps: Some(max_steps),
+                ..Default::default()
+            };
+            let mut found = vec![];
+            let status = search_with(&pattern, &options, |password| {
+                found.push(password.clone());
+                ControlFlow::Continue(())
+            });
+            assert_eq!(status, SearchStatus::BudgetExhausted);
+            assert_eq!(found, all[..found.len()]);
+            assert_eq!(search(&pattern, &options), found);
+        }
+
+        let options = SearchOptions {
+            max_steps: Some(u64::MAX),
+            ..Default::default()
+        };
+        let status = search_with(&pattern, &options, |_| ControlFlow::Continue(()));
+        assert_eq!(status, SearchStatus::Completed);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_search_tracing() {
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        type Fields = HashMap<&'static str, String>;
+
+        /// 全てのイベントのフィールドを記録するサブスクライバ。
+        #[derive(Default)]
+        struct Collector(Arc<Mutex<Vec<Fields>>>);
+
+        struct FieldsVisitor<'a>(&'a mut Fields);
+
+        impl Visit for FieldsVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.insert(field.name(), format!("{value:?}"));
+            }
+        }
+
+        impl Subscriber for Collector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields::new();
+                event.record(&mut FieldsVisitor(&mut fields));
+                self.0.lock().unwrap().push(fields);
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let collector = Collector::default();
+        let events = Arc::clone(&collector.0);
+
+        let pattern = Pattern::parse("おにのば?").unwrap();
+        let expected = search(&pattern, &SearchOptions::default());
+        let status = tracing::subscriber::with_default(collector, || {
+            search_with(&pattern, &SearchOptions::default(), |_| {
+                ControlFlow::Continue(())
+            })
+        });
+        assert_eq!(status, SearchStatus::Completed);
+
+        let events = events.lock().unwrap();
+        let summaries: Vec<_> = events
+            .iter()
+            .filter(|fields| fields["message"] == "search finished")
+            .collect();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0]["found"], expected.len().to_string());
+        assert_eq!(summaries[0]["status"], "Completed");
+
+        // "おに" は 2 文字目が有効なので、枝刈りは起こらない。
+        assert!(events.iter().all(|fields| fields["message"] != "pruned"));
+        drop(events);
+
+        let collector = Collector::default();
+        let events = Arc::clone(&collector.0);
+
+        let pattern = Pattern::parse("???").unwrap();
+        tracing::subscriber::with_default(collector, || {
+            search_with(&pattern, &SearchOptions::default(), |_| {
+                ControlFlow::Continue(())
+            })
+        });
+
+        let events = events.lock().unwrap();
+        let pruned: Vec<_> = events
+            .iter()
+            .filter(|fields| fields["message"] == "pruned")
+            .collect();
+        assert!(!pruned.is_empty());
+        assert!(pruned
+            .iter()
+            .all(|fields| fields["rule"] == "\"invalid_second_char\"" && fields["depth"] == "2"));
+    }
+}
@@ -1,10 +1,16 @@
 use arrayvec::ArrayVec;
 use bitvec::prelude::*;
+use thiserror::Error;
 
 use crate::bounded::BoundedU8;
-use crate::checksum::{Checksum, ChecksumAdd, ChecksumXor};
+use crate::checksum::{
+    Checksum, ChecksumAdd, ChecksumMismatch, ChecksumReport, ChecksumXor, SavedataDecodeError,
+};
+use crate::layout::FieldId;
+#[cfg(not(feature = "forbid-unsafe"))]
 use crate::macros::assert_unchecked;
-use crate::password::{Password, PasswordChar};
+use crate::macros::{new_unchecked, unsafe_or};
+use crate::password::{Password, PasswordChar, PasswordLengthError};
 use crate::savedata::*;
 
 /// `SerializedBytes` の要素。
@@ -24,18 +30,70 @@ pub type SerializedBytesInner = ArrayVec<SerializedByte, { Password::MAX_LEN }>;
 ///
 /// パスワードをデコードして得られたバイト列のバイト数は元のパスワードの文字数に等しい。
 /// デシリアライズの際にビット数が不足する場合、足りないビットは全て 1 として扱われる。
+///
+/// インベントリはビット列の最後のフィールドで、最大 8 個の 6bit 値として格納される。
+/// デシリアライズの際は最初の 0 (終端)で読むのをやめ、それ以降の値は無視する。
+/// インベントリの後にフィールドはないので、インベントリの読み方が他のフィールドの位置に影響することはない。
 #[repr(transparent)]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SerializedBytes(SerializedBytesInner);
 
 impl SerializedBytes {
-    /// パスワードのエンコード時に用いる加算値テーブル。
-    const ENCODE_ADD_TABLE: [u8; 4] = [0x05, 0x19, 0x32, 0x21];
+    /// パスワードのエンコード時に用いる加算値テーブル。`i` バイト目には `ENCODE_ADD_TABLE[i % 4]` を加算する。
+    pub const ENCODE_ADD_TABLE: [u8; 4] = [0x05, 0x19, 0x32, 0x21];
 
     /// `SerializedByte` のスライスから `SerializedBytes` を作る。バイト数が範囲外なら `None` を返す。
     pub fn new(buf: &[SerializedByte]) -> Option<Self> {
-        matches!(buf.len(), Password::MIN_LEN..=Password::MAX_LEN)
-            .then(|| unsafe { Self::new_unchecked(buf) })
+        Self::new_checked(buf).ok()
+    }
+
+    /// `SerializedByte` のスライスから `SerializedBytes` を作る。
+    ///
+    /// バイト数が範囲外(パスワードの文字数と同じ `Password::MIN_LEN..=Password::MAX_LEN`)ならエラーを返す。
+    pub fn new_checked(buf: &[SerializedByte]) -> Result<Self, PasswordLengthError> {
+        if !matches!(buf.len(), Password::MIN_LEN..=Password::MAX_LEN) {
+            return Err(PasswordLengthError::new(buf.len()));
+        }
+
+        Ok(unsafe_or!(
+            Self::new_unchecked(buf),
+            Self(buf.iter().copied().collect())
+        ))
+    }
+
+    /// `u8` のスライスから `SerializedBytes` を作る。バイト数や値が範囲外ならエラーを返す。
+    ///
+    /// エミュレータの RAM から読み出したデコード後のバッファをそのまま渡すためのもの。
+    pub fn from_u8_slice(bytes: &[u8]) -> Result<Self, SerializedBytesFromU8Error> {
+        if !matches!(bytes.len(), Password::MIN_LEN..=Password::MAX_LEN) {
+            return Err(SerializedBytesFromU8Error::InvalidLength { len: bytes.len() });
+        }
+
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                SerializedByte::new(value)
+                    .ok_or(SerializedBytesFromU8Error::OutOfRange { index, value })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// `u8` のスライスから `SerializedBytes` を作る。各バイトは下位 6bit のみを用いる。
+    ///
+    /// バイト数が範囲外ならエラーを返す。
+    pub fn from_u8_slice_masked(bytes: &[u8]) -> Result<Self, PasswordLengthError> {
+        if !matches!(bytes.len(), Password::MIN_LEN..=Password::MAX_LEN) {
+            return Err(PasswordLengthError::new(bytes.len()));
+        }
+
+        Ok(Self(
+            bytes
+                .iter()
+                .map(|&b| new_unchecked!(SerializedByte, b & 0x3F))
+                .collect(),
+        ))
     }
 
     /// `SerializedByte` のスライスから `SerializedBytes` を作る。
@@ -43,150 +101,197 @@ impl SerializedBytes {
     /// # Safety
     ///
     /// バイト数は `Password::MIN_LEN..=Password::MAX_LEN` の範囲内になければならない。
+    #[cfg(not(feature = "forbid-unsafe"))]
     pub unsafe fn new_unchecked(buf: &[SerializedByte]) -> Self {
         assert_unchecked!(matches!(buf.len(), Password::MIN_LEN..=Password::MAX_LEN));
         Self(buf.iter().copied().collect())
     }
 
+    /// `SerializedByte` のスライスから `SerializedBytes` を作る。
+    ///
+    /// `forbid-unsafe` feature 有効時は安全な関数となり、バイト数が範囲外ならパニックする。
+    #[cfg(feature = "forbid-unsafe")]
+    pub fn new_unchecked(buf: &[SerializedByte]) -> Self {
+        Self::new_checked(buf).expect("serialized bytes length out of range")
+    }
+
     /// パスワードをデコードして `SerializedBytes` を得る。特殊パスワードでも構わずデコードする。
     ///
     /// 戻り値はチェックサムが一致していない可能性がある。
     pub fn from_password(password: &Password) -> Self {
-        // 演算は u8 で行う。最終結果は 6bit 値になる。
-        let mut inner: ArrayVec<u8, { Password::MAX_LEN }> = password
-            .iter()
-            .copied()
-            .map(PasswordChar::to_inner)
-            .collect();
-
-        // デコード: XOR
-        for i in (1..inner.len()).rev() {
-            inner[i] ^= inner[i - 1];
-        }
-        inner[0] ^= 0x1F;
-
-        // デコード: mod 64 減算
-        for (i, b) in inner.iter_mut().enumerate() {
-            *b = b.wrapping_sub(Self::ENCODE_ADD_TABLE[i % 4]);
-            *b &= 0x3F;
-        }
-
-        let inner: SerializedBytesInner = inner
-            .into_iter()
-            .map(|b| unsafe { SerializedByte::new_unchecked(b) })
-            .collect();
-
-        Self(inner)
+        let mut bytes = Self(SerializedBytesInner::new());
+        password.decode_into(&mut bytes);
+        bytes
     }
 
     /// `SerializedBytes` をパスワードにエンコードする。
     pub fn to_password(&self) -> Password {
         // 演算は u8 で行う。最終結果は 6bit 値になる。
-        let mut inner: ArrayVec<u8, { Password::MAX_LEN }> =
-            self.iter().copied().map(SerializedByte::get).collect();
-
-        // エンコード: mod 64 加算
-        for (i, b) in inner.iter_mut().enumerate() {
-            *b = b.wrapping_add(Self::ENCODE_ADD_TABLE[i % 4]);
-            *b &= 0x3F;
-        }
-
-        // エンコード: XOR
-        inner[0] ^= 0x1F;
-        for i in 1..inner.len() {
-            inner[i] ^= inner[i - 1];
-        }
+        let mut inner: ArrayVec<u8, { Password::MAX_LEN }> = self.iter_u8().collect();
+        encode_password_bytes(&mut inner);
 
         let inner: ArrayVec<PasswordChar, { Password::MAX_LEN }> = inner
             .into_iter()
-            .map(|b| unsafe { PasswordChar::from_inner_unchecked(b) })
+            .map(|b| {
+                unsafe_or!(
+                    PasswordChar::from_inner_unchecked(b),
+                    PasswordChar::from_inner(b).unwrap()
+                )
+            })
             .collect();
 
-        unsafe { Password::new_unchecked(&inner) }
+        unsafe_or!(
+            Password::new_unchecked(&inner),
+            Password::new(&inner).unwrap()
+        )
     }
 
     /// ゲーム状態をシリアライズして `SerializedBytes` を得る。
     ///
     /// 戻り値はチェックサムが一致していることが保証される。
     pub fn from_savedata(savedata: &Savedata) -> Self {
-        let mut bits = SerializedBits::new();
-
-        let xp_lo = savedata.xp as u8;
-        let xp_hi = (savedata.xp >> 8) as u8;
-        let purse_lo = savedata.purse as u8;
-        let purse_hi = (savedata.purse >> 8) as u8;
+        let mut bytes = Self(SerializedBytesInner::new());
+        savedata.encode_into(&mut bytes);
+        bytes
+    }
 
-        bits.push_bits(8, savedata.age_timer_hi);
-        bits.push_bits(8, purse_hi);
-        bits.push_bits(8, savedata.age);
-        bits.push_bits(8, purse_lo);
-        bits.push_bits(8, xp_lo);
-        bits.push_bits(6, savedata.deposit.get());
-        bits.push_bits(8, xp_hi);
-        serialize_spells(&mut bits, savedata.spells);
-        serialize_treasures(&mut bits, savedata.treasures);
-        bits.push_bits(4, savedata.respawn.get());
-        serialize_bookmarks1(&mut bits, savedata.bookmarks);
-        serialize_minions(&mut bits, savedata.minions);
-        serialize_bookmarks0(&mut bits, savedata.bookmarks);
-        serialize_events(&mut bits, savedata.events);
-        serialize_equipment(&mut bits, savedata.equipment);
-        serialize_inventory(&mut bits, &savedata.inventory);
+    /// ゲーム状態を `options` に従ってシリアライズして `SerializedBytes` を得る。
+    ///
+    /// `options` はゲーム状態として読まれないビットのみに影響するので、デシリアライズすると常に `savedata` に戻る。
+    /// `EncodeOptions::default()` なら `from_savedata()` と同じ結果になる。
+    pub fn from_savedata_with_options(savedata: &Savedata, options: EncodeOptions) -> Self {
+        let mut bits = savedata.to_bits();
+
+        // 最後のフィールドの後、6bit 境界までのビットは読まれない。
+        let n_pad = (6 - bits.len() % 6) % 6;
+        let mask = (1_u8 << n_pad) - 1;
+        let padding = match options.padding {
+            PaddingBits::Zeros => 0,
+            PaddingBits::Ones => mask,
+            PaddingBits::Custom(value) => value & mask,
+        };
+        bits.push_bits(n_pad, padding);
 
         bits.to_bytes()
     }
 
+    /// ゲーム状態をシリアライズし、`len` バイトの `SerializedBytes` を得る。
+    ///
+    /// パスワードの末尾で途切れたビットは 1 として読まれ、読まれない末尾のバイトは無視されるので、
+    /// ゲーム状態は `from_savedata()` と異なる長さでも表せることがある。
+    /// 短くする場合、切り捨てられるビットのうち読まれるものが全て 1 でなければならない。
+    /// そうでなければ、最初に値が変わってしまうフィールドを含むエラーを返す。
+    /// 長くする場合、末尾には 0 を補う。
+    ///
+    /// 戻り値はチェックサムが一致しており、デシリアライズすると `savedata` に戻ることが保証される。
+    pub fn from_savedata_with_len(savedata: &Savedata, len: usize) -> Result<Self, EncodeLenError> {
+        if !matches!(len, Password::MIN_LEN..=Password::MAX_LEN) {
+            return Err(PasswordLengthError::new(len).into());
+        }
+
+        let writer = savedata.to_bits();
+        let bits = writer.as_bitslice();
+
+        // 先頭 2 バイトはチェックサム。1 バイトの場合もデータ部は 0 バイト。
+        let n_kept = (6 * len.saturating_sub(2)).min(bits.len());
+        if let Some(pos) = bits[n_kept..].first_zero().map(|i| n_kept + i) {
+            return Err(EncodeLenError::Truncated {
+                len,
+                field: FieldId::at_bit(pos).expect("bit position out of layout"),
+                min_len: min_len_of_bits(bits),
+            });
+        }
+
+        let mut bytes = Self(SerializedBytesInner::new());
+        writer.write_bytes_without_checksum(&mut bytes);
+        bytes.0.truncate(len);
+        while bytes.len() < len {
+            bytes.0.push(SerializedByte::MIN);
+        }
+        bytes.fix_checksum();
+
+        Ok(bytes)
+    }
+
     /// `SerializedBytes` をゲーム状態にデシリアライズする。チェックサムが一致していなければ `None` を返す。
     pub fn to_savedata(&self) -> Option<Savedata> {
-        if !self.checksum_is_ok() {
-            return None;
+        self.to_savedata_checked().ok()
+    }
+
+    /// `SerializedBytes` をゲーム状態にデシリアライズする。
+    ///
+    /// チェックサムが一致していなければ、格納されたチェックサムと計算されたチェックサム、バイト数を含むエラーを返す。
+    pub fn to_savedata_checked(&self) -> Result<Savedata, SavedataDecodeError> {
+        let mut savedata = Savedata::default();
+        self.decode_into(&mut savedata)
+            .map_err(
+                |ChecksumMismatch { embed, calculated }| SavedataDecodeError {
+                    embed,
+                    calculated,
+                    len: self.len(),
+                },
+            )?;
+        Ok(savedata)
+    }
+
+    /// `SerializedBytes` をゲーム状態にデシリアライズし、`out` に上書きする。
+    ///
+    /// チェックサムが一致していなければエラーを返す(`out` は変更されない)。
+    pub fn decode_into(&self, out: &mut Savedata) -> Result<(), ChecksumMismatch> {
+        let embed = self.checksum_embed();
+        let calculated = self.checksum_calculated();
+        if embed != calculated {
+            return Err(ChecksumMismatch { embed, calculated });
         }
 
-        let bits = SerializedBits::from_bytes(self);
-        let bits = bits.as_bitslice();
-
-        let (age_timer_hi, bits) = deserialize_bits(bits, 8);
-        let (purse_hi, bits) = deserialize_bits(bits, 8);
-        let (age, bits) = deserialize_bits(bits, 8);
-        let (purse_lo, bits) = deserialize_bits(bits, 8);
-        let (xp_lo, bits) = deserialize_bits(bits, 8);
-        let (deposit, bits) = deserialize_bits(bits, 6);
-        let (xp_hi, bits) = deserialize_bits(bits, 8);
-        let (spells, bits) = deserialize_bits(bits, 8);
-        let (treasures, bits) = deserialize_bits(bits, 5);
-        let (respawn, bits) = deserialize_bits(bits, 4);
-        let (bookmarks1, bits) = deserialize_bits(bits, 2);
-        let (minions, bits) = deserialize_bits(bits, 3);
-        let (bookmarks0, bits) = deserialize_bits(bits, 8);
-        let (events, bits) = deserialize_bits(bits, 8);
-        let (equipment, bits) = deserialize_equipment(bits);
-        let (inventory, _) = deserialize_inventory(bits);
-
-        let xp = u16::from(xp_lo) | (u16::from(xp_hi) << 8);
-        let purse = u16::from(purse_lo) | (u16::from(purse_hi) << 8);
-        let deposit = unsafe { Deposit::new_unchecked(deposit) };
-        let spells = unpack_spells(spells);
-        let events = unpack_events(events);
-        let treasures = unpack_treasures(treasures);
-        let minions = unpack_minions(minions);
-        let bookmarks = unpack_bookmarks([bookmarks0, bookmarks1]);
-        let respawn = unsafe { RespawnId::new_unchecked(respawn) };
-
-        Some(Savedata {
-            xp,
-            purse,
-            deposit,
-            age,
-            age_timer_hi,
-            spells,
-            events,
-            treasures,
-            minions,
-            bookmarks,
-            respawn,
-            equipment,
-            inventory,
-        })
+        self.decode_bits_into(out);
+        Ok(())
+    }
+
+    /// チェックサムを検査せずに `SerializedBytes` をゲーム状態にデシリアライズする。
+    ///
+    /// チェックサムが一致しないバイト列に対しても、ゲームが検査を行わなかったと仮定した場合の仮想的なゲーム状態を返す。
+    /// 実際のゲームではこの状態にはならない(パスワードが拒否される)ことに注意。
+    /// 足りないビットは `to_savedata()` と同様に全て 1 とみなす。
+    pub fn to_savedata_unchecked(&self) -> Savedata {
+        let mut savedata = Savedata::default();
+        self.decode_bits_into(&mut savedata);
+        savedata
+    }
+
+    /// チェックサムを検査せずにデシリアライズし、`out` に上書きする。
+    fn decode_bits_into(&self, out: &mut Savedata) {
+        let mut reader = BitReader::new(self);
+
+        let age_timer_hi = reader.read_bits(8);
+        let purse_hi = reader.read_bits(8);
+        let age = reader.read_bits(8);
+        let purse_lo = reader.read_bits(8);
+        let xp_lo = reader.read_bits(8);
+        let deposit = reader.read_bits(6);
+        let xp_hi = reader.read_bits(8);
+        let spells = reader.read_bits(8);
+        let treasures = reader.read_bits(5);
+        let respawn = reader.read_bits(4);
+        let bookmarks1 = reader.read_bits(2);
+        let minions = reader.read_bits(3);
+        let bookmarks0 = reader.read_bits(8);
+        let events = reader.read_bits(8);
+        let equipment = deserialize_equipment(&mut reader);
+        deserialize_inventory(&mut reader, &mut out.inventory);
+
+        out.xp = u16::from(xp_lo) | (u16::from(xp_hi) << 8);
+        out.purse = u16::from(purse_lo) | (u16::from(purse_hi) << 8);
+        out.deposit = new_unchecked!(Deposit, deposit);
+        out.age = age;
+        out.age_timer_hi = age_timer_hi;
+        out.spells = unpack_spells(spells);
+        out.events = unpack_events(events);
+        out.treasures = unpack_treasures(treasures);
+        out.minions = unpack_minions(minions);
+        out.bookmarks = unpack_bookmarks([bookmarks0, bookmarks1]);
+        out.respawn = new_unchecked!(RespawnId, respawn);
+        out.equipment = equipment;
     }
 
     /// 内部バッファを返す。
@@ -210,6 +315,46 @@ impl SerializedBytes {
         self.0.len()
     }
 
+    /// バイト列を 16 進ダンプ(空白区切りの 2 桁の 16 進数)としてフォーマットする `Display` オブジェクトを返す。
+    ///
+    /// `Password::display_hex()` と同じ書式。`SerializedBytes` 自体の `Display` もこれと同じ。
+    pub fn display_hex(&self) -> SerializedBytesDisplayHex<'_> {
+        SerializedBytesDisplayHex(self)
+    }
+
+    /// 各バイトを、エンコード後のパスワードの同じ位置の文字と並べてフォーマットする `Display` オブジェクトを返す。
+    ///
+    /// `3F:ふ 27:に` のように `バイト:文字` を空白区切りで出力する。
+    pub fn display_as_password(&self) -> SerializedBytesDisplayAsPassword<'_> {
+        SerializedBytesDisplayAsPassword(self)
+    }
+
+    /// 各バイトを `u8` として返すイテレータを返す。
+    pub fn iter_u8(&self) -> impl Iterator<Item = u8> + '_ {
+        self.iter().map(|b| b.get())
+    }
+
+    /// 各バイトを `u8` とした `Vec` を返す。
+    pub fn to_u8_vec(&self) -> Vec<u8> {
+        self.iter_u8().collect()
+    }
+
+    /// 各バイトを `u8` として `out` にコピーする。`out` の長さがバイト数と異なればエラーを返す。
+    pub fn copy_to_slice(&self, out: &mut [u8]) -> Result<(), LengthMismatch> {
+        if out.len() != self.len() {
+            return Err(LengthMismatch {
+                expected: self.len(),
+                actual: out.len(),
+            });
+        }
+
+        for (dst, src) in out.iter_mut().zip(self.iter_u8()) {
+            *dst = src;
+        }
+
+        Ok(())
+    }
+
     /// バイト列に格納されたチェックサムを返す。
     pub fn checksum_embed(&self) -> Checksum {
         // 最低でも 1 バイトある。
@@ -242,18 +387,179 @@ impl SerializedBytes {
         }
         sum_add &= 0x3F;
 
-        unsafe {
-            Checksum::new(
-                ChecksumAdd::new_unchecked(sum_add),
-                ChecksumXor::new_unchecked(sum_xor),
-            )
-        }
+        Checksum::new(
+            new_unchecked!(ChecksumAdd, sum_add),
+            new_unchecked!(ChecksumXor, sum_xor),
+        )
     }
 
     /// バイト列に格納されたチェックサムと計算されたチェックサムが一致するかどうかを返す。
     pub fn checksum_is_ok(&self) -> bool {
         self.checksum_embed() == self.checksum_calculated()
     }
+
+    /// 計算されたチェックサムを先頭 2 バイトに格納し、`checksum_is_ok()` が `true` になるようにする。
+    ///
+    /// 1 バイトしかない場合、XOR チェックサムを格納するバイトはないが、`checksum_embed()` はこれを
+    /// `ChecksumXor::MAX` とみなし、`checksum_calculated()` も同じ値を返すので、先頭バイトのみを書き換えれば一致する。
+    pub fn fix_checksum(&mut self) {
+        let checksum = self.checksum_calculated();
+        self[0] = checksum.sum_add();
+        if self.len() >= 2 {
+            self[1] = checksum.sum_xor();
+        }
+    }
+
+    /// チェックサム格納領域を除いたバイト列(3 バイト目以降)を `f` で書き換え、その後 `fix_checksum()` する。
+    ///
+    /// 2 バイト以下しかない場合、`f` には空のスライスが渡される。`f` の戻り値をそのまま返す。
+    pub fn edit<R>(&mut self, f: impl FnOnce(&mut [SerializedByte]) -> R) -> R {
+        let data = self.0.get_mut(2..).unwrap_or_default();
+        let res = f(data);
+        self.fix_checksum();
+        res
+    }
+
+    /// バイト列全体を `f` で書き換え、その後 `fix_checksum()` する。
+    ///
+    /// `f` がチェックサム格納領域を書き換えても、その内容は上書きされる。`f` の戻り値をそのまま返す。
+    pub fn edit_raw<R>(&mut self, f: impl FnOnce(&mut [SerializedByte]) -> R) -> R {
+        let res = f(self.as_mut_slice());
+        self.fix_checksum();
+        res
+    }
+
+    /// `fix_checksum()` したものを返す。
+    pub fn with_fixed_checksum(mut self) -> Self {
+        self.fix_checksum();
+        self
+    }
+}
+
+impl Password {
+    /// パスワードをデコードし、格納されたチェックサムと計算されたチェックサムを返す。
+    ///
+    /// `SerializedBytes::checksum_embed()`, `SerializedBytes::checksum_calculated()` と同じ値を返す
+    /// (1 文字のパスワードでは、格納された XOR チェックサムを `ChecksumXor::MAX` とみなす)。
+    pub fn checksum_report(&self) -> ChecksumReport {
+        let bytes = SerializedBytes::from_password(self);
+        let embed = bytes.checksum_embed();
+        let calculated = bytes.checksum_calculated();
+
+        ChecksumReport {
+            embed,
+            calculated,
+            matches: embed == calculated,
+        }
+    }
+
+    /// パスワードをデコードし、`bytes` に上書きする。特殊パスワードでも構わずデコードする。
+    ///
+    /// `SerializedBytes::from_password` と同じ結果になる。
+    pub fn decode_into(&self, bytes: &mut SerializedBytes) {
+        bytes.0.clear();
+
+        let mut inner: ArrayVec<u8, { Password::MAX_LEN }> =
+            self.iter().map(|pc| pc.to_inner()).collect();
+        decode_password_bytes(&mut inner);
+
+        bytes
+            .0
+            .extend(inner.into_iter().map(|b| new_unchecked!(SerializedByte, b)));
+    }
+
+    /// ロードした結果のゲーム状態(`Savedata::normalize()` 済み)を返す。
+    /// チェックサムが一致しないか、特殊パスワードなら `None` を返す。
+    ///
+    /// 実質的に同じパスワードの重複を除くための `HashMap` 等のキーとして使える。
+    pub fn equivalence_key(&self) -> Option<Savedata> {
+        if self.is_special() {
+            return None;
+        }
+        SerializedBytes::from_password(self)
+            .to_savedata()
+            .map(|savedata| savedata.normalize())
+    }
+
+    /// ロードした結果のゲーム状態が `other` と同じかどうかを返す。
+    ///
+    /// 一方でもロードできない(`equivalence_key()` が `None`)なら、自身との比較でも `false` を返す。
+    pub fn equivalent_to(&self, other: &Password) -> bool {
+        match (self.equivalence_key(), other.equivalence_key()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// パスワードの各文字の値(6bit 値)の列をその場でデコードし、シリアライズされたバイト列にする。
+///
+/// 直前の文字との XOR (先頭は 0x1F との XOR)をとり、その後 `SerializedBytes::ENCODE_ADD_TABLE` の値を mod 64 で減算する。
+/// 入力の上位 2bit は無視され、結果は全て 6bit 値になる。
+/// 各バイトはそれ以前の文字にしか依存しないので、入力途中のパスワードにも使える。
+pub fn decode_password_bytes(bytes: &mut [u8]) {
+    let mut prev = 0x1F;
+    for (i, b) in bytes.iter_mut().enumerate() {
+        let c = *b;
+        *b = (c ^ prev).wrapping_sub(SerializedBytes::ENCODE_ADD_TABLE[i % 4]) & 0x3F;
+        prev = c;
+    }
+}
+
+/// シリアライズされたバイト列(6bit 値)をその場でエンコードし、パスワードの各文字の値の列にする。
+///
+/// `decode_password_bytes` の逆変換。入力の上位 2bit は無視され、結果は全て 6bit 値になる。
+pub fn encode_password_bytes(bytes: &mut [u8]) {
+    let mut prev = 0x1F;
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = (b.wrapping_add(SerializedBytes::ENCODE_ADD_TABLE[i % 4]) & 0x3F) ^ prev;
+        prev = *b;
+    }
+}
+
+impl Savedata {
+    /// ゲーム状態をシリアライズし、`out` に上書きする。
+    ///
+    /// `SerializedBytes::from_savedata` と同じ結果になる。
+    pub fn encode_into(&self, out: &mut SerializedBytes) {
+        self.to_bits().write_bytes(out);
+    }
+
+    /// `SerializedBytes::from_savedata_with_len()` でエンコードできる最小のバイト数を返す。
+    pub(crate) fn min_encoded_len(&self) -> usize {
+        min_len_of_bits(self.to_bits().as_bitslice())
+    }
+
+    /// ゲーム状態をビット列にシリアライズする。
+    ///
+    /// 各フィールドのビット数は固定なので、合計が `BitWriter::CAPACITY` を超えることはない。
+    pub(crate) fn to_bits(&self) -> BitWriter {
+        let mut bits = BitWriter::new();
+
+        let xp_lo = self.xp as u8;
+        let xp_hi = (self.xp >> 8) as u8;
+        let purse_lo = self.purse as u8;
+        let purse_hi = (self.purse >> 8) as u8;
+
+        bits.push_bits(8, self.age_timer_hi);
+        bits.push_bits(8, purse_hi);
+        bits.push_bits(8, self.age);
+        bits.push_bits(8, purse_lo);
+        bits.push_bits(8, xp_lo);
+        bits.push_bits(6, self.deposit.get());
+        bits.push_bits(8, xp_hi);
+        serialize_spells(&mut bits, self.spells);
+        serialize_treasures(&mut bits, self.treasures);
+        bits.push_bits(4, self.respawn.get());
+        serialize_bookmarks1(&mut bits, self.bookmarks);
+        serialize_minions(&mut bits, self.minions);
+        serialize_bookmarks0(&mut bits, self.bookmarks);
+        serialize_events(&mut bits, self.events);
+        serialize_equipment(&mut bits, self.equipment);
+        serialize_inventory(&mut bits, &self.inventory);
+
+        bits
+    }
 }
 
 impl std::ops::Deref for SerializedBytes {
@@ -294,6 +600,31 @@ impl std::borrow::BorrowMut<[SerializedByte]> for SerializedBytes {
     }
 }
 
+/// バイト数を `Password::MIN_LEN..=Password::MAX_LEN` から選び、各バイトを選ぶ。
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SerializedBytes {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(Password::MIN_LEN..=Password::MAX_LEN)?;
+        (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()
+            .map(Self)
+    }
+
+    /// 残りのバイト数(`Password::MIN_LEN..=Password::MAX_LEN` に丸める)をバイト数とする。
+    fn arbitrary_take_rest(mut u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.len().clamp(Password::MIN_LEN, Password::MAX_LEN);
+        (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()
+            .map(Self)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1 + Password::MIN_LEN, Some(1 + Password::MAX_LEN))
+    }
+}
+
 impl IntoIterator for SerializedBytes {
     type Item = SerializedByte;
     type IntoIter = arrayvec::IntoIter<SerializedByte, { Password::MAX_LEN }>;
@@ -312,169 +643,521 @@ impl<'a> IntoIterator for &'a SerializedBytes {
     }
 }
 
-type SerializedBitArray = BitArr!(for SerializedBits::CAPACITY, in usize, Msb0);
-type SerializedBitSlice = BitSlice<usize, Msb0>;
+/// `SerializedBytes::from_u8_slice()` と同じ。
+impl TryFrom<&[u8]> for SerializedBytes {
+    type Error = SerializedBytesFromU8Error;
 
-/// ゲーム状態のシリアライズ用ビットベクター。容量固定。チェックサムは含まない。
-#[derive(Debug, Default)]
-struct SerializedBits {
-    inner: SerializedBitArray,
-    len: usize,
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_u8_slice(bytes)
+    }
 }
 
-impl SerializedBits {
-    // チェックサムを除いたゲーム状態は最大 159bit。
-    // 簡単のため、6 の倍数に切り上げて 162bit とする。
-    const CAPACITY: usize = 6 * 27;
+/// `SerializedBytes::from_u8_slice()` と同じ。
+impl TryFrom<Vec<u8>> for SerializedBytes {
+    type Error = SerializedBytesFromU8Error;
 
-    /// 空の `SerializedBits` を返す。
-    fn new() -> Self {
-        Self::default()
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_u8_slice(&bytes)
     }
+}
 
-    /// `SerializedBytes` から変換する。
-    fn from_bytes(bytes: &SerializedBytes) -> Self {
-        let mut this = Self::default();
-
-        // チェックサムを除いたバイト列をビット列に変換する。
-        // 意味を持つのは CAPACITY/6 バイトまでなので、それより多くは読まない。
-        if bytes.len() > 2 {
-            for &b in bytes[2..].iter().take(Self::CAPACITY / 6) {
-                this.push_bits(6, b.get());
-            }
-        }
-
-        // 長さ CAPACITY になるまで 1 を追加する。
-        this.inner[this.len..Self::CAPACITY].fill(true);
-        this.len = Self::CAPACITY;
-
-        this
+/// `SerializedBytes::to_u8_vec()` と同じ。
+impl From<SerializedBytes> for Vec<u8> {
+    fn from(bytes: SerializedBytes) -> Self {
+        bytes.to_u8_vec()
     }
+}
 
-    /// `SerializedBytes` に変換する。
-    #[allow(clippy::wrong_self_convention)]
-    fn to_bytes(&mut self) -> SerializedBytes {
-        // 長さが 6 の倍数になるまで 0 を追加する。
-        let len = (self.len + 6 - 1) / 6 * 6;
-        self.inner[self.len..len].fill(false);
-        self.len = len;
+impl std::fmt::Display for SerializedBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display_hex().fmt(f)
+    }
+}
 
-        // ビット列をバイト列に変換する。先頭にチェックサム格納用の 2 バイトを補う。
-        let inner: SerializedBytesInner = [0; 2]
-            .into_iter()
-            .chain(
-                self.as_bitslice()
-                    .chunks_exact(6)
-                    .map(|chunk| chunk.load_be::<u8>()),
-            )
-            .map(|b| unsafe { SerializedByte::new_unchecked(b) })
-            .collect();
-        let mut bytes = unsafe { SerializedBytes::new_unchecked(&inner) };
+#[derive(Debug)]
+pub struct SerializedBytesDisplayHex<'a>(&'a SerializedBytes);
 
-        // チェックサムを計算し、格納する。
-        let checksum = bytes.checksum_calculated();
-        bytes[0] = checksum.sum_add();
-        bytes[1] = checksum.sum_xor();
+impl std::fmt::Display for SerializedBytesDisplayHex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, b) in self.0.iter_u8().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{b:02X}")?;
+        }
 
-        bytes
+        Ok(())
     }
+}
 
-    /// ビットベクター全体を表すビットスライスを返す。
-    fn as_bitslice(&self) -> &SerializedBitSlice {
-        &self.inner[..self.len]
-    }
+#[derive(Debug)]
+pub struct SerializedBytesDisplayAsPassword<'a>(&'a SerializedBytes);
 
-    /// 1 個のビットを末尾に追加する。
-    fn push_bit(&mut self, bit: bool) {
-        self.inner.set(self.len, bit);
-        self.len += 1;
-    }
+impl std::fmt::Display for SerializedBytesDisplayAsPassword<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let password = self.0.to_password();
 
-    /// `n` 個のビットを末尾に追加する。
-    ///
-    /// `bits` は追加するビットたちを右詰めした値。
-    /// たとえば `[1, 0, 1, 1, 0]` を追加するなら `0b00010110` を渡す。
-    fn push_bits(&mut self, n: usize, bits: u8) {
-        self.inner[self.len..][..n].store_be(bits);
-        self.len += n;
+        for (i, (b, pc)) in self.0.iter_u8().zip(password.iter()).enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{b:02X}:{}", pc.to_char())?;
+        }
+
+        Ok(())
     }
 }
 
-fn serialize_spells(bits: &mut SerializedBits, spells: Spells) {
-    bits.push_bit(spells.houhi);
-    bits.push_bit(spells.dadadidi);
-    bits.push_bit(spells.fuyuu);
-    bits.push_bit(spells.mankintan);
-    bits.push_bit(spells.hien);
-    bits.push_bit(spells.inazuma);
-    bits.push_bit(spells.rokkaku);
-    bits.push_bit(spells.kintan);
+/// `u8` のスライスから `SerializedBytes` を作る際に発生しうるエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum SerializedBytesFromU8Error {
+    /// バイト数が正しくない。
+    #[error(
+        "serialized bytes must contain {}..={} bytes, got {len}",
+        Password::MIN_LEN,
+        Password::MAX_LEN
+    )]
+    InvalidLength { len: usize },
+
+    /// 値が 6bit 値の範囲(0x00..=0x3F)外。
+    #[error("byte 0x{value:02X} at index {index} is out of range")]
+    OutOfRange { index: usize, value: u8 },
 }
 
-fn serialize_events(bits: &mut SerializedBits, events: Events) {
-    bits.push_bit(events.hohoemi);
-    bits.push_bit(events.dragon);
-    bits.push_bit(events.sarukani);
-    bits.push_bit(events.murata);
-    bits.push_bit(events.netaro);
-    bits.push_bit(events.urashima);
-    bits.push_bit(events.kintaro);
-    bits.push_bit(events.hanasaka);
+/// `SerializedBytes::copy_to_slice()` のコピー先の長さが正しくないことを表すエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("length mismatch: expected {expected}, got {actual}")]
+pub struct LengthMismatch {
+    /// 期待される長さ(バイト数)。
+    pub expected: usize,
+    /// 実際の長さ。
+    pub actual: usize,
 }
 
-fn serialize_treasures(bits: &mut SerializedBits, treasures: Treasures) {
-    bits.push_bit(treasures.swallow);
-    bits.push_bit(treasures.hourai);
-    bits.push_bit(treasures.hotoke);
-    bits.push_bit(treasures.fur);
-    bits.push_bit(treasures.dragon);
+/// `SerializedBytes::from_savedata_with_options()` の設定。
+///
+/// `Default` は `SerializedBytes::from_savedata()` と同じ設定。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct EncodeOptions {
+    /// ビット列の末尾から 6bit 境界までの、読まれないビットの埋め方。
+    pub padding: PaddingBits,
 }
 
-fn serialize_minions(bits: &mut SerializedBits, minions: Minions) {
-    bits.push_bit(minions.monkey);
-    bits.push_bit(minions.pheasant);
-    bits.push_bit(minions.dog);
+/// シリアライズ時に読まれないビットの埋め方。
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum PaddingBits {
+    /// 全て 0 で埋める。
+    #[default]
+    Zeros,
+    /// 全て 1 で埋める。
+    Ones,
+    /// 指定した値で埋める。読まれないビットが `n` 個なら、値の下位 `n` ビットを上位から順に用いる。
+    Custom(u8),
 }
 
-fn serialize_bookmarks0(bits: &mut SerializedBits, bookmarks: Bookmarks) {
-    bits.push_bit(bookmarks.taketori);
-    bits.push_bit(bookmarks.sarukani);
-    bits.push_bit(bookmarks.kibou);
-    bits.push_bit(bookmarks.netaro);
-    bits.push_bit(bookmarks.urashima);
-    bits.push_bit(bookmarks.kintaro);
-    bits.push_bit(bookmarks.hanasaka);
-    bits.push_bit(bookmarks.tabidachi);
+/// `SerializedBytes::from_savedata_with_len()` で発生しうるエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum EncodeLenError {
+    /// バイト数が範囲外。
+    #[error(transparent)]
+    Length(#[from] PasswordLengthError),
+
+    /// 指定されたバイト数に切り詰めると、読まれるビットのうち 0 のものが 1 として読まれてしまう。
+    #[error("savedata cannot be encoded in {len} bytes: field {field} would change (needs at least {min_len} bytes)")]
+    Truncated {
+        /// 指定されたバイト数。
+        len: usize,
+        /// 最初に値が変わってしまうフィールド。
+        field: FieldId,
+        /// ゲーム状態を表せる最小のバイト数。
+        min_len: usize,
+    },
 }
 
-fn serialize_bookmarks1(bits: &mut SerializedBits, bookmarks: Bookmarks) {
-    bits.push_bit(bookmarks.hien);
-    bits.push_bit(bookmarks.hohoemi);
+/// チェックサムを除いたビット列 `bits` を表せる最小のバイト数を返す。末尾の 1 のビットは省略できる。
+fn min_len_of_bits(bits: &SerializedBitSlice) -> usize {
+    // `BitSlice::last_zero()` は全て 1 のときにオーバーフローするので使わない。
+    bits.iter()
+        .by_vals()
+        .rposition(|b| !b)
+        .map_or(Password::MIN_LEN, |i| 2 + i / 6 + 1)
 }
 
-fn serialize_equipment(bits: &mut SerializedBits, equipment: Equipment) {
-    bits.push_bits(2, equipment.helm.get());
-    bits.push_bits(4, equipment.weapon.get());
-    bits.push_bits(4, equipment.armor.get());
-    bits.push_bits(3, equipment.shoes.get());
-    bits.push_bits(2, equipment.accessory0.get());
-    bits.push_bits(2, equipment.accessory1.get());
-    bits.push_bits(1, equipment.accessory2.get());
-    bits.push_bits(1, equipment.accessory3.get());
+type SerializedBitArray = BitArr!(for BitWriter::CAPACITY, in usize, Msb0);
+pub(crate) type SerializedBitSlice = BitSlice<usize, Msb0>;
+
+/// ゲーム状態をシリアライズしたビット列(チェックサムを除く)を組み立てるビットライター。容量固定。
+///
+/// `to_bytes()` で `SerializedBytes` に変換すると、ビット列は 3 バイト目以降の各バイトの上位ビットから順に格納される。
+/// `Savedata` のシリアライズもこれを用いている。
+#[derive(Clone, Debug, Default)]
+pub struct BitWriter {
+    inner: SerializedBitArray,
+    len: usize,
 }
 
-fn serialize_inventory(bits: &mut SerializedBits, inventory: &Inventory) {
-    for item in inventory {
-        bits.push_bits(6, item.get());
+impl BitWriter {
+    /// 書き込めるビット数の上限。
+    ///
+    /// チェックサムを除いたゲーム状態は最大 `SerializedBytes::PAYLOAD_BITS` (159) bit。
+    /// 簡単のため、6 の倍数に切り上げて 162bit とする。
+    pub const CAPACITY: usize = 6 * (SerializedBytes::FULL_LEN - 2);
+
+    /// 空の `BitWriter` を返す。
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    if !inventory.is_full() {
-        bits.push_bits(6, 0);
+    /// 書き込んだビット数を返す。
+    pub fn len(&self) -> usize {
+        self.len
     }
-}
 
-fn unpack_spells(spells: u8) -> Spells {
-    let bits = spells.view_bits::<Lsb0>();
+    /// 1 ビットも書き込んでいないかどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// `SerializedBytes` に変換する。先頭 2 バイトには計算したチェックサムを格納する。
+    ///
+    /// 長さが 6 の倍数でなければ、末尾に 0 を補ったものとして変換する。
+    pub fn to_bytes(&self) -> SerializedBytes {
+        let mut bytes = SerializedBytes(SerializedBytesInner::new());
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    /// `SerializedBytes` に変換する。先頭 2 バイト(チェックサム格納領域)は 0 のままにする。
+    ///
+    /// 長さが 6 の倍数でなければ、末尾に 0 を補ったものとして変換する。
+    pub fn to_bytes_without_checksum(&self) -> SerializedBytes {
+        let mut bytes = SerializedBytes(SerializedBytesInner::new());
+        self.write_bytes_without_checksum(&mut bytes);
+        bytes
+    }
+
+    /// `to_bytes()` と同じ結果を `out` に上書きする。
+    pub(crate) fn write_bytes(&self, out: &mut SerializedBytes) {
+        self.write_bytes_without_checksum(out);
+        out.fix_checksum();
+    }
+
+    fn write_bytes_without_checksum(&self, out: &mut SerializedBytes) {
+        // ビット列をバイト列に変換する。先頭にチェックサム格納用の 2 バイトを補う。
+        out.0.clear();
+        out.0.extend(
+            [0; 2]
+                .into_iter()
+                .chain(
+                    self.as_bitslice()
+                        .chunks(6)
+                        .map(|chunk| chunk.load_be::<u8>() << (6 - chunk.len())),
+                )
+                .map(|b| new_unchecked!(SerializedByte, b)),
+        );
+    }
+
+    /// 書き込んだビット列全体を表すビットスライスを返す。
+    pub(crate) fn as_bitslice(&self) -> &SerializedBitSlice {
+        &self.inner[..self.len]
+    }
+
+    /// 1 個のビットを末尾に追加する。
+    ///
+    /// 容量を超える場合、パニックする。
+    pub fn push_bit(&mut self, bit: bool) {
+        self.try_push_bit(bit)
+            .unwrap_or_else(|e| panic!("BitWriter::push_bit: {e}"));
+    }
+
+    /// `n` (`n <= 8`) 個のビットを末尾に追加する。
+    ///
+    /// `bits` は追加するビットたちを右詰めした値。
+    /// たとえば `[1, 0, 1, 1, 0]` を追加するなら `0b00010110` を渡す。
+    ///
+    /// `n > 8` の場合、`bits` が `n` ビットに収まらない場合、容量を超える場合、パニックする。
+    pub fn push_bits(&mut self, n: usize, bits: u8) {
+        self.try_push_bits(n, bits)
+            .unwrap_or_else(|e| panic!("BitWriter::push_bits: {e}"));
+    }
+
+    /// `n` (`n <= 16`) 個のビットを末尾に追加する。`push_bits()` の 16bit 版。
+    ///
+    /// `n > 16` の場合、`bits` が `n` ビットに収まらない場合、容量を超える場合、パニックする。
+    pub fn push_bits_u16(&mut self, n: usize, bits: u16) {
+        self.try_push_bits_u16(n, bits)
+            .unwrap_or_else(|e| panic!("BitWriter::push_bits_u16: {e}"));
+    }
+
+    /// 1 個のビットを末尾に追加する。容量を超える場合、何もせずにエラーを返す。
+    pub fn try_push_bit(&mut self, bit: bool) -> Result<(), BitCapacityError> {
+        self.reserve(1)?;
+        self.inner.set(self.len, bit);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// `n` (`n <= 8`) 個のビットを末尾に追加する。`push_bits()` のエラーを返す版。
+    ///
+    /// エラーの場合は何も追加しない。
+    pub fn try_push_bits(&mut self, n: usize, bits: u8) -> Result<(), BitCapacityError> {
+        self.push_bits_impl(n, u16::from(bits), 8)
+    }
+
+    /// `n` (`n <= 16`) 個のビットを末尾に追加する。`push_bits_u16()` のエラーを返す版。
+    ///
+    /// エラーの場合は何も追加しない。
+    pub fn try_push_bits_u16(&mut self, n: usize, bits: u16) -> Result<(), BitCapacityError> {
+        self.push_bits_impl(n, bits, 16)
+    }
+
+    /// `bits` の下位 `n` (`n <= max_n`) ビットを末尾に追加する。
+    fn push_bits_impl(
+        &mut self,
+        n: usize,
+        bits: u16,
+        max_n: usize,
+    ) -> Result<(), BitCapacityError> {
+        if n > max_n {
+            return Err(BitCapacityError::TooWide { n, max: max_n });
+        }
+        if n < 16 && bits >> n != 0 {
+            return Err(BitCapacityError::ValueOverflow { n, value: bits });
+        }
+        self.reserve(n)?;
+
+        if n == 0 {
+            return Ok(());
+        }
+        self.inner[self.len..][..n].store_be(bits);
+        self.len += n;
+        Ok(())
+    }
+
+    /// `n` 個のビットを追加できることを確かめる。
+    fn reserve(&self, n: usize) -> Result<(), BitCapacityError> {
+        if self.len + n > Self::CAPACITY {
+            return Err(BitCapacityError::CapacityExceeded {
+                len: self.len,
+                n,
+                capacity: Self::CAPACITY,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// `BitWriter` へのビットの追加で発生しうるエラー。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum BitCapacityError {
+    /// 一度に追加するビット数が多すぎる。
+    #[error("cannot push {n} bits at once (max {max})")]
+    TooWide { n: usize, max: usize },
+
+    /// 値が指定したビット数に収まらない。
+    #[error("value 0x{value:X} does not fit in {n} bits")]
+    ValueOverflow { n: usize, value: u16 },
+
+    /// 容量を超える。
+    #[error("capacity exceeded ({len} + {n} > {capacity})")]
+    CapacityExceeded {
+        len: usize,
+        n: usize,
+        capacity: usize,
+    },
+}
+
+/// `SerializedBytes` のチェックサムを除いた部分を先頭からビット単位で読むビットリーダー。
+///
+/// ゲームと同様、バイト列が足りない部分のビットは全て 1 として読む。
+/// 読めるのは `BitWriter::CAPACITY` ビットまでで、それ以降のバイトは無視する。
+/// `SerializedBytes` のデシリアライズもこれを用いている。
+#[derive(Clone, Debug)]
+pub struct BitReader {
+    bits: SerializedBitArray,
+    pos: usize,
+}
+
+impl BitReader {
+    /// `bytes` の 3 バイト目以降を読む `BitReader` を返す。
+    pub fn new(bytes: &SerializedBytes) -> Self {
+        let mut writer = BitWriter::new();
+
+        // 意味を持つのは CAPACITY/6 バイトまでなので、それより多くは読まない。
+        if bytes.len() > 2 {
+            for &b in bytes[2..].iter().take(BitWriter::CAPACITY / 6) {
+                writer.push_bits(6, b.get());
+            }
+        }
+
+        // 長さ CAPACITY になるまで 1 を追加する。
+        writer.inner[writer.len..BitWriter::CAPACITY].fill(true);
+
+        Self {
+            bits: writer.inner,
+            pos: 0,
+        }
+    }
+
+    /// 残りのビット数(1 として補われるものを含む)を返す。
+    pub fn remaining(&self) -> usize {
+        BitWriter::CAPACITY - self.pos
+    }
+
+    /// 1 個のビットを読む。
+    ///
+    /// 残りのビットがなければパニックする。
+    pub fn read_bit(&mut self) -> bool {
+        self.try_read_bit()
+            .expect("BitReader::read_bit: out of bits")
+    }
+
+    /// `n` (`n <= 8`) 個のビットを読み、右詰めした値を返す。
+    ///
+    /// `n > 8` の場合、`n` が残りのビット数より大きい場合、パニックする。
+    pub fn read_bits(&mut self, n: usize) -> u8 {
+        assert!(n <= 8, "BitReader::read_bits: n must be <= 8, got {n}");
+        self.try_read_bits(n).unwrap_or_else(|| {
+            panic!(
+                "BitReader::read_bits: out of bits ({} + {n} > {})",
+                self.pos,
+                BitWriter::CAPACITY
+            )
+        })
+    }
+
+    /// `n` (`n <= 16`) 個のビットを読み、右詰めした値を返す。`read_bits()` の 16bit 版。
+    ///
+    /// `n > 16` の場合、`n` が残りのビット数より大きい場合、パニックする。
+    pub fn read_bits_u16(&mut self, n: usize) -> u16 {
+        assert!(
+            n <= 16,
+            "BitReader::read_bits_u16: n must be <= 16, got {n}"
+        );
+        self.try_read_bits_u16(n).unwrap_or_else(|| {
+            panic!(
+                "BitReader::read_bits_u16: out of bits ({} + {n} > {})",
+                self.pos,
+                BitWriter::CAPACITY
+            )
+        })
+    }
+
+    /// 1 個のビットを読む。残りのビットがなければ `None` を返す。
+    pub fn try_read_bit(&mut self) -> Option<bool> {
+        self.try_read_bits(1).map(|b| b != 0)
+    }
+
+    /// `n` (`n <= 8`) 個のビットを読み、右詰めした値を返す。
+    ///
+    /// `n > 8` の場合、`n` が残りのビット数より大きい場合、何も読まずに `None` を返す。
+    pub fn try_read_bits(&mut self, n: usize) -> Option<u8> {
+        if n > 8 {
+            return None;
+        }
+        self.try_read_bits_u16(n).map(|value| value as u8)
+    }
+
+    /// `n` (`n <= 16`) 個のビットを読み、右詰めした値を返す。`try_read_bits()` の 16bit 版。
+    pub fn try_read_bits_u16(&mut self, n: usize) -> Option<u16> {
+        if n > 16 || n > self.remaining() {
+            return None;
+        }
+
+        if n == 0 {
+            return Some(0);
+        }
+        let value = self.bits[self.pos..][..n].load_be::<u16>();
+        self.pos += n;
+
+        Some(value)
+    }
+}
+
+fn serialize_spells(bits: &mut BitWriter, spells: Spells) {
+    bits.push_bit(spells.houhi);
+    bits.push_bit(spells.dadadidi);
+    bits.push_bit(spells.fuyuu);
+    bits.push_bit(spells.mankintan);
+    bits.push_bit(spells.hien);
+    bits.push_bit(spells.inazuma);
+    bits.push_bit(spells.rokkaku);
+    bits.push_bit(spells.kintan);
+}
+
+fn serialize_events(bits: &mut BitWriter, events: Events) {
+    bits.push_bit(events.hohoemi);
+    bits.push_bit(events.dragon);
+    bits.push_bit(events.sarukani);
+    bits.push_bit(events.murata);
+    bits.push_bit(events.netaro);
+    bits.push_bit(events.urashima);
+    bits.push_bit(events.kintaro);
+    bits.push_bit(events.hanasaka);
+}
+
+fn serialize_treasures(bits: &mut BitWriter, treasures: Treasures) {
+    bits.push_bit(treasures.swallow);
+    bits.push_bit(treasures.hourai);
+    bits.push_bit(treasures.hotoke);
+    bits.push_bit(treasures.fur);
+    bits.push_bit(treasures.dragon);
+}
+
+fn serialize_minions(bits: &mut BitWriter, minions: Minions) {
+    bits.push_bit(minions.monkey);
+    bits.push_bit(minions.pheasant);
+    bits.push_bit(minions.dog);
+}
+
+fn serialize_bookmarks0(bits: &mut BitWriter, bookmarks: Bookmarks) {
+    bits.push_bit(bookmarks.taketori);
+    bits.push_bit(bookmarks.sarukani);
+    bits.push_bit(bookmarks.kibou);
+    bits.push_bit(bookmarks.netaro);
+    bits.push_bit(bookmarks.urashima);
+    bits.push_bit(bookmarks.kintaro);
+    bits.push_bit(bookmarks.hanasaka);
+    bits.push_bit(bookmarks.tabidachi);
+}
+
+fn serialize_bookmarks1(bits: &mut BitWriter, bookmarks: Bookmarks) {
+    bits.push_bit(bookmarks.hien);
+    bits.push_bit(bookmarks.hohoemi);
+}
+
+impl Equipment {
+    /// 装備をビット列にシリアライズする。`Savedata::to_bits()` の装備の部分と一致する。
+    pub(crate) fn to_bits(self) -> BitWriter {
+        let mut bits = BitWriter::new();
+        serialize_equipment(&mut bits, self);
+        bits
+    }
+}
+
+fn serialize_equipment(bits: &mut BitWriter, equipment: Equipment) {
+    bits.push_bits(2, equipment.helm.get());
+    bits.push_bits(4, equipment.weapon.get());
+    bits.push_bits(4, equipment.armor.get());
+    bits.push_bits(3, equipment.shoes.get());
+    bits.push_bits(2, equipment.accessory0.get());
+    bits.push_bits(2, equipment.accessory1.get());
+    bits.push_bits(1, equipment.accessory2.get());
+    bits.push_bits(1, equipment.accessory3.get());
+}
+
+fn serialize_inventory(bits: &mut BitWriter, inventory: &Inventory) {
+    for item in inventory {
+        bits.push_bits(6, item.get());
+    }
+
+    if !inventory.is_full() {
+        bits.push_bits(6, 0);
+    }
+}
+
+fn unpack_spells(spells: u8) -> Spells {
+    let bits = spells.view_bits::<Lsb0>();
 
     Spells {
         kintan: bits[0],
@@ -542,26 +1225,26 @@ fn unpack_bookmarks(bookmarks: [u8; 2]) -> Bookmarks {
     }
 }
 
-fn deserialize_equipment(bits: &SerializedBitSlice) -> (Equipment, &SerializedBitSlice) {
-    let (helm, bits) = deserialize_bits(bits, 2);
-    let (weapon, bits) = deserialize_bits(bits, 4);
-    let (armor, bits) = deserialize_bits(bits, 4);
-    let (shoes, bits) = deserialize_bits(bits, 3);
-    let (accessory0, bits) = deserialize_bits(bits, 2);
-    let (accessory1, bits) = deserialize_bits(bits, 2);
-    let (accessory2, bits) = deserialize_bits(bits, 1);
-    let (accessory3, bits) = deserialize_bits(bits, 1);
-
-    let helm = unsafe { HelmIndex::new_unchecked(helm) };
-    let weapon = unsafe { WeaponIndex::new_unchecked(weapon) };
-    let armor = unsafe { ArmorIndex::new_unchecked(armor) };
-    let shoes = unsafe { ShoesIndex::new_unchecked(shoes) };
-    let accessory0 = unsafe { Accessory0Index::new_unchecked(accessory0) };
-    let accessory1 = unsafe { Accessory1Index::new_unchecked(accessory1) };
-    let accessory2 = unsafe { Accessory2Index::new_unchecked(accessory2) };
-    let accessory3 = unsafe { Accessory3Index::new_unchecked(accessory3) };
-
-    let equipment = Equipment {
+fn deserialize_equipment(reader: &mut BitReader) -> Equipment {
+    let helm = reader.read_bits(2);
+    let weapon = reader.read_bits(4);
+    let armor = reader.read_bits(4);
+    let shoes = reader.read_bits(3);
+    let accessory0 = reader.read_bits(2);
+    let accessory1 = reader.read_bits(2);
+    let accessory2 = reader.read_bits(1);
+    let accessory3 = reader.read_bits(1);
+
+    let helm = new_unchecked!(HelmIndex, helm);
+    let weapon = new_unchecked!(WeaponIndex, weapon);
+    let armor = new_unchecked!(ArmorIndex, armor);
+    let shoes = new_unchecked!(ShoesIndex, shoes);
+    let accessory0 = new_unchecked!(Accessory0Index, accessory0);
+    let accessory1 = new_unchecked!(Accessory1Index, accessory1);
+    let accessory2 = new_unchecked!(Accessory2Index, accessory2);
+    let accessory3 = new_unchecked!(Accessory3Index, accessory3);
+
+    Equipment {
         helm,
         weapon,
         armor,
@@ -570,34 +1253,21 @@ fn deserialize_equipment(bits: &SerializedBitSlice) -> (Equipment, &SerializedBi
         accessory1,
         accessory2,
         accessory3,
-    };
-
-    (equipment, bits)
+    }
 }
 
-fn deserialize_inventory(mut bits: &SerializedBitSlice) -> (Inventory, &SerializedBitSlice) {
-    let mut inventory = Inventory::default();
+/// インベントリを読む。最初の 0 までの値(最大 8 個)をアイテムIDとして読み、0 以降は読まない。
+fn deserialize_inventory(reader: &mut BitReader, inventory: &mut Inventory) {
+    inventory.clear();
 
     for _ in 0..8 {
-        let item;
-        (item, bits) = deserialize_bits(bits, 6);
+        let item = reader.read_bits(6);
         if item == 0 {
             break;
         }
-        let item = unsafe { ItemId::new_unchecked(item) };
+        let item = new_unchecked!(ItemId, item);
         inventory.push(item);
     }
-
-    (inventory, bits)
-}
-
-fn deserialize_bits(bits: &SerializedBitSlice, n: usize) -> (u8, &SerializedBitSlice) {
-    debug_assert!(matches!(n, 1..=8));
-
-    let (bits, remain) = bits.split_at(n);
-    let value = bits.load_be::<u8>();
-
-    (value, remain)
 }
 
 #[cfg(test)]
@@ -628,6 +1298,36 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "forbid-unsafe")]
+    #[should_panic(expected = "serialized bytes length out of range")]
+    fn test_bytes_new_unchecked_out_of_range() {
+        SerializedBytes::new_unchecked(&[SerializedByte::MIN; Password::MAX_LEN + 1]);
+    }
+
+    #[test]
+    fn test_bytes_new_checked() {
+        assert!(SerializedBytes::new_checked(&[SerializedByte::MIN]).is_ok());
+        assert!(SerializedBytes::new_checked(&[SerializedByte::MIN; Password::MAX_LEN]).is_ok());
+
+        assert_eq!(
+            SerializedBytes::new_checked(&[]),
+            Err(PasswordLengthError {
+                actual: 0,
+                min: 1,
+                max: 38
+            })
+        );
+        assert_eq!(
+            SerializedBytes::new_checked(&[SerializedByte::MIN; Password::MAX_LEN + 1]),
+            Err(PasswordLengthError {
+                actual: 39,
+                min: 1,
+                max: 38
+            })
+        );
+    }
+
     #[test]
     fn test_bytes_password_roundtrip() {
         fn f(s: &str) {
@@ -644,6 +1344,154 @@ mod tests {
         f("すべてのてきがみたいな");
     }
 
+    #[test]
+    fn test_password_bytes() {
+        let password = Password::parse("おにのばか").unwrap();
+        let mut buf: Vec<u8> = password.iter().map(|pc| pc.to_inner()).collect();
+        decode_password_bytes(&mut buf);
+        assert_eq!(buf, [0x16, 0x38, 0x1B, 0x0D, 0x2E]);
+        assert_eq!(buf, SerializedBytes::from_password(&password).to_u8_vec());
+
+        encode_password_bytes(&mut buf);
+        assert!(buf
+            .iter()
+            .copied()
+            .eq(password.iter().map(|pc| pc.to_inner())));
+
+        // 入力途中のパスワードでも、それまでのバイトは入力完了後と一致する。
+        let mut prefix: Vec<u8> = password.iter().take(3).map(|pc| pc.to_inner()).collect();
+        decode_password_bytes(&mut prefix);
+        assert_eq!(prefix, [0x16, 0x38, 0x1B]);
+
+        // 上位 2bit は無視される。
+        let mut buf = [0xC0 | 0x16, 0x38];
+        encode_password_bytes(&mut buf);
+        let mut expected = [0x16, 0x38];
+        encode_password_bytes(&mut expected);
+        assert_eq!(buf, expected);
+
+        let mut empty: [u8; 0] = [];
+        decode_password_bytes(&mut empty);
+        encode_password_bytes(&mut empty);
+    }
+
+    #[test]
+    fn test_bytes_from_u8_slice() {
+        let bytes = SerializedBytes::from_savedata(&Savedata::default());
+        let raw = bytes.to_u8_vec();
+        assert_eq!(SerializedBytes::from_u8_slice(&raw), Ok(bytes.clone()));
+        assert_eq!(SerializedBytes::from_u8_slice_masked(&raw), Ok(bytes));
+
+        assert_eq!(
+            SerializedBytes::from_u8_slice(&[]),
+            Err(SerializedBytesFromU8Error::InvalidLength { len: 0 })
+        );
+        assert_eq!(
+            SerializedBytes::from_u8_slice(&[0; Password::MAX_LEN + 1]),
+            Err(SerializedBytesFromU8Error::InvalidLength {
+                len: Password::MAX_LEN + 1
+            })
+        );
+        assert_eq!(
+            SerializedBytes::from_u8_slice(&[0x3F, 0x40, 0xFF]),
+            Err(SerializedBytesFromU8Error::OutOfRange {
+                index: 1,
+                value: 0x40
+            })
+        );
+
+        assert_eq!(
+            SerializedBytes::from_u8_slice_masked(&[0x3F, 0x40, 0xFF]).ok(),
+            SerializedBytes::from_u8_slice(&[0x3F, 0x00, 0x3F]).ok()
+        );
+        assert_eq!(
+            SerializedBytes::from_u8_slice_masked(&[0; Password::MAX_LEN + 1]),
+            Err(PasswordLengthError::new(Password::MAX_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn test_bytes_u8_conversion() {
+        let bytes = SerializedBytes::from_savedata(&Savedata::default());
+        let raw: Vec<u8> = bytes.clone().into();
+        assert_eq!(raw, bytes.to_u8_vec());
+        assert_eq!(SerializedBytes::try_from(raw.as_slice()), Ok(bytes.clone()));
+        assert_eq!(SerializedBytes::try_from(raw), Ok(bytes));
+
+        for (raw, err) in [
+            (vec![], SerializedBytesFromU8Error::InvalidLength { len: 0 }),
+            (
+                vec![0; Password::MAX_LEN + 1],
+                SerializedBytesFromU8Error::InvalidLength {
+                    len: Password::MAX_LEN + 1,
+                },
+            ),
+            (
+                vec![0x3F, 0x3F, 0x40],
+                SerializedBytesFromU8Error::OutOfRange {
+                    index: 2,
+                    value: 0x40,
+                },
+            ),
+        ] {
+            assert_eq!(SerializedBytes::try_from(raw.as_slice()), Err(err));
+            assert_eq!(SerializedBytes::try_from(raw), Err(err));
+        }
+    }
+
+    #[test]
+    fn test_bytes_u8_view() {
+        for s in [
+            "ふ",
+            "おにのばか",
+            "ああああああああああああああああああああああああああああああ",
+        ] {
+            let bytes = SerializedBytes::from_password(&Password::parse(s).unwrap());
+            let expected: Vec<u8> = bytes.iter().map(|b| b.get()).collect();
+
+            assert!(bytes.iter_u8().eq(expected.iter().copied()));
+            assert_eq!(bytes.to_u8_vec(), expected);
+
+            let mut out = vec![0xFF; bytes.len()];
+            assert_eq!(bytes.copy_to_slice(&mut out), Ok(()));
+            assert_eq!(out, expected);
+        }
+
+        let bytes = SerializedBytes::from_password(&Password::parse("おにのばか").unwrap());
+        let mut out = [0xFF; 6];
+        assert_eq!(
+            bytes.copy_to_slice(&mut out),
+            Err(LengthMismatch {
+                expected: 5,
+                actual: 6
+            })
+        );
+        assert_eq!(out, [0xFF; 6]);
+        assert_eq!(
+            bytes.copy_to_slice(&mut out[..4]),
+            Err(LengthMismatch {
+                expected: 5,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_bytes_display() {
+        let bytes = SerializedBytes::from_password(&Password::parse("ふ").unwrap());
+        assert_eq!(bytes.display_hex().to_string(), "3F");
+        assert_eq!(bytes.to_string(), "3F");
+        assert_eq!(bytes.display_as_password().to_string(), "3F:ふ");
+
+        let bytes = SerializedBytes::from_password(&Password::parse("おにのばか").unwrap());
+        assert_eq!(bytes.display_hex().to_string(), "16 38 1B 0D 2E");
+        assert_eq!(bytes.to_string(), "16 38 1B 0D 2E");
+        assert_eq!(
+            bytes.display_as_password().to_string(),
+            "16:お 38:に 1B:の 0D:ば 2E:か"
+        );
+    }
+
     #[test]
     fn test_bytes_checksum() {
         fn f(s: &str) -> SerializedBytes {
@@ -657,6 +1505,269 @@ mod tests {
         assert!(!f("ああああ").checksum_is_ok());
     }
 
+    #[test]
+    fn test_bytes_from_savedata_with_options() {
+        for savedata in [
+            Savedata::default(),
+            Savedata {
+                xp: 1234,
+                inventory: [ItemId::MIN, ItemId::MAX].into_iter().collect(),
+                ..Savedata::default()
+            },
+        ] {
+            let encode = |padding| {
+                SerializedBytes::from_savedata_with_options(&savedata, EncodeOptions { padding })
+            };
+
+            let zeros = encode(PaddingBits::Zeros);
+            assert_eq!(zeros, SerializedBytes::from_savedata(&savedata));
+            assert_eq!(
+                SerializedBytes::from_savedata_with_options(&savedata, EncodeOptions::default()),
+                zeros
+            );
+
+            // 読まれない末尾のビットのみが異なる。
+            let ones = encode(PaddingBits::Ones);
+            let custom = encode(PaddingBits::Custom(0b1111_1010));
+            assert_ne!(zeros.to_password(), ones.to_password());
+            assert_ne!(zeros.to_password(), custom.to_password());
+            for bytes in [&zeros, &ones, &custom] {
+                assert_eq!(bytes.len(), zeros.len());
+                assert_eq!(bytes[2..bytes.len() - 1], zeros[2..zeros.len() - 1]);
+                assert_eq!(bytes.to_savedata(), Some(savedata.clone()));
+            }
+
+            // ビット列は 111 + 6 * (アイテム数 + 1) ビットなので、読まれないビットは 3 個。
+            let last = |bytes: &SerializedBytes| bytes.iter_u8().last().unwrap();
+            assert_eq!(last(&ones), last(&zeros) | 0b111);
+            assert_eq!(last(&custom), last(&zeros) | 0b010);
+        }
+    }
+
+    #[test]
+    fn test_bytes_from_savedata_with_len() {
+        // "ふ" のゲーム状態はビットが全て 1 なので、任意の長さで表せる。
+        let all_ones = SerializedBytes::from_password(&Password::parse("ふ").unwrap())
+            .to_savedata()
+            .unwrap();
+        for len in Password::MIN_LEN..=Password::MAX_LEN {
+            let bytes = SerializedBytes::from_savedata_with_len(&all_ones, len).unwrap();
+            assert_eq!(bytes.len(), len);
+            assert_eq!(bytes.to_savedata(), Some(all_ones.clone()));
+        }
+        assert_eq!(
+            SerializedBytes::from_savedata_with_len(&all_ones, 1)
+                .unwrap()
+                .to_password(),
+            Password::parse("ふ").unwrap()
+        );
+
+        // `events` (84..92 ビット目) の最後のビットのみが 0。データ部は 92/6 = 15.3.. バイト必要。
+        let savedata = Savedata {
+            events: Events {
+                hanasaka: false,
+                ..all_ones.events
+            },
+            ..all_ones.clone()
+        };
+        for len in 18..=Password::MAX_LEN {
+            let bytes = SerializedBytes::from_savedata_with_len(&savedata, len).unwrap();
+            assert_eq!(bytes.len(), len);
+            assert_eq!(bytes.to_savedata(), Some(savedata.clone()));
+        }
+        let err = SerializedBytes::from_savedata_with_len(&savedata, 17).unwrap_err();
+        assert_eq!(
+            err,
+            EncodeLenError::Truncated {
+                len: 17,
+                field: FieldId::Events,
+                min_len: 18,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "savedata cannot be encoded in 17 bytes: field events would change (needs at least 18 bytes)"
+        );
+        assert!(matches!(
+            SerializedBytes::from_savedata_with_len(&savedata, 1),
+            Err(EncodeLenError::Truncated { min_len: 18, .. })
+        ));
+
+        // デフォルトのゲーム状態では空のインベントリの終端も読まれる。
+        let savedata = Savedata::default();
+        let natural = SerializedBytes::from_savedata(&savedata);
+        assert_eq!(
+            SerializedBytes::from_savedata_with_len(&savedata, natural.len()),
+            Ok(natural.clone())
+        );
+        let err =
+            SerializedBytes::from_savedata_with_len(&savedata, natural.len() - 1).unwrap_err();
+        assert!(
+            matches!(&err, EncodeLenError::Truncated { field, min_len, .. } if *field == FieldId::InventorySlot(0) && *min_len == natural.len()),
+            "{err:?}"
+        );
+
+        assert_eq!(
+            SerializedBytes::from_savedata_with_len(&savedata, 0),
+            Err(PasswordLengthError::new(0).into())
+        );
+        assert_eq!(
+            SerializedBytes::from_savedata_with_len(&savedata, Password::MAX_LEN + 1),
+            Err(PasswordLengthError::new(Password::MAX_LEN + 1).into())
+        );
+    }
+
+    #[test]
+    fn test_bytes_edit() {
+        // 最初のフィールド(`age_timer_hi`)の上位 6bit を書き換える。
+        let mut bytes = SerializedBytes::from_savedata(&Savedata::default());
+        let len = bytes.edit(|data| {
+            data[0] = SerializedByte::MAX;
+            data.len()
+        });
+        assert_eq!(len, bytes.len() - 2);
+        assert!(bytes.checksum_is_ok());
+        assert_eq!(
+            bytes.to_savedata(),
+            Some(Savedata {
+                age_timer_hi: 0xFC,
+                ..Savedata::default()
+            })
+        );
+
+        // チェックサム格納領域を書き換えても上書きされる。
+        let mut raw = SerializedBytes::from_savedata(&Savedata::default());
+        raw.edit_raw(|bytes| {
+            bytes[0] = SerializedByte::MIN;
+            bytes[1] = SerializedByte::MIN;
+            bytes[2] = SerializedByte::MAX;
+        });
+        assert_eq!(raw, bytes);
+
+        // 1 バイトの場合は空のスライスが渡される。
+        let mut bytes = SerializedBytes::from_password(&Password::parse("あ").unwrap());
+        bytes.edit(|data| assert!(data.is_empty()));
+        assert_eq!(bytes.to_password(), Password::parse("ふ").unwrap());
+    }
+
+    #[test]
+    fn test_bytes_to_savedata_unchecked() {
+        fn f(s: &str) -> SerializedBytes {
+            SerializedBytes::from_password(&Password::parse(s).unwrap())
+        }
+
+        for s in ["ふ", "おにのばか"] {
+            let bytes = f(s);
+            assert_eq!(Some(bytes.to_savedata_unchecked()), bytes.to_savedata());
+        }
+
+        // "あ" はチェックサムのみが "ふ" と異なり、データ部のビットは全て 1 になる。
+        let all_ones = f("ふ").to_savedata().unwrap();
+        assert_eq!(f("あ").to_savedata(), None);
+        assert_eq!(f("あ").to_savedata_unchecked(), all_ones);
+
+        // "ああああ" のデータ部は [0x0E, 0x1F]。`age_timer_hi` の上位 6bit が 0x0E になり、残りは全て 1。
+        let bytes = f("ああああ");
+        assert_eq!(bytes.to_savedata(), None);
+        assert_eq!(
+            bytes.to_savedata_unchecked(),
+            Savedata {
+                age_timer_hi: 0x39,
+                ..all_ones.clone()
+            }
+        );
+        assert_eq!(bytes.to_savedata_unchecked(), bytes.to_savedata_unchecked());
+    }
+
+    #[test]
+    fn test_bytes_to_savedata_checked() {
+        fn f(s: &str) -> SerializedBytes {
+            SerializedBytes::from_password(&Password::parse(s).unwrap())
+        }
+        fn checksum(sum_add: u8, sum_xor: u8) -> Checksum {
+            Checksum::new(
+                ChecksumAdd::new(sum_add).unwrap(),
+                ChecksumXor::new(sum_xor).unwrap(),
+            )
+        }
+
+        let bytes = f("おにのばか");
+        assert_eq!(bytes.to_savedata_checked().ok(), bytes.to_savedata());
+        assert!(bytes.to_savedata().is_some());
+
+        // 1 バイトの場合、格納された XOR チェックサムは 0x3F とみなされる。
+        let bytes = f("あ");
+        let err = bytes.to_savedata_checked().unwrap_err();
+        assert_eq!(
+            err,
+            SavedataDecodeError {
+                embed: checksum(0x1A, 0x3F),
+                calculated: checksum(0x3F, 0x3F),
+                len: 1,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "checksum mismatch: embedded add=0x1A xor=0x3F, calculated add=0x3F xor=0x3F"
+        );
+        assert_eq!(bytes.to_savedata(), None);
+
+        // XOR チェックサムのみが一致しない場合。
+        let mut bytes = SerializedBytes::from_savedata(&Savedata::default());
+        let embed = bytes.checksum_embed();
+        bytes[1] = ChecksumXor::new(embed.sum_xor().get() ^ 1).unwrap();
+        let err = bytes.to_savedata_checked().unwrap_err();
+        assert_eq!(err.embed.sum_add(), err.calculated.sum_add());
+        assert_ne!(err.embed.sum_xor(), err.calculated.sum_xor());
+        assert_eq!(err.calculated, embed);
+        assert_eq!(err.len, bytes.len());
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "checksum mismatch: embedded add={:#04X} xor={:#04X}, calculated add={:#04X} xor={:#04X}",
+                embed.sum_add(),
+                embed.sum_xor().get() ^ 1,
+                embed.sum_add(),
+                embed.sum_xor(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_bytes_fix_checksum() {
+        // 最初のフィールド(`age_timer_hi`)の上位 6bit を書き換える。
+        let mut bytes = SerializedBytes::from_savedata(&Savedata::default());
+        bytes.as_mut_slice()[2] = SerializedByte::MAX;
+        assert!(!bytes.checksum_is_ok());
+        assert_eq!(bytes.to_savedata(), None);
+
+        bytes.fix_checksum();
+        assert!(bytes.checksum_is_ok());
+        assert_eq!(
+            bytes.to_savedata(),
+            Some(Savedata {
+                age_timer_hi: 0xFC,
+                ..Savedata::default()
+            })
+        );
+
+        // 1, 2 バイトの場合も `checksum_is_ok()` が `true` になる。
+        for s in ["あ", "ああ", "ああああ"] {
+            let bytes = SerializedBytes::from_password(&Password::parse(s).unwrap());
+            assert!(!bytes.checksum_is_ok());
+            let fixed = bytes.clone().with_fixed_checksum();
+            assert!(fixed.checksum_is_ok());
+            assert_eq!(fixed.len(), bytes.len());
+            assert_eq!(fixed.get(2..), bytes.get(2..));
+        }
+        assert_eq!(
+            SerializedBytes::from_password(&Password::parse("あ").unwrap())
+                .with_fixed_checksum()
+                .to_password(),
+            Password::parse("ふ").unwrap()
+        );
+    }
+
     #[test]
     fn test_load_fu() {
         let bytes = SerializedBytes::from_password(&Password::parse("ふ").unwrap());
@@ -708,6 +1819,441 @@ mod tests {
         .is_some());
     }
 
+    /// チェックサムを除いたビット列のうち、インベントリより前のフィールドが占めるビット数。
+    const INVENTORY_OFFSET: usize = 111;
+
+    /// デフォルトのセーブデータのインベントリ部分を任意の 6bit 値の列で置き換えたバイト列を作る。
+    fn craft_inventory(slots: &[u8]) -> SerializedBytes {
+        let mut bytes = SerializedBytes::from_savedata(&Savedata::default());
+
+        let mut head = BitReader::new(&bytes);
+        let mut bits = BitWriter::new();
+        for _ in 0..INVENTORY_OFFSET {
+            bits.push_bit(head.read_bit());
+        }
+        for &slot in slots {
+            bits.push_bits(6, slot);
+        }
+        bits.write_bytes(&mut bytes);
+
+        bytes
+    }
+
+    fn items(ids: &[u8]) -> Inventory {
+        ids.iter().map(|&id| ItemId::new(id).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_inventory_offset() {
+        // 空のインベントリは終端の 0 のみからなる。
+        let bytes = SerializedBytes::from_savedata(&Savedata::default());
+        assert_eq!(
+            6 * (bytes.len() - 2),
+            (INVENTORY_OFFSET + 6).div_ceil(6) * 6
+        );
+        assert_eq!(craft_inventory(&[0]), bytes);
+    }
+
+    #[test]
+    fn test_inventory_terminator() {
+        let load = |slots: &[u8]| craft_inventory(slots).to_savedata().unwrap();
+
+        // 0 以降の値は無視される。
+        assert_eq!(load(&[1, 2, 0, 3, 4]).inventory, items(&[1, 2]));
+        assert_eq!(load(&[0, 5, 6, 7, 8, 9, 10, 11]).inventory, items(&[]));
+
+        // 8 個読んだら終わり。それ以降のビットは読まれない。
+        let expected = items(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(load(&[1, 2, 3, 4, 5, 6, 7, 8]).inventory, expected);
+        let mut bytes = craft_inventory(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        bytes.0.push(SerializedByte::new(9).unwrap());
+        bytes.fix_checksum();
+        assert_eq!(bytes.to_savedata().unwrap().inventory, expected);
+
+        // 終端がないまま途切れた場合、足りないビットは 1 として扱われる。
+        // ここではバイト境界まで 0 が 3bit 補われるので、次の値は 0b000111 となる。
+        assert_eq!(
+            load(&[1]).inventory,
+            items(&[1, 0b000111, 0x3F, 0x3F, 0x3F, 0x3F, 0x3F, 0x3F])
+        );
+
+        // インベントリの内容は他のフィールドに影響しない。
+        for slots in [&[0][..], &[1, 0, 2], &[0x3F; 8]] {
+            let savedata = load(slots);
+            assert_eq!(
+                Savedata {
+                    inventory: Inventory::new(),
+                    ..savedata
+                },
+                Savedata::default()
+            );
+        }
+    }
+
+    #[test]
+    fn test_password_checksum_report() {
+        let max = Checksum::new(ChecksumAdd::MAX, ChecksumXor::MAX);
+
+        assert_eq!(
+            Password::parse("ふ").unwrap().checksum_report(),
+            ChecksumReport {
+                embed: max,
+                calculated: max,
+                matches: true,
+            }
+        );
+        assert_eq!(
+            Password::parse("あ").unwrap().checksum_report(),
+            ChecksumReport {
+                embed: Checksum::new(ChecksumAdd::new(0x1A).unwrap(), ChecksumXor::MAX),
+                calculated: max,
+                matches: false,
+            }
+        );
+
+        for s in ["おにのばか", "おにのばき"] {
+            let password = Password::parse(s).unwrap();
+            let bytes = SerializedBytes::from_password(&password);
+            let report = password.checksum_report();
+            assert_eq!(report.embed, bytes.checksum_embed());
+            assert_eq!(report.calculated, bytes.checksum_calculated());
+            assert_eq!(report.matches, password.is_valid());
+        }
+    }
+
+    #[test]
+    fn test_password_equivalent() {
+        // 末尾に読まれないバイトを追加した、長さの異なるパスワード。
+        let bytes = SerializedBytes::from_savedata(&Savedata::default());
+        let mut padded = bytes.clone();
+        padded.0.push(SerializedByte::new(0x2A).unwrap());
+        padded.fix_checksum();
+
+        let a = bytes.to_password();
+        let b = padded.to_password();
+        assert_ne!(a.len(), b.len());
+        assert!(a.equivalent_to(&b));
+        assert!(b.equivalent_to(&a));
+        assert!(a.equivalent_to(&a));
+        assert_eq!(a.equivalence_key(), Some(Savedata::default()));
+        assert_eq!(a.equivalence_key(), b.equivalence_key());
+
+        // 不正な装備インデックスは正規化してから比べる(武器 11 は装備なしとなる)。
+        let savedata = Savedata {
+            equipment: Equipment {
+                weapon: WeaponIndex::new(11).unwrap(),
+                ..Equipment::default()
+            },
+            ..Savedata::default()
+        };
+        let c = SerializedBytes::from_savedata(&savedata).to_password();
+        assert_ne!(c, a);
+        assert_eq!(c.equivalence_key(), Some(Savedata::default()));
+        assert!(a.equivalent_to(&c));
+
+        let d = SerializedBytes::from_savedata(&Savedata {
+            xp: 1,
+            ..Savedata::default()
+        })
+        .to_password();
+        assert!(!a.equivalent_to(&d));
+
+        // チェックサムが一致しないものや特殊パスワードは、自身とも同値でない。
+        for s in ["あ", "すべてのてきがみたいな", "すべての"] {
+            let password = Password::parse(s).unwrap();
+            assert_eq!(password.equivalence_key(), None);
+            assert!(!password.equivalent_to(&password));
+            assert!(!password.equivalent_to(&a));
+            assert!(!a.equivalent_to(&password));
+        }
+    }
+
+    /// インベントリの個数 0..=8 それぞれについて、パスワードの文字数を確かめる。
+    ///
+    /// ゲームと照合できているのはインベントリ満杯(8 個)の場合のみで、ゲームから書き起こしたパスワードは 1 件しかない。
+    /// 0..=7 個の場合の文字数はビット数からの計算によるもので、ゲームでは未確認。
+    #[test]
+    fn test_save_len() {
+        // インベントリより前が 111bit、アイテム 1 個が 6bit、満杯でなければ終端の 0 が 6bit。
+        // これを 6bit 単位に切り上げ、チェックサムの 2 文字を加えたものが文字数になる。
+        const EXPECTED: [usize; 9] = [22, 23, 24, 25, 26, 27, 28, 29, 29];
+
+        for (n, &expected) in EXPECTED.iter().enumerate() {
+            let savedata = Savedata {
+                inventory: std::iter::repeat_n(ItemId::MAX, n).collect(),
+                ..Default::default()
+            };
+            let bytes = SerializedBytes::from_savedata(&savedata);
+            assert_eq!(bytes.len(), expected, "inventory count {n}");
+
+            // 末尾に補われるのは 6bit 未満、つまり丸ごと補われたバイトはない。
+            let n_bits = INVENTORY_OFFSET + 6 * n + if n < 8 { 6 } else { 0 };
+            assert!(6 * (bytes.len() - 2) - n_bits < 6);
+
+            // 長さを変えずに元に戻る。
+            let password = bytes.to_password();
+            assert_eq!(password.len(), expected);
+            assert_eq!(
+                SerializedBytes::from_password(&password).to_savedata(),
+                Some(savedata)
+            );
+        }
+
+        // ゲーム内で得たパスワード(インベントリ満杯)と一致する。
+        let password =
+            Password::parse("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ").unwrap();
+        let savedata = SerializedBytes::from_password(&password)
+            .to_savedata()
+            .unwrap();
+        assert!(savedata.inventory.is_full());
+        assert_eq!(password.len(), EXPECTED[8]);
+        assert_eq!(
+            SerializedBytes::from_savedata(&savedata).to_password(),
+            password
+        );
+    }
+
+    #[test]
+    fn test_bits_write_bytes() {
+        // 長さが 6 の倍数でないビット列は末尾に 0 を補って変換される。何度変換しても同じ結果になる。
+        let mut bits = BitWriter::new();
+        bits.push_bits(6, 0b101010);
+        bits.push_bits(4, 0b1111);
+
+        let mut bytes = SerializedBytes::from_savedata(&Savedata::default());
+        bits.write_bytes(&mut bytes);
+        assert_eq!(bytes.len(), 4);
+        assert_eq!(bytes[2].get(), 0b101010);
+        assert_eq!(bytes[3].get(), 0b111100);
+        assert!(bytes.checksum_is_ok());
+
+        let mut again = SerializedBytes::from_savedata(&Savedata::default());
+        bits.write_bytes(&mut again);
+        assert_eq!(again, bytes);
+        assert_eq!(bits.as_bitslice().len(), 10);
+    }
+
+    #[test]
+    fn test_bits_reader_writer() {
+        // 6bit のバイト境界をまたぐ様々な幅のフィールドを書いて読む。
+        const FIELDS: &[(usize, u8)] = &[
+            (3, 0b101),
+            (5, 0b01101),
+            (8, 0xC3),
+            (1, 1),
+            (7, 0b0010011),
+            (0, 0),
+            (2, 0b10),
+            (8, 0x5A),
+        ];
+
+        let mut writer = BitWriter::new();
+        assert!(writer.is_empty());
+        for &(n, value) in FIELDS {
+            writer.push_bits(n, value);
+        }
+        assert_eq!(writer.len(), 34);
+
+        // 34bit は 6 バイトに収まる(末尾 2bit は 0 で補われる)。
+        let bytes = writer.to_bytes();
+        assert_eq!(bytes.len(), 2 + 6);
+        assert!(bytes.checksum_is_ok());
+        assert_eq!(
+            bytes.iter_u8().skip(2).collect::<Vec<_>>(),
+            [0b101011, 0b011100, 0b001110, 0b010011, 0b100101, 0b101000]
+        );
+
+        let raw = writer.to_bytes_without_checksum();
+        assert_eq!(raw.iter_u8().take(2).collect::<Vec<_>>(), [0, 0]);
+        assert_eq!(raw[2..], bytes[2..]);
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(reader.remaining(), BitWriter::CAPACITY);
+        for &(n, value) in FIELDS {
+            assert_eq!(reader.read_bits(n), value);
+        }
+        assert_eq!(reader.remaining(), BitWriter::CAPACITY - 34);
+
+        // 補われた 0 の後は、バイト列が足りない部分として 1 が読まれる。
+        assert_eq!(reader.read_bits(2), 0);
+        assert!(reader.read_bit());
+        assert_eq!(reader.read_bits(8), 0xFF);
+        while reader.remaining() > 0 {
+            assert!(reader.read_bit());
+        }
+    }
+
+    #[test]
+    fn test_bits_u16() {
+        // 8bit を超えるフィールドも 6bit のバイト境界をまたいで読み書きできる。
+        const FIELDS: &[(usize, u16)] = &[
+            (9, 0b1_0110_1001),
+            (3, 0b011),
+            (12, 0xA5C),
+            (16, 0xBEEF),
+            (16, 0x0001),
+            (0, 0),
+        ];
+
+        let mut writer = BitWriter::new();
+        for &(n, value) in FIELDS {
+            writer.push_bits_u16(n, value);
+        }
+        assert_eq!(writer.len(), 56);
+
+        let mut reader = BitReader::new(&writer.to_bytes());
+        for &(n, value) in FIELDS {
+            assert_eq!(reader.read_bits_u16(n), value);
+        }
+
+        // 8bit ずつ読み書きした場合と一致する。
+        let mut reader = BitReader::new(&writer.to_bytes());
+        for b in [0xB4, 0xBA, 0x5C, 0xBE, 0xEF, 0x00, 0x01] {
+            assert_eq!(reader.read_bits(8), b);
+        }
+
+        let mut narrow = BitWriter::new();
+        narrow.push_bits(8, 0xBE);
+        narrow.push_bits(8, 0xEF);
+        let mut wide = BitWriter::new();
+        wide.push_bits_u16(16, 0xBEEF);
+        assert_eq!(narrow.to_bytes(), wide.to_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in 9 bits")]
+    fn test_bits_push_bits_u16_overwide() {
+        BitWriter::new().push_bits_u16(9, 0x0345);
+    }
+
+    #[test]
+    fn test_bits_try_push() {
+        let mut bits = BitWriter::new();
+        assert_eq!(
+            bits.try_push_bits(9, 0),
+            Err(BitCapacityError::TooWide { n: 9, max: 8 })
+        );
+        assert_eq!(
+            bits.try_push_bits_u16(17, 0),
+            Err(BitCapacityError::TooWide { n: 17, max: 16 })
+        );
+        assert_eq!(
+            bits.try_push_bits(4, 0b1_0101),
+            Err(BitCapacityError::ValueOverflow {
+                n: 4,
+                value: 0b1_0101
+            })
+        );
+        assert_eq!(
+            bits.try_push_bits(0, 1),
+            Err(BitCapacityError::ValueOverflow { n: 0, value: 1 })
+        );
+        assert_eq!(
+            bits.try_push_bits_u16(9, 0x0345),
+            Err(BitCapacityError::ValueOverflow {
+                n: 9,
+                value: 0x0345
+            })
+        );
+        assert!(bits.is_empty());
+
+        assert_eq!(bits.try_push_bits_u16(16, 0xFFFF), Ok(()));
+        while bits.len() < BitWriter::CAPACITY {
+            assert_eq!(bits.try_push_bit(false), Ok(()));
+        }
+        let full = bits.clone();
+        for res in [
+            bits.try_push_bit(true),
+            bits.try_push_bits(1, 1),
+            bits.try_push_bits_u16(1, 1),
+        ] {
+            assert_eq!(
+                res,
+                Err(BitCapacityError::CapacityExceeded {
+                    len: BitWriter::CAPACITY,
+                    n: 1,
+                    capacity: BitWriter::CAPACITY,
+                })
+            );
+        }
+        // 失敗しても何も追加されない。
+        assert_eq!(bits.as_bitslice(), full.as_bitslice());
+        assert_eq!(bits.try_push_bits(0, 0), Ok(()));
+    }
+
+    #[test]
+    fn test_bits_try_read() {
+        let mut reader = BitReader::new(&SerializedBytes::from_savedata(&Savedata::default()));
+        assert_eq!(reader.try_read_bits(9), None);
+        assert_eq!(reader.try_read_bits_u16(17), None);
+        assert_eq!(reader.remaining(), BitWriter::CAPACITY);
+
+        while reader.remaining() >= 16 {
+            assert!(reader.try_read_bits_u16(16).is_some());
+        }
+        let remaining = reader.remaining();
+        assert_eq!(reader.try_read_bits(remaining + 1), None);
+        assert_eq!(reader.remaining(), remaining);
+        for _ in 0..remaining {
+            assert!(reader.try_read_bit().is_some());
+        }
+        assert_eq!(reader.try_read_bit(), None);
+        assert_eq!(reader.try_read_bits(0), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bits (160 + 3 > 162)")]
+    fn test_bits_reader_overflow() {
+        let mut reader = BitReader::new(&SerializedBytes::from_savedata(&Savedata::default()));
+        for _ in 0..20 {
+            reader.read_bits(8);
+        }
+        reader.read_bits(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in 4 bits")]
+    fn test_bits_push_bits_overwide() {
+        // ビルドによらず、上位ビットが立っていればパニックする。
+        BitWriter::new().push_bits(4, 0b1111_0101);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity exceeded (160 + 3 > 162)")]
+    fn test_bits_push_bits_overflow() {
+        let mut bits = BitWriter::new();
+        for _ in 0..20 {
+            bits.push_bits(8, 0);
+        }
+        bits.push_bits(3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity exceeded (162 + 1 > 162)")]
+    fn test_bits_push_bit_overflow() {
+        let mut bits = BitWriter::new();
+        for _ in 0..BitWriter::CAPACITY {
+            bits.push_bit(true);
+        }
+        bits.push_bit(true);
+    }
+
+    #[test]
+    fn test_bits_total_len() {
+        // 全フィールドが最大ビット数となるゲーム状態でも容量に収まる。
+        let savedata = Savedata {
+            inventory: Inventory::from([ItemId::MAX; 8]),
+            ..Savedata::default()
+        };
+        let len = savedata.to_bits().as_bitslice().len();
+        assert_eq!(len, INVENTORY_OFFSET + 6 * 8);
+        assert_eq!(
+            FieldId::InventorySlot(0).spec().bit_offset,
+            INVENTORY_OFFSET
+        );
+        assert!(len <= BitWriter::CAPACITY);
+    }
+
     #[test]
     fn test_save() {
         let bytes = SerializedBytes::from_password(&Password::parse("ふ").unwrap());
@@ -721,4 +2267,118 @@ mod tests {
             "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ"
         );
     }
+
+    /// 任意のバイト列。2 バイト以上ならば半分の確率でチェックサムを合わせる。
+    fn arb_bytes() -> impl proptest::strategy::Strategy<Value = SerializedBytes> {
+        use proptest::prelude::*;
+
+        (
+            proptest::collection::vec(0..=0x3F_u8, Password::MIN_LEN..=Password::MAX_LEN),
+            any::<bool>(),
+        )
+            .prop_map(|(buf, fix_checksum)| {
+                let buf: Vec<_> = buf
+                    .into_iter()
+                    .map(|b| SerializedByte::new(b).unwrap())
+                    .collect();
+                let mut bytes = SerializedBytes::new(&buf).unwrap();
+                if fix_checksum && bytes.len() >= 2 {
+                    bytes.fix_checksum();
+                }
+                bytes
+            })
+    }
+
+    /// 任意のセーブデータ。
+    fn arb_savedata() -> impl proptest::strategy::Strategy<Value = Savedata> {
+        use proptest::prelude::*;
+
+        arb_bytes().prop_filter_map("checksum mismatch", |bytes| bytes.to_savedata())
+    }
+
+    #[cfg(feature = "arbitrary")]
+    proptest::proptest! {
+        #[test]
+        fn test_bytes_arbitrary(buf in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..64)) {
+            use arbitrary::{Arbitrary as _, Unstructured};
+
+            let bytes = SerializedBytes::arbitrary(&mut Unstructured::new(&buf)).unwrap();
+            proptest::prop_assert!((Password::MIN_LEN..=Password::MAX_LEN).contains(&bytes.len()));
+            proptest::prop_assert!(bytes.iter().all(|b| b.get() <= 0x3F));
+
+            // 残りのバイト数がバイト数になる。
+            let bytes = SerializedBytes::arbitrary_take_rest(Unstructured::new(&buf)).unwrap();
+            proptest::prop_assert_eq!(
+                bytes.len(),
+                buf.len().clamp(Password::MIN_LEN, Password::MAX_LEN)
+            );
+        }
+
+        #[test]
+        fn test_savedata_arbitrary(buf in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..256)) {
+            use arbitrary::{Arbitrary as _, Unstructured};
+
+            let savedata = Savedata::arbitrary(&mut Unstructured::new(&buf)).unwrap();
+            proptest::prop_assert!(savedata.inventory.len() <= 8);
+            proptest::prop_assert!(savedata.inventory.iter().all(|&item| ItemId::in_range(item.get())));
+            proptest::prop_assert!(Deposit::in_range(savedata.deposit.get()));
+            proptest::prop_assert!(RespawnId::in_range(savedata.respawn.get()));
+
+            // 全てのフィールドがパスワードで表現できる。
+            let bytes = SerializedBytes::from_savedata(&savedata);
+            proptest::prop_assert_eq!(bytes.to_savedata(), Some(savedata));
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_password_bytes_roundtrip(buf in proptest::collection::vec(0..=0x3F_u8, 0..=64)) {
+            let mut encoded = buf.clone();
+            encode_password_bytes(&mut encoded);
+            proptest::prop_assert!(encoded.iter().all(|&c| c <= 0x3F));
+            let mut decoded = encoded.clone();
+            decode_password_bytes(&mut decoded);
+            proptest::prop_assert_eq!(&decoded, &buf);
+
+            // SerializedBytes のメソッドと一致する。
+            if let Ok(bytes) = SerializedBytes::from_u8_slice(&buf) {
+                let password = bytes.to_password();
+                proptest::prop_assert!(password.iter().map(|pc| pc.to_inner()).eq(encoded.iter().copied()));
+                proptest::prop_assert_eq!(SerializedBytes::from_password(&password), bytes);
+            }
+        }
+
+        #[test]
+        fn test_password_decode_into(bytes in arb_bytes(), mut out in arb_bytes()) {
+            // 参照実装と比較する。
+            let password = bytes.to_password();
+            let codes: Vec<_> = password.iter().map(|pc| pc.to_inner()).collect();
+            password.decode_into(&mut out);
+            proptest::prop_assert_eq!(out.to_u8_vec(), crate::reference::decode(&codes));
+        }
+
+        #[test]
+        fn test_encode_into(savedata in arb_savedata(), mut out in arb_bytes()) {
+            savedata.encode_into(&mut out);
+            proptest::prop_assert_eq!(out.to_u8_vec(), crate::reference::serialize(&savedata));
+        }
+
+        #[test]
+        fn test_decode_into(bytes in arb_bytes(), init in arb_savedata()) {
+            let mut out = init.clone();
+            let expected = crate::reference::deserialize(&bytes.to_u8_vec());
+            match bytes.decode_into(&mut out) {
+                Ok(()) => proptest::prop_assert_eq!(Some(out), expected),
+                Err(e) => {
+                    proptest::prop_assert_eq!(expected, None);
+                    let raw = bytes.to_u8_vec();
+                    let embed = (e.embed.sum_add().get(), e.embed.sum_xor().get());
+                    let calculated = (e.calculated.sum_add().get(), e.calculated.sum_xor().get());
+                    proptest::prop_assert_eq!(embed, crate::reference::checksum_embed(&raw));
+                    proptest::prop_assert_eq!(calculated, crate::reference::checksum_calculated(&raw));
+                    proptest::prop_assert_eq!(out, init);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use thiserror::Error;
+
+use crate::password::{Password, PasswordChar, PasswordInner};
+
+/// 共有コードに用いる文字(URL-safe base64 と同じ並び)。内部値 `i` の文字は `SHARE_CODE_ALPHABET[i]`。
+pub const SHARE_CODE_ALPHABET: &[u8; 0x40] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Password {
+    /// URL やチャットにそのまま貼れる共有コードを返す。
+    ///
+    /// 共有コードは先頭の 1 文字で文字数を表し、以降の各文字がパスワードの各文字の内部値を表す。
+    /// 単なる文字の置き換えであり、暗号化ではない(誰でもパスワードに戻せる)。
+    /// 先頭の文字が表す値のうち `Password::MAX_LEN` を超えるものは、将来の形式のために予約されている。
+    pub fn to_share_code(&self) -> String {
+        std::iter::once(self.len() as u8)
+            .chain(self.iter().map(|pc| pc.to_inner()))
+            .map(|x| char::from(SHARE_CODE_ALPHABET[usize::from(x)]))
+            .collect()
+    }
+
+    /// 共有コードから `Password` を作る。
+    ///
+    /// 共有コードとしての形式のみを検査し、チェックサムは検査しない(`Password::is_valid()` を参照)。
+    pub fn from_share_code(s: &str) -> Result<Self, ShareCodeError> {
+        let mut values = s
+            .chars()
+            .enumerate()
+            .map(|(pos, ch)| share_code_value(ch).ok_or(ShareCodeError::InvalidChar { pos, ch }));
+
+        let len = values.next().ok_or(ShareCodeError::Empty)??;
+        if !matches!(usize::from(len), Password::MIN_LEN..=Password::MAX_LEN) {
+            return Err(ShareCodeError::UnsupportedPrefix(s.chars().next().unwrap()));
+        }
+
+        let mut inner = PasswordInner::new();
+        for value in values {
+            let pc = PasswordChar::from_inner(value?).unwrap();
+            if inner.try_push(pc).is_err() {
+                break;
+            }
+        }
+
+        let actual = s.chars().count() - 1;
+        if actual != usize::from(len) {
+            return Err(ShareCodeError::LengthMismatch {
+                expected: usize::from(len),
+                actual,
+            });
+        }
+
+        Ok(Password::new(&inner).unwrap())
+    }
+}
+
+fn share_code_value(ch: char) -> Option<u8> {
+    let b = u8::try_from(ch).ok()?;
+    SHARE_CODE_ALPHABET
+        .iter()
+        .position(|&x| x == b)
+        .map(|i| i as u8)
+}
+
+/// 共有コードのデコード時に発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum ShareCodeError {
+    /// 共有コードが空。
+    #[error("share code is empty")]
+    Empty,
+
+    /// 先頭の文字が未対応の形式を表している。
+    #[error("share code has an unsupported prefix '{0}'")]
+    UnsupportedPrefix(char),
+
+    /// 先頭の文字が表す文字数と実際の文字数が一致しない。
+    #[error("share code declares {expected} chars but contains {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+
+    /// 共有コードに無効な文字が含まれている。
+    #[error("share code contains an invalid character '{ch}' at position {pos}")]
+    InvalidChar { pos: usize, ch: char },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_code() {
+        let password = Password::parse("おにのばか").unwrap();
+        assert_eq!(password.to_share_code(), "FEVY2F");
+        assert_eq!(Password::from_share_code("FEVY2F"), Ok(password));
+    }
+
+    #[test]
+    fn test_share_code_round_trip() {
+        for len in Password::MIN_LEN..=Password::MAX_LEN {
+            // 全ての文字が現れるよう、内部値をずらしながら並べる。
+            let chars: Vec<_> = (0..len)
+                .map(|i| PasswordChar::from_inner(((i * 7 + len) % 0x40) as u8).unwrap())
+                .collect();
+            let password = Password::new(&chars).unwrap();
+
+            let code = password.to_share_code();
+            assert_eq!(code.len(), len + 1);
+            assert!(code
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+            assert_eq!(Password::from_share_code(&code), Ok(password));
+        }
+
+        for pc in PasswordChar::all() {
+            let password = Password::new(&[pc]).unwrap();
+            assert_eq!(
+                Password::from_share_code(&password.to_share_code()),
+                Ok(password)
+            );
+        }
+    }
+
+    #[test]
+    fn test_share_code_tampered() {
+        assert_eq!(Password::from_share_code(""), Err(ShareCodeError::Empty));
+
+        // 文字の欠落、追加。
+        assert_eq!(
+            Password::from_share_code("FEVY2"),
+            Err(ShareCodeError::LengthMismatch {
+                expected: 5,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            Password::from_share_code("FEVY2FA"),
+            Err(ShareCodeError::LengthMismatch {
+                expected: 5,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            Password::from_share_code(&format!("m{}", "A".repeat(100))),
+            Err(ShareCodeError::LengthMismatch {
+                expected: 38,
+                actual: 100
+            })
+        );
+
+        // 先頭の文字の改変。
+        assert_eq!(
+            Password::from_share_code("AEVY2F"),
+            Err(ShareCodeError::UnsupportedPrefix('A'))
+        );
+        assert_eq!(
+            Password::from_share_code("_EVY2F"),
+            Err(ShareCodeError::UnsupportedPrefix('_'))
+        );
+
+        // アルファベット外の文字(base64 の '+', '/', '=' や、パーセントエンコードの名残)。
+        for (code, pos, ch) in [
+            ("FEV+2F", 3, '+'),
+            ("FEVY2/", 5, '/'),
+            ("FEVY2F=", 6, '='),
+            ("F%E3", 1, '%'),
+            ("Fおにのばか", 1, 'お'),
+        ] {
+            assert_eq!(
+                Password::from_share_code(code),
+                Err(ShareCodeError::InvalidChar { pos, ch })
+            );
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use proptest::prelude::*;
+
+use crate::password::{Password, PasswordChar};
+use crate::savedata::*;
+use crate::serialized::{SerializedByte, SerializedBytes};
+
+/// 値域全体から選び、最小値に向かって縮小する `define_bounded!` 型の `Strategy`。
+macro_rules! bounded_strategy {
+    ($ty:ident) => {
+        ($ty::MIN_VALUE..=$ty::MAX_VALUE).prop_map(|x| $ty::new(x).unwrap())
+    };
+}
+
+/// 各フラグが `false` に向かって縮小するフラグ群の構造体の `Strategy`。
+macro_rules! flags_strategy {
+    ($ty:ident { $($field:ident),+ $(,)? }) => {
+        proptest::collection::vec(any::<bool>(), [$(stringify!($field)),+].len()).prop_map(|flags| {
+            let mut flags = flags.into_iter();
+            $ty {
+                $($field: flags.next().unwrap()),+
+            }
+        })
+    };
+}
+
+/// 任意の `PasswordChar`。'あ' に向かって縮小する。
+pub fn any_password_char() -> impl Strategy<Value = PasswordChar> {
+    (0..=0x3F_u8).prop_map(|x| PasswordChar::ALL[usize::from(x)])
+}
+
+/// 任意のパスワード。文字数は `Password::MIN_LEN` に、各文字は 'あ' に向かって縮小する。
+pub fn any_password() -> impl Strategy<Value = Password> {
+    proptest::collection::vec(any_password_char(), Password::MIN_LEN..=Password::MAX_LEN)
+        .prop_map(|chars| Password::new(&chars).unwrap())
+}
+
+/// 任意の有効なパスワード。
+///
+/// デコード後のバイト列を生成し、先頭 2 バイトをチェックサムに合わせてからエンコードするので、縮小しても常に有効。
+/// 文字数は `Password::MIN_LEN` に、3 バイト目以降のデコード後のバイトは 0 に向かって縮小する。
+pub fn any_valid_password() -> impl Strategy<Value = Password> {
+    proptest::collection::vec(0..=0x3F_u8, Password::MIN_LEN..=Password::MAX_LEN).prop_map(|buf| {
+        let buf: Vec<_> = buf
+            .into_iter()
+            .map(|b| SerializedByte::new(b).unwrap())
+            .collect();
+        SerializedBytes::new(&buf)
+            .unwrap()
+            .with_fixed_checksum()
+            .to_password()
+    })
+}
+
+/// 任意のセーブデータ。各フィールドは `Default` の値に向かって縮小する(インベントリは空に向かう)。
+pub fn any_savedata() -> impl Strategy<Value = Savedata> {
+    let spells = flags_strategy!(Spells {
+        kintan,
+        rokkaku,
+        inazuma,
+        hien,
+        mankintan,
+        fuyuu,
+        dadadidi,
+        houhi
+    });
+    let events = flags_strategy!(Events {
+        hanasaka,
+        kintaro,
+        urashima,
+        netaro,
+        murata,
+        sarukani,
+        dragon,
+        hohoemi
+    });
+    let treasures = flags_strategy!(Treasures {
+        dragon,
+        fur,
+        hotoke,
+        hourai,
+        swallow
+    });
+    let minions = flags_strategy!(Minions {
+        dog,
+        pheasant,
+        monkey
+    });
+    let bookmarks = flags_strategy!(Bookmarks {
+        tabidachi,
+        hanasaka,
+        kintaro,
+        urashima,
+        netaro,
+        kibou,
+        sarukani,
+        taketori,
+        hohoemi,
+        hien
+    });
+
+    let equipment = (
+        bounded_strategy!(HelmIndex),
+        bounded_strategy!(WeaponIndex),
+        bounded_strategy!(ArmorIndex),
+        bounded_strategy!(ShoesIndex),
+        bounded_strategy!(Accessory0Index),
+        bounded_strategy!(Accessory1Index),
+        bounded_strategy!(Accessory2Index),
+        bounded_strategy!(Accessory3Index),
+    )
+        .prop_map(
+            |(helm, weapon, armor, shoes, accessory0, accessory1, accessory2, accessory3)| {
+                Equipment {
+                    helm,
+                    weapon,
+                    armor,
+                    shoes,
+                    accessory0,
+                    accessory1,
+                    accessory2,
+                    accessory3,
+                }
+            },
+        );
+
+    let inventory = proptest::collection::vec(bounded_strategy!(ItemId), 0..=8)
+        .prop_map(|items| items.into_iter().collect::<Inventory>());
+
+    (
+        (
+            any::<u16>(),
+            any::<u16>(),
+            bounded_strategy!(Deposit),
+            any::<u8>(),
+            any::<u8>(),
+        ),
+        (spells, events, treasures, minions, bookmarks),
+        (bounded_strategy!(RespawnId), equipment, inventory),
+    )
+        .prop_map(
+            |(
+                (xp, purse, deposit, age, age_timer_hi),
+                (spells, events, treasures, minions, bookmarks),
+                (respawn, equipment, inventory),
+            )| Savedata {
+                xp,
+                purse,
+                deposit,
+                age,
+                age_timer_hi,
+                spells,
+                events,
+                treasures,
+                minions,
+                bookmarks,
+                respawn,
+                equipment,
+                inventory,
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::test_runner::{TestError, TestRunner};
+
+    use super::*;
+
+    /// 常に失敗する性質を調べ、縮小された反例を返す。
+    fn minimal<S: Strategy>(strategy: S, fails: impl Fn(&S::Value) -> bool) -> S::Value {
+        let mut runner = TestRunner::deterministic();
+        match runner.run(&strategy, |value| {
+            if fails(&value) {
+                Err(proptest::test_runner::TestCaseError::fail("fail"))
+            } else {
+                Ok(())
+            }
+        }) {
+            Err(TestError::Fail(_, value)) => value,
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!(
+            minimal(any_password(), |_| true),
+            Password::parse("あ").unwrap()
+        );
+        assert_eq!(
+            minimal(any_valid_password(), |_| true),
+            Password::parse("ふ").unwrap()
+        );
+        assert_eq!(minimal(any_savedata(), |_| true), Savedata::default());
+
+        // 縮小しても有効なまま。
+        let password = minimal(any_valid_password(), |password| password.len() >= 4);
+        assert_eq!(password.len(), 4);
+        assert!(password.is_valid());
+
+        let savedata = minimal(any_savedata(), |savedata| savedata.inventory.len() >= 2);
+        assert_eq!(
+            savedata,
+            Savedata {
+                inventory: [ItemId::MIN; 2].into_iter().collect(),
+                ..Savedata::default()
+            }
+        );
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_any_password(password in any_password()) {
+            proptest::prop_assert!((Password::MIN_LEN..=Password::MAX_LEN).contains(&password.len()));
+            proptest::prop_assert_eq!(SerializedBytes::from_password(&password).to_password(), password);
+        }
+
+        #[test]
+        fn test_any_valid_password(password in any_valid_password()) {
+            proptest::prop_assert!(password.is_valid());
+
+            let savedata = SerializedBytes::from_password(&password).to_savedata().unwrap();
+            let reencoded = SerializedBytes::from_savedata(&savedata).to_password();
+            proptest::prop_assert!(reencoded.is_valid());
+            proptest::prop_assert_eq!(
+                SerializedBytes::from_password(&reencoded).to_savedata(),
+                Some(savedata)
+            );
+        }
+
+        #[test]
+        fn test_any_savedata(savedata in any_savedata()) {
+            let password = SerializedBytes::from_savedata(&savedata).to_password();
+            proptest::prop_assert!(password.is_valid());
+            proptest::prop_assert_eq!(
+                SerializedBytes::from_password(&password).to_savedata(),
+                Some(savedata)
+            );
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use crate::layout::{FieldId, SERIALIZED_LAYOUT};
+use crate::password::{Password, PasswordChar};
+use crate::serialized::{BitReader, SerializedBytes};
+
+/// パスワードのデコード過程を記録したもの。`SerializedBytes::decode_trace()` で得られる。
+///
+/// `Display` で表形式に整形できる。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodeTrace {
+    /// 各文字のデコード過程。
+    pub chars: Vec<CharTrace>,
+    /// 各フィールドがどのバイトのどのビットから読まれたか。
+    pub fields: Vec<FieldTrace>,
+}
+
+/// 1 文字分のデコード過程。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CharTrace {
+    /// パスワードの文字。
+    pub ch: PasswordChar,
+    /// 文字の内部値。
+    pub code: u8,
+    /// 直前の文字(先頭は 0x1F)との XOR を取った値。
+    pub unxored: u8,
+    /// さらに加算値テーブルの値を mod 64 で引いた値。`SerializedBytes` の要素となる。
+    pub byte: u8,
+}
+
+/// 1 フィールド分のデシリアライズ過程。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldTrace {
+    /// フィールド名。
+    pub name: String,
+    /// チェックサムを除いたビット列における範囲。
+    pub bits: Range<usize>,
+    /// 読まれた値。
+    pub value: u8,
+    /// 値を構成する (`SerializedBytes` のインデックス, バイト内のビット範囲) の列。
+    /// ビット範囲は上位ビットを 0 とする。
+    pub sources: Vec<(usize, Range<usize>)>,
+    /// パスワードの長さが足りず、1 として補われたビット数。
+    pub n_padded: usize,
+}
+
+impl SerializedBytes {
+    /// パスワードのデコード過程を記録する。
+    ///
+    /// チェックサムは検査しないので、無効なパスワードに対しても使える。
+    pub fn decode_trace(password: &Password) -> DecodeTrace {
+        let mut chars = Vec::with_capacity(password.len());
+        let mut prev = 0x1F;
+        for (i, &ch) in password.iter().enumerate() {
+            let code = ch.to_inner();
+            let unxored = code ^ prev;
+            let byte = unxored.wrapping_sub(Self::ENCODE_ADD_TABLE[i % 4]) & 0x3F;
+            chars.push(CharTrace {
+                ch,
+                code,
+                unxored,
+                byte,
+            });
+            prev = code;
+        }
+
+        let bytes = Self::from_password(password);
+        let mut reader = BitReader::new(&bytes);
+        let n_avail = 6 * bytes.len().saturating_sub(2);
+
+        let mut fields = vec![];
+        for spec in SERIALIZED_LAYOUT {
+            let range = spec.bits();
+            let value = reader.read_bits(spec.bit_len);
+            let avail = range.start.min(n_avail)..range.end.min(n_avail);
+            fields.push(FieldTrace {
+                name: spec.field.to_string(),
+                bits: range,
+                value,
+                sources: sources(avail.clone()),
+                n_padded: spec.bit_len - avail.len(),
+            });
+            // インベントリは終端の 0 を含めて読まれる(満杯なら終端はない)。
+            if matches!(spec.field, FieldId::InventorySlot(_)) && value == 0 {
+                break;
+            }
+        }
+
+        DecodeTrace { chars, fields }
+    }
+}
+
+/// ビット列の範囲を、それを含むバイトごとに分割する。
+fn sources(bits: Range<usize>) -> Vec<(usize, Range<usize>)> {
+    let mut res = vec![];
+
+    let mut pos = bits.start;
+    while pos < bits.end {
+        let byte_end = (pos / 6 + 1) * 6;
+        let end = byte_end.min(bits.end);
+        // 先頭 2 バイトはチェックサム。
+        res.push((2 + pos / 6, pos % 6..pos % 6 + (end - pos)));
+        pos = end;
+    }
+
+    res
+}
+
+impl std::fmt::Display for DecodeTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "pos char code unxored byte")?;
+        for (i, c) in self.chars.iter().enumerate() {
+            writeln!(
+                f,
+                "{i:>3} {}     {:02X}      {:02X}   {:02X}{}",
+                c.ch.to_char(),
+                c.code,
+                c.unxored,
+                c.byte,
+                if i < 2 { "  (checksum)" } else { "" }
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:<24} {:<9} {:>5}  sources", "field", "bits", "value")?;
+        for field in &self.fields {
+            let mut sources = String::new();
+            for (i, (byte, range)) in field.sources.iter().enumerate() {
+                if i != 0 {
+                    sources.push(' ');
+                }
+                write!(sources, "{byte}[{}..{}]", range.start, range.end)?;
+            }
+            if field.n_padded > 0 {
+                if !sources.is_empty() {
+                    sources.push(' ');
+                }
+                write!(sources, "pad({})", field.n_padded)?;
+            }
+
+            let bits = format!("{}..{}", field.bits.start, field.bits.end);
+            writeln!(
+                f,
+                "{:<24} {bits:<9}    {:02X}  {sources}",
+                field.name, field.value
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::*;
+
+    use super::*;
+
+    /// トレースの値が実際のデコード結果と一致することを確かめる。
+    fn assert_consistent(password: &Password) {
+        let trace = SerializedBytes::decode_trace(password);
+        let bytes = SerializedBytes::from_password(password);
+
+        assert_eq!(trace.chars.len(), bytes.len());
+        for (c, b) in trace.chars.iter().zip(&bytes) {
+            assert_eq!(c.byte, b.get());
+        }
+
+        // 各フィールドの値は、セーブデータを再びシリアライズしたビット列の同じ位置にある。
+        let Some(savedata) = bytes.to_savedata() else {
+            return;
+        };
+        let bits = savedata.to_bits();
+        let bits = bits.as_bitslice();
+        for field in &trace.fields {
+            assert_eq!(
+                field.value,
+                bits[field.bits.clone()].load_be::<u8>(),
+                "{}",
+                field.name
+            );
+        }
+        let n_slots = (savedata.inventory.len() + 1).min(8);
+        assert_eq!(trace.fields.len(), SERIALIZED_LAYOUT.len() - 8 + n_slots);
+
+        // ソースのバイトのビットを連結すると値になる。
+        for field in &trace.fields {
+            let mut value = 0_u16;
+            for (byte, range) in &field.sources {
+                let b = bytes[*byte].get();
+                let n = range.len();
+                value = (value << n) | u16::from((b >> (6 - range.end)) & ((1 << n) - 1));
+            }
+            let pad = (1_u16 << field.n_padded) - 1;
+            value = (value << field.n_padded) | pad;
+            assert_eq!(value, u16::from(field.value), "{}", field.name);
+        }
+    }
+
+    #[test]
+    fn test_decode_trace_consistent() {
+        for s in [
+            "あばぽ",
+            "ふ",
+            "おにのばか",
+            "おにのばき",
+            "おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ",
+        ] {
+            assert_consistent(&Password::parse(s).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_decode_trace_golden() {
+        let trace = SerializedBytes::decode_trace(&Password::parse("あばぽ").unwrap());
+        assert_eq!(
+            trace.to_string(),
+            include_str!("../testdata/trace/abapo.txt")
+        );
+    }
+}
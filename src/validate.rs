@@ -0,0 +1,218 @@
+use std::io::{BufRead, Read as _};
+
+use thiserror::Error;
+
+use crate::checksum::ChecksumMismatch;
+use crate::error::Error;
+use crate::password::{Password, PasswordParseError};
+use crate::savedata::Savedata;
+use crate::serialized::SerializedBytes;
+
+/// `validate_stream` が 1 行として扱う最大バイト数。これを超える行は文字数エラーとなる。
+///
+/// 有効なパスワードは高々 `Password::MAX_LEN` 文字なので、前後の空白を考慮しても十分な長さ。
+pub const MAX_VALIDATE_LINE_LEN: usize = 0x1000;
+
+/// パスワードをロードする際に発生しうるエラー。
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum LoadError {
+    /// パスワードのパースに失敗した。
+    #[error(transparent)]
+    Parse(#[from] PasswordParseError),
+
+    /// チェックサムが一致しない。
+    #[error(transparent)]
+    Checksum(#[from] ChecksumMismatch),
+}
+
+/// パスワード文字列をロードし、セーブデータを返す。
+///
+/// 戻り値は生のセーブデータで、装備の正規化は行わない(`Savedata::normalize()` を参照)。
+pub fn load_password(s: &str) -> Result<Savedata, Error> {
+    let password = Password::parse(s)?;
+    let mut savedata = Savedata::default();
+    SerializedBytes::from_password(&password).decode_into(&mut savedata)?;
+    Ok(savedata)
+}
+
+/// 1 行に 1 つずつパスワードが書かれた入力を読み、各行の検証結果を `on_result` に渡す。
+///
+/// `on_result` には行番号(1 始まり)、前後の空白を除いた行、検証結果が渡される。
+/// 空行と `#` で始まる行(コメント)は読み飛ばす。
+///
+/// 行バッファとバイト列バッファは使い回すので、行ごとのメモリ確保は行わない
+/// (UTF-8 として不正な行を除く)。`MAX_VALIDATE_LINE_LEN` バイトを超える行は残りを読み捨て、
+/// `PasswordParseError::InvalidLength`(`len` は `Password::MAX_LEN + 1`)として扱う。
+pub fn validate_stream<R, F>(mut r: R, mut on_result: F) -> std::io::Result<()>
+where
+    R: BufRead,
+    F: FnMut(usize, &str, Result<(), LoadError>),
+{
+    let mut buf = Vec::with_capacity(MAX_VALIDATE_LINE_LEN + 1);
+    let mut bytes = SerializedBytes::from_savedata(&Savedata::default());
+
+    for line in 1.. {
+        buf.clear();
+        let limit = MAX_VALIDATE_LINE_LEN as u64 + 1;
+        if (&mut r).take(limit).read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+
+        let overlong = buf.len() > MAX_VALIDATE_LINE_LEN && buf.last() != Some(&b'\n');
+        if overlong {
+            skip_line(&mut r)?;
+        }
+
+        let s = String::from_utf8_lossy(&buf);
+        let s = s.trim();
+        if s.is_empty() || s.starts_with('#') {
+            continue;
+        }
+
+        let res = if overlong {
+            Err(PasswordParseError::InvalidLength {
+                len: Password::MAX_LEN + 1,
+            }
+            .into())
+        } else {
+            validate_one(s, &mut bytes)
+        };
+        on_result(line, s, res);
+    }
+
+    Ok(())
+}
+
+fn validate_one(s: &str, bytes: &mut SerializedBytes) -> Result<(), LoadError> {
+    let password = Password::parse(s)?;
+    password.decode_into(bytes);
+
+    let embed = bytes.checksum_embed();
+    let calculated = bytes.checksum_calculated();
+    if embed != calculated {
+        return Err(ChecksumMismatch { embed, calculated }.into());
+    }
+
+    Ok(())
+}
+
+/// 次の改行(または EOF)までを読み捨てる。
+pub(crate) fn skip_line<R: BufRead>(r: &mut R) -> std::io::Result<()> {
+    loop {
+        let chunk = r.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        match chunk.iter().position(|&b| b == b'\n') {
+            Some(pos) => {
+                r.consume(pos + 1);
+                return Ok(());
+            }
+            None => {
+                let len = chunk.len();
+                r.consume(len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (行番号, 行, 結果の分類) の列を返す。
+    fn collect(input: &[u8]) -> Vec<(usize, String, &'static str)> {
+        let mut res = vec![];
+        validate_stream(input, |line, s, r| {
+            let kind = match r {
+                Ok(()) => "valid",
+                Err(LoadError::Parse(_)) => "parse",
+                Err(LoadError::Checksum(_)) => "checksum",
+            };
+            res.push((line, s.to_owned(), kind));
+        })
+        .unwrap();
+        res
+    }
+
+    #[test]
+    fn test_load_password() {
+        let expected = SerializedBytes::from_password(&Password::parse("おにのばか").unwrap())
+            .to_savedata()
+            .unwrap();
+        assert_eq!(load_password("おにのばか"), Ok(expected));
+
+        assert!(matches!(
+            load_password("おにのばき"),
+            Err(Error::Checksum(_))
+        ));
+        assert!(matches!(
+            load_password("おにのばx"),
+            Err(Error::PasswordParse(PasswordParseError::InvalidChar {
+                pos: 4,
+                ch: 'x'
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_validate_stream() {
+        let input = "\
+おにのばか
+おにのばき
+あいう?
+";
+        assert_eq!(
+            collect(input.as_bytes()),
+            [
+                (1, "おにのばか".to_owned(), "valid"),
+                (2, "おにのばき".to_owned(), "checksum"),
+                (3, "あいう?".to_owned(), "parse"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_stream_skip() {
+        let input = "\
+# comment
+
+  おにのばか\t
+   # indented comment
+\t
+おにのばき";
+        assert_eq!(
+            collect(input.as_bytes()),
+            [
+                (3, "おにのばか".to_owned(), "valid"),
+                (6, "おにのばき".to_owned(), "checksum"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_stream_crlf() {
+        let input = "おにのばか\r\n\r\n# comment\r\nおにのばき\r\n";
+        assert_eq!(
+            collect(input.as_bytes()),
+            [
+                (1, "おにのばか".to_owned(), "valid"),
+                (4, "おにのばき".to_owned(), "checksum"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_stream_overlong() {
+        let mut input = "?".repeat(MAX_VALIDATE_LINE_LEN + 1).into_bytes();
+        input.extend(b"\n\xFF\n");
+        input.extend("おにのばか\n".as_bytes());
+
+        let res = collect(&input);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0].0, 1);
+        assert_eq!(res[0].2, "parse");
+        assert_eq!(res[1], (2, "\u{FFFD}".to_owned(), "parse"));
+        assert_eq!(res[2], (3, "おにのばか".to_owned(), "valid"));
+    }
+}
@@ -0,0 +1,266 @@
+//! clap の `ValueParser` 実装。
+//!
+//! `Password`, `Pattern`, `ItemId` は `ValueParserFactory` を実装しているので、
+//! derive API では `#[arg(value_parser)]` を指定する(または省略する)だけでよい。
+//! パースに失敗した場合、エラーメッセージには問題のある文字の位置と、あれば修正候補が示される。
+//!
+//! アイテム名の表はまだないので、`ItemId` は数値(10 進または `0x` 付きの 16 進)で指定する。
+//!
+//! ```
+//! use clap::Parser;
+//! use momoden_password::{ItemId, Password, Pattern};
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[arg(long)]
+//!     password: Password,
+//!
+//!     #[arg(long)]
+//!     pattern: Option<Pattern>,
+//!
+//!     #[arg(long)]
+//!     item: Vec<ItemId>,
+//! }
+//!
+//! let cli = Cli::try_parse_from([
+//!     "app", "--password", "おにのばか", "--pattern", "おに???", "--item", "0x3F", "--item", "1",
+//! ])
+//! .unwrap();
+//! assert_eq!(cli.password, Password::parse("おにのばか").unwrap());
+//! assert_eq!(cli.item, [ItemId::MAX, ItemId::MIN]);
+//!
+//! let err = Cli::try_parse_from(["app", "--password", "おにのばカ"]).err().unwrap();
+//! assert!(err.to_string().contains("did you mean 'か'?"));
+//! ```
+
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command};
+
+use crate::password::{Password, PasswordChar, PasswordParseError};
+use crate::savedata::ItemId;
+use crate::search::{Pattern, PatternParseError};
+
+/// `Password` 用の `TypedValueParser`。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PasswordValueParser;
+
+impl TypedValueParser for PasswordValueParser {
+    type Value = Password;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let s = to_str(cmd, arg, value)?;
+        Password::parse(s).map_err(|e| {
+            let pos = match e {
+                PasswordParseError::InvalidLength { .. } => None,
+                PasswordParseError::InvalidChar { pos, ch } => Some((pos, ch)),
+                PasswordParseError::InvalidChars(ref invalids) => invalids.first().copied(),
+            };
+            // 修正候補はエラーメッセージ自体に含まれる。
+            value_error(cmd, arg, s, &e, pos, false)
+        })
+    }
+}
+
+impl ValueParserFactory for Password {
+    type Parser = PasswordValueParser;
+
+    fn value_parser() -> Self::Parser {
+        PasswordValueParser
+    }
+}
+
+/// `Pattern` 用の `TypedValueParser`。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PatternValueParser;
+
+impl TypedValueParser for PatternValueParser {
+    type Value = Pattern;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let s = to_str(cmd, arg, value)?;
+        Pattern::parse(s).map_err(|e| {
+            let pos = match e {
+                PatternParseError::InvalidLength => None,
+                PatternParseError::InvalidChar { pos, ch } => Some((pos, ch)),
+            };
+            value_error(cmd, arg, s, &e, pos, true)
+        })
+    }
+}
+
+impl ValueParserFactory for Pattern {
+    type Parser = PatternValueParser;
+
+    fn value_parser() -> Self::Parser {
+        PatternValueParser
+    }
+}
+
+/// `ItemId` 用の `TypedValueParser`。10 進数または `0x` 付きの 16 進数を受け付ける。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ItemIdValueParser;
+
+impl TypedValueParser for ItemIdValueParser {
+    type Value = ItemId;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let s = to_str(cmd, arg, value)?;
+        let res = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => ItemId::from_str_radix(hex, 16),
+            None => ItemId::from_str_radix(s, 10),
+        };
+        res.map_err(|e| {
+            let msg = format!(
+                "{e} (item id must be {}..={} or 0x{:02X}..=0x{:02X})",
+                ItemId::MIN,
+                ItemId::MAX,
+                ItemId::MIN.get(),
+                ItemId::MAX.get()
+            );
+            value_error(cmd, arg, s, &msg, None, false)
+        })
+    }
+}
+
+impl ValueParserFactory for ItemId {
+    type Parser = ItemIdValueParser;
+
+    fn value_parser() -> Self::Parser {
+        ItemIdValueParser
+    }
+}
+
+fn to_str<'a>(cmd: &Command, arg: Option<&Arg>, value: &'a OsStr) -> Result<&'a str, clap::Error> {
+    value.to_str().ok_or_else(|| {
+        let s = value.to_string_lossy();
+        value_error(cmd, arg, &s, &"value is not valid UTF-8", None, false)
+    })
+}
+
+/// 値のパースエラーを作る。`invalid` が指定されていれば、その位置に印を付け、
+/// `tip` が真なら修正候補があれば示す。
+fn value_error(
+    cmd: &Command,
+    arg: Option<&Arg>,
+    value: &str,
+    err: &dyn std::fmt::Display,
+    invalid: Option<(usize, char)>,
+    tip: bool,
+) -> clap::Error {
+    let arg = arg.map_or_else(|| "...".to_owned(), ToString::to_string);
+
+    let mut msg = format!("invalid value '{value}' for '{arg}': {err}\n");
+
+    if let Some((pos, ch)) = invalid {
+        let indent: usize = value.chars().take(pos).map(char_width).sum();
+        writeln!(msg, "\n  {value}").unwrap();
+        writeln!(
+            msg,
+            "  {}{}",
+            " ".repeat(indent),
+            "^".repeat(char_width(ch))
+        )
+        .unwrap();
+        if let Some(pc) = PasswordChar::suggest(ch).first().filter(|_| tip) {
+            writeln!(msg, "\n  tip: did you mean '{}'?", pc.to_char()).unwrap();
+        }
+    }
+
+    clap::Error::raw(ErrorKind::ValueValidation, msg).with_cmd(cmd)
+}
+
+/// 端末上での文字幅の近似値(ASCII なら 1, それ以外は 2)。
+fn char_width(c: char) -> usize {
+    if c.is_ascii() {
+        1
+    } else {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[arg(long)]
+        password: Option<Password>,
+
+        #[arg(long)]
+        pattern: Option<Pattern>,
+
+        #[arg(long)]
+        item: Option<ItemId>,
+    }
+
+    fn parse_err(args: &[&str]) -> String {
+        let args = std::iter::once("app").chain(args.iter().copied());
+        Cli::try_parse_from(args).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let cli = Cli::try_parse_from([
+            "app",
+            "--password",
+            "おにのばか",
+            "--pattern",
+            "お?の",
+            "--item",
+            "0x20",
+        ])
+        .unwrap();
+        assert_eq!(cli.password, Some(Password::parse("おにのばか").unwrap()));
+        assert_eq!(cli.pattern, Some(Pattern::parse("お?の").unwrap()));
+        assert_eq!(cli.item, Some(ItemId::new(0x20).unwrap()));
+
+        let cli = Cli::try_parse_from(["app", "--item", "63"]).unwrap();
+        assert_eq!(cli.item, Some(ItemId::MAX));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_err(&["--password", "おにのバか"]);
+        assert!(err.contains("invalid character 'バ' at position 3"));
+        assert!(err.contains("\n  おにのバか\n        ^^\n"));
+        assert!(err.contains("did you mean 'ば'?"));
+
+        assert!(!err.contains("tip:"));
+
+        let err = parse_err(&["--pattern", "?カ"]);
+        assert!(err.contains("tip: did you mean 'か'?"));
+
+        let err = parse_err(&["--pattern", "?x"]);
+        assert!(err.contains("invalid character 'x' at position 1"));
+        assert!(err.contains("\n  ?x\n   ^\n"));
+        assert!(!err.contains("did you mean"));
+
+        let err = parse_err(&["--password", ""]);
+        assert!(err.contains("password must contain"));
+
+        assert!(parse_err(&["--item", "0"]).contains("item id must be"));
+        assert!(parse_err(&["--item", "0x40"]).contains("item id must be"));
+    }
+}
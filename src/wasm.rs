@@ -0,0 +1,190 @@
+//! ブラウザ向けの wasm-bindgen バインディング。
+//!
+//! 入力文字列は JS 側の UTF-16 から UTF-8 に変換されて渡される。
+//! 入力中の空白は無視し、結合文字の濁点/半濁点 (U+3099, U+309A) は直前のかなと合成する。
+
+use std::ops::ControlFlow;
+
+use wasm_bindgen::prelude::*;
+
+use crate::password::Password;
+use crate::savedata::Savedata;
+use crate::search::{search_with, Pattern, SearchOptions, SearchStatus};
+use crate::serialized::SerializedBytes;
+
+/// `search()` で探索ステップ数の上限が省略された場合の値。
+pub const DEFAULT_MAX_STEPS: u32 = 1_000_000;
+
+/// パスワードをデコードし、それに記録されたセーブデータを JS オブジェクトとして返す。
+#[wasm_bindgen]
+pub fn decode(password: &str) -> Result<JsValue, JsError> {
+    guard(|| {
+        let password = parse_password(password)?;
+        let bytes = SerializedBytes::from_password(&password);
+        let savedata = bytes
+            .to_savedata()
+            .ok_or_else(|| JsError::new("checksum mismatch"))?;
+
+        Ok(serde_wasm_bindgen::to_value(&savedata)?)
+    })
+}
+
+/// JS オブジェクトとして与えられたセーブデータをパスワード化する。
+#[wasm_bindgen]
+pub fn encode(savedata: JsValue) -> Result<String, JsError> {
+    guard(|| {
+        let savedata: Savedata = serde_wasm_bindgen::from_value(savedata)?;
+        let password = SerializedBytes::from_savedata(&savedata).to_password();
+
+        Ok(password.display().to_string())
+    })
+}
+
+/// `validate()` の結果。
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationResult {
+    /// パスワードが有効かどうか。
+    #[wasm_bindgen(readonly)]
+    pub valid: bool,
+
+    /// 特殊パスワード(音楽室/美術室)かどうか。
+    #[wasm_bindgen(readonly)]
+    pub special: bool,
+
+    /// 無効な場合、その理由。
+    #[wasm_bindgen(readonly)]
+    pub error: Option<String>,
+}
+
+/// パスワード文字列が有効かどうかを調べる。
+#[wasm_bindgen]
+pub fn validate(password: &str) -> Result<ValidationResult, JsError> {
+    guard(|| {
+        let res = match Password::parse(&normalize_input(password)) {
+            Ok(password) => ValidationResult {
+                valid: password.is_valid(),
+                special: password.is_special(),
+                error: (!password.is_valid()).then(|| "checksum mismatch".to_owned()),
+            },
+            Err(e) => ValidationResult {
+                valid: false,
+                special: false,
+                error: Some(e.to_string()),
+            },
+        };
+
+        Ok(res)
+    })
+}
+
+/// `search()` の結果。
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchResult {
+    /// 見つかったパスワードたち(辞書順)。
+    #[wasm_bindgen(readonly)]
+    pub passwords: Vec<String>,
+
+    /// 探索ステップ数の上限に達せずに探索を終えたかどうか。
+    #[wasm_bindgen(readonly)]
+    pub complete: bool,
+}
+
+/// パターン('?' はワイルドカード)にマッチする有効なパスワードを探索する。
+///
+/// ブラウザを固まらせないよう、探索ステップ数は `max_steps` (省略時は `DEFAULT_MAX_STEPS`) までに制限される。
+#[wasm_bindgen]
+pub fn search(
+    pattern: &str,
+    limit: Option<u32>,
+    max_steps: Option<u32>,
+) -> Result<SearchResult, JsError> {
+    guard(|| {
+        let pattern = Pattern::parse(&normalize_input(pattern))?;
+        let options = SearchOptions {
+            limit: limit.map(|limit| limit as usize),
+            max_steps: Some(max_steps.unwrap_or(DEFAULT_MAX_STEPS).into()),
+            ..Default::default()
+        };
+
+        let mut passwords = vec![];
+        let status = search_with(&pattern, &options, |password| {
+            passwords.push(password.display().to_string());
+            ControlFlow::Continue(())
+        });
+
+        Ok(SearchResult {
+            passwords,
+            complete: status != SearchStatus::BudgetExhausted,
+        })
+    })
+}
+
+fn parse_password(s: &str) -> Result<Password, JsError> {
+    Ok(Password::parse(&normalize_input(s))?)
+}
+
+/// 入力文字列から空白を除き、結合文字の濁点/半濁点を直前のかなと合成する。
+fn normalize_input(s: &str) -> String {
+    const VOICED: char = '\u{3099}';
+    const SEMI_VOICED: char = '\u{309A}';
+    const VOICEABLE: &str = "かきくけこさしすせそたちつてとはひふへほ";
+    const SEMI_VOICEABLE: &str = "はひふへほ";
+
+    let mut res = String::with_capacity(s.len());
+
+    for c in s.chars().filter(|c| !c.is_whitespace()) {
+        let composed = res.chars().next_back().and_then(|prev| {
+            let offset = match c {
+                VOICED if VOICEABLE.contains(prev) => 1,
+                SEMI_VOICED if SEMI_VOICEABLE.contains(prev) => 2,
+                _ => return None,
+            };
+            char::from_u32(u32::from(prev) + offset)
+        });
+
+        match composed {
+            Some(composed) => {
+                res.pop();
+                res.push(composed);
+            }
+            None => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// `f` を実行し、パニックした場合は JS のエラーに変換する。
+///
+/// `panic=abort` でビルドした場合はパニックを捕捉できないが、その場合もパニックメッセージはコンソールに出力される。
+fn guard<T>(f: impl FnOnce() -> Result<T, JsError>) -> Result<T, JsError> {
+    console_error_panic_hook::set_once();
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        Err(JsError::new(&format!("internal error: {msg}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_input() {
+        assert_eq!(normalize_input("おにのばか"), "おにのばか");
+        assert_eq!(normalize_input("おにの\u{306F}\u{3099}か"), "おにのばか");
+        assert_eq!(normalize_input("\u{3075}\u{309A}"), "ぷ");
+        assert_eq!(normalize_input("おにの ばか\u{3000}"), "おにのばか");
+
+        // 合成できない結合文字はそのまま残す。
+        assert_eq!(normalize_input("あ\u{3099}"), "あ\u{3099}");
+        assert_eq!(normalize_input("か\u{309A}"), "か\u{309A}");
+    }
+}
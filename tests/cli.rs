@@ -0,0 +1,124 @@
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use momoden_password::*;
+
+fn cli() -> Command {
+    Command::cargo_bin("momoden-password").unwrap()
+}
+
+#[test]
+fn test_load() {
+    let output = cli().args(["load", "ふ", "--json"]).output().unwrap();
+    assert!(output.status.success());
+
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["password"], "ふ");
+    assert_eq!(value["raw"]["xp"], 0xFFFF);
+    assert_eq!(value["raw"]["equipment"]["weapon"], 0xF);
+    assert_eq!(value["normalized"]["equipment"]["weapon"], 0);
+
+    cli()
+        .args(["load", "おにのばか"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("password: おにのばか\n"));
+}
+
+#[test]
+fn test_load_invalid() {
+    cli()
+        .args(["load", "あ"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains("checksum mismatch"));
+
+    cli()
+        .args(["load", "abc"])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(predicate::str::contains("invalid character"));
+}
+
+#[test]
+fn test_save() {
+    // パスワード「ふ」で始めてすぐ神主に話しかけたときのパスワード。
+    cli()
+        .args(["save", "--from", "ふ"])
+        .assert()
+        .success()
+        .stdout("おしぼひまきびねとしぼひまきびねとひげがけちめいかほがすざ\n");
+
+    let expected = SerializedBytes::from_savedata(&Savedata {
+        xp: 1234,
+        ..Default::default()
+    })
+    .to_password();
+    cli()
+        .args(["save", "--xp", "1234"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", expected.display()));
+}
+
+#[test]
+fn test_save_json() {
+    let savedata = Savedata {
+        purse: 500,
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&savedata).unwrap();
+    let expected = SerializedBytes::from_savedata(&savedata).to_password();
+
+    cli()
+        .args(["save", "--json", "-"])
+        .write_stdin(json)
+        .assert()
+        .success()
+        .stdout(format!("{}\n", expected.display()));
+
+    cli()
+        .args(["save", "--json", "-"])
+        .write_stdin(r#"{"xp": 1}"#)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid savedata JSON"));
+}
+
+#[test]
+fn test_search() {
+    let output = cli()
+        .args(["search", "ふ???", "--limit", "3", "--threads", "4"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let passwords: Vec<_> = stdout
+        .lines()
+        .map(|line| Password::parse(line).unwrap())
+        .collect();
+    assert_eq!(passwords.len(), 3);
+    assert!(passwords.iter().all(Password::is_valid));
+
+    cli().args(["search", "あ"]).assert().code(1).stdout("");
+}
+
+#[test]
+fn test_fix() {
+    cli()
+        .args(["fix", "おにのばき"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("おにのばか\n"));
+
+    cli()
+        .args(["fix", "おにのばか"])
+        .assert()
+        .success()
+        .stdout("おにのばか\n");
+}
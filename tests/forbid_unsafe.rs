@@ -0,0 +1,23 @@
+//! forbid-unsafe feature を有効にしてテストスイート全体を実行する。CI の設定に依存せずに確認できる。
+//!
+//! cargo test --test=forbid_unsafe -- --ignored
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::process::Command;
+
+#[test]
+#[ignore = "runs the whole test suite with nested cargo"]
+fn test_forbid_unsafe() {
+    // 外側の cargo とビルドロックを奪い合わないよう、別のターゲットディレクトリを使う。
+    let target_dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/forbid-unsafe");
+
+    let status = Command::new(env!("CARGO"))
+        .args(["test", "--features=forbid-unsafe,cli"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("CARGO_TARGET_DIR", target_dir)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
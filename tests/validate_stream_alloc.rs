@@ -0,0 +1,44 @@
+//! `validate_stream` が行ごとにメモリ確保を行わないことを確認する。
+//!
+//! グローバルアロケータを差し替えるので、独立したテストバイナリとする。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use momoden_password::*;
+
+struct CountingAlloc;
+
+static N_ALLOC: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        N_ALLOC.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// `n` 組の行を含む入力を検証し、その間のメモリ確保回数を返す。
+fn count_allocs(n: usize) -> usize {
+    let input = "# comment\r\nおにのばか\r\n\r\nおにのばき\nあいう?\n".repeat(n);
+
+    let mut n_result = 0;
+    let before = N_ALLOC.load(Ordering::Relaxed);
+    validate_stream(input.as_bytes(), |_, _, _| n_result += 1).unwrap();
+    let after = N_ALLOC.load(Ordering::Relaxed);
+
+    assert_eq!(n_result, 3 * n);
+    after - before
+}
+
+#[test]
+fn test_validate_stream_no_alloc_per_line() {
+    assert_eq!(count_allocs(10), count_allocs(10000));
+}
@@ -0,0 +1,66 @@
+//! wasm バインディングのテスト。
+//!
+//! wasm-pack test --node --features=wasm
+//! (ブラウザで実行する場合は wasm-pack test --headless --firefox --features=wasm)
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use wasm_bindgen_test::*;
+
+use momoden_password::wasm::*;
+use momoden_password::{Password, Savedata, SerializedBytes};
+
+fn decode_savedata(password: &str) -> Savedata {
+    serde_wasm_bindgen::from_value(decode(password).unwrap()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_round_trip() {
+    let savedata = decode_savedata("おにのばか");
+    let expected = SerializedBytes::from_password(&Password::parse("おにのばか").unwrap())
+        .to_savedata()
+        .unwrap();
+    assert_eq!(savedata, expected);
+
+    let password = encode(decode("おにのばか").unwrap()).unwrap();
+    assert_eq!(decode_savedata(&password), savedata);
+
+    // 結合文字の濁点や空白を含む入力も受け付ける。
+    assert_eq!(decode_savedata("おにの \u{306F}\u{3099}か"), savedata);
+
+    assert!(decode("おにのばき").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_validate() {
+    let res = validate("おにのばか").unwrap();
+    assert!(res.valid);
+    assert!(!res.special);
+    assert_eq!(res.error, None);
+
+    let res = validate("おにのばき").unwrap();
+    assert!(!res.valid);
+    assert!(res.error.is_some());
+
+    let res = validate("abc").unwrap();
+    assert!(!res.valid);
+    assert!(res.error.is_some());
+}
+
+#[wasm_bindgen_test]
+fn test_search() {
+    let res = search("おにのば?", None, None).unwrap();
+    assert!(res.complete);
+    assert!(res
+        .passwords
+        .iter()
+        .any(|password| password == "おにのばか"));
+
+    let res = search("??????", Some(3), Some(1000)).unwrap();
+    assert!(res.passwords.len() <= 3);
+
+    let res = search("????????", None, Some(1000)).unwrap();
+    assert!(!res.complete);
+
+    assert!(search("x", None, None).is_err());
+}